@@ -0,0 +1,16 @@
+// Embed the SHA-256 of the asset manifest (`../integrity.sha256`) so a
+// release build trusts only the manifest it was built with; see
+// `src/integrity.rs`.
+
+use sha2::{Digest, Sha256};
+
+fn main() {
+    let manifest = "../integrity.sha256";
+    println!("cargo:rerun-if-changed={manifest}");
+    if let Ok(bytes) = std::fs::read(manifest) {
+        println!(
+            "cargo:rustc-env=SOVEREIGN_MANIFEST_SHA256={:x}",
+            Sha256::digest(&bytes)
+        );
+    }
+}
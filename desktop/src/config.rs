@@ -0,0 +1,148 @@
+// ═════════════════════════════════════════════════════════════════
+//  Launcher Configuration — sovereign.toml
+// ═════════════════════════════════════════════════════════════════
+//
+// Optional file read from the launcher's working directory. Every
+// field has a default, so a missing or partial file is fine; a file
+// that fails to parse is reported and the defaults are used instead.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Config file name, resolved relative to the working directory.
+pub const CONFIG_FILE: &str = "sovereign.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// UI locale tag ("en", "th", "ja", …). `None` → "en".
+    pub locale: Option<String>,
+    pub fonts: FontConfig,
+    pub input: InputConfig,
+    pub preview: PreviewConfig,
+    pub accessibility: AccessibilityConfig,
+}
+
+/// Following Windows Magnifier and the system text size.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityConfig {
+    /// Zoom the webview and widen the title bar and resize edges to
+    /// match the text size, and to `magnifier_zoom` while Magnifier
+    /// runs. The web UI is told either way (`desktop:zoom` event).
+    pub follow_zoom: bool,
+    /// Webview zoom in percent while Magnifier runs.
+    pub magnifier_zoom: u32,
+}
+
+impl Default for AccessibilityConfig {
+    fn default() -> Self {
+        Self {
+            follow_zoom: true,
+            magnifier_zoom: 150,
+        }
+    }
+}
+
+/// Taskbar thumbnail and Aero Peek preview.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct PreviewConfig {
+    /// Show the app icon and streak instead of the live window, so
+    /// hovering the taskbar never reveals a question or answer.
+    pub private: bool,
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        Self { private: true }
+    }
+}
+
+/// Keyboard input restrictions.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct InputConfig {
+    /// Mode at startup: "full" (every key) or "numpad" (digits, '/',
+    /// '-', Enter, Backspace only). The web UI switches it per student
+    /// profile and per drill with `__ipc.setInputMode`.
+    pub mode: String,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        Self {
+            mode: "full".to_string(),
+        }
+    }
+}
+
+/// Bundled font registration and per-locale fallback chains.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct FontConfig {
+    /// Directory holding the bundled font files.
+    pub directory: PathBuf,
+    /// Font family name → file name inside `directory`.
+    pub families: BTreeMap<String, String>,
+    /// Locale (primary subtag) → ordered family fallback chain.
+    pub locales: BTreeMap<String, Vec<String>>,
+    /// Chain used when the locale has no entry of its own.
+    pub default: Vec<String>,
+}
+
+impl Default for FontConfig {
+    fn default() -> Self {
+        let families = [
+            ("STIX Two Math", "STIXTwoMath-Regular.otf"),
+            ("Noto Sans Thai", "NotoSansThai-Regular.ttf"),
+            ("Noto Sans CJK SC", "NotoSansCJKsc-Regular.otf"),
+        ];
+        let cjk = vec!["Noto Sans CJK SC".to_string(), "STIX Two Math".to_string()];
+        Self {
+            directory: PathBuf::from("fonts"),
+            families: families
+                .iter()
+                .map(|(family, file)| (family.to_string(), file.to_string()))
+                .collect(),
+            locales: BTreeMap::from([
+                ("th".to_string(), vec!["Noto Sans Thai".to_string(), "STIX Two Math".to_string()]),
+                ("zh".to_string(), cjk.clone()),
+                ("ja".to_string(), cjk.clone()),
+                ("ko".to_string(), cjk),
+            ]),
+            default: vec!["STIX Two Math".to_string()],
+        }
+    }
+}
+
+impl Config {
+    /// Load `sovereign.toml` from `dir`, falling back to defaults.
+    pub fn load(dir: &Path) -> Config {
+        let path = dir.join(CONFIG_FILE);
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) => return Config::default(),
+        };
+        match toml::from_str(&text) {
+            Ok(config) => {
+                println!("[Desktop] ✓ Loaded {}", path.display());
+                config
+            }
+            Err(err) => {
+                eprintln!("[Desktop] WARNING: {} is invalid, using defaults: {err}", path.display());
+                Config::default()
+            }
+        }
+    }
+
+    /// Effective locale tag, lowercased ("en" when unset).
+    pub fn locale(&self) -> String {
+        self.locale
+            .as_deref()
+            .unwrap_or("en")
+            .trim()
+            .to_ascii_lowercase()
+    }
+}
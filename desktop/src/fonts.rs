@@ -0,0 +1,131 @@
+// ═════════════════════════════════════════════════════════════════
+//  Bundled Fonts — offline-identical worksheet rendering
+// ═════════════════════════════════════════════════════════════════
+//
+// Fonts shipped next to the launcher are served to the webview over a
+// custom `fonts://` protocol and registered with @font-face. The
+// locale's fallback chain is prepended to the theme's --font-primary,
+// so machines missing system math/CJK fonts render the same glyphs.
+
+use crate::config::Config;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use wry::http::{header, Request, Response, StatusCode};
+
+/// Custom protocol name used to serve bundled fonts.
+pub const FONT_PROTOCOL: &str = "fonts";
+
+/// Base URL of the font protocol as seen from the page.
+/// WebView2 exposes custom protocols as http://<name>.localhost/.
+#[cfg(target_os = "windows")]
+const FONT_BASE_URL: &str = "http://fonts.localhost/";
+#[cfg(not(target_os = "windows"))]
+const FONT_BASE_URL: &str = "fonts://localhost/";
+
+/// The fonts resolved for the active locale.
+#[derive(Debug, Clone, Default)]
+pub struct FontSet {
+    /// (family, file name) pairs whose files exist on disk, in order.
+    faces: Vec<(String, String)>,
+    directory: PathBuf,
+}
+
+impl FontSet {
+    /// Resolve the locale's fallback chain against the files on disk.
+    /// Families whose file is missing are skipped with a warning.
+    pub fn resolve(config: &Config) -> FontSet {
+        let fonts = &config.fonts;
+        let locale = config.locale();
+        let primary = locale.split(['-', '_']).next().unwrap_or("en");
+        let chain = fonts.locales.get(primary).unwrap_or(&fonts.default);
+
+        let mut faces = Vec::new();
+        for family in chain {
+            let Some(file) = fonts.families.get(family) else {
+                eprintln!("[Desktop] WARNING: font family '{family}' is not declared");
+                continue;
+            };
+            if fonts.directory.join(file).is_file() {
+                faces.push((family.clone(), file.clone()));
+            } else {
+                eprintln!("[Desktop] WARNING: bundled font missing: {file}");
+            }
+        }
+
+        println!("[Desktop] ✓ Fonts for '{locale}': {} bundled", faces.len());
+        FontSet {
+            faces,
+            directory: fonts.directory.clone(),
+        }
+    }
+
+    /// Serve a font file for the custom protocol.
+    /// Only files registered in the set are reachable (no path traversal).
+    pub fn serve(&self, request: &Request<Vec<u8>>) -> Response<Cow<'static, [u8]>> {
+        let name = request.uri().path().trim_start_matches('/');
+        let body = self
+            .faces
+            .iter()
+            .find(|(_, file)| file == name)
+            .and_then(|(_, file)| std::fs::read(self.directory.join(file)).ok());
+
+        let response = match body {
+            Some(bytes) => Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, content_type(name))
+                // @font-face loads are CORS requests from the app origin
+                .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+                .body(Cow::Owned(bytes)),
+            None => Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Cow::Borrowed(&[][..])),
+        };
+        response.unwrap_or_else(|_| Response::new(Cow::Borrowed(&[][..])))
+    }
+
+    /// Initialization script registering the fonts with the page.
+    pub fn init_script(&self) -> String {
+        let mut css = String::new();
+        for (family, file) in &self.faces {
+            css.push_str(&format!(
+                "@font-face{{font-family:'{family}';src:url('{FONT_BASE_URL}{file}');font-display:swap;}}"
+            ));
+        }
+        let stack: Vec<String> = self.faces.iter().map(|(f, _)| format!("'{f}'")).collect();
+        if !stack.is_empty() {
+            css.push_str(&format!(
+                ":root{{--font-primary:'gg sans',{},'Noto Sans','Helvetica Neue',Helvetica,Arial,sans-serif !important;}}",
+                stack.join(",")
+            ));
+        }
+
+        format!(
+            r#"
+            (function() {{
+                var css = {css:?};
+                if (!css) return;
+                function inject() {{
+                    if (document.getElementById('__desktop_fonts')) return;
+                    var style = document.createElement('style');
+                    style.id = '__desktop_fonts';
+                    style.textContent = css;
+                    document.head.appendChild(style);
+                }}
+                if (document.head) inject();
+                else document.addEventListener('DOMContentLoaded', inject);
+            }})();
+            "#
+        )
+    }
+}
+
+fn content_type(file: &str) -> &'static str {
+    match file.rsplit('.').next().map(|ext| ext.to_ascii_lowercase()).as_deref() {
+        Some("otf") => "font/otf",
+        Some("ttf") => "font/ttf",
+        Some("ttc") => "font/collection",
+        Some("woff") => "font/woff",
+        Some("woff2") => "font/woff2",
+        _ => "application/octet-stream",
+    }
+}
@@ -0,0 +1,386 @@
+// ═════════════════════════════════════════════════════════════════
+//  Asset Integrity — content-hash manifest checked at startup
+// ═════════════════════════════════════════════════════════════════
+//
+// Release builds serve only the built Fresh bundle (`_fresh/`) and
+// `static/`, which holds the WASM math engine, and ship a manifest of
+// SHA-256 hashes for every file in them. Before the server starts, every
+// listed file is re-hashed; a missing, modified, or unexpected file
+// stops the launcher and offers a repair through the updater.
+//
+// The manifest itself is trusted only if its own SHA-256 matches the
+// digest `build.rs` embedded in the executable, so re-blessing changed
+// assets means rebuilding the launcher.
+//
+// Debug builds (development) skip the check — they run the Vite dev
+// server over live sources. The manifest uses `sha256sum` format
+// ("<hex>  <path>"), so it can be audited with standard tools. Only a
+// debug build writes it, before the release build that embeds it:
+//
+//     cargo run -- --write-integrity-manifest && cargo build --release
+
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// Manifest file name, relative to the app root.
+pub const MANIFEST_FILE: &str = "integrity.sha256";
+
+/// Directories (relative to the app root) covered by the manifest.
+pub const COVERED_DIRS: [&str; 2] = ["_fresh", "static"];
+
+/// SHA-256 of the manifest this executable was built with (see
+/// `build.rs`); `None` if there was no manifest at build time.
+const EMBEDDED_MANIFEST_DIGEST: Option<&str> = option_env!("SOVEREIGN_MANIFEST_SHA256");
+
+/// Command-line flag that regenerates the manifest and exits (debug
+/// builds only).
+#[cfg(debug_assertions)]
+pub const WRITE_MANIFEST_FLAG: &str = "--write-integrity-manifest";
+
+/// File name of the updater, looked for next to the executable.
+#[cfg(target_os = "windows")]
+const UPDATER: &str = "sovereign-updater.exe";
+
+/// Whether startup verification applies to this build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Debug build: assets are live sources, nothing is verified.
+    Development,
+    /// Release build: assets must match the manifest.
+    Production,
+}
+
+impl Mode {
+    pub fn current() -> Mode {
+        if cfg!(debug_assertions) {
+            Mode::Development
+        } else {
+            Mode::Production
+        }
+    }
+}
+
+/// One reason the shipped assets cannot be trusted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    ManifestMissing,
+    /// The manifest is not the one this executable was built with.
+    ManifestUntrusted,
+    /// Manifest line that is not "<64 hex digits>  <path>".
+    ManifestInvalid(usize),
+    /// Listed in the manifest but not on disk.
+    Missing(String),
+    /// On disk, but its hash differs from the manifest.
+    Modified(String),
+    /// In a covered directory but not listed in the manifest.
+    Unexpected(String),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::ManifestMissing => write!(f, "{MANIFEST_FILE} is missing"),
+            Problem::ManifestUntrusted => {
+                write!(f, "{MANIFEST_FILE} does not match this build")
+            }
+            Problem::ManifestInvalid(line) => write!(f, "{MANIFEST_FILE} line {line} is invalid"),
+            Problem::Missing(path) => write!(f, "missing: {path}"),
+            Problem::Modified(path) => write!(f, "modified: {path}"),
+            Problem::Unexpected(path) => write!(f, "unexpected file: {path}"),
+        }
+    }
+}
+
+/// SHA-256 of `bytes`, as lowercase hex.
+fn hash_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// SHA-256 of a file's contents, as lowercase hex.
+fn hash_file(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Every file under the covered directories, as '/'-separated paths
+/// relative to `root`, in sorted order.
+fn covered_files(root: &Path) -> io::Result<Vec<String>> {
+    fn walk(dir: &Path, prefix: &str, out: &mut Vec<String>) -> io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = format!("{prefix}/{}", entry.file_name().to_string_lossy());
+            if entry.file_type()?.is_dir() {
+                walk(&entry.path(), &name, out)?;
+            } else {
+                out.push(name);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    for dir in COVERED_DIRS {
+        let path = root.join(dir);
+        if path.is_dir() {
+            walk(&path, dir, &mut files)?;
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Parse the manifest into path → hash.
+fn parse_manifest(text: &str) -> Result<BTreeMap<String, String>, Problem> {
+    let mut entries = BTreeMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (hash, path) = line
+            .split_once("  ")
+            .filter(|(hash, path)| {
+                hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit()) && !path.is_empty()
+            })
+            .ok_or(Problem::ManifestInvalid(index + 1))?;
+        entries.insert(path.to_string(), hash.to_ascii_lowercase());
+    }
+    Ok(entries)
+}
+
+/// Check the assets under `root` against its manifest. Empty = intact.
+pub fn verify(root: &Path) -> Vec<Problem> {
+    verify_against(root, EMBEDDED_MANIFEST_DIGEST)
+}
+
+/// `verify` with the manifest digest to trust (`None` trusts none).
+fn verify_against(root: &Path, trusted_digest: Option<&str>) -> Vec<Problem> {
+    let Ok(text) = std::fs::read_to_string(root.join(MANIFEST_FILE)) else {
+        return vec![Problem::ManifestMissing];
+    };
+    if trusted_digest != Some(hash_bytes(text.as_bytes()).as_str()) {
+        return vec![Problem::ManifestUntrusted];
+    }
+    let expected = match parse_manifest(&text) {
+        Ok(expected) => expected,
+        Err(problem) => return vec![problem],
+    };
+
+    let mut problems = Vec::new();
+    for (path, hash) in &expected {
+        match hash_file(&root.join(path)) {
+            Ok(actual) if actual == *hash => {}
+            Ok(_) => problems.push(Problem::Modified(path.clone())),
+            Err(_) => problems.push(Problem::Missing(path.clone())),
+        }
+    }
+    for path in covered_files(root).unwrap_or_default() {
+        if !expected.contains_key(&path) {
+            problems.push(Problem::Unexpected(path));
+        }
+    }
+    problems
+}
+
+/// A manifest of the covered directories, and the number of files listed.
+#[cfg(any(debug_assertions, test))]
+fn manifest_text(root: &Path) -> io::Result<(String, usize)> {
+    let files = covered_files(root)?;
+    let mut text = String::from("# Sovereign Academy asset manifest (sha256sum format)\n");
+    for path in &files {
+        text.push_str(&format!("{}  {path}\n", hash_file(&root.join(path))?));
+    }
+    Ok((text, files.len()))
+}
+
+/// Hash the covered directories into a fresh manifest.
+/// Returns the number of files listed.
+#[cfg(debug_assertions)]
+pub fn write_manifest(root: &Path) -> io::Result<usize> {
+    let (text, count) = manifest_text(root)?;
+    std::fs::write(root.join(MANIFEST_FILE), text)?;
+    Ok(count)
+}
+
+/// Returns `true` if startup may continue: always in development, and
+/// in production once the assets match the manifest (after a repair,
+/// if the family accepts one).
+pub fn ensure_intact(root: &Path) -> bool {
+    if Mode::current() == Mode::Development {
+        return true;
+    }
+
+    let problems = verify(root);
+    if problems.is_empty() {
+        println!("[Desktop] ✓ Asset integrity verified");
+        return true;
+    }
+
+    eprintln!("[Desktop] ERROR: asset integrity check failed:");
+    for problem in &problems {
+        eprintln!("[Desktop]   {problem}");
+    }
+    offer_repair(root)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn offer_repair(_root: &Path) -> bool {
+    eprintln!("[Desktop] Reinstall Sovereign Academy to repair the damaged files.");
+    false
+}
+
+/// Ask for consent, run the updater in repair mode, and re-verify.
+#[cfg(target_os = "windows")]
+fn offer_repair(root: &Path) -> bool {
+    use crate::webview2::message_box;
+    use crate::win32::{IDYES, MB_ICONERROR, MB_OK, MB_YESNO};
+
+    let Some(updater) = find_updater() else {
+        message_box(
+            "Some Sovereign Academy files are damaged or have been changed, and \
+             the updater could not be found.\n\nPlease reinstall Sovereign Academy.",
+            MB_OK | MB_ICONERROR,
+        );
+        return false;
+    };
+
+    let consent = message_box(
+        "Some Sovereign Academy files are damaged or have been changed, so it \
+         will not start.\n\nRepair them now? The updater will download fresh copies.",
+        MB_YESNO | MB_ICONERROR,
+    );
+    if consent != IDYES {
+        eprintln!("[Desktop] Repair declined");
+        return false;
+    }
+
+    println!("[Desktop] Running {} --repair…", updater.display());
+    match std::process::Command::new(&updater).arg("--repair").status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!("[Desktop] Updater exited with {status}"),
+        Err(err) => eprintln!("[Desktop] Could not run updater: {err}"),
+    }
+
+    if verify(root).is_empty() {
+        println!("[Desktop] ✓ Assets repaired");
+        true
+    } else {
+        message_box(
+            "The damaged files could not be repaired.\n\nPlease reinstall Sovereign Academy.",
+            MB_OK | MB_ICONERROR,
+        );
+        false
+    }
+}
+
+/// Look for the updater next to the executable.
+#[cfg(target_os = "windows")]
+fn find_updater() -> Option<std::path::PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let path = exe.parent()?.join(UPDATER);
+    path.is_file().then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const HASH_A: &str = "ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb";
+
+    /// A scratch app root with `_fresh/app.js` and `static/wasm/engine.wasm`.
+    fn app_root(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("sovereign-integrity-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("_fresh")).unwrap();
+        std::fs::create_dir_all(root.join("static/wasm")).unwrap();
+        std::fs::write(root.join("_fresh/app.js"), "a").unwrap();
+        std::fs::write(root.join("static/wasm/engine.wasm"), [0, 97, 115, 109]).unwrap();
+        root
+    }
+
+    /// Write the manifest for `root` and return its trusted digest.
+    fn bless(root: &Path) -> String {
+        let (text, count) = manifest_text(root).unwrap();
+        assert_eq!(count, 2);
+        std::fs::write(root.join(MANIFEST_FILE), &text).unwrap();
+        hash_bytes(text.as_bytes())
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let text = format!(
+            "# comment\n\n{}  _fresh/app.js\n{HASH_A}  static/a b.txt  \n",
+            HASH_A.to_ascii_uppercase()
+        );
+        let entries = parse_manifest(&text).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries["_fresh/app.js"], HASH_A);
+        assert_eq!(entries["static/a b.txt"], HASH_A);
+    }
+
+    #[test]
+    fn test_parse_manifest_rejects_bad_lines() {
+        let short = format!("{HASH_A}  ok\n{}  short\n", &HASH_A[..63]);
+        assert_eq!(parse_manifest(&short), Err(Problem::ManifestInvalid(2)));
+        let one_space = format!("{HASH_A} _fresh/app.js\n");
+        assert_eq!(parse_manifest(&one_space), Err(Problem::ManifestInvalid(1)));
+        let not_hex = format!("{}g  x\n", &HASH_A[..63]);
+        assert_eq!(parse_manifest(&not_hex), Err(Problem::ManifestInvalid(1)));
+        assert_eq!(
+            parse_manifest(&format!("{HASH_A}  \n")),
+            Err(Problem::ManifestInvalid(1))
+        );
+    }
+
+    #[test]
+    fn test_verify_intact() {
+        let root = app_root("intact");
+        let digest = bless(&root);
+        assert_eq!(verify_against(&root, Some(&digest)), []);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_verify_reports_modified_missing_and_extra_files() {
+        let root = app_root("tampered");
+        let digest = bless(&root);
+        std::fs::write(root.join("_fresh/app.js"), "tampered").unwrap();
+        std::fs::remove_file(root.join("static/wasm/engine.wasm")).unwrap();
+        std::fs::write(root.join("_fresh/extra.js"), "x").unwrap();
+        assert_eq!(
+            verify_against(&root, Some(&digest)),
+            [
+                Problem::Modified("_fresh/app.js".to_string()),
+                Problem::Missing("static/wasm/engine.wasm".to_string()),
+                Problem::Unexpected("_fresh/extra.js".to_string()),
+            ]
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_reblessed_manifest_is_untrusted() {
+        let root = app_root("reblessed");
+        let digest = bless(&root);
+        std::fs::write(root.join("_fresh/app.js"), "tampered").unwrap();
+        bless(&root);
+        assert_eq!(
+            verify_against(&root, Some(&digest)),
+            [Problem::ManifestUntrusted]
+        );
+        assert_eq!(verify_against(&root, None), [Problem::ManifestUntrusted]);
+        std::fs::remove_file(root.join(MANIFEST_FILE)).unwrap();
+        assert_eq!(
+            verify_against(&root, Some(&digest)),
+            [Problem::ManifestMissing]
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,248 @@
+// ═════════════════════════════════════════════════════════════════
+//  IPC Queue — batching + backpressure for webview messages
+// ═════════════════════════════════════════════════════════════════
+//
+// The webview may post a single message ("minimize") or a batch of
+// newline-separated messages ("drag-move:10,20\ndrag-move:12,21").
+// Every message lands in a bounded queue; the event loop drains a
+// fixed number per wake-up so bursty telemetry can never stall it.
+//
+// Message format:  <kind>[:<payload>]
+// The kind decides what happens when the queue is under pressure.
+
+use std::collections::VecDeque;
+
+/// Maximum number of queued messages before the drop policies kick in.
+pub const IPC_QUEUE_CAPACITY: usize = 256;
+
+/// Hard limit once only critical messages are left to evict. Reaching it
+/// means the event loop has stopped draining, so further messages of any
+/// kind are dropped rather than growing the queue without bound.
+pub const IPC_QUEUE_HARD_LIMIT: usize = 2 * IPC_QUEUE_CAPACITY;
+
+/// Maximum number of messages handled per event-loop wake-up.
+pub const IPC_DRAIN_PER_TICK: usize = 64;
+
+/// What to do with a message when the queue is full (or already holds
+/// a message of the same kind).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// Window controls — evict non-critical messages if full, and are
+    /// only dropped at `IPC_QUEUE_HARD_LIMIT`.
+    Critical,
+    /// High-rate telemetry — only the latest value matters, so a queued
+    /// message of the same kind is removed and the new one goes to the
+    /// back, keeping messages in the order they were sent.
+    Coalesce,
+    /// Best-effort — silently dropped when the queue is full.
+    Droppable,
+}
+
+/// Extract the kind of a message (text before the first ':').
+pub fn message_kind(msg: &str) -> &str {
+    msg.split(':').next().unwrap_or(msg)
+}
+
+/// Per-kind backpressure policy.
+pub fn policy_for(kind: &str) -> Policy {
+    match kind {
+        "minimize" | "maximize" | "close" | "support-bundle" | "input-mode" => Policy::Critical,
+        _ if kind.starts_with("resize-") => Policy::Critical,
+        "drag-move" | "pointer" | "scroll" | "hit-regions" | "streak" | "zoom-state" => {
+            Policy::Coalesce
+        }
+        _ => Policy::Droppable,
+    }
+}
+
+/// Bounded FIFO of pending IPC messages.
+#[derive(Debug, Default)]
+pub struct IpcQueue {
+    items: VecDeque<String>,
+}
+
+impl IpcQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enqueue every message of a (possibly batched) IPC body.
+    pub fn push_body(&mut self, body: &str) {
+        for msg in body.split('\n') {
+            let msg = msg.trim();
+            if !msg.is_empty() {
+                self.push(msg.to_string());
+            }
+        }
+    }
+
+    /// Enqueue one message according to its policy.
+    /// Returns `false` if the message was dropped.
+    pub fn push(&mut self, msg: String) -> bool {
+        let policy = policy_for(message_kind(&msg));
+
+        if policy == Policy::Coalesce {
+            let kind = message_kind(&msg).to_string();
+            if let Some(index) = self.items.iter().position(|m| message_kind(m) == kind) {
+                self.items.remove(index);
+            }
+        }
+
+        if self.items.len() >= IPC_QUEUE_CAPACITY {
+            match policy {
+                Policy::Critical => {
+                    // Make room by evicting the oldest non-critical message.
+                    // If everything queued is critical, exceed capacity up
+                    // to the hard limit.
+                    let victim = self
+                        .items
+                        .iter()
+                        .position(|m| policy_for(message_kind(m)) != Policy::Critical);
+                    match victim {
+                        Some(index) => {
+                            self.items.remove(index);
+                        }
+                        None if self.items.len() >= IPC_QUEUE_HARD_LIMIT => return false,
+                        None => {}
+                    }
+                }
+                Policy::Coalesce | Policy::Droppable => return false,
+            }
+        }
+
+        self.items.push_back(msg);
+        true
+    }
+
+    /// Take up to `max` messages from the front of the queue.
+    pub fn drain(&mut self, max: usize) -> Vec<String> {
+        let count = max.min(self.items.len());
+        self.items.drain(..count).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Web-side half of the batching: messages posted through `__ipc.send`
+/// are flushed once per animation frame as a single newline-joined body.
+/// Window controls flush immediately so native resize keeps zero lag.
+pub const BATCHING_SCRIPT: &str = r#"
+(function() {
+    var MAX_PENDING = 512;
+    var pending = [];
+    var scheduled = false;
+
+    function flush() {
+        scheduled = false;
+        if (!pending.length) return;
+        var body = pending.join('\n');
+        pending = [];
+        window.ipc.postMessage(body);
+    }
+
+    function send(msg) {
+        if (pending.length >= MAX_PENDING) pending.shift();
+        pending.push(String(msg));
+        if (!scheduled) {
+            scheduled = true;
+            (window.requestAnimationFrame || setTimeout)(flush);
+        }
+    }
+
+    function sendNow(msg) {
+        pending.push(String(msg));
+        flush();
+    }
+
+    window.__ipc = {
+        send: send,
+        sendNow: sendNow,
+        minimize: function() { sendNow('minimize'); },
+        maximize: function() { sendNow('maximize'); },
+        close:    function() { sendNow('close'); },
+        // mode: 'numpad' (digits, '/', '-', Enter, Backspace) or 'full'
+        setInputMode: function(mode) { sendNow('input-mode:' + mode); },
+        // Current streak, shown in the private taskbar preview.
+        setStreak: function(count) { send('streak:' + count); },
+        // Ask for a 'desktop:zoom' event with the current Magnifier state.
+        requestZoom: function() { send('zoom-state'); },
+        // regions: [{ kind: 'minimize'|'maximize'|'close', x, y, width, height }]
+        // in window-relative physical pixels (CSS px × devicePixelRatio).
+        declareHitRegions: function(regions) {
+            send('hit-regions:' + regions.map(function(r) {
+                return [r.kind, r.x, r.y, r.width, r.height].join(',');
+            }).join(';'));
+        },
+    };
+})();
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(queue: &mut IpcQueue, count: usize, msg: &str) {
+        for _ in 0..count {
+            queue.push(msg.to_string());
+        }
+    }
+
+    #[test]
+    fn test_coalesce_keeps_latest_at_back() {
+        let mut queue = IpcQueue::new();
+        queue.push_body("drag-move:1,1\nminimize\ndrag-move:2,2");
+        assert_eq!(queue.drain(10), ["minimize", "drag-move:2,2"]);
+    }
+
+    #[test]
+    fn test_droppable_dropped_when_full() {
+        let mut queue = IpcQueue::new();
+        fill(&mut queue, IPC_QUEUE_CAPACITY, "log:x");
+        assert!(!queue.push("log:y".to_string()));
+        assert!(!queue.push("drag-move:1,1".to_string()));
+        assert_eq!(queue.items.len(), IPC_QUEUE_CAPACITY);
+    }
+
+    #[test]
+    fn test_critical_evicts_oldest_droppable() {
+        let mut queue = IpcQueue::new();
+        queue.push("log:first".to_string());
+        fill(&mut queue, IPC_QUEUE_CAPACITY - 1, "log:x");
+        assert!(queue.push("close".to_string()));
+        assert_eq!(queue.items.len(), IPC_QUEUE_CAPACITY);
+        let drained = queue.drain(IPC_QUEUE_CAPACITY);
+        assert_eq!(drained[0], "log:x");
+        assert_eq!(drained.last().map(String::as_str), Some("close"));
+    }
+
+    #[test]
+    fn test_critical_overflow_is_bounded() {
+        let mut queue = IpcQueue::new();
+        fill(&mut queue, IPC_QUEUE_CAPACITY, "minimize");
+        assert!(queue.push("maximize".to_string()));
+        assert_eq!(queue.items.len(), IPC_QUEUE_CAPACITY + 1);
+        fill(&mut queue, IPC_QUEUE_HARD_LIMIT, "maximize");
+        assert_eq!(queue.items.len(), IPC_QUEUE_HARD_LIMIT);
+        assert!(!queue.push("close".to_string()));
+        assert_eq!(queue.items.len(), IPC_QUEUE_HARD_LIMIT);
+    }
+
+    #[test]
+    fn test_drain_per_tick() {
+        let mut queue = IpcQueue::new();
+        for i in 0..100 {
+            queue.push(format!("log:{i}"));
+        }
+        let batch = queue.drain(IPC_DRAIN_PER_TICK);
+        assert_eq!(batch.len(), IPC_DRAIN_PER_TICK);
+        assert_eq!(batch[0], "log:0");
+        assert_eq!(queue.items.len(), 100 - IPC_DRAIN_PER_TICK);
+        assert_eq!(
+            queue.drain(IPC_DRAIN_PER_TICK)[0],
+            format!("log:{IPC_DRAIN_PER_TICK}")
+        );
+        assert!(queue.is_empty());
+    }
+}
@@ -13,34 +13,57 @@
 // ╚═══════════════════════════════════════════════════════════════════╝
 //
 // Discord-quality frameless native window with embedded WebView2.
-// Uses Win32 DWM APIs for proper frameless behavior:
+// Uses Win32 DWM APIs for proper frameless behavior (shared with the
+// FFI library in `native_window::frameless`):
 //   - DwmExtendFrameIntoClientArea → DWM shadow + composition
 //   - WM_NCCALCSIZE → entire window is client area
 //   - WM_NCHITTEST  → custom drag/resize hit-testing
+//   - Iconic taskbar/peek previews → no question or answer leaks
+//   - Magnifier / text-size polling → zoom + wider hit targets
 //
 // All UI logic lives in the Fresh app (Preact + Signals).
 //
 // Usage:  cargo run            (from desktop/)
 //    or:  deno task launch:desktop   (from project root)
 
+mod config;
+mod fonts;
+mod integrity;
+mod ipc;
+mod support;
+mod trace;
+mod webview2;
+
+#[cfg(target_os = "windows")]
+use config::AccessibilityConfig;
+use config::Config;
+use fonts::FontSet;
+use ipc::{IpcQueue, IPC_DRAIN_PER_TICK};
+#[cfg(target_os = "windows")]
+use native_window::frameless::{self, CaptionButton, FramelessOptions, HitRegion, ResizeDirection};
+#[cfg(target_os = "windows")]
+use native_window::keyboard::{self, InputMode};
+#[cfg(target_os = "windows")]
+use native_window::magnifier::{self, ZoomState};
+#[cfg(target_os = "windows")]
+use native_window::preview;
+use std::cell::RefCell;
 use std::process::{Child, Command};
-use std::sync::atomic::{AtomicIsize, Ordering};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 use tao::{
     dpi::PhysicalSize,
     event::{Event, WindowEvent},
     event_loop::{ControlFlow, EventLoopBuilder},
-    window::WindowBuilder,
+    window::{Window, WindowBuilder},
 };
-use wry::WebViewBuilder;
-
-/// Title bar height in physical pixels (matches the CSS drag bar).
-const TITLEBAR_HEIGHT: i32 = 32;
-
-/// Resize border width in physical pixels.
-/// Matches Windows SM_CXFRAME + SM_CXPADDEDBORDER (~8px at 100% DPI).
-const RESIZE_BORDER: i32 = 8;
+#[cfg(target_os = "windows")]
+use tao::event_loop::EventLoopProxy;
+use trace::EventTrace;
+use wry::{WebView, WebViewBuilder};
 
 // ═════════════════════════════════════════════════════════════════
 //  Raw Win32 FFI declarations (avoids windows-sys version conflicts)
@@ -48,123 +71,124 @@ const RESIZE_BORDER: i32 = 8;
 #[cfg(target_os = "windows")]
 mod win32 {
     pub type HWND = isize;
-    pub type HBRUSH = isize;
-    pub type LRESULT = isize;
-    pub type WPARAM = usize;
-    pub type LPARAM = isize;
-
-    #[repr(C)]
-    #[derive(Copy, Clone)]
-    pub struct RECT {
-        pub left: i32,
-        pub top: i32,
-        pub right: i32,
-        pub bottom: i32,
-    }
-
-    #[repr(C)]
-    pub struct MARGINS {
-        pub cx_left_width: i32,
-        pub cx_right_width: i32,
-        pub cy_top_height: i32,
-        pub cy_bottom_height: i32,
-    }
 
-    // Window style constants
-    pub const WS_CAPTION: u32 = 0x00C00000;
-    pub const WS_THICKFRAME: u32 = 0x00040000;
-    pub const WS_CLIPCHILDREN: u32 = 0x02000000;
-
-    // GetWindowLongPtr / SetWindowLongPtr indices
-    pub const GWL_STYLE: i32 = -16;
-    pub const GWLP_WNDPROC: i32 = -4;
-
-    // SetClassLongPtr indices
-    pub const GCLP_HBRBACKGROUND: i32 = -10;
-
-    // SetWindowPos flags
-    pub const SWP_FRAMECHANGED: u32 = 0x0020;
-    pub const SWP_NOMOVE: u32 = 0x0002;
-    pub const SWP_NOSIZE: u32 = 0x0001;
-    pub const SWP_NOZORDER: u32 = 0x0004;
-
-    // Window messages
-    pub const WM_NCCALCSIZE: u32 = 0x0083;
-    pub const WM_NCHITTEST: u32 = 0x0084;
-    pub const WM_ERASEBKGND: u32 = 0x0014;
-    pub const WM_SIZE: u32 = 0x0005;
-
-    // WM_NCHITTEST return values
-    pub const HTCLIENT: isize = 1;
-    pub const HTCAPTION: isize = 2;
-    pub const HTLEFT: isize = 10;
-    pub const HTRIGHT: isize = 11;
-    pub const HTTOP: isize = 12;
-    pub const HTTOPLEFT: isize = 13;
-    pub const HTTOPRIGHT: isize = 14;
-    pub const HTBOTTOM: isize = 15;
-    pub const HTBOTTOMLEFT: isize = 16;
-    pub const HTBOTTOMRIGHT: isize = 17;
-
-    // WM_NCLBUTTONDOWN — used to initiate native resize from IPC
-    pub const WM_NCLBUTTONDOWN: u32 = 0x00A1;
+    // MessageBoxW flags + return values
+    pub const MB_OK: u32 = 0x0000_0000;
+    pub const MB_YESNO: u32 = 0x0000_0004;
+    pub const MB_ICONERROR: u32 = 0x0000_0010;
+    pub const MB_ICONQUESTION: u32 = 0x0000_0020;
+    pub const IDYES: i32 = 6;
 
     extern "system" {
         // user32.dll
-        pub fn GetWindowLongPtrW(hwnd: HWND, index: i32) -> isize;
-        pub fn SetWindowLongPtrW(hwnd: HWND, index: i32, new_long: isize) -> isize;
-        pub fn SetClassLongPtrW(hwnd: HWND, index: i32, new_long: isize) -> isize;
-        pub fn SetWindowPos(
-            hwnd: HWND, hwnd_after: HWND,
-            x: i32, y: i32, cx: i32, cy: i32, flags: u32,
-        ) -> i32;
-        pub fn GetWindowRect(hwnd: HWND, rect: *mut RECT) -> i32;
-        pub fn DefWindowProcW(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT;
-        pub fn InvalidateRect(hwnd: HWND, rect: *const RECT, erase: i32) -> i32;
-        pub fn ReleaseCapture() -> i32;
-        pub fn SendMessageW(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT;
-    }
-
-    extern "system" {
-        // gdi32.dll
-        pub fn CreateSolidBrush(color: u32) -> HBRUSH;
-    }
-
-    #[link(name = "dwmapi")]
-    extern "system" {
-        // dwmapi.dll
-        pub fn DwmExtendFrameIntoClientArea(hwnd: HWND, margins: *const MARGINS) -> i32;
+        pub fn MessageBoxW(hwnd: HWND, text: *const u16, caption: *const u16, flags: u32) -> i32;
     }
 }
 
-/// Stores the original WndProc so our subclass can forward messages.
-/// Must be static because GWLP_USERDATA is used internally by tao.
-#[cfg(target_os = "windows")]
-static ORIGINAL_WNDPROC: AtomicIsize = AtomicIsize::new(0);
-
 /// Custom events sent from webview IPC to the native event loop.
 #[derive(Debug)]
 enum UserEvent {
     Minimize,
     Maximize,
     Close,
-    /// Initiate native resize drag. Value is the HT* direction constant.
-    StartResize(isize),
+    /// Initiate native resize drag from an edge or corner.
+    #[cfg(target_os = "windows")]
+    StartResize(ResizeDirection),
+    /// The title bar reported where its caption buttons are.
+    #[cfg(target_os = "windows")]
+    DeclareHitRegions(Vec<HitRegion>),
+    /// Restrict (or restore) which keys reach the webview.
+    #[cfg(target_os = "windows")]
+    SetInputMode(InputMode),
+    /// The student's current streak, for the taskbar preview.
+    #[cfg(target_os = "windows")]
+    SetStreak(u32),
+    /// Magnifier started or stopped, or the system text size changed.
+    #[cfg(target_os = "windows")]
+    ZoomChanged(ZoomState),
+    /// The web UI asked for the current zoom state.
+    #[cfg(target_os = "windows")]
+    ReportZoom,
+    /// The IPC queue has messages waiting to be drained.
+    IpcPending,
+    /// Zip logs, config, crash reports, and the event trace.
+    CollectSupportBundle,
+}
+
+/// Everything the event loop owns once startup is complete.
+struct Shell {
+    window: Window,
+    webview: WebView,
+    deno_server: Child,
+    trace: Rc<RefCell<EventTrace>>,
+    #[cfg(target_os = "windows")]
+    accessibility: AccessibilityConfig,
+    /// Last Magnifier / text-size state reported by the poller.
+    #[cfg(target_os = "windows")]
+    zoom: ZoomState,
 }
 
+/// How often Magnifier and the text size are polled.
+#[cfg(target_os = "windows")]
+const ZOOM_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 fn main() -> wry::Result<()> {
-    // ── 1. Start Fresh Vite dev server ───────────────────────────
+    // The app root: the Fresh server and shipped assets live in `..`.
+    let app_root = std::path::Path::new("..");
+
+    #[cfg(debug_assertions)]
+    if std::env::args().any(|arg| arg == integrity::WRITE_MANIFEST_FLAG) {
+        match integrity::write_manifest(app_root) {
+            Ok(count) => println!("[Desktop] ✓ Wrote {} ({count} files)", integrity::MANIFEST_FILE),
+            Err(err) => {
+                eprintln!("[Desktop] ERROR: could not write {}: {err}", integrity::MANIFEST_FILE);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    // ── 0. Make sure the WebView2 runtime exists ─────────────────
+    if !webview2::ensure_runtime() {
+        std::process::exit(1);
+    }
+
+    // ── 0b. Release builds: verify shipped assets against manifest ─
+    if !integrity::ensure_intact(app_root) {
+        std::process::exit(1);
+    }
+
+    // ── 1. Start Fresh server (Vite dev / built bundle) ──────────
     println!("[Desktop] Starting Fresh server...");
-    let mut deno_server = start_fresh_server();
+    let deno_server = start_fresh_server();
 
     thread::sleep(Duration::from_secs(2));
     wait_for_server(30);
 
-    // ── 2. Create frameless window ───────────────────────────────
+    // ── 2. Load config + resolve bundled fonts for the locale ────
+    let config = Config::load(std::path::Path::new("."));
+    let font_set = FontSet::resolve(&config);
+    let font_script = font_set.init_script();
+
+    // ── 3. Create frameless window ───────────────────────────────
     println!("[Desktop] Creating frameless window...");
 
     let event_loop = EventLoopBuilder::<UserEvent>::with_user_event().build();
     let proxy = event_loop.create_proxy();
+    let loop_proxy = event_loop.create_proxy();
+    #[cfg(target_os = "windows")]
+    let zoom_proxy = event_loop.create_proxy();
+
+    // IPC messages are queued here and drained by the event loop.
+    // `ipc_wake_pending` ensures at most one IpcPending event is in flight.
+    let ipc_queue = Arc::new(Mutex::new(IpcQueue::new()));
+    let ipc_wake_pending = Arc::new(AtomicBool::new(false));
+    let handler_queue = Arc::clone(&ipc_queue);
+    let handler_wake_pending = Arc::clone(&ipc_wake_pending);
+
+    // Recent IPC + navigation events, for support bundles.
+    let trace = Rc::new(RefCell::new(EventTrace::new()));
+    let nav_trace = Rc::clone(&trace);
 
     let window = WindowBuilder::new()
         .with_title("Sovereign Academy")
@@ -174,28 +198,45 @@ fn main() -> wry::Result<()> {
         .build(&event_loop)
         .expect("Failed to create window");
 
-    // ── 3. Win32: DWM frameless setup ────────────────────────────
+    // ── 4. Win32: DWM frameless setup ────────────────────────────
     #[cfg(target_os = "windows")]
     {
         use tao::platform::windows::WindowExtWindows;
-        let hwnd = window.hwnd() as isize;
-        setup_frameless_window(hwnd);
+        frameless::setup_frameless(window.hwnd(), FramelessOptions::default());
+        println!("[Desktop] ✓ Win32 frameless setup complete (DWM + NCHITTEST)");
+
+        if config.preview.private {
+            if preview::enable_private_preview(window.hwnd()) {
+                println!("[Desktop] ✓ Private taskbar preview enabled");
+            } else {
+                eprintln!("[Desktop] WARNING: DWM refused the private taskbar preview");
+            }
+        }
+
+        match InputMode::from_name(&config.input.mode) {
+            Some(mode) => apply_input_mode(&window, mode),
+            None => eprintln!(
+                "[Desktop] WARNING: unknown input mode {:?}, using full keyboard",
+                config.input.mode
+            ),
+        }
     }
 
-    // ── 4. Build WebView2 ────────────────────────────────────────
-    let _webview = WebViewBuilder::new()
+    // ── 5. Build WebView2 ────────────────────────────────────────
+    let webview = WebViewBuilder::new()
         .with_url("http://127.0.0.1:5173?desktop=1")
         .with_background_color((30, 31, 34, 255))
         .with_devtools(cfg!(debug_assertions))
+        // Expose batched IPC to the Fresh app (window.__ipc)
+        .with_initialization_script(ipc::BATCHING_SCRIPT)
+        // Register bundled fonts + the locale's fallback chain
+        .with_initialization_script(&font_script)
+        .with_custom_protocol(fonts::FONT_PROTOCOL.to_string(), move |_id, request| {
+            font_set.serve(&request)
+        })
         .with_initialization_script(
             r#"
-            // Expose IPC to the Fresh app
             window.__DESKTOP__ = true;
-            window.__ipc = {
-                minimize: () => window.ipc.postMessage('minimize'),
-                maximize: () => window.ipc.postMessage('maximize'),
-                close:    () => window.ipc.postMessage('close'),
-            };
 
             // ── Invisible resize handles at window edges ──
             // On mousedown, sends IPC to trigger native Win32 resize.
@@ -230,7 +271,7 @@ fn main() -> wry::Result<()> {
                         el.addEventListener('mousedown', function(e) {
                             e.preventDefault();
                             e.stopPropagation();
-                            window.ipc.postMessage('resize-' + h[0]);
+                            window.__ipc.sendNow('resize-' + h[0]);
                         });
                         document.body.appendChild(el);
                     });
@@ -253,27 +294,16 @@ fn main() -> wry::Result<()> {
             })();
             "#,
         )
+        .with_navigation_handler(move |url| {
+            nav_trace.borrow_mut().record_navigation(&url);
+            true
+        })
         .with_ipc_handler(move |req| {
-            let msg = req.body();
-            match msg.as_str() {
-                "minimize" => { let _ = proxy.send_event(UserEvent::Minimize); }
-                "maximize" => { let _ = proxy.send_event(UserEvent::Maximize); }
-                "close"    => { let _ = proxy.send_event(UserEvent::Close); }
-                _ if msg.starts_with("resize-") => {
-                    let dir = match &msg[7..] {
-                        "top"         => win32::HTTOP,
-                        "bottom"      => win32::HTBOTTOM,
-                        "left"        => win32::HTLEFT,
-                        "right"       => win32::HTRIGHT,
-                        "topleft"     => win32::HTTOPLEFT,
-                        "topright"    => win32::HTTOPRIGHT,
-                        "bottomleft"  => win32::HTBOTTOMLEFT,
-                        "bottomright" => win32::HTBOTTOMRIGHT,
-                        _ => return,
-                    };
-                    let _ = proxy.send_event(UserEvent::StartResize(dir));
-                }
-                _ => {}
+            if let Ok(mut queue) = handler_queue.lock() {
+                queue.push_body(req.body());
+            }
+            if !handler_wake_pending.swap(true, Ordering::AcqRel) {
+                let _ = proxy.send_event(UserEvent::IpcPending);
             }
         })
         .build(&window)?;
@@ -283,7 +313,23 @@ fn main() -> wry::Result<()> {
     println!("[Desktop]   Window: {}×{} frameless", size.width, size.height);
     println!("[Desktop]   Server: http://127.0.0.1:5173");
 
-    // ── 5. Event loop ────────────────────────────────────────────
+    let mut shell = Shell {
+        window,
+        webview,
+        deno_server,
+        trace,
+        #[cfg(target_os = "windows")]
+        accessibility: config.accessibility,
+        #[cfg(target_os = "windows")]
+        zoom: ZoomState::default(),
+    };
+
+    // Magnifier sends no notifications, so poll it (and the text size)
+    // off the UI thread; only changes reach the event loop.
+    #[cfg(target_os = "windows")]
+    thread::spawn(move || watch_zoom(zoom_proxy));
+
+    // ── 6. Event loop ────────────────────────────────────────────
     event_loop.run(move |event, _, control_flow| {
         *control_flow = ControlFlow::Wait;
 
@@ -293,234 +339,260 @@ fn main() -> wry::Result<()> {
                 ..
             } => {
                 println!("[Desktop] Closing…");
-                let _ = deno_server.kill();
-                *control_flow = ControlFlow::Exit;
-            }
-            Event::UserEvent(UserEvent::Minimize) => {
-                window.set_minimized(true);
-            }
-            Event::UserEvent(UserEvent::Maximize) => {
-                window.set_maximized(!window.is_maximized());
-            }
-            Event::UserEvent(UserEvent::Close) => {
-                println!("[Desktop] Closing…");
-                let _ = deno_server.kill();
+                let _ = shell.deno_server.kill();
                 *control_flow = ControlFlow::Exit;
             }
-            #[cfg(target_os = "windows")]
-            Event::UserEvent(UserEvent::StartResize(direction)) => {
-                // Initiate native Win32 resize — Windows takes over the
-                // resize loop, so this is instant with zero IPC lag.
-                use tao::platform::windows::WindowExtWindows;
-                let hwnd = window.hwnd() as isize;
-                unsafe {
-                    win32::ReleaseCapture();
-                    win32::SendMessageW(
-                        hwnd,
-                        win32::WM_NCLBUTTONDOWN,
-                        direction as usize,
-                        0,
-                    );
+            Event::UserEvent(UserEvent::IpcPending) => {
+                ipc_wake_pending.store(false, Ordering::Release);
+
+                // Handle a bounded slice of the queue, then yield back to
+                // the event loop so window messages are never starved.
+                let (batch, more) = match ipc_queue.lock() {
+                    Ok(mut queue) => {
+                        let batch = queue.drain(IPC_DRAIN_PER_TICK);
+                        (batch, !queue.is_empty())
+                    }
+                    Err(_) => (Vec::new(), false),
+                };
+
+                for msg in &batch {
+                    shell.trace.borrow_mut().record_ipc(msg);
+                    if let Some(user_event) = parse_ipc_message(msg) {
+                        handle_user_event(&mut shell, user_event, control_flow);
+                    }
+                }
+
+                if more && !ipc_wake_pending.swap(true, Ordering::AcqRel) {
+                    let _ = loop_proxy.send_event(UserEvent::IpcPending);
                 }
             }
+            Event::UserEvent(user_event) => {
+                handle_user_event(&mut shell, user_event, control_flow);
+            }
             _ => {}
         }
     });
 }
 
-// ═════════════════════════════════════════════════════════════════
-//  Win32 Frameless Window Setup
-// ═════════════════════════════════════════════════════════════════
-//
-// This is the "Discord technique":
-//   1. Remove WS_CAPTION but keep WS_THICKFRAME for native resize
-//   2. DwmExtendFrameIntoClientArea(-1) for DWM shadow & composition
-//   3. Subclass the WndProc to handle:
-//      - WM_NCCALCSIZE: return 0 so entire window = client area
-//      - WM_NCHITTEST:  custom hit-testing for drag bar + resize edges
-
-#[cfg(target_os = "windows")]
-fn setup_frameless_window(hwnd: isize) {
-    use win32::*;
-
-    unsafe {
-        // Remove caption but keep thick frame + clip children (reduces flicker)
-        let style = GetWindowLongPtrW(hwnd, GWL_STYLE);
-        let new_style = (style & !(WS_CAPTION as isize))
-            | (WS_THICKFRAME as isize)
-            | (WS_CLIPCHILDREN as isize);
-        SetWindowLongPtrW(hwnd, GWL_STYLE, new_style);
-
-        // Extend DWM frame into entire client area → enables shadow + composition
-        let margins = MARGINS {
-            cx_left_width: -1,
-            cx_right_width: -1,
-            cy_top_height: -1,
-            cy_bottom_height: -1,
-        };
-        DwmExtendFrameIntoClientArea(hwnd, &margins);
-
-        // Paint background dark (#1e1f22 = 0x00221F1E in BGR COLORREF)
-        let brush = CreateSolidBrush(0x0022_1F1E);
-        SetClassLongPtrW(hwnd, GCLP_HBRBACKGROUND, brush);
-
-        // Save original WndProc in a static (NOT GWLP_USERDATA — tao uses that)
-        let original_proc = GetWindowLongPtrW(hwnd, GWLP_WNDPROC);
-        ORIGINAL_WNDPROC.store(original_proc, Ordering::SeqCst);
-        SetWindowLongPtrW(hwnd, GWLP_WNDPROC, custom_wndproc as isize);
-
-        // Force Windows to recalculate the frame
-        SetWindowPos(
-            hwnd, 0, 0, 0, 0, 0,
-            SWP_FRAMECHANGED | SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER,
-        );
-
-        InvalidateRect(hwnd, std::ptr::null(), 1);
+/// Map one IPC message to the event it triggers.
+/// Unknown kinds (telemetry the shell doesn't consume) map to `None`.
+fn parse_ipc_message(msg: &str) -> Option<UserEvent> {
+    match msg {
+        "minimize" => Some(UserEvent::Minimize),
+        "maximize" => Some(UserEvent::Maximize),
+        "close" => Some(UserEvent::Close),
+        "support-bundle" => Some(UserEvent::CollectSupportBundle),
+        #[cfg(target_os = "windows")]
+        "zoom-state" => Some(UserEvent::ReportZoom),
+        #[cfg(target_os = "windows")]
+        _ if msg.starts_with("resize-") => {
+            ResizeDirection::from_name(&msg[7..]).map(UserEvent::StartResize)
+        }
+        #[cfg(target_os = "windows")]
+        _ if msg.starts_with("input-mode:") => {
+            InputMode::from_name(&msg[11..]).map(UserEvent::SetInputMode)
+        }
+        #[cfg(target_os = "windows")]
+        _ if msg.starts_with("streak:") => msg[7..].parse().ok().map(UserEvent::SetStreak),
+        #[cfg(target_os = "windows")]
+        _ if msg.starts_with("hit-regions:") => {
+            parse_hit_regions(&msg[12..]).map(UserEvent::DeclareHitRegions)
+        }
+        _ => None,
     }
-
-    println!("[Desktop] ✓ Win32 frameless setup complete (DWM + NCHITTEST)");
 }
 
-/// Custom WndProc for frameless hit-testing.
-///
-/// Handles:
-/// - `WM_NCCALCSIZE` → returns 0 so entire window is client area
-/// - `WM_NCHITTEST`  → returns HTCAPTION for drag bar, HT*LEFT/RIGHT/etc.
-///   for resize edges, HTCLIENT for everything else
+/// Most caption-button regions accepted from one declaration.
 #[cfg(target_os = "windows")]
-unsafe extern "system" fn custom_wndproc(
-    hwnd: isize,
-    msg: u32,
-    wparam: usize,
-    lparam: isize,
-) -> isize {
-    use win32::*;
+const MAX_HIT_REGIONS: usize = 8;
 
-    match msg {
-        // ── WM_NCCALCSIZE: make entire window = client area ──
-        WM_NCCALCSIZE => {
-            if wparam != 0 {
-                // Return 0, but set SWP_NOCOPYBITS in SetWindowPos logic (implicitly handled)
-                // Returning 0 allows client area = window rect.
-                //
-                // The key to reducing lag is NOT necessarily WVR_VALIDRECTS, which can cause
-                // artifacts if we don't supply correct rects.
-                //
-                // Instead, we rely on DwmExtendFrameIntoClientArea(-1) + WS_CLIPCHILDREN.
-                // We also intentionally avoid DefWindowProc here because that adds borders/caption back.
-                return 0;
-            }
-        }
+/// Parse "minimize,x,y,w,h;maximize,x,y,w,h;…" (window-relative physical
+/// pixels). An empty payload clears the regions; any malformed entry
+/// rejects the whole declaration.
+#[cfg(target_os = "windows")]
+fn parse_hit_regions(payload: &str) -> Option<Vec<HitRegion>> {
+    let regions = payload
+        .split(';')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let mut fields = entry.split(',').map(str::trim);
+            let button = CaptionButton::from_name(fields.next()?)?;
+            let mut next = || fields.next()?.parse::<f64>().ok().map(|v| v.round() as i32);
+            let (x, y, width, height) = (next()?, next()?, next()?, next()?);
+            (width > 0 && height > 0).then_some(HitRegion {
+                button,
+                x,
+                y,
+                width,
+                height,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    (regions.len() <= MAX_HIT_REGIONS).then_some(regions)
+}
 
-        // ── WM_ERASEBKGND: dark fill immediately (no white flash) ──
-        WM_ERASEBKGND => {
-            // Background brush is already set on the class, so returning 1
-            // tells Windows "I handled it" — it paints with our dark brush.
-            return 1;
+/// Apply a window-control event on the event-loop thread.
+fn handle_user_event(shell: &mut Shell, event: UserEvent, control_flow: &mut ControlFlow) {
+    let window = &shell.window;
+    match event {
+        UserEvent::Minimize => {
+            window.set_minimized(true);
         }
-
-        // ── WM_SIZE: standard resize handling ──
-        WM_SIZE => {
-            // Forward to original proc so tao/wry resize the WebView
-            let original_proc = ORIGINAL_WNDPROC.load(Ordering::SeqCst);
-            if original_proc != 0 {
-                let proc_fn: unsafe extern "system" fn(isize, u32, usize, isize) -> isize =
-                    std::mem::transmute(original_proc);
-                return proc_fn(hwnd, msg, wparam, lparam);
-            }
-            return DefWindowProcW(hwnd, msg, wparam, lparam);
+        UserEvent::Maximize => {
+            window.set_maximized(!window.is_maximized());
         }
-
-        // ── WM_NCHITTEST: custom drag bar + resize edges ──
-        WM_NCHITTEST => {
-            // Cursor position in screen coords (packed in lparam)
-            let cursor_x = (lparam & 0xFFFF) as i16 as i32;
-            let cursor_y = ((lparam >> 16) & 0xFFFF) as i16 as i32;
-
-            // Window rect in screen coords
-            let mut rect = RECT {
-                left: 0,
-                top: 0,
-                right: 0,
-                bottom: 0,
+        UserEvent::Close => {
+            println!("[Desktop] Closing…");
+            let _ = shell.deno_server.kill();
+            *control_flow = ControlFlow::Exit;
+        }
+        UserEvent::CollectSupportBundle => {
+            let trace_text = shell.trace.borrow().to_text();
+            let result = support::collect_bundle(&support::default_output_dir(), &trace_text);
+            let detail = match &result {
+                Ok(path) => {
+                    println!("[Desktop] ✓ Support bundle written to {}", path.display());
+                    format!("{{ok:true,path:{:?}}}", path.display().to_string())
+                }
+                Err(err) => {
+                    eprintln!("[Desktop] ERROR: support bundle failed: {err}");
+                    format!("{{ok:false,error:{:?}}}", err.to_string())
+                }
             };
-            GetWindowRect(hwnd, &mut rect);
-
-            // Distance from each edge
-            let left = cursor_x - rect.left;
-            let right = rect.right - cursor_x;
-            let top = cursor_y - rect.top;
-            let bottom = rect.bottom - cursor_y;
-
-            let border = RESIZE_BORDER;
-
-            // Corners first (they overlap edges)
-            if top <= border && left <= border {
-                return HTTOPLEFT;
-            }
-            if top <= border && right <= border {
-                return HTTOPRIGHT;
-            }
-            if bottom <= border && left <= border {
-                return HTBOTTOMLEFT;
-            }
-            if bottom <= border && right <= border {
-                return HTBOTTOMRIGHT;
-            }
+            shell.trace.borrow_mut().record("support bundle collected");
+            let _ = shell.webview.evaluate_script(&format!(
+                "window.dispatchEvent(new CustomEvent('desktop:support-bundle',{{detail:{detail}}}))"
+            ));
+        }
+        #[cfg(target_os = "windows")]
+        UserEvent::StartResize(direction) => {
+            // Initiate native Win32 resize — Windows takes over the
+            // resize loop, so this is instant with zero IPC lag.
+            use tao::platform::windows::WindowExtWindows;
+            frameless::start_native_resize(window.hwnd(), direction);
+        }
+        #[cfg(target_os = "windows")]
+        UserEvent::DeclareHitRegions(regions) => {
+            // WM_NCHITTEST now reports these as the real caption buttons
+            // (hover, snap layouts, accessibility roles).
+            frameless::set_hit_regions(regions);
+        }
+        #[cfg(target_os = "windows")]
+        UserEvent::SetInputMode(mode) => {
+            apply_input_mode(window, mode);
+        }
+        #[cfg(target_os = "windows")]
+        UserEvent::SetStreak(streak) => {
+            use tao::platform::windows::WindowExtWindows;
+            preview::set_streak(window.hwnd(), streak);
+        }
+        #[cfg(target_os = "windows")]
+        UserEvent::ZoomChanged(state) => {
+            shell.zoom = state;
+            apply_zoom(shell);
+        }
+        #[cfg(target_os = "windows")]
+        UserEvent::ReportZoom => {
+            report_zoom(shell);
+        }
+        _ => {}
+    }
+}
 
-            // Edges
-            if top <= border {
-                return HTTOP;
-            }
-            if bottom <= border {
-                return HTBOTTOM;
-            }
-            if left <= border {
-                return HTLEFT;
-            }
-            if right <= border {
-                return HTRIGHT;
-            }
+/// Install or remove the numpad-only keyboard filter.
+#[cfg(target_os = "windows")]
+fn apply_input_mode(window: &Window, mode: InputMode) {
+    use tao::platform::windows::WindowExtWindows;
+    if keyboard::set_input_mode(window.hwnd(), mode) {
+        println!("[Desktop] ✓ Input mode: {mode:?}");
+    } else {
+        eprintln!("[Desktop] ERROR: could not install keyboard filter");
+    }
+}
 
-            // Title bar drag region (top TITLEBAR_HEIGHT pixels)
-            if top <= TITLEBAR_HEIGHT {
-                return HTCAPTION;
+/// Report every Magnifier / text-size change (and the state at startup)
+/// to the event loop. Returns once the event loop has exited.
+#[cfg(target_os = "windows")]
+fn watch_zoom(proxy: EventLoopProxy<UserEvent>) {
+    let mut last = None;
+    loop {
+        let state = magnifier::detect();
+        if last != Some(state) {
+            if proxy.send_event(UserEvent::ZoomChanged(state)).is_err() {
+                return;
             }
-
-            // Everything else = client area (WebView content)
-            return HTCLIENT;
+            last = Some(state);
         }
+        thread::sleep(ZOOM_POLL_INTERVAL);
+    }
+}
 
-        _ => {}
+/// Webview zoom in percent for the current state (100 unless
+/// `follow_zoom` is on).
+#[cfg(target_os = "windows")]
+fn zoom_percent(shell: &Shell) -> u32 {
+    let settings = &shell.accessibility;
+    if settings.follow_zoom {
+        shell.zoom.zoom_percent(settings.magnifier_zoom)
+    } else {
+        100
     }
+}
 
-    // All other messages → forward to original WndProc
-    let original_proc = ORIGINAL_WNDPROC.load(Ordering::SeqCst);
-    if original_proc != 0 {
-        let proc_fn: unsafe extern "system" fn(isize, u32, usize, isize) -> isize =
-            std::mem::transmute(original_proc);
-        return proc_fn(hwnd, msg, wparam, lparam);
+/// Zoom the webview and scale the native hit targets to match, then
+/// tell the web UI.
+#[cfg(target_os = "windows")]
+fn apply_zoom(shell: &Shell) {
+    if shell.accessibility.follow_zoom {
+        let percent = zoom_percent(shell);
+        if let Err(err) = shell.webview.zoom(f64::from(percent) / 100.0) {
+            eprintln!("[Desktop] ERROR: could not zoom the webview: {err}");
+        }
+        frameless::set_hit_target_scale(percent);
+        println!(
+            "[Desktop] ✓ Zoom {percent}% (magnifier: {}, text size: {}%)",
+            shell.zoom.magnifier, shell.zoom.text_scale
+        );
     }
+    report_zoom(shell);
+}
 
-    DefWindowProcW(hwnd, msg, wparam, lparam)
+/// Dispatch `desktop:zoom` with `{magnifier, textScale, zoom}` so the
+/// title bar can re-declare its (now larger) caption buttons.
+#[cfg(target_os = "windows")]
+fn report_zoom(shell: &Shell) {
+    let _ = shell.webview.evaluate_script(&format!(
+        "window.dispatchEvent(new CustomEvent('desktop:zoom',{{detail:{{magnifier:{},textScale:{},zoom:{}}}}}))",
+        shell.zoom.magnifier,
+        shell.zoom.text_scale,
+        f64::from(zoom_percent(shell)) / 100.0,
+    ));
 }
 
 // ═════════════════════════════════════════════════════════════════
 //  Server Management
 // ═════════════════════════════════════════════════════════════════
 
-/// Start the Fresh 2 Vite dev server as a subprocess.
+/// Start the Fresh 2 server as a subprocess: the Vite dev server over
+/// live sources in debug builds, the built bundle in `_fresh/` (the
+/// files the integrity manifest covers) in release builds.
 fn start_fresh_server() -> Child {
     #[cfg(target_os = "windows")]
     let deno_cmd = "deno.exe";
     #[cfg(not(target_os = "windows"))]
     let deno_cmd = "deno";
 
+    let args: &[&str] = if cfg!(debug_assertions) {
+        &["task", "dev"]
+    } else {
+        &["serve", "-A", "--host", "127.0.0.1", "--port", "5173", "_fresh/server.js"]
+    };
+
     Command::new(deno_cmd)
-        .args(["task", "dev"])
+        .args(args)
         .current_dir("..")
         .spawn()
-        .expect("Failed to start Vite dev server — is 'deno' in PATH?")
+        .expect("Failed to start the Fresh server — is 'deno' in PATH?")
 }
 
 /// Block until the TCP server accepts connections.
@@ -0,0 +1,317 @@
+// ═════════════════════════════════════════════════════════════════
+//  Support Bundle — one file the family can email to support
+// ═════════════════════════════════════════════════════════════════
+//
+// Collects into a single .zip:
+//   system.txt      launcher + WebView2 version, OS
+//   trace.txt       recent IPC / navigation events (see trace.rs)
+//   sovereign.toml  launcher config with secret-looking values redacted
+//   logs/…          the most recent files from ./logs
+//   crash/…         the most recent files from ./crash-reports
+//
+// The zip is written with "stored" entries (no compression), which
+// keeps this dependency-free; the payload is small text anyway.
+
+use crate::config::CONFIG_FILE;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Newest files taken from each of logs/ and crash-reports/.
+const MAX_FILES_PER_DIR: usize = 10;
+
+/// Largest single file included (bytes); bigger files are skipped.
+const MAX_FILE_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Config keys whose values are replaced by "<redacted>".
+const SECRET_MARKERS: [&str; 5] = ["token", "secret", "password", "key", "auth"];
+
+const REDACTED: &str = "<redacted>";
+
+/// Write a support bundle into `out_dir` and return its path.
+pub fn collect_bundle(out_dir: &Path, trace_text: &str) -> io::Result<PathBuf> {
+    let mut zip = ZipWriter::new();
+
+    zip.add("system.txt", system_report().as_bytes());
+    zip.add("trace.txt", trace_text.as_bytes());
+
+    if let Ok(config) = fs::read_to_string(CONFIG_FILE) {
+        zip.add(CONFIG_FILE, redact_config(&config).as_bytes());
+    }
+
+    for (dir, prefix) in [("logs", "logs"), ("crash-reports", "crash")] {
+        for path in newest_files(Path::new(dir), MAX_FILES_PER_DIR) {
+            if let (Ok(bytes), Some(name)) = (fs::read(&path), path.file_name()) {
+                zip.add(&format!("{prefix}/{}", name.to_string_lossy()), &bytes);
+            }
+        }
+    }
+
+    fs::create_dir_all(out_dir)?;
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = out_dir.join(format!("sovereign-support-{stamp}.zip"));
+    fs::write(&path, zip.finish())?;
+    Ok(path)
+}
+
+/// Default output folder: the user's Desktop, else the working dir.
+pub fn default_output_dir() -> PathBuf {
+    let home = std::env::var_os("USERPROFILE").or_else(|| std::env::var_os("HOME"));
+    match home.map(|h| PathBuf::from(h).join("Desktop")) {
+        Some(desktop) if desktop.is_dir() => desktop,
+        _ => PathBuf::from("."),
+    }
+}
+
+fn system_report() -> String {
+    format!(
+        "Sovereign Academy Desktop {}\nOS: {} ({})\nWebView: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        wry::webview_version().unwrap_or_else(|_| "unavailable".to_string()),
+    )
+}
+
+/// Replace values of secret-looking keys in a TOML file, at any depth
+/// (including inline tables such as `proxy = { password = "…" }`).
+/// Comments and layout are lost. A file that doesn't parse is redacted
+/// line by line, dropping the value of any line that mentions a secret.
+fn redact_config(text: &str) -> String {
+    match text.parse::<toml::Table>() {
+        Ok(mut table) => {
+            redact_table(&mut table);
+            toml::to_string(&table).unwrap_or_else(|_| redact_lines(text))
+        }
+        Err(_) => redact_lines(text),
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SECRET_MARKERS.iter().any(|m| key.contains(m))
+}
+
+fn redact_table(table: &mut toml::Table) {
+    for (key, value) in table.iter_mut() {
+        if is_secret_key(key) {
+            *value = toml::Value::String(REDACTED.to_string());
+        } else {
+            redact_value(value);
+        }
+    }
+}
+
+fn redact_value(value: &mut toml::Value) {
+    match value {
+        toml::Value::Table(table) => redact_table(table),
+        toml::Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+fn redact_lines(text: &str) -> String {
+    let mut out = String::new();
+    for line in text.lines() {
+        match line.split_once('=') {
+            Some((key, _)) if is_secret_key(line) => {
+                out.push_str(key.trim_end());
+                out.push_str(&format!(" = \"{REDACTED}\""));
+            }
+            _ => out.push_str(line),
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// The `limit` most recently modified regular files in `dir`.
+fn newest_files(dir: &Path, limit: usize) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            if !meta.is_file() || meta.len() > MAX_FILE_SIZE {
+                return None;
+            }
+            Some((meta.modified().ok()?, entry.path()))
+        })
+        .collect();
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    files.into_iter().take(limit).map(|(_, path)| path).collect()
+}
+
+// ─── Minimal ZIP writer (stored entries) ─────────────────────────
+
+struct ZipWriter {
+    data: Vec<u8>,
+    central: Vec<u8>,
+    count: u16,
+}
+
+impl ZipWriter {
+    fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            central: Vec::new(),
+            count: 0,
+        }
+    }
+
+    fn add(&mut self, name: &str, bytes: &[u8]) {
+        let crc = crc32(bytes);
+        let size = bytes.len() as u32;
+        let offset = self.data.len() as u32;
+        let name = name.as_bytes();
+
+        // Local file header
+        let _ = self.data.write_all(&0x0403_4b50u32.to_le_bytes());
+        // version, flags, method, time, date (1980-01-01)
+        let _ = self.data.write_all(&[20, 0, 0, 0, 0, 0, 0, 0, 0x21, 0]);
+        let _ = self.data.write_all(&crc.to_le_bytes());
+        let _ = self.data.write_all(&size.to_le_bytes());
+        let _ = self.data.write_all(&size.to_le_bytes());
+        let _ = self.data.write_all(&(name.len() as u16).to_le_bytes());
+        let _ = self.data.write_all(&0u16.to_le_bytes());
+        let _ = self.data.write_all(name);
+        let _ = self.data.write_all(bytes);
+
+        // Central directory entry
+        let _ = self.central.write_all(&0x0201_4b50u32.to_le_bytes());
+        // made-by, needed, flags, method, time, date
+        let _ = self.central.write_all(&[20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0x21, 0]);
+        let _ = self.central.write_all(&crc.to_le_bytes());
+        let _ = self.central.write_all(&size.to_le_bytes());
+        let _ = self.central.write_all(&size.to_le_bytes());
+        let _ = self.central.write_all(&(name.len() as u16).to_le_bytes());
+        let _ = self.central.write_all(&[0; 12]); // extra, comment, disk, attrs
+        let _ = self.central.write_all(&offset.to_le_bytes());
+        let _ = self.central.write_all(name);
+
+        self.count += 1;
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let central_offset = self.data.len() as u32;
+        let central_size = self.central.len() as u32;
+        self.data.extend_from_slice(&self.central);
+
+        // End of central directory record
+        let _ = self.data.write_all(&0x0605_4b50u32.to_le_bytes());
+        let _ = self.data.write_all(&[0; 4]); // disk numbers
+        let _ = self.data.write_all(&self.count.to_le_bytes());
+        let _ = self.data.write_all(&self.count.to_le_bytes());
+        let _ = self.data.write_all(&central_size.to_le_bytes());
+        let _ = self.data.write_all(&central_offset.to_le_bytes());
+        let _ = self.data.write_all(&0u16.to_le_bytes());
+        self.data
+    }
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_top_level_and_section_keys() {
+        let config = "locale = \"th\"\napi_token = \"abc123\"\n\n[sync]\nAuthHeader = \"Bearer xyz\"\n";
+        let redacted = redact_config(config);
+        assert!(!redacted.contains("abc123"));
+        assert!(!redacted.contains("xyz"));
+        assert!(redacted.contains("locale = \"th\""));
+        let table: toml::Table = redacted.parse().unwrap();
+        assert_eq!(table["api_token"].as_str(), Some(REDACTED));
+        assert_eq!(table["sync"]["AuthHeader"].as_str(), Some(REDACTED));
+    }
+
+    #[test]
+    fn test_redacts_inside_inline_tables_and_arrays() {
+        let config = "proxy = { host = \"10.0.0.1\", password = \"hunter2\" }\n\
+                      mirrors = [{ url = \"a\", token = \"t0k\" }]\n";
+        let redacted = redact_config(config);
+        assert!(!redacted.contains("hunter2"));
+        assert!(!redacted.contains("t0k"));
+        let table: toml::Table = redacted.parse().unwrap();
+        assert_eq!(table["proxy"]["host"].as_str(), Some("10.0.0.1"));
+        assert_eq!(table["proxy"]["password"].as_str(), Some(REDACTED));
+        assert_eq!(table["mirrors"][0]["token"].as_str(), Some(REDACTED));
+    }
+
+    #[test]
+    fn test_unparseable_config_redacts_whole_lines() {
+        let config = "locale = \"th\nproxy = { password = \"hunter2\" }\n";
+        let redacted = redact_config(config);
+        assert!(!redacted.contains("hunter2"));
+        assert!(redacted.contains("proxy = \"<redacted>\""));
+    }
+
+    /// Read back the entries of a stored zip via its central directory.
+    fn read_zip(data: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let u16_at = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+        let u32_at = |at: usize| {
+            u32::from_le_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]]) as usize
+        };
+        let eocd = data.len() - 22;
+        assert_eq!(u32_at(eocd), 0x0605_4b50);
+        let count = u16_at(eocd + 10);
+        let mut at = u32_at(eocd + 16);
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            assert_eq!(u32_at(at), 0x0201_4b50);
+            let crc = u32_at(at + 16);
+            let size = u32_at(at + 20);
+            let name_len = u16_at(at + 28);
+            let local = u32_at(at + 42);
+            let name = String::from_utf8(data[at + 46..at + 46 + name_len].to_vec()).unwrap();
+
+            assert_eq!(u32_at(local), 0x0403_4b50);
+            assert_eq!(u32_at(local + 14), crc);
+            let start = local + 30 + u16_at(local + 26);
+            let bytes = data[start..start + size].to_vec();
+            assert_eq!(crc32(&bytes) as usize, crc);
+
+            entries.push((name, bytes));
+            at += 46 + name_len;
+        }
+        entries
+    }
+
+    #[test]
+    fn test_zip_round_trip() {
+        let mut zip = ZipWriter::new();
+        zip.add("system.txt", b"hello");
+        zip.add("logs/empty.log", b"");
+        let entries = read_zip(&zip.finish());
+        assert_eq!(
+            entries,
+            [
+                ("system.txt".to_string(), b"hello".to_vec()),
+                ("logs/empty.log".to_string(), Vec::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+}
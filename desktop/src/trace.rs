@@ -0,0 +1,134 @@
+// ═════════════════════════════════════════════════════════════════
+//  Event Trace — recent IPC + navigation for support bundles
+// ═════════════════════════════════════════════════════════════════
+//
+// A fixed-size ring buffer of what the shell saw most recently. It is
+// only ever written into a support bundle the family chooses to send.
+// IPC payloads are never kept (only the message kind), and navigation
+// URLs lose their query and fragment, so answers typed into lessons
+// don't leak.
+
+use crate::ipc::message_kind;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Number of events kept in memory.
+const TRACE_CAPACITY: usize = 500;
+
+/// Longest IPC kind recorded as-is; anything longer (or not a plain
+/// kind name) is recorded as `?`.
+const KIND_LIMIT: usize = 32;
+
+#[derive(Debug)]
+pub struct EventTrace {
+    started: Instant,
+    events: VecDeque<String>,
+}
+
+impl EventTrace {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            events: VecDeque::with_capacity(TRACE_CAPACITY),
+        }
+    }
+
+    /// Record an IPC message (kind only, never the payload).
+    pub fn record_ipc(&mut self, msg: &str) {
+        self.push("ipc", trace_kind(msg));
+    }
+
+    /// Record a navigation request (without query or fragment).
+    pub fn record_navigation(&mut self, url: &str) {
+        let end = url.find(['?', '#']).unwrap_or(url.len());
+        self.push("nav", &url[..end]);
+    }
+
+    /// Record a shell-side event (window state, errors, …).
+    pub fn record(&mut self, what: &str) {
+        self.push("app", what);
+    }
+
+    fn push(&mut self, channel: &str, text: &str) {
+        if self.events.len() == TRACE_CAPACITY {
+            self.events.pop_front();
+        }
+        let ms = self.started.elapsed().as_millis();
+        self.events.push_back(format!("+{ms:>9}ms  {channel:<3}  {text}"));
+    }
+
+    /// The trace as plain text, oldest event first.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for line in &self.events {
+            out.push_str(line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// The kind of an IPC message, or `?` when it doesn't look like one.
+/// A message with no ':' is all kind, so free text sent without a kind
+/// (e.g. a bare answer) must not pass through.
+fn trace_kind(msg: &str) -> &str {
+    let kind = message_kind(msg);
+    let plain = kind
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+    if plain && !kind.is_empty() && kind.len() <= KIND_LIMIT {
+        kind
+    } else {
+        "?"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ipc_payloads_are_not_recorded() {
+        let mut trace = EventTrace::new();
+        trace.record_ipc("answer:42");
+        trace.record_ipc("input-mode:numpad");
+        trace.record_ipc("resize-top");
+        let text = trace.to_text();
+        assert!(!text.contains("42"));
+        assert!(!text.contains("numpad"));
+        assert!(text.contains("answer\n"));
+        assert!(text.contains("input-mode\n"));
+        assert!(text.contains("resize-top\n"));
+    }
+
+    #[test]
+    fn test_free_text_ipc_is_opaque() {
+        let mut trace = EventTrace::new();
+        trace.record_ipc("3/4");
+        trace.record_ipc("The Answer Is 7");
+        trace.record_ipc(&"a".repeat(KIND_LIMIT + 1));
+        let text = trace.to_text();
+        assert_eq!(text.lines().filter(|l| l.ends_with("  ?")).count(), 3);
+        assert!(!text.contains("3/4"));
+    }
+
+    #[test]
+    fn test_navigation_drops_query_and_fragment() {
+        let mut trace = EventTrace::new();
+        trace.record_navigation("http://127.0.0.1:5173/lesson/3?answer=12#step");
+        let text = trace.to_text();
+        assert!(text.ends_with("nav  http://127.0.0.1:5173/lesson/3\n"));
+    }
+
+    #[test]
+    fn test_capacity_keeps_newest() {
+        let mut trace = EventTrace::new();
+        for i in 0..=TRACE_CAPACITY {
+            trace.record(&format!("event {i}"));
+        }
+        let text = trace.to_text();
+        assert_eq!(text.lines().count(), TRACE_CAPACITY);
+        assert!(!text.contains("event 0\n"));
+        assert!(text.ends_with(&format!("event {TRACE_CAPACITY}\n")));
+    }
+}
@@ -0,0 +1,98 @@
+// ═════════════════════════════════════════════════════════════════
+//  WebView2 Runtime — first-chance detection + bundled bootstrapper
+// ═════════════════════════════════════════════════════════════════
+//
+// Older Windows 10 installs may lack the Evergreen WebView2 runtime,
+// which makes wry fail deep inside webview creation. Instead we check
+// up front, ask the family for consent, run the bundled Evergreen
+// bootstrapper, wait for it, and re-check before continuing startup.
+
+/// File name of the bundled Evergreen bootstrapper.
+#[cfg(target_os = "windows")]
+const BOOTSTRAPPER: &str = "MicrosoftEdgeWebview2Setup.exe";
+
+/// Returns `true` once a WebView2 runtime is available.
+/// On other platforms the system webview is always present.
+#[cfg(not(target_os = "windows"))]
+pub fn ensure_runtime() -> bool {
+    true
+}
+
+/// Returns `true` once a WebView2 runtime is available, installing it
+/// with the bundled bootstrapper (after consent) if it is missing.
+#[cfg(target_os = "windows")]
+pub fn ensure_runtime() -> bool {
+    use crate::win32::{IDYES, MB_ICONERROR, MB_ICONQUESTION, MB_OK, MB_YESNO};
+
+    if let Ok(version) = wry::webview_version() {
+        println!("[Desktop] ✓ WebView2 runtime {version}");
+        return true;
+    }
+
+    eprintln!("[Desktop] WebView2 runtime not found");
+    let Some(bootstrapper) = find_bootstrapper() else {
+        message_box(
+            "Sovereign Academy needs the Microsoft Edge WebView2 runtime, and the \
+             bundled installer could not be found.\n\nPlease install WebView2 from \
+             Microsoft and start Sovereign Academy again.",
+            MB_OK | MB_ICONERROR,
+        );
+        return false;
+    };
+
+    let consent = message_box(
+        "Sovereign Academy needs the Microsoft Edge WebView2 runtime to show \
+         lessons.\n\nInstall it now? This takes about a minute.",
+        MB_YESNO | MB_ICONQUESTION,
+    );
+    if consent != IDYES {
+        eprintln!("[Desktop] WebView2 install declined");
+        return false;
+    }
+
+    println!("[Desktop] Running {}…", bootstrapper.display());
+    let status = std::process::Command::new(&bootstrapper)
+        .args(["/silent", "/install"])
+        .status();
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!("[Desktop] WebView2 bootstrapper exited with {status}"),
+        Err(err) => eprintln!("[Desktop] Could not run WebView2 bootstrapper: {err}"),
+    }
+
+    match wry::webview_version() {
+        Ok(version) => {
+            println!("[Desktop] ✓ WebView2 runtime {version} installed");
+            true
+        }
+        Err(_) => {
+            message_box(
+                "The WebView2 runtime could not be installed.\n\nPlease check your \
+                 internet connection and try again.",
+                MB_OK | MB_ICONERROR,
+            );
+            false
+        }
+    }
+}
+
+/// Look for the bootstrapper next to the executable, then in ./redist.
+#[cfg(target_os = "windows")]
+fn find_bootstrapper() -> Option<std::path::PathBuf> {
+    use std::path::PathBuf;
+
+    let beside_exe = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(BOOTSTRAPPER)));
+    let candidates = [beside_exe, Some(PathBuf::from("redist").join(BOOTSTRAPPER))];
+    candidates.into_iter().flatten().find(|path| path.is_file())
+}
+
+/// Show a native message box (no webview needed) and return the button.
+#[cfg(target_os = "windows")]
+pub(crate) fn message_box(text: &str, flags: u32) -> i32 {
+    let wide = |s: &str| s.encode_utf16().chain(std::iter::once(0)).collect::<Vec<u16>>();
+    let text = wide(text);
+    let caption = wide("Sovereign Academy");
+    unsafe { crate::win32::MessageBoxW(0, text.as_ptr(), caption.as_ptr(), flags) }
+}
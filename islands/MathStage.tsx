@@ -25,6 +25,12 @@ import { validateAnswer } from "../lib/validation.ts";
 export default function MathStage() {
   const showSuccess = useSignal(false);
   const streak = useSignal(0);
+
+  // Desktop: keep the private taskbar preview's streak current.
+  useEffect(() => {
+    // deno-lint-ignore no-explicit-any
+    (globalThis as any).__ipc?.setStreak?.(streak.value);
+  }, [streak.value]);
   const advanceTimer = useSignal<ReturnType<typeof setTimeout> | null>(null);
   const mode = useSignal<"lesson" | "practice">("lesson");
   const lang = useSignal<"EN" | "TH">("EN");
@@ -2,14 +2,69 @@
 //
 // Discord-style draggable title bar with window controls.
 // Communicates with Rust FFI to minimize/maximize/close the native window.
+// In the desktop shell it also declares where its caption buttons are,
+// so Windows treats them as real minimize/maximize/close buttons (snap
+// layouts on the maximize button, accessibility roles). While Windows
+// Magnifier runs the buttons widen, and are re-declared at their new
+// size whenever the shell reports a zoom change.
 
 import { useSignal } from "@preact/signals";
-import { isMaximized } from "../lib/state.ts";
+import { useEffect, useRef } from "preact/hooks";
+import { isMaximized, zoomState } from "../lib/state.ts";
 
 export default function TitleBar() {
   const isHoverMin = useSignal(false);
   const isHoverMax = useSignal(false);
   const isHoverClose = useSignal(false);
+  const minRef = useRef<HTMLButtonElement>(null);
+  const maxRef = useRef<HTMLButtonElement>(null);
+  const closeRef = useRef<HTMLButtonElement>(null);
+
+  // Report caption-button rects (physical px) to the desktop shell,
+  // again whenever the layout may have moved them.
+  useEffect(() => {
+    // deno-lint-ignore no-explicit-any
+    const bridge = (globalThis as any).__ipc;
+    if (!bridge?.declareHitRegions) return;
+
+    const declare = () => {
+      const scale = globalThis.devicePixelRatio || 1;
+      const buttons = [
+        ["minimize", minRef.current],
+        ["maximize", maxRef.current],
+        ["close", closeRef.current],
+      ] as const;
+      bridge.declareHitRegions(
+        buttons.flatMap(([kind, el]) => {
+          if (!el) return [];
+          const r = el.getBoundingClientRect();
+          return [{
+            kind,
+            x: r.left * scale,
+            y: r.top * scale,
+            width: r.width * scale,
+            height: r.height * scale,
+          }];
+        }),
+      );
+    };
+
+    // The buttons' size follows `zoomState`, so re-declare after the
+    // re-render that a zoom report causes.
+    const onZoom = (event: Event) => {
+      zoomState.value = (event as CustomEvent).detail;
+      requestAnimationFrame(declare);
+    };
+
+    declare();
+    globalThis.addEventListener("resize", declare);
+    globalThis.addEventListener("desktop:zoom", onZoom);
+    bridge.requestZoom?.();
+    return () => {
+      globalThis.removeEventListener("resize", declare);
+      globalThis.removeEventListener("desktop:zoom", onZoom);
+    };
+  }, []);
 
   // Window controls — try wry IPC first, then browser fallback
   // deno-lint-ignore no-explicit-any
@@ -41,7 +96,7 @@ export default function TitleBar() {
 
   return (
     <div
-      class="title-bar"
+      class={`title-bar ${zoomState.value?.magnifier ? "title-bar-magnified" : ""}`}
       style={{ WebkitAppRegion: "drag" } as Record<string, string>}
     >
       {/* App icon + name */}
@@ -58,6 +113,7 @@ export default function TitleBar() {
         {/* Minimize */}
         <button
           type="button"
+          ref={minRef}
           class={`title-btn title-btn-minimize ${isHoverMin.value ? "title-btn-hover" : ""}`}
           onClick={handleMinimize}
           onMouseEnter={() => (isHoverMin.value = true)}
@@ -80,6 +136,7 @@ export default function TitleBar() {
         {/* Maximize / Restore */}
         <button
           type="button"
+          ref={maxRef}
           class={`title-btn title-btn-maximize ${isHoverMax.value ? "title-btn-hover" : ""}`}
           onClick={handleMaximize}
           onMouseEnter={() => (isHoverMax.value = true)}
@@ -130,6 +187,7 @@ export default function TitleBar() {
         {/* Close */}
         <button
           type="button"
+          ref={closeRef}
           class={`title-btn title-btn-close ${isHoverClose.value ? "title-btn-close-hover" : ""}`}
           onClick={handleClose}
           onMouseEnter={() => (isHoverClose.value = true)}
@@ -7,6 +7,10 @@
 //   import { NativeWindow } from "../lib/native.ts";
 //   NativeWindow.create(1280, 720);
 //   NativeWindow.minimize();
+//
+// While a window is open this module sends the native watchdog a
+// heartbeat; if Deno's event loop hangs, the beats stop and the native
+// layer shows a page offering the student to reload or quit.
 
 const LIB_SUFFIX: Record<string, string> = {
   windows: "dll",
@@ -20,36 +24,517 @@ const libPath = `./native/target/release/native_window.${suffix}`;
 let lib: Deno.DynamicLibrary<typeof symbols> | null = null;
 
 const symbols = {
-  create_frameless_window: {
-    parameters: ["i32", "i32"],
-    result: "pointer",
+  create_frameless_window_with_url: {
+    parameters: ["buffer", "u32", "i32", "i32"],
+    result: "u32",
+  },
+  create_window_with_options: {
+    parameters: ["buffer", "u32", "buffer", "u32", "i32", "i32", "i32"],
+    result: "u32",
+  },
+  show_window: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  hide_window: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  focus_window: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  request_user_attention: {
+    parameters: ["u32", "i32"],
+    result: "i32",
+  },
+  center_window: {
+    parameters: ["u32"],
+    result: "i32",
   },
   minimize_window: {
-    parameters: [],
-    result: "void",
+    parameters: ["u32"],
+    result: "i32",
   },
   maximize_window: {
-    parameters: [],
-    result: "void",
+    parameters: ["u32"],
+    result: "i32",
   },
   close_window: {
-    parameters: [],
-    result: "void",
+    parameters: ["u32"],
+    result: "i32",
   },
   set_window_title: {
-    parameters: ["buffer", "u32"],
-    result: "void",
+    parameters: ["u32", "buffer", "u32"],
+    result: "i32",
+  },
+  set_window_icon: {
+    parameters: ["u32", "buffer", "u32", "u32", "u32"],
+    result: "i32",
   },
   set_window_size: {
-    parameters: ["i32", "i32"],
-    result: "void",
+    parameters: ["u32", "i32", "i32"],
+    result: "i32",
+  },
+  set_min_size: {
+    parameters: ["u32", "i32", "i32"],
+    result: "i32",
+  },
+  set_max_size: {
+    parameters: ["u32", "i32", "i32"],
+    result: "i32",
   },
   is_maximized: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  is_minimized: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  get_window_size: {
+    parameters: ["u32", "buffer"],
+    result: "i32",
+  },
+  get_outer_window_size: {
+    parameters: ["u32", "buffer"],
+    result: "i32",
+  },
+  set_watchdog_timeout: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  heartbeat: {
+    parameters: [],
+    result: "i32",
+  },
+  webview_navigate: {
+    parameters: ["u32", "buffer", "u32"],
+    result: "i32",
+  },
+  webview_reload: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  webview_go_back: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  webview_go_forward: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  add_init_script: {
+    parameters: ["u32", "buffer", "u32"],
+    result: "i32",
+  },
+  set_init_script: {
+    parameters: ["u32", "buffer", "u32", "i32", "buffer", "u32"],
+    result: "i32",
+  },
+  remove_init_script: {
+    parameters: ["u32", "buffer", "u32"],
+    result: "i32",
+  },
+  webview_eval: {
+    parameters: ["u32", "buffer", "u32"],
+    result: "i32",
+  },
+  set_close_policy: {
+    parameters: ["u32", "i32"],
+    result: "i32",
+  },
+  set_devtools_enabled: {
+    parameters: ["i32"],
+    result: "i32",
+  },
+  set_user_agent: {
+    parameters: ["buffer", "u32"],
+    result: "i32",
+  },
+  set_proxy: {
+    parameters: ["buffer", "u32"],
+    result: "i32",
+  },
+  set_taskbar_progress: {
+    parameters: ["u32", "i32", "u32"],
+    result: "i32",
+  },
+  set_window_shadow: {
+    parameters: ["u32", "i32"],
+    result: "i32",
+  },
+  set_corner_preference: {
+    parameters: ["u32", "i32"],
+    result: "i32",
+  },
+  set_backdrop: {
+    parameters: ["u32", "i32"],
+    result: "i32",
+  },
+  set_titlebar_theme: {
+    parameters: ["u32", "i32", "u32"],
+    result: "i32",
+  },
+  capture_window: {
+    parameters: ["u32", "buffer", "u32"],
+    result: "i32",
+    nonblocking: true,
+  },
+  enter_kiosk_mode: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  exit_kiosk_mode: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  set_overlay_badge: {
+    parameters: ["u32", "buffer", "u32", "u32", "u32"],
+    result: "i32",
+  },
+  register_accelerator: {
+    parameters: ["u32", "buffer", "u32", "u32"],
+    result: "i32",
+  },
+  set_external_link_policy: {
+    parameters: ["u32", "i32"],
+    result: "i32",
+  },
+  set_navigation_policy: {
+    parameters: ["u32", "buffer", "u32"],
+    result: "i32",
+  },
+  clear_browsing_data: {
+    parameters: ["u32", "u32"],
+    result: "i32",
+  },
+  open_devtools: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  close_devtools: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  poll_event: {
+    parameters: ["u32", "buffer", "u32", "buffer"],
+    result: "i32",
+  },
+  start_drag_window: {
+    parameters: ["u32"],
+    result: "i32",
+  },
+  start_resize: {
+    parameters: ["u32", "i32"],
+    result: "i32",
+  },
+  register_scheme_handler: {
+    parameters: ["buffer", "u32", "function"],
+    result: "i32",
+  },
+  respond_scheme_request: {
+    parameters: ["u32", "u32", "buffer", "u32", "buffer", "u32"],
+    result: "i32",
+  },
+  // Waits for the student on a Deno worker thread, not the event loop.
+  show_message_box: {
+    parameters: ["i32", "buffer", "u32", "buffer", "u32"],
+    result: "i32",
+    nonblocking: true,
+  },
+  set_jump_list: {
+    parameters: ["buffer", "u32"],
+    result: "i32",
+  },
+  get_system_theme: {
+    parameters: [],
+    result: "i32",
+  },
+  inhibit_sleep: {
+    parameters: ["buffer", "u32"],
+    result: "i32",
+  },
+  allow_sleep: {
     parameters: [],
     result: "i32",
   },
+  // Windows builds with the "windows" feature only.
+  create_tray: {
+    parameters: ["buffer", "u32", "u32", "u32", "buffer", "u32"],
+    result: "u32",
+    optional: true,
+  },
+  remove_tray: {
+    parameters: ["u32"],
+    result: "i32",
+    optional: true,
+  },
 } as const;
 
+/** Win32 HT* hit-test codes for each resize edge. */
+const RESIZE_EDGES = {
+  left: 10,
+  right: 11,
+  top: 12,
+  topleft: 13,
+  topright: 14,
+  bottom: 15,
+  bottomleft: 16,
+  bottomright: 17,
+} as const;
+
+export type ResizeEdge = keyof typeof RESIZE_EDGES;
+
+/**
+ * A window lifecycle event from `NativeWindow.pollEvents`. Sizes and
+ * positions are physical pixels.
+ */
+export type WindowEvent =
+  | { type: "resized"; width: number; height: number }
+  | { type: "moved"; x: number; y: number }
+  | { type: "focused"; focused: boolean }
+  | { type: "dpi-changed"; scale_factor: number }
+  | { type: "close-requested" }
+  // A link asked for a popup; `opened` if it went to the system browser.
+  | { type: "new-window"; url: string; opened: boolean }
+  // A combo from `registerAccelerator` was pressed.
+  | { type: "accelerator"; id: number }
+  // The OS theme the window follows changed.
+  | { type: "theme-changed"; theme: SystemTheme };
+
+/** The OS theme, from `getSystemTheme` or a "theme-changed" event. */
+export type SystemTheme = "light" | "dark" | "high-contrast";
+
+/** `get_system_theme` results, by code. */
+const SYSTEM_THEMES: Record<number, SystemTheme> = {
+  0: "light",
+  1: "dark",
+  2: "high-contrast",
+};
+
+/** Message box kinds, which set its icon and buttons. */
+const MESSAGE_BOX_KINDS = {
+  info: 0,
+  warning: 1,
+  error: 2,
+  confirm: 3, // OK / Cancel
+  question: 4, // Yes / No
+} as const;
+
+export type MessageBoxKind = keyof typeof MESSAGE_BOX_KINDS;
+
+/** The button pressed in a message box. */
+export type MessageBoxButton = "ok" | "cancel" | "yes" | "no";
+
+const MESSAGE_BOX_BUTTONS: Record<number, MessageBoxButton> = {
+  1: "ok",
+  2: "cancel",
+  3: "yes",
+  4: "no",
+};
+
+/** One line of the tray icon's right-click menu. */
+export type TrayMenuItem =
+  | { id: string; label: string; enabled?: boolean }
+  | { separator: true };
+
+/** A click on the tray icon or one of its menu items. */
+export type TrayEvent =
+  | { type: "tray-click" }
+  | { type: "menu-click"; id: string };
+
+/**
+ * An entry of the taskbar button's jump list; it starts the app again
+ * with `arguments` on its command line.
+ */
+export interface JumpListTask {
+  title: string;
+  arguments: string;
+  /** Hover text. */
+  description?: string;
+}
+
+/**
+ * Starting room for one encoded event. Most fit; `drainEvents` grows
+ * the buffer for longer ones (a "new-window" URL or a tray item id).
+ */
+const EVENT_BUFFER_BYTES = 256;
+
+/** How urgently `NativeWindow.requestAttention` flashes the taskbar. */
+const ATTENTION_LEVELS = {
+  none: 0,
+  informational: 1,
+  critical: 2,
+} as const;
+
+export type AttentionLevel = keyof typeof ATTENTION_LEVELS;
+
+/** How `NativeWindow.setTaskbarProgress` shows progress. */
+const TASKBAR_PROGRESS_STATES = {
+  none: 0,
+  indeterminate: 1,
+  normal: 2,
+  error: 3,
+  paused: 4,
+} as const;
+
+export type TaskbarProgressState = keyof typeof TASKBAR_PROGRESS_STATES;
+
+/** How `NativeWindow.setCorners` draws the window's corners. */
+const CORNER_PREFERENCES = {
+  default: 0,
+  square: 1,
+  round: 2,
+  "round-small": 3,
+} as const;
+
+export type CornerPreference = keyof typeof CORNER_PREFERENCES;
+
+/** What `NativeWindow.setBackdrop` draws behind the page. */
+const BACKDROPS = {
+  none: 0,
+  mica: 1,
+  acrylic: 2,
+} as const;
+
+export type Backdrop = keyof typeof BACKDROPS;
+
+/** What `NativeWindow.clearBrowsingData` wipes, as native mask bits. */
+const BROWSING_DATA_KINDS = {
+  cache: 1,
+  storage: 2,
+  cookies: 4,
+} as const;
+
+/**
+ * Browsing data of a profile: "cache" (cached lesson assets), "storage"
+ * (localStorage, IndexedDB) or "cookies".
+ */
+export type BrowsingDataKind = keyof typeof BROWSING_DATA_KINDS;
+
+/** What a scheme handler answers; `status` defaults to 200. */
+export interface SchemeResponse {
+  status?: number;
+  mimeType: string;
+  body: Uint8Array | string;
+}
+
+/** Serves one request of a registered scheme, given its full URL. */
+export type SchemeHandler = (
+  url: string,
+) => SchemeResponse | Promise<SchemeResponse>;
+
+/** The native side calls this with a request id and its URL. */
+const SCHEME_CALLBACK = {
+  parameters: ["u32", "pointer", "u32"],
+  result: "void",
+} as const;
+
+/** Registered callbacks, kept alive for the webviews that use them. */
+const schemeCallbacks: Deno.UnsafeCallback<typeof SCHEME_CALLBACK>[] = [];
+
+/** A window size in physical pixels. */
+export interface WindowSize {
+  width: number;
+  height: number;
+}
+
+/**
+ * Status codes returned by the native window functions. Queries such as
+ * `is_maximized` return their answer instead of `OK`; errors are always
+ * negative.
+ */
+export const NativeStatus = {
+  OK: 0,
+  /** The window was closed (or the handle never existed). */
+  INVALID_HANDLE: -1,
+  INVALID_ARGUMENT: -2,
+  /** The native UI thread is not running. */
+  UI_UNAVAILABLE: -3,
+  /** The webview refused the request. */
+  WEBVIEW_FAILED: -4,
+  BUFFER_TOO_SMALL: -5,
+  /** The OS refused, e.g. a drag with no mouse button down. */
+  OS_ERROR: -6,
+  /** Not in this build, e.g. native dialogs without "windows". */
+  UNSUPPORTED: -7,
+} as const;
+
+/**
+ * Handle of the window created by `NativeWindow.create`. Handles are
+ * opaque and owned by the native library; 0 means "no window".
+ */
+let handle = 0;
+
+/** Page a new window loads: the Fresh server. */
+export const DEFAULT_URL = "http://localhost:8000";
+
+/** Default time without a heartbeat before the app counts as hung. */
+export const WATCHDOG_TIMEOUT_MS = 10_000;
+
+/** Interval timer sending heartbeats, while the watchdog is armed. */
+let heartbeatTimer: number | null = null;
+
+function stopHeartbeat(): void {
+  if (heartbeatTimer !== null) {
+    clearInterval(heartbeatTimer);
+    heartbeatTimer = null;
+  }
+}
+
+/**
+ * Check a native call's status. An invalid handle means the window is
+ * gone (e.g. the student pressed Alt+F4): forget it and disarm the
+ * watchdog so later calls are quiet no-ops instead of errors.
+ */
+function checkStatus(action: string, status: number): void {
+  if (status >= NativeStatus.OK) return;
+  if (status === NativeStatus.INVALID_HANDLE) {
+    if (handle !== 0) {
+      console.warn(`[Native] ${action}: window ${handle} is closed`);
+      handle = 0;
+      NativeWindow.setWatchdog(0);
+    }
+    return;
+  }
+  console.warn(`[Native] ${action} failed with status ${status}`);
+}
+
+/**
+ * Poll every pending event of a window or tray handle. Also returns
+ * the status that ended the drain: 0 once the queue is empty. An event
+ * longer than the buffer is retried with one large enough for it.
+ */
+function drainEvents<T>(target: number): { events: T[]; status: number } {
+  const poll = loadLibrary().symbols.poll_event;
+  let buf = new Uint8Array(EVENT_BUFFER_BYTES);
+  const needed = new Uint32Array(1);
+  const decoder = new TextDecoder();
+  const events: T[] = [];
+  for (;;) {
+    const status = poll(target, buf, buf.length, needed);
+    if (status === NativeStatus.BUFFER_TOO_SMALL && needed[0] > buf.length) {
+      buf = new Uint8Array(needed[0]);
+      continue;
+    }
+    if (status <= 0) return { events, status };
+    events.push(JSON.parse(decoder.decode(buf.subarray(0, status))));
+  }
+}
+
+/** Read a size through a `get_*_size` symbol; null on failure. */
+function readSize(
+  action: string,
+  query: (handle: number, out: Uint32Array) => number,
+): WindowSize | null {
+  const out = new Uint32Array(2);
+  const status = query(handle, out);
+  checkStatus(action, status);
+  if (status !== NativeStatus.OK) return null;
+  return { width: out[0], height: out[1] };
+}
+
 function loadLibrary(): Deno.DynamicLibrary<typeof symbols> {
   if (!lib) {
     try {
@@ -69,20 +554,89 @@ function loadLibrary(): Deno.DynamicLibrary<typeof symbols> {
 }
 
 export const NativeWindow = {
-  /** Create a frameless window. Returns the window handle pointer. */
-  create(width = 1280, height = 720): Deno.PointerObject | null {
+  /**
+   * Create a frameless window and arm the hang watchdog (0 disables it).
+   * The webview loads `url` (an http(s) URL or one of a scheme from
+   * `registerSchemeHandler`; the Fresh server by default; "" leaves
+   * the webview blank until `navigate`). With
+   * `visible` false the window starts hidden until `show`, e.g. to
+   * skip the blank first paint while the page loads.
+   * `dataDirectory` is the folder for the webview's cookies and
+   * localStorage, so each student profile gets its own ("" shares the
+   * default folder).
+   * Returns the window's handle, or null on failure.
+   */
+  create(
+    width = 1280,
+    height = 720,
+    watchdogMs = WATCHDOG_TIMEOUT_MS,
+    url = DEFAULT_URL,
+    visible = true,
+    dataDirectory = "",
+  ): number | null {
     try {
-      const nativeLib = loadLibrary();
-      return nativeLib.symbols.create_frameless_window(width, height);
+      const encoder = new TextEncoder();
+      const buf = encoder.encode(url);
+      const dir = encoder.encode(dataDirectory);
+      handle = loadLibrary().symbols.create_window_with_options(
+        buf,
+        buf.length,
+        dir,
+        dir.length,
+        width,
+        height,
+        visible ? 1 : 0,
+      );
+      if (handle === 0) return null;
+      NativeWindow.setWatchdog(watchdogMs);
+      return handle;
     } catch {
       return null;
     }
   },
 
+  /**
+   * Arm the watchdog: heartbeats go out every quarter of `timeoutMs`,
+   * and the native layer steps in if they stop for `timeoutMs`.
+   * 0 disarms it.
+   */
+  setWatchdog(timeoutMs: number): void {
+    try {
+      const native = loadLibrary().symbols;
+      stopHeartbeat();
+      native.set_watchdog_timeout(timeoutMs);
+      if (timeoutMs > 0) {
+        heartbeatTimer = setInterval(() => native.heartbeat(), timeoutMs / 4);
+        // Heartbeats alone must not keep Deno running.
+        Deno.unrefTimer(heartbeatTimer);
+      }
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Show a window created hidden (or hidden with `hide`). */
+  show(): void {
+    try {
+      checkStatus("show", loadLibrary().symbols.show_window(handle));
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Hide the window without closing it. */
+  hide(): void {
+    try {
+      checkStatus("hide", loadLibrary().symbols.hide_window(handle));
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
   /** Minimize the window to the taskbar. */
   minimize(): void {
     try {
-      loadLibrary().symbols.minimize_window();
+      checkStatus("minimize", loadLibrary().symbols.minimize_window(handle));
     } catch {
       // Browser-only mode - no-op
     }
@@ -91,7 +645,7 @@ export const NativeWindow = {
   /** Toggle maximize/restore. */
   maximize(): void {
     try {
-      loadLibrary().symbols.maximize_window();
+      checkStatus("maximize", loadLibrary().symbols.maximize_window(handle));
     } catch {
       // Browser-only mode - no-op
     }
@@ -100,7 +654,9 @@ export const NativeWindow = {
   /** Close and destroy the window. */
   close(): void {
     try {
-      loadLibrary().symbols.close_window();
+      loadLibrary().symbols.close_window(handle);
+      handle = 0;
+      NativeWindow.setWatchdog(0);
     } catch {
       // Browser-only mode - no-op
     }
@@ -111,7 +667,67 @@ export const NativeWindow = {
     try {
       const encoder = new TextEncoder();
       const buf = encoder.encode(title);
-      loadLibrary().symbols.set_window_title(buf, buf.length);
+      checkStatus(
+        "setTitle",
+        loadLibrary().symbols.set_window_title(handle, buf, buf.length),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Bring the window to the front and focus it. */
+  focus(): void {
+    try {
+      checkStatus("focus", loadLibrary().symbols.focus_window(handle));
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Flash the taskbar entry until the window is focused, e.g. when a
+   * lesson reminder fires in the background; "none" stops it.
+   */
+  requestAttention(level: AttentionLevel = "informational"): void {
+    try {
+      checkStatus(
+        "requestAttention",
+        loadLibrary().symbols.request_user_attention(
+          handle,
+          ATTENTION_LEVELS[level],
+        ),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Center the window on the monitor under the mouse cursor. */
+  center(): void {
+    try {
+      checkStatus("center", loadLibrary().symbols.center_window(handle));
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Set the taskbar/title bar icon from `width` x `height` RGBA pixels,
+   * e.g. the student's profile avatar.
+   */
+  setIcon(rgba: Uint8Array, width: number, height: number): void {
+    try {
+      checkStatus(
+        "setIcon",
+        loadLibrary().symbols.set_window_icon(
+          handle,
+          rgba,
+          rgba.length,
+          width,
+          height,
+        ),
+      );
     } catch {
       // Browser-only mode - no-op
     }
@@ -120,7 +736,60 @@ export const NativeWindow = {
   /** Resize the window. */
   setSize(width: number, height: number): void {
     try {
-      loadLibrary().symbols.set_window_size(width, height);
+      checkStatus(
+        "setSize",
+        loadLibrary().symbols.set_window_size(handle, width, height),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Stop the window shrinking below `width` x `height`, e.g. where the
+   * lesson layout breaks. Pass null to remove the limit.
+   */
+  setMinSize(size: WindowSize | null): void {
+    try {
+      const { width, height } = size ?? { width: 0, height: 0 };
+      checkStatus(
+        "setMinSize",
+        loadLibrary().symbols.set_min_size(handle, width, height),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Stop the window growing past `size`; null removes the limit. */
+  setMaxSize(size: WindowSize | null): void {
+    try {
+      const { width, height } = size ?? { width: 0, height: 0 };
+      checkStatus(
+        "setMaxSize",
+        loadLibrary().symbols.set_max_size(handle, width, height),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Start moving the window (call on title bar mousedown). */
+  startDrag(): void {
+    try {
+      checkStatus("startDrag", loadLibrary().symbols.start_drag_window(handle));
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Start a native resize drag from an edge (call on mousedown). */
+  startResize(edge: ResizeEdge): void {
+    try {
+      checkStatus(
+        "startResize",
+        loadLibrary().symbols.start_resize(handle, RESIZE_EDGES[edge]),
+      );
     } catch {
       // Browser-only mode - no-op
     }
@@ -129,17 +798,742 @@ export const NativeWindow = {
   /** Check if the window is maximized. */
   isMaximized(): boolean {
     try {
-      return loadLibrary().symbols.is_maximized() === 1;
+      const status = loadLibrary().symbols.is_maximized(handle);
+      checkStatus("isMaximized", status);
+      return status === 1;
     } catch {
       return false;
     }
   },
 
+  /** Check if the window is minimized to the taskbar. */
+  isMinimized(): boolean {
+    try {
+      const status = loadLibrary().symbols.is_minimized(handle);
+      checkStatus("isMinimized", status);
+      return status === 1;
+    } catch {
+      return false;
+    }
+  },
+
+  /** The window's inner (content) size, or null without a window. */
+  getSize(): WindowSize | null {
+    try {
+      return readSize("getSize", loadLibrary().symbols.get_window_size);
+    } catch {
+      return null;
+    }
+  },
+
+  /** The window's outer size including any frame, or null. */
+  getOuterSize(): WindowSize | null {
+    try {
+      const native = loadLibrary().symbols;
+      return readSize("getOuterSize", native.get_outer_window_size);
+    } catch {
+      return null;
+    }
+  },
+
+  /**
+   * Load an http(s) URL, or one of a scheme from `registerSchemeHandler`,
+   * in the webview, e.g. a lesson route.
+   */
+  navigate(url: string): void {
+    try {
+      const buf = new TextEncoder().encode(url);
+      checkStatus(
+        "navigate",
+        loadLibrary().symbols.webview_navigate(handle, buf, buf.length),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Reload the current page. */
+  reload(): void {
+    try {
+      checkStatus("reload", loadLibrary().symbols.webview_reload(handle));
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Go back one page in the webview's history. */
+  goBack(): void {
+    try {
+      checkStatus("goBack", loadLibrary().symbols.webview_go_back(handle));
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Go forward one page in the webview's history. */
+  goForward(): void {
+    try {
+      checkStatus(
+        "goForward",
+        loadLibrary().symbols.webview_go_forward(handle),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Run a script in the page, e.g. to push a theme change. It runs
+   * asynchronously; its result is not returned.
+   */
+  evalScript(script: string): void {
+    try {
+      const buf = new TextEncoder().encode(script);
+      checkStatus(
+        "evalScript",
+        loadLibrary().symbols.webview_eval(handle, buf, buf.length),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Keep the window open when the student closes it from the OS
+   * (Alt+F4) and only report "close-requested" through `pollEvents`,
+   * e.g. to confirm quitting mid-quiz before calling `close`.
+   */
+  interceptClose(intercept: boolean): void {
+    try {
+      checkStatus(
+        "interceptClose",
+        loadLibrary().symbols.set_close_policy(handle, intercept ? 1 : 0),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Run `script` at the start of every page loaded from now on, before
+   * the page's own scripts (e.g. the `__DESKTOP__` bridge). To cover the
+   * first page, create the window with url "", add the scripts, then
+   * `navigate`. Windows only; elsewhere it fails with `UNSUPPORTED`.
+   */
+  addInitScript(script: string): void {
+    try {
+      const buf = new TextEncoder().encode(script);
+      checkStatus(
+        "addInitScript",
+        loadLibrary().symbols.add_init_script(handle, buf, buf.length),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Add or replace the init script `name`. Scripts run by ascending
+   * `order` (ties in the order first set), so e.g. the IPC bridge,
+   * resize handles and exam lockdown stay independent. Changes apply
+   * from the next page. Windows only, like `addInitScript`.
+   */
+  setInitScript(name: string, script: string, order = 0): void {
+    try {
+      const encoder = new TextEncoder();
+      const nameBuf = encoder.encode(name);
+      const buf = encoder.encode(script);
+      checkStatus(
+        "setInitScript",
+        loadLibrary().symbols.set_init_script(
+          handle,
+          nameBuf,
+          nameBuf.length,
+          order,
+          buf,
+          buf.length,
+        ),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Stop injecting the init script `name` from the next page on. */
+  removeInitScript(name: string): void {
+    try {
+      const buf = new TextEncoder().encode(name);
+      checkStatus(
+        "removeInitScript",
+        loadLibrary().symbols.remove_init_script(handle, buf, buf.length),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Fill the window's taskbar button like a progress bar, e.g. with the
+   * current lesson's completion; `percent` (0-100) is ignored for
+   * "none" and "indeterminate". Windows only.
+   */
+  setTaskbarProgress(state: TaskbarProgressState, percent = 0): void {
+    try {
+      checkStatus(
+        "setTaskbarProgress",
+        loadLibrary().symbols.set_taskbar_progress(
+          handle,
+          TASKBAR_PROGRESS_STATES[state],
+          Math.round(Math.min(100, Math.max(0, percent))),
+        ),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Show a small badge over the window's taskbar button from `width` x
+   * `height` RGBA pixels, e.g. the number of reviews due; `null` removes
+   * it. Drawn at 16 x 16. Windows only.
+   */
+  setOverlayBadge(
+    rgba: Uint8Array | null,
+    width = 0,
+    height = 0,
+  ): void {
+    const pixels = rgba ?? new Uint8Array(0);
+    try {
+      checkStatus(
+        "setOverlayBadge",
+        loadLibrary().symbols.set_overlay_badge(
+          handle,
+          pixels,
+          pixels.length,
+          width,
+          height,
+        ),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Turn the window's drop shadow on or off (on by default in builds
+   * with the "windows" feature). Windows only.
+   */
+  setShadow(enabled: boolean): void {
+    try {
+      checkStatus(
+        "setShadow",
+        loadLibrary().symbols.set_window_shadow(handle, enabled ? 1 : 0),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Round or square the window's corners. Windows 11 only. */
+  setCorners(preference: CornerPreference): void {
+    try {
+      checkStatus(
+        "setCorners",
+        loadLibrary().symbols.set_corner_preference(
+          handle,
+          CORNER_PREFERENCES[preference],
+        ),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Draw Mica or Acrylic behind the page, visible wherever the page's
+   * own background is transparent; "none" makes the webview opaque
+   * white again. Windows 11 only.
+   */
+  setBackdrop(backdrop: Backdrop): void {
+    try {
+      checkStatus(
+        "setBackdrop",
+        loadLibrary().symbols.set_backdrop(handle, BACKDROPS[backdrop]),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Make the window frame, which Windows shows while minimizing and
+   * restoring, dark or light, with `color` (0xRRGGBB) as its caption
+   * color; null keeps the system color. Windows only (the color on
+   * Windows 11).
+   */
+  setTitlebarTheme(dark: boolean, color: number | null = null): void {
+    try {
+      checkStatus(
+        "setTitlebarTheme",
+        loadLibrary().symbols.set_titlebar_theme(
+          handle,
+          dark ? 1 : 0,
+          color ?? 0xffffffff,
+        ),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Save what the webview shows as a PNG file at `path`, e.g. to attach
+   * to a problem report. Resolves to false if it could not be captured
+   * (always outside Windows).
+   */
+  async capture(path: string): Promise<boolean> {
+    try {
+      const buf = new TextEncoder().encode(path);
+      const status = await loadLibrary().symbols.capture_window(
+        handle,
+        buf,
+        buf.length,
+      );
+      checkStatus("capture", status);
+      return status === NativeStatus.OK;
+    } catch {
+      return false;
+    }
+  },
+
+  /**
+   * Lock the window down for a timed assessment: fullscreen, always on
+   * top, no context menu, and no navigation away from the current page's
+   * origin. Call once the assessment page is loaded. Windows only;
+   * elsewhere the window is left unchanged.
+   */
+  enterKioskMode(): void {
+    try {
+      checkStatus(
+        "enterKioskMode",
+        loadLibrary().symbols.enter_kiosk_mode(handle),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Leave kiosk mode, restoring the window as it was. */
+  exitKioskMode(): void {
+    try {
+      checkStatus(
+        "exitKioskMode",
+        loadLibrary().symbols.exit_kiosk_mode(handle),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Take a key combo such as "Ctrl+R" or "F11" from the page and report
+   * it through `pollEvents` as `{ type: "accelerator", id }`, so app
+   * shortcuts work while the webview has focus. Combos need Ctrl or Alt
+   * unless the key types no character. Windows only.
+   */
+  registerAccelerator(combo: string, id: number): void {
+    try {
+      const buf = new TextEncoder().encode(combo);
+      checkStatus(
+        "registerAccelerator",
+        loadLibrary().symbols.register_accelerator(
+          handle,
+          buf,
+          buf.length,
+          id,
+        ),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Choose where links that ask for a new window (`target="_blank"`)
+   * go: "browser" opens them in the system browser, "block" drops them
+   * (e.g. in exam mode). Either way `pollEvents` reports "new-window".
+   */
+  setExternalLinkPolicy(policy: "browser" | "block"): void {
+    try {
+      checkStatus(
+        "setExternalLinkPolicy",
+        loadLibrary().symbols.set_external_link_policy(
+          handle,
+          policy === "block" ? 1 : 0,
+        ),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Keep the webview on the given hosts, e.g. `["localhost"]`;
+   * "*.example.org" allows the subdomains of example.org. Schemes from
+   * `registerSchemeHandler` are always allowed. Navigations to other
+   * hosts are blocked everywhere, subresource loads on Windows only.
+   * null lifts the restriction.
+   */
+  setNavigationPolicy(allowlist: string[] | null): void {
+    try {
+      const buf = new TextEncoder().encode(
+        allowlist === null ? "" : JSON.stringify(allowlist),
+      );
+      checkStatus(
+        "setNavigationPolicy",
+        loadLibrary().symbols.set_navigation_policy(handle, buf, buf.length),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Wipe the window profile's browsing data, e.g. when a parent resets
+   * the student's profile. Outside Windows only all kinds at once are
+   * supported.
+   */
+  clearBrowsingData(
+    kinds: BrowsingDataKind[] = ["cache", "storage", "cookies"],
+  ): void {
+    const mask = kinds.reduce(
+      (bits, kind) => bits | BROWSING_DATA_KINDS[kind],
+      0,
+    );
+    try {
+      checkStatus(
+        "clearBrowsingData",
+        loadLibrary().symbols.clear_browsing_data(handle, mask),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Allow the web inspector in windows created after this call, so
+   * content authors can inspect lessons in a release build. Call it
+   * before `create` (e.g. behind a `--devtools` launcher flag).
+   */
+  setDevtoolsEnabled(enabled: boolean): void {
+    try {
+      checkStatus(
+        "setDevtoolsEnabled",
+        loadLibrary().symbols.set_devtools_enabled(enabled ? 1 : 0),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Set the webview user-agent string of windows created after this
+   * call; "" restores the default. Call it before `create`.
+   */
+  setUserAgent(userAgent: string): void {
+    try {
+      const buf = new TextEncoder().encode(userAgent);
+      checkStatus(
+        "setUserAgent",
+        loadLibrary().symbols.set_user_agent(buf, buf.length),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Route windows created after this call through a proxy, for school
+   * networks that require one: `http://host:port` (HTTP CONNECT) or
+   * `socks5://host:port`; "" connects directly. Call it before `create`.
+   */
+  setProxy(url: string): void {
+    try {
+      const buf = new TextEncoder().encode(url);
+      checkStatus("setProxy", loadLibrary().symbols.set_proxy(buf, buf.length));
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Open the web inspector. Fails with `UNSUPPORTED` unless the window
+   * was created after `setDevtoolsEnabled(true)`.
+   */
+  openDevtools(): void {
+    try {
+      checkStatus("openDevtools", loadLibrary().symbols.open_devtools(handle));
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /** Close the web inspector (a no-op on Windows). */
+  closeDevtools(): void {
+    try {
+      checkStatus(
+        "closeDevtools",
+        loadLibrary().symbols.close_devtools(handle),
+      );
+    } catch {
+      // Browser-only mode - no-op
+    }
+  },
+
+  /**
+   * Drain the window's pending events, oldest first. After the student
+   * closes the window from the OS (Alt+F4), the last event is
+   * "close-requested" and the window is forgotten, unless
+   * `interceptClose` keeps it open.
+   */
+  pollEvents(): WindowEvent[] {
+    if (handle === 0) return [];
+    try {
+      const { events, status } = drainEvents<WindowEvent>(handle);
+      checkStatus("pollEvents", status);
+      return events;
+    } catch {
+      return []; // Browser-only mode - no events
+    }
+  },
+
   /** Cleanup: unload the library. */
   destroy(): void {
+    stopHeartbeat();
     if (lib) {
       lib.close();
       lib = null;
     }
   },
 };
+
+/** Handle of the icon created by `NativeTray.create`; 0 = none. */
+let trayHandle = 0;
+
+/**
+ * The notification-area icon (Windows builds with the "windows"
+ * feature), e.g. to offer show / hide / quit from its menu.
+ */
+export const NativeTray = {
+  /**
+   * Show `width` x `height` RGBA pixels as the tray icon with `menu` on
+   * right click, replacing any previous icon. Returns its handle, or
+   * null where trays are unsupported.
+   */
+  create(
+    rgba: Uint8Array,
+    width: number,
+    height: number,
+    menu: TrayMenuItem[],
+  ): number | null {
+    try {
+      const native = loadLibrary().symbols;
+      if (!native.create_tray) return null;
+      NativeTray.remove();
+      const menuBuf = new TextEncoder().encode(JSON.stringify(menu));
+      trayHandle = native.create_tray(
+        rgba,
+        rgba.length,
+        width,
+        height,
+        menuBuf,
+        menuBuf.length,
+      );
+      return trayHandle === 0 ? null : trayHandle;
+    } catch {
+      return null;
+    }
+  },
+
+  /** Drain the icon's pending clicks, oldest first. */
+  pollEvents(): TrayEvent[] {
+    if (trayHandle === 0) return [];
+    try {
+      const { events, status } = drainEvents<TrayEvent>(trayHandle);
+      if (status < 0) {
+        console.warn(`[Native] Tray pollEvents failed with status ${status}`);
+        if (status === NativeStatus.INVALID_HANDLE) trayHandle = 0;
+      }
+      return events;
+    } catch {
+      return [];
+    }
+  },
+
+  /** Remove the tray icon. */
+  remove(): void {
+    if (trayHandle === 0) return;
+    try {
+      loadLibrary().symbols.remove_tray?.(trayHandle);
+    } catch {
+      // Browser-only mode - no-op
+    }
+    trayHandle = 0;
+  },
+};
+
+/**
+ * Show a native message box, drawn by the OS so it works even if the
+ * webview is unresponsive (e.g. an unsaved quiz, a corrupted profile).
+ * Resolves to the button pressed, or null where there is no native
+ * dialog (the caller should fall back to its own UI).
+ */
+export async function showMessageBox(
+  kind: MessageBoxKind,
+  title: string,
+  text: string,
+): Promise<MessageBoxButton | null> {
+  try {
+    const encoder = new TextEncoder();
+    const titleBuf = encoder.encode(title);
+    const textBuf = encoder.encode(text);
+    const result = await loadLibrary().symbols.show_message_box(
+      MESSAGE_BOX_KINDS[kind],
+      titleBuf,
+      titleBuf.length,
+      textBuf,
+      textBuf.length,
+    );
+    return MESSAGE_BOX_BUTTONS[result] ?? null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * The OS theme, so the UI can follow it; windows report changes as
+ * "theme-changed" events. Null where it is unknown (no native library,
+ * or no open window outside Windows).
+ */
+export function getSystemTheme(): SystemTheme | null {
+  try {
+    return SYSTEM_THEMES[loadLibrary().symbols.get_system_theme()] ?? null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Keep the display and system awake, e.g. while a timed exam runs,
+ * until `allowSleep`; `reason` is shown where the OS lists who keeps it
+ * awake. Returns false where sleep cannot be inhibited.
+ */
+export function inhibitSleep(reason: string): boolean {
+  try {
+    const buf = new TextEncoder().encode(reason);
+    const status = loadLibrary().symbols.inhibit_sleep(buf, buf.length);
+    return status === NativeStatus.OK;
+  } catch {
+    return false;
+  }
+}
+
+/** Let the system sleep again after `inhibitSleep`. */
+export function allowSleep(): void {
+  try {
+    loadLibrary().symbols.allow_sleep();
+  } catch {
+    // Browser-only mode - no-op
+  }
+}
+
+/**
+ * Offer `tasks` (e.g. "Resume lesson", "Parent dashboard") when the
+ * student right-clicks the taskbar button; an empty list removes them.
+ * Windows keeps them after the app exits. Returns false where jump
+ * lists are unsupported or the list could not be written.
+ */
+export function setJumpList(tasks: JumpListTask[]): boolean {
+  try {
+    const buf = new TextEncoder().encode(JSON.stringify(tasks));
+    const status = loadLibrary().symbols.set_jump_list(buf, buf.length);
+    return status === NativeStatus.OK;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Serve `scheme://` URLs (e.g. `app://localhost/lesson.html`) from
+ * Deno instead of an HTTP server on localhost. Applies to windows
+ * created afterwards, which may then load such URLs. A request the
+ * handler has not answered within 30 seconds gets a 504. Returns false if
+ * the scheme was rejected (built-in schemes such as http cannot be
+ * replaced) or there is no native library.
+ */
+export function registerSchemeHandler(
+  scheme: string,
+  handler: SchemeHandler,
+): boolean {
+  try {
+    const native = loadLibrary().symbols;
+    const decoder = new TextDecoder();
+    const callback = Deno.UnsafeCallback.threadSafe(
+      SCHEME_CALLBACK,
+      (request, urlPtr, urlLen) => {
+        // The URL is only valid during the call, so decode it now.
+        const url = urlPtr === null ? "" : decoder.decode(
+          new Deno.UnsafePointerView(urlPtr).getArrayBuffer(urlLen),
+        );
+        void answerSchemeRequest(handler, request, url);
+      },
+    );
+    // Pending requests alone must not keep Deno running.
+    callback.unref();
+    const buf = new TextEncoder().encode(scheme);
+    const status = native.register_scheme_handler(
+      buf,
+      buf.length,
+      callback.pointer,
+    );
+    if (status !== NativeStatus.OK) {
+      callback.close();
+      return false;
+    }
+    schemeCallbacks.push(callback);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/** Run `handler` for one request and send its answer to the webview. */
+async function answerSchemeRequest(
+  handler: SchemeHandler,
+  request: number,
+  url: string,
+): Promise<void> {
+  let response: SchemeResponse;
+  try {
+    response = await handler(url);
+  } catch (error) {
+    console.warn(`[Native] scheme handler failed for ${url}:`, error);
+    response = { status: 500, mimeType: "text/plain", body: "" };
+  }
+  const encoder = new TextEncoder();
+  const mime = encoder.encode(response.mimeType);
+  const body = typeof response.body === "string"
+    ? encoder.encode(response.body)
+    : response.body;
+  const status = loadLibrary().symbols.respond_scheme_request(
+    request,
+    response.status ?? 200,
+    mime,
+    mime.length,
+    body,
+    body.length,
+  );
+  if (status !== NativeStatus.OK) {
+    console.warn(`[Native] answering ${url} failed with status ${status}`);
+  }
+}
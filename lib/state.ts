@@ -526,10 +526,29 @@ export const isLoading = signal<boolean>(false);
 /** Student's typed answer. */
 export const studentAnswer = signal<string>("");
 
+/** Structured hint from the math engine. */
+export interface HintData {
+  kind: "correct" | "input" | "strategy" | "misconception" | "error" | "review";
+  /** Answer part the hint is about (list item, chosen option), 0-based. */
+  step_index: number | null;
+  /** Problem operands to highlight, as written in the problem. */
+  referenced_operands: string[];
+  /** Hint-catalog ID of the template (same as `hint_id`). */
+  template_id: string;
+  /** Values for the template's `{placeholders}`. */
+  params: Record<string, string>;
+}
+
 /** Result of last validation. */
 export interface ValidationResult {
   correct: boolean;
   hint: string;
+  /** Stable hint-catalog ID (e.g. "fraction.simplify"); key translations on this. */
+  hint_id?: string;
+  /** The hint as data, for rendering it interactively. */
+  hint_data?: HintData;
+  /** Misconception behind a wrong multiple-choice pick (e.g. "forgotten_carry"). */
+  misconception?: string;
   problem: string;
   answer: string;
 }
@@ -560,6 +579,18 @@ export const isMaximized = signal<boolean>(false);
 /** Whether the sidebar is collapsed (mobile). */
 export const sidebarCollapsed = signal<boolean>(false);
 
+/** Windows Magnifier / text-size state (synced from Rust). */
+export interface ZoomState {
+  magnifier: boolean;
+  /** System text size in percent (100 = default). */
+  textScale: number;
+  /** Zoom the desktop shell applied to the app (1 = none). */
+  zoom: number;
+}
+
+/** Latest `desktop:zoom` report; `null` outside the desktop shell. */
+export const zoomState = signal<ZoomState | null>(null);
+
 // ─── Actions ─────────────────────────────────────────────────────────
 
 /** Select a topic and reset exercise index. */
@@ -15,5 +15,28 @@ export interface MathWasm {
   validate_arithmetic: (expr: string, answer: number) => boolean;
   validate_fraction: (en: bigint, ed: bigint, sn: bigint, sd: bigint) => boolean;
   simplify_fraction: (numerator: bigint, denominator: bigint) => BigInt64Array;
-  batch_validate: (problems: string, answers: string) => number;
+  /** JSON `{"results":[…]}` of per-problem results (see `BatchResult`). */
+  batch_validate: (problems: string, answers: string) => string;
+}
+
+/** What `batch_validate` returns. */
+export interface BatchResult {
+  results: BatchItemResult[];
+  result_schema_version: number;
+}
+
+/** One entry of `BatchResult.results`. */
+export interface BatchItemResult {
+  problem: string | null;
+  /** The answer normalized ("5.0" → "5"); null if it is not a number. */
+  answer: string | null;
+  correct: boolean;
+  error:
+    | null
+    | "invalid_problem"
+    | "invalid_answer"
+    | "missing_problem"
+    | "missing_answer";
+  hint: string;
+  hint_id: string;
 }
@@ -42,7 +42,7 @@ function createMockWasm(correctAnswer: string): MathWasm {
     validate_arithmetic: (_expr: string, _answer: number) => true,
     validate_fraction: () => true,
     simplify_fraction: () => new BigInt64Array([1n, 2n]),
-    batch_validate: () => 0,
+    batch_validate: () => '{"result_schema_version":2,"results":[]}',
   };
 }
 
@@ -100,11 +100,10 @@ function createCorrectWasm() {
       });
     },
 
-    batch_validate(problems: string, answers: string): number {
+    batch_validate(problems: string, answers: string): string {
       const probs = problems.split(";");
       const ans = answers.split(";");
-      if (probs.length !== ans.length) return 0;
-      let count = 0;
+      const results = [];
       for (let i = 0; i < probs.length; i++) {
         const parts = probs[i].trim().split(/\s+/);
         const a = parseFloat(parts[0]);
@@ -127,9 +126,10 @@ function createCorrectWasm() {
           default:
             result = NaN;
         }
-        if (Math.abs(result - parseFloat(ans[i].trim())) < 1e-9) count++;
+        const correct = Math.abs(result - parseFloat(ans[i]?.trim())) < 1e-9;
+        results.push({ problem: probs[i].trim(), correct });
       }
-      return count;
+      return JSON.stringify({ results, result_schema_version: 2 });
     },
   };
 }
@@ -164,8 +164,8 @@ function createBrokenWasm() {
         answer,
       });
     },
-    batch_validate(_problems: string, _answers: string): number {
-      return 0;
+    batch_validate(_problems: string, _answers: string): string {
+      return '{"result_schema_version":2,"results":[]}';
     },
   };
 }
@@ -234,7 +234,7 @@ describe("runHealthCheck", () => {
       check_answer(): string {
         throw new Error("WASM crashed");
       },
-      batch_validate(): number {
+      batch_validate(): string {
         throw new Error("WASM crashed");
       },
     };
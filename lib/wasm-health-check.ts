@@ -9,7 +9,7 @@
 // and display an error modal instead.
 // ═══════════════════════════════════════════════════════════════════
 
-import type { MathWasm } from "./types.ts";
+import type { BatchResult, MathWasm } from "./types.ts";
 
 export type { MathWasm };
 
@@ -195,12 +195,15 @@ function testBatchValidate(
   expectedCount: number,
 ): HealthCheck {
   try {
-    const result = wasm.batch_validate(problems, answers);
+    const { results }: BatchResult = JSON.parse(
+      wasm.batch_validate(problems, answers),
+    );
+    const count = results.filter((r) => r.correct).length;
     return {
       name,
-      passed: result === expectedCount,
+      passed: count === expectedCount,
       expected: String(expectedCount),
-      actual: String(result),
+      actual: String(count),
     };
   } catch (e) {
     return {
@@ -0,0 +1,65 @@
+// ─── Operation Budget ────────────────────────────────────────────────
+//
+// Every parse and evaluation spends from a fixed budget of operations
+// (tokens read, AST nodes visited) and is capped in nesting depth, so a
+// WASM call returns within a predictable bound even for pathological
+// authored content like "((((…" or a 1 MB formula. Running out is
+// reported as `BudgetExceeded`, never as a hang or stack overflow.
+
+use core::fmt;
+
+/// Operations allowed per parse or evaluation.
+pub const DEFAULT_OPERATION_BUDGET: u32 = 4_096;
+
+/// Deepest allowed nesting of parentheses and unary operators.
+pub const MAX_NESTING_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetExceeded;
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("operation budget exceeded")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    remaining: u32,
+}
+
+impl Budget {
+    pub fn new(operations: u32) -> Budget {
+        Budget {
+            remaining: operations,
+        }
+    }
+
+    /// Spend `operations`, or fail once the budget is used up.
+    pub fn spend(&mut self, operations: u32) -> Result<(), BudgetExceeded> {
+        self.remaining = self
+            .remaining
+            .checked_sub(operations)
+            .ok_or(BudgetExceeded)?;
+        Ok(())
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Budget {
+        Budget::new(DEFAULT_OPERATION_BUDGET)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_runs_out() {
+        let mut budget = Budget::new(3);
+        assert!(budget.spend(2).is_ok());
+        assert!(budget.spend(1).is_ok());
+        assert_eq!(budget.spend(1), Err(BudgetExceeded));
+    }
+}
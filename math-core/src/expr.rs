@@ -0,0 +1,754 @@
+// ─── Expression Parser ───────────────────────────────────────────────
+//
+// Recursive-descent parser for authored formulas and student input:
+//
+//   expr    = term (('+' | '-') term)*
+//   term    = unary (('*' | '/' | '×' | '÷') unary | implicit-mul)*
+//   unary   = '-' unary | '+' unary | power
+//   power   = primary ('^' unary)?
+//   primary = number | ident | ident '(' expr ')' | '(' expr ')'
+//           | '√' power
+//
+// Implicit multiplication covers "2x", "3(x + 1)", and "(a)(b)".
+// Numbers are kept as exact rationals; evaluation is available both
+// exactly (Rational) and in floating point (f64), on a parsed tree or,
+// for text typed live, during the parse itself (`eval_f64`,
+// `eval_rational`), which builds no tree and, for short input, touches
+// no heap: tokens are slices of the input in a stack buffer.
+//
+// Parsing and evaluation spend from an operation budget (see budget.rs)
+// and nesting is depth-limited, so hostile input fails fast with
+// "operation budget exceeded" instead of hanging or overflowing the stack.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::Peekable;
+use core::str::CharIndices;
+
+use crate::budget::{Budget, BudgetExceeded, MAX_NESTING_DEPTH};
+use crate::rational::Rational;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Func {
+    Sqrt,
+    Abs,
+}
+
+impl Func {
+    fn from_name(name: &str) -> Option<Func> {
+        match name {
+            "sqrt" => Some(Func::Sqrt),
+            "abs" => Some(Func::Abs),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Num(Rational),
+    Var(String),
+    Neg(Box<Expr>),
+    Bin(BinOp, Box<Expr>, Box<Expr>),
+    Call(Func, Box<Expr>),
+}
+
+/// Why a parse failed, and where (character offset).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub message: &'static str,
+}
+
+const BUDGET_EXCEEDED: &str = "operation budget exceeded";
+
+impl ParseError {
+    /// Did parsing stop because the operation budget ran out?
+    pub fn is_budget_exceeded(&self) -> bool {
+        self.message == BUDGET_EXCEEDED
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Num(Rational),
+    Ident(&'a str),
+    Op(char),
+    Root,
+    LParen,
+    RParen,
+}
+
+/// Tokens kept on the stack; only longer input spills to the heap.
+const INLINE_TOKENS: usize = 32;
+
+/// The tokens of one input, each with its character offset.
+struct Tokens<'a> {
+    inline: [(usize, Token<'a>); INLINE_TOKENS],
+    len: usize,
+    spilled: Vec<(usize, Token<'a>)>,
+}
+
+impl<'a> Tokens<'a> {
+    fn new() -> Tokens<'a> {
+        Tokens {
+            inline: [(0, Token::LParen); INLINE_TOKENS],
+            len: 0,
+            spilled: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, token: (usize, Token<'a>)) {
+        if let Some(slot) = self.inline.get_mut(self.len) {
+            *slot = token;
+        } else {
+            if self.spilled.is_empty() {
+                self.spilled.extend_from_slice(&self.inline);
+            }
+            self.spilled.push(token);
+        }
+        self.len += 1;
+    }
+
+    fn as_slice(&self) -> &[(usize, Token<'a>)] {
+        if self.spilled.is_empty() {
+            self.inline.get(..self.len).unwrap_or_default()
+        } else {
+            &self.spilled
+        }
+    }
+}
+
+/// Consume characters while `accept` holds, counting them into
+/// `position`; the byte offset just past them (`end` at end of input).
+fn take_while(
+    chars: &mut Peekable<CharIndices<'_>>,
+    position: &mut usize,
+    end: usize,
+    accept: fn(char) -> bool,
+) -> usize {
+    while chars.next_if(|&(_, c)| accept(c)).is_some() {
+        *position += 1;
+    }
+    chars.peek().map_or(end, |&(offset, _)| offset)
+}
+
+/// Tokenize `input`, returning its tokens and its length in characters.
+/// Identifiers and numbers are slices of `input`, not copies.
+fn tokenize<'a>(input: &'a str, budget: &mut Budget) -> Result<(Tokens<'a>, usize), ParseError> {
+    let mut tokens = Tokens::new();
+    let mut chars = input.char_indices().peekable();
+    let mut position = 0;
+
+    while let Some((offset, c)) = chars.next() {
+        let start = position;
+        position += 1;
+        if budget.spend(1).is_err() {
+            return Err(ParseError {
+                position: start,
+                message: BUDGET_EXCEEDED,
+            });
+        }
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '0'..='9' | '.' => {
+                let accept = |c: char| c.is_ascii_digit() || c == '.';
+                let end = take_while(&mut chars, &mut position, input.len(), accept);
+                let value = Rational::parse(input.get(offset..end).unwrap_or_default()).ok_or(
+                    ParseError {
+                        position: start,
+                        message: "invalid number",
+                    },
+                )?;
+                Token::Num(value)
+            }
+            _ if c.is_alphabetic() => {
+                let end = take_while(
+                    &mut chars,
+                    &mut position,
+                    input.len(),
+                    char::is_alphanumeric,
+                );
+                Token::Ident(input.get(offset..end).unwrap_or_default())
+            }
+            '+' | '-' | '*' | '/' | '^' => Token::Op(c),
+            '×' | '·' => Token::Op('*'),
+            '÷' => Token::Op('/'),
+            '−' => Token::Op('-'),
+            '√' => Token::Root,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            _ => {
+                return Err(ParseError {
+                    position: start,
+                    message: "unexpected character",
+                })
+            }
+        };
+        tokens.push((start, token));
+    }
+
+    Ok((tokens, position))
+}
+
+/// What the parser makes of each grammar rule: an `Expr` tree for
+/// `parse`, or, for `eval_f64` and `eval_rational`, the value itself,
+/// so that evaluating typed input never builds (or allocates) a tree.
+trait Build {
+    type Node;
+    fn num(&mut self, value: Rational) -> Result<Self::Node, BudgetExceeded>;
+    fn var(&mut self, name: &str) -> Result<Self::Node, BudgetExceeded>;
+    fn neg(&mut self, inner: Self::Node) -> Result<Self::Node, BudgetExceeded>;
+    fn bin(
+        &mut self,
+        op: BinOp,
+        left: Self::Node,
+        right: Self::Node,
+    ) -> Result<Self::Node, BudgetExceeded>;
+    fn call(&mut self, func: Func, arg: Self::Node) -> Result<Self::Node, BudgetExceeded>;
+}
+
+/// Builds the `Expr` tree.
+struct Tree;
+
+impl Build for Tree {
+    type Node = Expr;
+
+    fn num(&mut self, value: Rational) -> Result<Expr, BudgetExceeded> {
+        Ok(Expr::Num(value))
+    }
+
+    fn var(&mut self, name: &str) -> Result<Expr, BudgetExceeded> {
+        Ok(Expr::Var(name.into()))
+    }
+
+    fn neg(&mut self, inner: Expr) -> Result<Expr, BudgetExceeded> {
+        Ok(Expr::Neg(Box::new(inner)))
+    }
+
+    fn bin(&mut self, op: BinOp, left: Expr, right: Expr) -> Result<Expr, BudgetExceeded> {
+        Ok(Expr::Bin(op, Box::new(left), Box::new(right)))
+    }
+
+    fn call(&mut self, func: Func, arg: Expr) -> Result<Expr, BudgetExceeded> {
+        Ok(Expr::Call(func, Box::new(arg)))
+    }
+}
+
+/// Evaluates in floating point, one operation per node; `None` for
+/// undefined or non-finite results.
+struct F64<'v, 'b> {
+    vars: &'v dyn Fn(&str) -> Option<f64>,
+    budget: &'b mut Budget,
+}
+
+impl Build for F64<'_, '_> {
+    type Node = Option<f64>;
+
+    fn num(&mut self, value: Rational) -> Result<Option<f64>, BudgetExceeded> {
+        self.budget.spend(1)?;
+        Ok(finite(value.to_f64()))
+    }
+
+    fn var(&mut self, name: &str) -> Result<Option<f64>, BudgetExceeded> {
+        self.budget.spend(1)?;
+        Ok((self.vars)(name).and_then(finite))
+    }
+
+    fn neg(&mut self, inner: Option<f64>) -> Result<Option<f64>, BudgetExceeded> {
+        self.budget.spend(1)?;
+        Ok(inner.map(|v| -v))
+    }
+
+    fn bin(
+        &mut self,
+        op: BinOp,
+        left: Option<f64>,
+        right: Option<f64>,
+    ) -> Result<Option<f64>, BudgetExceeded> {
+        self.budget.spend(1)?;
+        let (Some(a), Some(b)) = (left, right) else {
+            return Ok(None);
+        };
+        Ok(finite(match op {
+            BinOp::Add => a + b,
+            BinOp::Sub => a - b,
+            BinOp::Mul => a * b,
+            BinOp::Div => {
+                if b.abs() < 1e-15 {
+                    return Ok(None); // Division by zero
+                }
+                a / b
+            }
+            BinOp::Pow => libm::pow(a, b),
+        }))
+    }
+
+    fn call(&mut self, func: Func, arg: Option<f64>) -> Result<Option<f64>, BudgetExceeded> {
+        self.budget.spend(1)?;
+        let Some(x) = arg else {
+            return Ok(None);
+        };
+        Ok(finite(match func {
+            Func::Sqrt if x < 0.0 => return Ok(None),
+            Func::Sqrt => libm::sqrt(x),
+            Func::Abs => x.abs(),
+        }))
+    }
+}
+
+fn finite(value: f64) -> Option<f64> {
+    value.is_finite().then_some(value)
+}
+
+/// Evaluates exactly, one operation per node; `None` for irrational
+/// results, non-integer exponents, and overflow.
+struct Exact<'v, 'b> {
+    vars: &'v dyn Fn(&str) -> Option<Rational>,
+    budget: &'b mut Budget,
+}
+
+impl Build for Exact<'_, '_> {
+    type Node = Option<Rational>;
+
+    fn num(&mut self, value: Rational) -> Result<Option<Rational>, BudgetExceeded> {
+        self.budget.spend(1)?;
+        Ok(Some(value))
+    }
+
+    fn var(&mut self, name: &str) -> Result<Option<Rational>, BudgetExceeded> {
+        self.budget.spend(1)?;
+        Ok((self.vars)(name))
+    }
+
+    fn neg(&mut self, inner: Option<Rational>) -> Result<Option<Rational>, BudgetExceeded> {
+        self.budget.spend(1)?;
+        Ok(inner.and_then(Rational::checked_neg))
+    }
+
+    fn bin(
+        &mut self,
+        op: BinOp,
+        left: Option<Rational>,
+        right: Option<Rational>,
+    ) -> Result<Option<Rational>, BudgetExceeded> {
+        self.budget.spend(1)?;
+        let (Some(a), Some(b)) = (left, right) else {
+            return Ok(None);
+        };
+        Ok(match op {
+            BinOp::Add => a.checked_add(b),
+            BinOp::Sub => a.checked_sub(b),
+            BinOp::Mul => a.checked_mul(b),
+            BinOp::Div => a.checked_div(b),
+            BinOp::Pow => a.checked_pow(b),
+        })
+    }
+
+    fn call(
+        &mut self,
+        func: Func,
+        arg: Option<Rational>,
+    ) -> Result<Option<Rational>, BudgetExceeded> {
+        self.budget.spend(1)?;
+        let Some(x) = arg else {
+            return Ok(None);
+        };
+        Ok(match func {
+            Func::Sqrt => x.checked_sqrt(),
+            Func::Abs if x < Rational::ZERO => x.checked_neg(),
+            Func::Abs => Some(x),
+        })
+    }
+}
+
+struct Parser<'t, 'a, B> {
+    tokens: &'t [(usize, Token<'a>)],
+    pos: usize,
+    end: usize,
+    depth: usize,
+    budget: Budget,
+    build: B,
+}
+
+type Rule<'t, 'a, B> = fn(&mut Parser<'t, 'a, B>) -> Result<<B as Build>::Node, ParseError>;
+
+impl<'t, 'a, B: Build> Parser<'t, 'a, B> {
+    /// Account for one grammar step one level deeper; run `rule`.
+    fn nested(&mut self, rule: Rule<'t, 'a, B>) -> Result<B::Node, ParseError> {
+        if self.depth >= MAX_NESTING_DEPTH || self.budget.spend(1).is_err() {
+            return Err(self.error(BUDGET_EXCEEDED));
+        }
+        self.depth += 1;
+        let result = rule(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.pos).map(|&(_, t)| t)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map(|(p, _)| *p)
+            .unwrap_or(self.end)
+    }
+
+    fn error(&self, message: &'static str) -> ParseError {
+        ParseError {
+            position: self.position(),
+            message,
+        }
+    }
+
+    /// Make a node with the builder, reporting a builder that ran out of
+    /// budget as a parse error here.
+    fn make(
+        &mut self,
+        node: impl FnOnce(&mut B) -> Result<B::Node, BudgetExceeded>,
+    ) -> Result<B::Node, ParseError> {
+        node(&mut self.build).map_err(|BudgetExceeded| self.error(BUDGET_EXCEEDED))
+    }
+
+    fn expr(&mut self) -> Result<B::Node, ParseError> {
+        let mut left = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek() {
+            let op = if op == '+' { BinOp::Add } else { BinOp::Sub };
+            self.pos += 1;
+            let right = self.term()?;
+            left = self.make(|build| build.bin(op, left, right))?;
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<B::Node, ParseError> {
+        let mut left = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Op('*')) => BinOp::Mul,
+                Some(Token::Op('/')) => BinOp::Div,
+                // Implicit multiplication: "2x", "3(x+1)", "(a)(b)", "2√3"
+                Some(Token::Num(_) | Token::Ident(_) | Token::LParen | Token::Root) => {
+                    let right = self.power()?;
+                    left = self.make(|build| build.bin(BinOp::Mul, left, right))?;
+                    continue;
+                }
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.unary()?;
+            left = self.make(|build| build.bin(op, left, right))?;
+        }
+    }
+
+    fn unary(&mut self) -> Result<B::Node, ParseError> {
+        self.nested(Parser::unary_rule)
+    }
+
+    fn unary_rule(&mut self) -> Result<B::Node, ParseError> {
+        match self.peek() {
+            Some(Token::Op('-')) => {
+                self.pos += 1;
+                let inner = self.unary()?;
+                self.make(|build| build.neg(inner))
+            }
+            Some(Token::Op('+')) => {
+                self.pos += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<B::Node, ParseError> {
+        let base = self.primary()?;
+        if let Some(Token::Op('^')) = self.peek() {
+            self.pos += 1;
+            let exponent = self.unary()?;
+            return self.make(|build| build.bin(BinOp::Pow, base, exponent));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<B::Node, ParseError> {
+        self.nested(Parser::primary_rule)
+    }
+
+    fn primary_rule(&mut self) -> Result<B::Node, ParseError> {
+        match self.peek() {
+            Some(Token::Num(value)) => {
+                self.pos += 1;
+                self.make(|build| build.num(value))
+            }
+            Some(Token::Ident(name)) => {
+                self.pos += 1;
+                if let (Some(func), Some(Token::LParen)) = (Func::from_name(name), self.peek()) {
+                    self.pos += 1;
+                    let arg = self.expr()?;
+                    self.expect_rparen()?;
+                    return self.make(|build| build.call(func, arg));
+                }
+                self.make(|build| build.var(name))
+            }
+            Some(Token::Root) => {
+                self.pos += 1;
+                let arg = self.power()?;
+                self.make(|build| build.call(Func::Sqrt, arg))
+            }
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let inner = self.expr()?;
+                self.expect_rparen()?;
+                Ok(inner)
+            }
+            Some(_) => Err(self.error("unexpected token")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn expect_rparen(&mut self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(Token::RParen) => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => Err(self.error("expected ')'")),
+        }
+    }
+}
+
+/// Parse `input` with `build` making each node.
+fn parse_with<B: Build>(input: &str, build: B) -> Result<B::Node, ParseError> {
+    let mut budget = Budget::default();
+    let (tokens, end) = tokenize(input, &mut budget)?;
+    let mut parser = Parser {
+        tokens: tokens.as_slice(),
+        pos: 0,
+        end,
+        depth: 0,
+        budget,
+        build,
+    };
+    let node = parser.expr()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(parser.error("unexpected trailing input"));
+    }
+    Ok(node)
+}
+
+/// Parse an expression string into an AST.
+pub fn parse(input: &str) -> Result<Expr, ParseError> {
+    parse_with(input, Tree)
+}
+
+/// Parse and evaluate in floating point in one pass, without building
+/// an AST: the result of `parse` then `try_eval_f64` with a default
+/// budget, and no heap allocation for input of up to 32 tokens. A
+/// budget exhausted while evaluating is reported like one exhausted
+/// while parsing.
+pub fn eval_f64(
+    input: &str,
+    vars: &dyn Fn(&str) -> Option<f64>,
+) -> Result<Option<f64>, ParseError> {
+    parse_with(
+        input,
+        F64 {
+            vars,
+            budget: &mut Budget::default(),
+        },
+    )
+}
+
+/// Like `eval_f64`, evaluating exactly: the result of `parse` then
+/// `try_eval_rational` with a default budget.
+pub fn eval_rational(
+    input: &str,
+    vars: &dyn Fn(&str) -> Option<Rational>,
+) -> Result<Option<Rational>, ParseError> {
+    parse_with(
+        input,
+        Exact {
+            vars,
+            budget: &mut Budget::default(),
+        },
+    )
+}
+
+impl Expr {
+    /// Evaluate in floating point, spending one operation per node from
+    /// `budget`; `Ok(None)` for undefined results.
+    pub fn try_eval_f64(
+        &self,
+        vars: &dyn Fn(&str) -> Option<f64>,
+        budget: &mut Budget,
+    ) -> Result<Option<f64>, BudgetExceeded> {
+        self.fold(&mut F64 { vars, budget })
+    }
+
+    /// Evaluate exactly. Fails (None) for irrational results such as
+    /// sqrt(2), non-integer exponents, overflow, or an exhausted budget.
+    pub fn eval_rational(&self, vars: &dyn Fn(&str) -> Option<Rational>) -> Option<Rational> {
+        self.try_eval_rational(vars, &mut Budget::default())
+            .ok()
+            .flatten()
+    }
+
+    /// Like `eval_rational`, spending one operation per node from `budget`.
+    pub fn try_eval_rational(
+        &self,
+        vars: &dyn Fn(&str) -> Option<Rational>,
+        budget: &mut Budget,
+    ) -> Result<Option<Rational>, BudgetExceeded> {
+        self.fold(&mut Exact { vars, budget })
+    }
+
+    /// Rebuild this tree bottom-up with `build`, as the parser would.
+    fn fold<B: Build>(&self, build: &mut B) -> Result<B::Node, BudgetExceeded> {
+        match self {
+            Expr::Num(n) => build.num(*n),
+            Expr::Var(name) => build.var(name),
+            Expr::Neg(inner) => {
+                let inner = inner.fold(build)?;
+                build.neg(inner)
+            }
+            Expr::Bin(op, l, r) => {
+                let (l, r) = (l.fold(build)?, r.fold(build)?);
+                build.bin(*op, l, r)
+            }
+            Expr::Call(func, arg) => {
+                let arg = arg.fold(build)?;
+                build.call(*func, arg)
+            }
+        }
+    }
+
+    /// Names of all variables referenced, in first-seen order.
+    pub fn variables(&self) -> Vec<String> {
+        fn walk(e: &Expr, out: &mut Vec<String>) {
+            match e {
+                Expr::Num(_) => {}
+                Expr::Var(name) => {
+                    if !out.contains(name) {
+                        out.push(name.clone());
+                    }
+                }
+                Expr::Neg(inner) | Expr::Call(_, inner) => walk(inner, out),
+                Expr::Bin(_, l, r) => {
+                    walk(l, out);
+                    walk(r, out);
+                }
+            }
+        }
+        let mut out = Vec::new();
+        walk(self, &mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval_f64(e: &Expr, vars: &dyn Fn(&str) -> Option<f64>) -> Option<f64> {
+        e.try_eval_f64(vars, &mut Budget::default()).unwrap()
+    }
+
+    fn no_vars_f64(_: &str) -> Option<f64> {
+        None
+    }
+
+    fn no_vars(_: &str) -> Option<Rational> {
+        None
+    }
+
+    #[test]
+    fn test_precedence_and_implicit_mul() {
+        let e = parse("2 + 3 * 4").unwrap();
+        assert_eq!(eval_f64(&e, &no_vars_f64), Some(14.0));
+        let e = parse("-2^2").unwrap();
+        assert_eq!(eval_f64(&e, &no_vars_f64), Some(-4.0));
+        let e = parse("2x + 3(x - 1)").unwrap();
+        assert_eq!(eval_f64(&e, &|v| (v == "x").then_some(2.0)), Some(7.0));
+        assert_eq!(e.variables(), vec!["x".to_string()]);
+    }
+
+    #[test]
+    fn test_exact_evaluation() {
+        let e = parse("1/3 + 1/6").unwrap();
+        assert_eq!(e.eval_rational(&no_vars), Rational::new(1, 2));
+        let e = parse("sqrt(9/4) × 2^3").unwrap();
+        assert_eq!(e.eval_rational(&no_vars), Rational::new(12, 1));
+        assert_eq!(parse("sqrt(2)").unwrap().eval_rational(&no_vars), None);
+        let e = parse("2√9").unwrap();
+        assert_eq!(e.eval_rational(&no_vars), Some(Rational::from_integer(6)));
+    }
+
+    #[test]
+    fn test_parse_errors_have_positions() {
+        assert_eq!(parse("2 + ").unwrap_err().position, 4);
+        assert_eq!(parse("(1 + 2").unwrap_err().message, "expected ')'");
+        assert_eq!(parse("3 $ 4").unwrap_err().position, 2);
+        assert!(eval_f64(&parse("5 / 0").unwrap(), &no_vars_f64).is_none());
+    }
+
+    #[test]
+    fn test_one_pass_evaluation() {
+        let x = |v: &str| (v == "x").then_some(2.0);
+        assert_eq!(super::eval_f64("2x + 3(x - 1)", &x), Ok(Some(7.0)));
+        assert_eq!(super::eval_f64("5 / 0", &no_vars_f64), Ok(None));
+        assert_eq!(
+            eval_rational("1/3 + 1/6", &no_vars),
+            Ok(Rational::new(1, 2))
+        );
+        assert_eq!(eval_rational("2 + ", &no_vars).unwrap_err().position, 4);
+        // Past the inline token buffer, tokens spill to the heap.
+        let long = vec!["1"; 40].join(" + ");
+        let forty = Some(Rational::from_integer(40));
+        assert_eq!(eval_rational(&long, &no_vars), Ok(forty));
+        assert_eq!(parse(&long).unwrap().eval_rational(&no_vars), forty);
+        // Positions count characters, not bytes.
+        assert_eq!(parse("√4 × 2 $").unwrap_err().position, 7);
+        assert_eq!(parse("√4 × ").unwrap_err().position, 5);
+    }
+
+    #[test]
+    fn test_pathological_input_exceeds_budget() {
+        let deep = format!("{}1{}", "(".repeat(10_000), ")".repeat(10_000));
+        assert!(parse(&deep).unwrap_err().is_budget_exceeded());
+        let negations = format!("{}1", "-".repeat(500));
+        assert!(parse(&negations).unwrap_err().is_budget_exceeded());
+        let long = vec!["1"; 100_000].join("+");
+        assert!(parse(&long).unwrap_err().is_budget_exceeded());
+
+        let chain = parse(&vec!["x"; 400].join("+")).unwrap();
+        let mut small = Budget::new(100);
+        let vars = |_: &str| Some(Rational::ONE);
+        assert_eq!(
+            chain.try_eval_rational(&vars, &mut small),
+            Err(BudgetExceeded)
+        );
+        assert_eq!(
+            chain.eval_rational(&vars),
+            Some(Rational::from_integer(400))
+        );
+    }
+}
@@ -0,0 +1,42 @@
+// Sovereign Academy - Math Core
+//
+// Exact rationals, the expression parser, and evaluation: the part of
+// the engine that reads what students type. `no_std` (with `alloc`) so
+// it carries no platform assumptions; the WASM bindings, JSON, and
+// validators live in math-engine on top of it.
+//
+// No input may panic here — a panic aborts the WASM instance and ends
+// the lesson. That is enforced, not hoped for:
+//
+//   - the lints below reject every explicit panic source (unwrap,
+//     expect, indexing, panic!/unreachable!/todo!) in library code;
+//   - all arithmetic on values is checked and returns `None` on
+//     overflow, and parsing/evaluation run within an operation budget
+//     and nesting limit (see budget.rs), so neither overflow nor deep
+//     input can abort;
+//   - tests/no_panic.rs drives every public entry point with every
+//     short input over the parser's alphabet and with extreme values,
+//     in a debug build where integer overflow would panic;
+//   - release builds use `panic = "abort"`, so no unwinding machinery
+//     ships in the WASM binary.
+
+#![cfg_attr(not(test), no_std)]
+#![deny(
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic,
+    clippy::todo,
+    clippy::unimplemented,
+    clippy::unreachable,
+    clippy::unwrap_used
+)]
+
+extern crate alloc;
+
+pub mod budget;
+pub mod expr;
+pub mod rational;
+
+pub use budget::{Budget, BudgetExceeded};
+pub use expr::{eval_f64, eval_rational, parse, BinOp, Expr, Func, ParseError};
+pub use rational::Rational;
@@ -0,0 +1,376 @@
+// ─── Exact Rational Numbers ──────────────────────────────────────────
+//
+// A reduced fraction with a positive denominator. All arithmetic is
+// checked: overflow yields `None` instead of a wrong answer, keeping
+// validation "mathematically indestructible".
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::fmt;
+
+/// Most digits `to_exact_decimal` writes before giving up on a long
+/// repeating block.
+pub const MAX_DECIMAL_DIGITS: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i64,
+    den: i64,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    /// Build a reduced rational; `None` for a zero denominator.
+    pub fn new(num: i64, den: i64) -> Option<Rational> {
+        Self::from_i128(num as i128, den as i128)
+    }
+
+    pub fn from_integer(n: i64) -> Rational {
+        Rational { num: n, den: 1 }
+    }
+
+    fn from_i128(num: i128, den: i128) -> Option<Rational> {
+        if den == 0 {
+            return None;
+        }
+        let g = gcd_u128(num.unsigned_abs(), den.unsigned_abs()).max(1) as i128;
+        let sign = if den < 0 { -1 } else { 1 };
+        let num = i64::try_from(sign * num / g).ok()?;
+        let den = i64::try_from(sign * den / g).ok()?;
+        Some(Rational { num, den })
+    }
+
+    pub fn numer(&self) -> i64 {
+        self.num
+    }
+
+    pub fn denom(&self) -> i64 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        let (a, b, c, d) = self.wide(other);
+        Self::from_i128(a * d + c * b, b * d)
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Option<Rational> {
+        let (a, b, c, d) = self.wide(other);
+        Self::from_i128(a * d - c * b, b * d)
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Option<Rational> {
+        let (a, b, c, d) = self.wide(other);
+        Self::from_i128(a * c, b * d)
+    }
+
+    pub fn checked_div(self, other: Rational) -> Option<Rational> {
+        let (a, b, c, d) = self.wide(other);
+        Self::from_i128(a * d, b * c)
+    }
+
+    pub fn checked_neg(self) -> Option<Rational> {
+        Some(Rational {
+            num: self.num.checked_neg()?,
+            den: self.den,
+        })
+    }
+
+    /// Raise to an integer power; `None` for fractional exponents,
+    /// 0 to a negative power, or overflow.
+    pub fn checked_pow(self, exponent: Rational) -> Option<Rational> {
+        if !exponent.is_integer() {
+            return None;
+        }
+        let power = u32::try_from(exponent.num.unsigned_abs()).ok()?;
+        let num = self.num.checked_pow(power)?;
+        let den = self.den.checked_pow(power)?;
+        if exponent.num < 0 {
+            Rational::new(den, num)
+        } else {
+            Rational::new(num, den)
+        }
+    }
+
+    /// Exact square root; `None` unless both terms are perfect squares.
+    pub fn checked_sqrt(self) -> Option<Rational> {
+        if self.num < 0 {
+            return None;
+        }
+        Rational::new(exact_isqrt(self.num)?, exact_isqrt(self.den)?)
+    }
+
+    fn wide(self, other: Rational) -> (i128, i128, i128, i128) {
+        (
+            self.num as i128,
+            self.den as i128,
+            other.num as i128,
+            other.den as i128,
+        )
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    /// Does this value have a finite decimal expansion (den = 2^a·5^b)?
+    pub fn is_terminating(&self) -> bool {
+        let mut d = self.den;
+        while d % 2 == 0 {
+            d /= 2;
+        }
+        while d % 5 == 0 {
+            d /= 5;
+        }
+        d == 1
+    }
+
+    /// Round half away from zero to `places` decimal places.
+    pub fn round_to(&self, places: u32) -> Option<Rational> {
+        let scale = 10i128.checked_pow(places)?;
+        let scaled = (self.num as i128).checked_mul(scale)?;
+        let den = self.den as i128;
+        let twice = scaled.checked_abs()?.checked_mul(2)?.checked_add(den)?;
+        Self::from_i128(twice / (2 * den) * scaled.signum(), scale)
+    }
+
+    /// Decimal text with exactly `places` digits after the point,
+    /// rounded half away from zero: 5/4 → "1.25", 2/3 at 2 → "0.67".
+    pub fn to_decimal_string(&self, places: u32) -> Option<String> {
+        let rounded = self.round_to(places)?;
+        let scale = 10i128.checked_pow(places)?;
+        let scaled = (rounded.num as i128).checked_mul(scale)? / rounded.den as i128;
+        let sign = if scaled < 0 { "-" } else { "" };
+        let (whole, frac) = (scaled.abs() / scale, scaled.abs() % scale);
+        if places == 0 {
+            return Some(format!("{}{}", sign, whole));
+        }
+        Some(format!(
+            "{}{}.{:0width$}",
+            sign,
+            whole,
+            frac,
+            width = places as usize
+        ))
+    }
+
+    /// Parse "7", "-3/4", "0.75", "-1.5", or a mixed number "2 1/2".
+    pub fn parse(text: &str) -> Option<Rational> {
+        let s = text.trim();
+        if s.is_empty() {
+            return None;
+        }
+
+        // Mixed number: "2 1/2" or "-2 1/2"
+        if let Some((whole, frac)) = s.split_once(' ') {
+            let frac = frac.trim();
+            if !frac.contains('/') {
+                return None;
+            }
+            let whole_value = parse_integer(whole)?;
+            let frac_value = Rational::parse(frac)?;
+            if frac_value.num < 0 {
+                return None;
+            }
+            let magnitude =
+                Rational::from_integer(whole_value.checked_abs()?).checked_add(frac_value)?;
+            return if whole.trim().starts_with('-') {
+                magnitude.checked_neg()
+            } else {
+                Some(magnitude)
+            };
+        }
+
+        if let Some((n, d)) = s.split_once('/') {
+            return Rational::new(parse_integer(n)?, parse_integer(d)?);
+        }
+
+        if let Some((whole, frac)) = s.split_once('.') {
+            let whole = whole.trim();
+            let (negative, whole) = match whole.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, whole.strip_prefix('+').unwrap_or(whole)),
+            };
+            if frac.is_empty() || !frac.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            if !whole.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let scale = 10i64.checked_pow(frac.len() as u32)?;
+            let whole_value = if whole.is_empty() {
+                0
+            } else {
+                whole.parse::<i64>().ok()?
+            };
+            let frac_value = frac.parse::<i64>().ok()?;
+            let num = whole_value.checked_mul(scale)?.checked_add(frac_value)?;
+            return Rational::new(if negative { -num } else { num }, scale);
+        }
+
+        Some(Rational::from_integer(parse_integer(s)?))
+    }
+
+    /// The exact decimal expansion, with any repeating block in
+    /// parentheses: 3/8 → "0.375", 1/3 → "0.(3)", -7/6 → "-1.1(6)".
+    /// `None` past `MAX_DECIMAL_DIGITS` digits.
+    pub fn to_exact_decimal(&self) -> Option<String> {
+        let sign = if self.num < 0 { "-" } else { "" };
+        let (num, den) = (self.num.unsigned_abs() as u128, self.den as u128);
+        let mut text = format!("{}{}", sign, num / den);
+        let mut remainder = num % den;
+        if remainder == 0 {
+            return Some(text);
+        }
+        text.push('.');
+        let mut digits = String::new();
+        // Where each remainder was first seen: a repeat starts the cycle.
+        let mut seen = BTreeMap::new();
+        while remainder != 0 {
+            if let Some(&start) = seen.get(&remainder) {
+                digits.insert(start, '(');
+                digits.push(')');
+                break;
+            }
+            if digits.len() == MAX_DECIMAL_DIGITS {
+                return None;
+            }
+            seen.insert(remainder, digits.len());
+            remainder *= 10;
+            digits.push(char::from(b'0' + (remainder / den) as u8));
+            remainder %= den;
+        }
+        text.push_str(&digits);
+        Some(text)
+    }
+}
+
+fn gcd_u128(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd_u128(b, a % b)
+    }
+}
+
+/// Integer square root of a non-negative perfect square.
+fn exact_isqrt(n: i64) -> Option<i64> {
+    let mut root = libm::sqrt(n as f64) as i64;
+    // Correct the float estimate in either direction.
+    while root > 0 && root.checked_mul(root).is_none_or(|sq| sq > n) {
+        root -= 1;
+    }
+    while (root + 1).checked_mul(root + 1).is_some_and(|sq| sq <= n) {
+        root += 1;
+    }
+    (root * root == n).then_some(root)
+}
+
+fn parse_integer(text: &str) -> Option<i64> {
+    let t = text.trim();
+    let digits = t.strip_prefix(['-', '+']).unwrap_or(t);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    t.parse::<i64>().ok()
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b, c, d) = self.wide(*other);
+        (a * d).cmp(&(c * b))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_forms() {
+        assert_eq!(Rational::parse("3/4"), Rational::new(3, 4));
+        assert_eq!(Rational::parse("0.75"), Rational::new(3, 4));
+        assert_eq!(Rational::parse("-1.5"), Rational::new(-3, 2));
+        assert_eq!(Rational::parse("2 1/2"), Rational::new(5, 2));
+        assert_eq!(Rational::parse("-2 1/2"), Rational::new(-5, 2));
+        assert_eq!(Rational::parse("6/-8"), Rational::new(-3, 4));
+        assert_eq!(Rational::parse("1/0"), None);
+        assert_eq!(Rational::parse("abc"), None);
+    }
+
+    #[test]
+    fn test_arithmetic_and_ordering() {
+        let half = Rational::new(1, 2).unwrap();
+        let third = Rational::new(1, 3).unwrap();
+        assert_eq!(half.checked_add(third), Rational::new(5, 6));
+        assert_eq!(half.checked_div(third), Rational::new(3, 2));
+        assert!(half > third);
+        assert_eq!(
+            Rational::new(i64::MAX, 1)
+                .unwrap()
+                .checked_add(Rational::ONE),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rounding() {
+        let value = Rational::new(-110, 9).unwrap(); // -12.222…
+        assert_eq!(value.round_to(2), Rational::parse("-12.22"));
+        assert!(!value.is_terminating());
+        assert!(Rational::new(3, 8).unwrap().is_terminating());
+        assert_eq!(value.to_decimal_string(2).as_deref(), Some("-12.22"));
+        let small = Rational::new(-1, 20).unwrap();
+        assert_eq!(small.to_decimal_string(2).as_deref(), Some("-0.05"));
+    }
+
+    #[test]
+    fn test_pow_and_sqrt() {
+        let two_thirds = Rational::new(2, 3).unwrap();
+        assert_eq!(
+            two_thirds.checked_pow(Rational::from_integer(-2)),
+            Rational::new(9, 4)
+        );
+        assert_eq!(two_thirds.checked_pow(Rational::new(1, 2).unwrap()), None);
+        assert_eq!(
+            Rational::new(9, 4).unwrap().checked_sqrt(),
+            Rational::new(3, 2)
+        );
+        assert_eq!(Rational::from_integer(2).checked_sqrt(), None);
+        assert_eq!(Rational::ZERO.checked_pow(Rational::from_integer(-1)), None);
+    }
+
+    #[test]
+    fn test_exact_decimal() {
+        let decimal = |n, d| Rational::new(n, d).unwrap().to_exact_decimal();
+        assert_eq!(decimal(3, 8).as_deref(), Some("0.375"));
+        assert_eq!(decimal(1, 3).as_deref(), Some("0.(3)"));
+        assert_eq!(decimal(-7, 6).as_deref(), Some("-1.1(6)"));
+        assert_eq!(decimal(1, 7).as_deref(), Some("0.(142857)"));
+        assert_eq!(decimal(-4, 1).as_deref(), Some("-4"));
+        assert_eq!(decimal(1, 1_000_003), None);
+    }
+}
@@ -0,0 +1,100 @@
+// ═══════════════════════════════════════════════════════════════════
+// Sovereign Academy — Math Core Allocation Counts
+// ═══════════════════════════════════════════════════════════════════
+//
+// Live validation runs on every keystroke, so reading a typed answer
+// must not touch the heap. A counting allocator wraps the system one
+// and counts, on the test's own thread only, every allocation made
+// while a closure runs:
+//
+//   1. `eval_f64` / `eval_rational` on typical input: zero;
+//   2. `parse`: only the tree's own nodes (one box per child node,
+//      one string per variable), nothing for tokens;
+//   3. input past the inline token buffer: only the spilled token list.
+// ═══════════════════════════════════════════════════════════════════
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use math_core::{eval_f64, eval_rational, parse, Rational};
+
+struct Counting;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Heap allocations made while `f` runs.
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    ALLOCATIONS.with(|n| n.set(0));
+    COUNTING.with(|c| c.set(true));
+    let result = f();
+    COUNTING.with(|c| c.set(false));
+    drop(result);
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// What students type while a lesson validates live.
+const TYPED: [&str; 8] = [
+    "12 * 34",
+    "3/4 + 2/3",
+    "2x + 3(x - 1)",
+    "-2^2 × 5 ÷ 4",
+    "sqrt(9/4) + abs(-3)",
+    "2√9 - 0.75",
+    "((1 + 2) * 3 - 4) / 5",
+    "2 + ",
+];
+
+#[test]
+fn one_pass_evaluation_never_allocates() {
+    let x = |_: &str| Some(2.0);
+    let exact = |_: &str| Some(Rational::from_integer(2));
+    for text in TYPED {
+        assert_eq!(allocations(|| eval_f64(text, &x)), 0, "{}", text);
+        assert_eq!(allocations(|| eval_rational(text, &exact)), 0, "{}", text);
+    }
+}
+
+#[test]
+fn parsing_allocates_only_the_tree() {
+    // Num(12) * Num(34): one box for each operand.
+    assert_eq!(allocations(|| parse("12 * 34")), 2);
+    // 2x + 3(x - 1): four binary nodes, eight boxes, two variable names.
+    assert_eq!(allocations(|| parse("2x + 3(x - 1)")), 10);
+    assert_eq!(allocations(|| parse("7")), 0);
+    assert_eq!(allocations(|| parse("2 + ")), 0);
+}
+
+#[test]
+fn long_input_spills_tokens_once() {
+    let short = vec!["1"; 16].join("+"); // 31 tokens: fits inline
+    let long = vec!["1"; 40].join("+"); // 79 tokens: spills
+    assert_eq!(allocations(|| eval_f64(&short, &|_| None)), 0);
+    let spilled = allocations(|| eval_f64(&long, &|_| None));
+    assert!((1..=4).contains(&spilled), "{} allocations", spilled);
+}
@@ -0,0 +1,144 @@
+// ═══════════════════════════════════════════════════════════════════
+// Sovereign Academy — Math Core No-Panic Checker
+// ═══════════════════════════════════════════════════════════════════
+//
+// Drives every public entry point of math_core with:
+//
+//   1. every string of up to 5 characters over the parser's alphabet
+//      (~580k inputs), parsed and evaluated;
+//   2. every pair of extreme values through every arithmetic operation;
+//   3. pathological text: deep nesting, huge numbers, long decimals.
+//
+// Tests build in debug, where integer overflow panics as well, so any
+// panic path reachable from these inputs fails here instead of
+// aborting a WASM instance mid-lesson. The lints in src/lib.rs keep
+// explicit panic sources out of the library in the first place.
+//
+// Every input also checks that the one-pass evaluators (`eval_f64`,
+// `eval_rational`) agree with parsing and then evaluating the tree.
+// ═══════════════════════════════════════════════════════════════════
+
+use math_core::{eval_f64, eval_rational, parse, Budget, Expr, Rational};
+
+/// Every kind of character the tokenizer distinguishes.
+const ALPHABET: [char; 14] = [
+    '0', '9', '.', '/', '-', '+', '*', '^', '(', ')', 'x', '√', ' ', '×',
+];
+
+const EXTREMES: [i64; 9] = [i64::MIN, i64::MIN + 1, -10, -1, 0, 1, 2, 10, i64::MAX];
+
+/// Every representable value built from two extremes.
+fn extreme_values() -> Vec<Rational> {
+    EXTREMES
+        .iter()
+        .flat_map(|&n| EXTREMES.iter().filter_map(move |&d| Rational::new(n, d)))
+        .collect()
+}
+
+fn exercise_expr(expr: &Expr, x: Rational) {
+    expr.eval_rational(&|_| Some(x));
+    let _ = expr.try_eval_f64(&|_| Some(x.to_f64()), &mut Budget::default());
+    let _ = expr.try_eval_rational(&|_| None, &mut Budget::new(8));
+    expr.variables();
+}
+
+fn exercise_text(text: &str, x: Rational) {
+    let f64_vars = |_: &str| Some(x.to_f64());
+    let exact_vars = |_: &str| Some(x);
+    match parse(text) {
+        Ok(expr) => {
+            exercise_expr(&expr, x);
+            let mut budget = Budget::default();
+            let two_pass = expr.try_eval_f64(&f64_vars, &mut budget).unwrap();
+            assert_eq!(eval_f64(text, &f64_vars), Ok(two_pass), "{}", text);
+            let two_pass = expr.eval_rational(&exact_vars);
+            assert_eq!(eval_rational(text, &exact_vars), Ok(two_pass), "{}", text);
+        }
+        Err(error) => {
+            assert_eq!(eval_f64(text, &f64_vars), Err(error.clone()), "{}", text);
+            assert_eq!(eval_rational(text, &exact_vars), Err(error), "{}", text);
+        }
+    }
+    if let Some(value) = Rational::parse(text) {
+        value.to_exact_decimal();
+    }
+}
+
+// ─── Exhaustive Short Input ──────────────────────────────────────────
+
+#[test]
+fn every_short_input_is_handled() {
+    let values = extreme_values();
+    let mut inputs = vec![String::new()];
+    let mut checked = 0usize;
+    for _ in 0..5 {
+        let mut longer = Vec::with_capacity(inputs.len() * ALPHABET.len());
+        for prefix in &inputs {
+            for c in ALPHABET {
+                let mut text = prefix.clone();
+                text.push(c);
+                exercise_text(&text, values[checked % values.len()]);
+                checked += 1;
+                longer.push(text);
+            }
+        }
+        inputs = longer;
+    }
+    assert_eq!(checked, (1..=5).map(|k| ALPHABET.len().pow(k)).sum());
+}
+
+// ─── Extreme Values ──────────────────────────────────────────────────
+
+#[test]
+fn every_operation_on_extreme_values_is_handled() {
+    let values = extreme_values();
+    for &a in &values {
+        a.checked_neg();
+        a.checked_sqrt();
+        a.to_f64();
+        a.is_terminating();
+        a.to_exact_decimal();
+        a.to_string();
+        for places in [0, 1, 18, 19, 38, 39, u32::MAX] {
+            a.round_to(places);
+            a.to_decimal_string(places);
+        }
+        for &b in &values {
+            a.checked_add(b);
+            a.checked_sub(b);
+            a.checked_mul(b);
+            a.checked_div(b);
+            a.checked_pow(b);
+            let _ = a.cmp(&b);
+        }
+    }
+}
+
+// ─── Pathological Text ───────────────────────────────────────────────
+
+#[test]
+fn pathological_text_is_handled() {
+    let x = Rational::from_integer(i64::MAX);
+    let mut cases = vec![
+        "9223372036854775807".to_string(),
+        "9223372036854775808".to_string(),
+        "-9223372036854775808".to_string(),
+        "-9223372036854775808 1/2".to_string(),
+        "1/-9223372036854775808".to_string(),
+        "0.0000000000000000001".to_string(),
+        "92233720368547758.07".to_string(),
+        "9".repeat(10_000),
+        format!("1.{}", "0".repeat(10_000)),
+        "x^x^x^x^x^x".to_string(),
+        "9223372036854775807^2 + sqrt(-1) / 0".to_string(),
+        "abs(-9223372036854775807 - 1)".to_string(),
+        ".".repeat(1_000),
+        "1/2/3/4/5 2 1/2".to_string(),
+    ];
+    for unit in ["(", "-", "+", "√", "2^", "sqrt(", "abs(", "x", "1+"] {
+        cases.push(format!("{}1", unit.repeat(10_000)));
+    }
+    for text in &cases {
+        exercise_text(text, x);
+    }
+}
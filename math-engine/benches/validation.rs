@@ -0,0 +1,88 @@
+// ═══════════════════════════════════════════════════════════════════
+// Sovereign Academy — Validation Benchmarks
+// ═══════════════════════════════════════════════════════════════════
+//
+// Throughput of the grading hot path, from text in to verdict out:
+//
+//   1. parsing alone (math_core::parse, LaTeX conversion);
+//   2. single validations, one per entry point a lesson calls;
+//   3. whole batches, reported in items per second.
+//
+// Run with `cargo bench`; reports land in target/criterion. The
+// per-call ceilings that CI enforces live in tests/perf_budget.rs.
+// ═══════════════════════════════════════════════════════════════════
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use math_validator::*;
+
+/// Expressions from short drills to a full algebra line.
+const EXPRESSIONS: [(&str, &str); 4] = [
+    ("binary", "12 * 34"),
+    ("mixed", "3/4 + 2 1/2 - 0.75"),
+    ("polynomial", "3x^2 + 2(x - 1)/4 - sqrt(16)"),
+    ("nested", "((((1 + 2) * 3) - 4) / 5)^2"),
+];
+
+fn parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, expr) in EXPRESSIONS {
+        group.bench_with_input(BenchmarkId::new("plain", name), expr, |b, expr| {
+            b.iter(|| math_core::parse(black_box(expr)))
+        });
+    }
+    group.bench_function("canonical", |b| {
+        b.iter(|| format_canonical(black_box("3x^2 + 2(x - 1)/4")))
+    });
+    group.bench_function("latex", |b| {
+        b.iter(|| render_expression(black_box(r"\frac{3}{4} + \sqrt{16}"), "mathml"))
+    });
+    group.finish();
+}
+
+fn single_validation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validate");
+    group.bench_function("arithmetic", |b| {
+        b.iter(|| validate_arithmetic(black_box("12 * 34"), black_box(408.0)))
+    });
+    group.bench_function("equation", |b| {
+        b.iter(|| validate_equation(black_box("x * 3 = 12"), black_box(4.0)))
+    });
+    for (problem_type, problem, answer) in [
+        ("arithmetic", "12 * 34", "408"),
+        ("arithmetic", r"\frac{12}{4}", "3"),
+        ("fraction", "2/4", "1/2"),
+        ("comparison", "3/4 ? 0.7", ">"),
+        ("money", "$2.50 + $1.25", "$3.75"),
+    ] {
+        let id = BenchmarkId::new("check_answer", format!("{} {}", problem_type, problem));
+        group.bench_function(id, |b| {
+            b.iter(|| check_answer(problem_type, black_box(problem), black_box(answer)))
+        });
+    }
+    group.finish();
+}
+
+fn batches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch");
+    for len in [100, 1_000, 10_000] {
+        let problems: Vec<String> = (0..len).map(|i| format!("{} + {}", i, i % 7)).collect();
+        // Every tenth answer is wrong, so the per-item path runs too.
+        let answers: Vec<String> = (0..len)
+            .map(|i| (i + i % 7 + usize::from(i % 10 == 0)).to_string())
+            .collect();
+        let (problems, answers) = (problems.join(";"), answers.join(";"));
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("validate", len), &len, |b, _| {
+            b.iter(|| batch_validate(black_box(&problems), black_box(&answers)))
+        });
+        group.bench_with_input(BenchmarkId::new("score", len), &len, |b, _| {
+            b.iter(|| batch_score(black_box(&problems), black_box(&answers)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parsing, single_validation, batches);
+criterion_main!(benches);
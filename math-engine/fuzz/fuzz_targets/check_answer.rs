@@ -0,0 +1,22 @@
+// ─── Fuzz: Answer Checking ───────────────────────────────────────────
+//
+//   cargo +nightly fuzz run check_answer
+//
+// Arbitrary problems and answers for every built-in problem type (the
+// first byte picks the type). Nothing may panic, and the result must
+// always be JSON.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use math_validator::{check_answer, supported_types};
+use serde_json::Value;
+
+fuzz_target!(|input: (u8, &str, &str)| {
+    let (pick, problem, answer) = input;
+    let types: Value = serde_json::from_str(&supported_types()).unwrap();
+    let types = types["types"].as_array().unwrap();
+    let problem_type = types[pick as usize % types.len()]["type"].as_str().unwrap();
+    let result = check_answer(problem_type, problem, answer);
+    assert!(serde_json::from_str::<Value>(&result).is_ok(), "{}", result);
+});
@@ -0,0 +1,29 @@
+// ─── Fuzz: Expression Evaluation ─────────────────────────────────────
+//
+//   cargo +nightly fuzz run evaluate_expression
+//
+// Arbitrary text through the arithmetic evaluator and the expression
+// tools built on the parser. Nothing may panic or overflow the stack,
+// and a canonical form must be a fixed point that renders like the
+// input (same parse tree).
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use math_validator::{format_canonical, render_expression, validate_arithmetic};
+use serde_json::Value;
+
+fuzz_target!(|input: (&str, f64)| {
+    let (expr, answer) = input;
+    validate_arithmetic(expr, answer);
+
+    let first: Value = serde_json::from_str(&format_canonical(expr)).unwrap();
+    if let Some(canonical) = first["canonical"].as_str() {
+        let second: Value = serde_json::from_str(&format_canonical(canonical)).unwrap();
+        assert_eq!(second["canonical"].as_str(), Some(canonical));
+        let rendered = render_expression(expr, "latex");
+        if !rendered.contains("parse_error") {
+            assert_eq!(render_expression(canonical, "latex"), rendered);
+        }
+    }
+});
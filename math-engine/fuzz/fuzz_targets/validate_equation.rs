@@ -0,0 +1,15 @@
+// ─── Fuzz: Equation Validation ───────────────────────────────────────
+//
+//   cargo +nightly fuzz run validate_equation
+//
+// Arbitrary equations and substituted values; nothing may panic.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use math_validator::validate_equation;
+
+fuzz_target!(|input: (&str, f64)| {
+    let (equation, value) = input;
+    validate_equation(equation, value);
+});
@@ -0,0 +1,348 @@
+// ─── Question Bank ───────────────────────────────────────────────────
+//
+// An in-memory index over a content bundle's questions, so the UI can
+// ask for "grade 4 fractions between difficulty 900 and 1100" without
+// walking thousands of questions in JavaScript.
+//
+// Bundle (JSON):
+//   {"questions":[{"id":"f-12","type":"fraction","problem":"1/2 + 1/4",
+//                  "answer":"3/4","topic":"fractions","grade":4,
+//                  "difficulty":1050,"tags":["addition","unlike"]},…]}
+// `grade` is 0 (kindergarten) to 12; `difficulty` is on the mastery
+// rating scale; `tags` is optional. Any other fields are kept and
+// returned with the question.
+//
+// The bank is engine state, like the validator registry: loading a
+// bundle replaces the previous one, all-or-nothing.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::version;
+
+/// Highest grade level; 0 is kindergarten.
+const MAX_GRADE: u64 = 12;
+
+/// Why a bundle was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BundleError {
+    InvalidBundle,
+    /// A question (by position) is missing a field or has the wrong type.
+    InvalidQuestion(usize),
+    DuplicateId(String),
+}
+
+impl BundleError {
+    fn to_json(&self) -> Value {
+        match self {
+            BundleError::InvalidBundle => json!({ "ok": false, "error": "invalid_bundle" }),
+            BundleError::InvalidQuestion(index) => {
+                json!({ "ok": false, "error": "invalid_question", "index": index })
+            }
+            BundleError::DuplicateId(id) => {
+                json!({ "ok": false, "error": "duplicate_id", "id": id })
+            }
+        }
+    }
+}
+
+/// The fields the bank indexes, parsed once at load.
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    topic: String,
+    grade: u8,
+    difficulty: f64,
+    tags: Vec<String>,
+}
+
+impl Entry {
+    fn from_json(question: &Value) -> Option<Entry> {
+        question.get("id")?.as_str()?;
+        let grade = question
+            .get("grade")?
+            .as_u64()
+            .filter(|g| *g <= MAX_GRADE)?;
+        let tags = match question.get("tags") {
+            None => Vec::new(),
+            Some(tags) => tags
+                .as_array()?
+                .iter()
+                .map(|t| t.as_str().map(str::to_string))
+                .collect::<Option<_>>()?,
+        };
+        Some(Entry {
+            topic: question.get("topic")?.as_str()?.to_string(),
+            grade: grade as u8,
+            difficulty: question.get("difficulty")?.as_f64()?,
+            tags,
+        })
+    }
+}
+
+/// A query; unset fields match every question.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Query {
+    topic: Option<String>,
+    grade: Option<u8>,
+    min_difficulty: Option<f64>,
+    max_difficulty: Option<f64>,
+    tag: Option<String>,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl Query {
+    fn from_json(query: &Value) -> Option<Query> {
+        let fields = query.as_object()?;
+        let text = |name: &str| match fields.get(name) {
+            None => Some(None),
+            Some(v) => v.as_str().map(|s| Some(s.to_string())),
+        };
+        let number = |name: &str| match fields.get(name) {
+            None => Some(None),
+            Some(v) => v.as_f64().map(Some),
+        };
+        let count = |name: &str| match fields.get(name) {
+            None => Some(None),
+            Some(v) => v.as_u64().map(|n| Some(n as usize)),
+        };
+        let grade = match fields.get("grade") {
+            None => None,
+            Some(v) => Some(v.as_u64().filter(|g| *g <= MAX_GRADE)? as u8),
+        };
+        Some(Query {
+            topic: text("topic")?,
+            grade,
+            min_difficulty: number("min_difficulty")?,
+            max_difficulty: number("max_difficulty")?,
+            tag: text("tag")?,
+            offset: count("offset")?.unwrap_or(0),
+            limit: count("limit")?,
+        })
+    }
+
+    fn matches(&self, entry: &Entry) -> bool {
+        self.topic.as_ref().is_none_or(|t| *t == entry.topic)
+            && self.grade.is_none_or(|g| g == entry.grade)
+            && self.min_difficulty.is_none_or(|d| entry.difficulty >= d)
+            && self.max_difficulty.is_none_or(|d| entry.difficulty <= d)
+            && self.tag.as_ref().is_none_or(|t| entry.tags.contains(t))
+    }
+}
+
+/// Questions in bundle order, with an index per exact-match field.
+#[derive(Debug, Default)]
+pub(crate) struct QuestionBank {
+    questions: Vec<Value>,
+    entries: Vec<Entry>,
+    by_topic: BTreeMap<String, Vec<usize>>,
+    by_grade: BTreeMap<u8, Vec<usize>>,
+    by_tag: BTreeMap<String, Vec<usize>>,
+}
+
+impl QuestionBank {
+    fn parse(bundle_json: &str) -> Result<QuestionBank, BundleError> {
+        let mut bundle: Value =
+            serde_json::from_str(bundle_json).map_err(|_| BundleError::InvalidBundle)?;
+        let Some(Value::Array(questions)) = bundle.get_mut("questions").map(Value::take) else {
+            return Err(BundleError::InvalidBundle);
+        };
+
+        let mut bank = QuestionBank::default();
+        let mut ids = BTreeSet::new();
+        for (index, question) in questions.into_iter().enumerate() {
+            let entry = Entry::from_json(&question).ok_or(BundleError::InvalidQuestion(index))?;
+            let id = question["id"].as_str().unwrap_or_default();
+            if !ids.insert(id.to_string()) {
+                return Err(BundleError::DuplicateId(id.to_string()));
+            }
+            bank.by_topic
+                .entry(entry.topic.clone())
+                .or_default()
+                .push(index);
+            bank.by_grade.entry(entry.grade).or_default().push(index);
+            // A question tagged twice is still listed once.
+            for tag in entry.tags.iter().collect::<BTreeSet<_>>() {
+                bank.by_tag.entry(tag.clone()).or_default().push(index);
+            }
+            bank.questions.push(question);
+            bank.entries.push(entry);
+        }
+        Ok(bank)
+    }
+
+    /// Indices of matching questions, in bundle order.
+    fn query(&self, query: &Query) -> Vec<usize> {
+        // Start from the smallest index list the query pins down, then
+        // check the remaining conditions on just those questions.
+        let lists = [
+            query.topic.as_ref().map(|t| self.by_topic.get(t)),
+            query.grade.map(|g| self.by_grade.get(&g)),
+            query.tag.as_ref().map(|t| self.by_tag.get(t)),
+        ];
+        let narrowest = lists
+            .into_iter()
+            .flatten()
+            .min_by_key(|list| list.map_or(0, Vec::len));
+        let candidates: Box<dyn Iterator<Item = usize>> = match narrowest {
+            Some(None) => return Vec::new(),
+            Some(Some(list)) => Box::new(list.iter().copied()),
+            None => Box::new(0..self.entries.len()),
+        };
+        candidates
+            .filter(|&i| query.matches(&self.entries[i]))
+            .collect()
+    }
+}
+
+thread_local! {
+    // WASM is single-threaded; one bank per engine instance.
+    static BANK: RefCell<QuestionBank> = RefCell::new(QuestionBank::default());
+}
+
+/// The loaded questions as a bundle `load_question_bank` accepts, for
+/// engine snapshots.
+pub(crate) fn bundle_json() -> String {
+    BANK.with(|b| json!({ "questions": b.borrow().questions }).to_string())
+}
+
+/// Parse a bundle without loading it. The error is the same code
+/// `load_question_bank` reports.
+pub(crate) fn parse_bundle(bundle_json: &str) -> Result<QuestionBank, String> {
+    QuestionBank::parse(bundle_json).map_err(|error| {
+        error.to_json()["error"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string()
+    })
+}
+
+/// Replace the bank with one from `parse_bundle`; returns its size.
+pub(crate) fn replace(bank: QuestionBank) -> usize {
+    let count = bank.questions.len();
+    BANK.with(|b| *b.borrow_mut() = bank);
+    count
+}
+
+/// Load a content bundle's questions (see the module comment for the
+/// format), replacing the current bank.
+///
+/// Returns `{"ok":true,"questions":n}` or `{"ok":false,"error":…}`:
+/// "invalid_bundle", "invalid_question" (with its `index`), or
+/// "duplicate_id" (with the `id`). On error the current bank is kept.
+#[wasm_bindgen]
+pub fn load_question_bank(bundle_json: &str) -> String {
+    version::result(match QuestionBank::parse(bundle_json) {
+        Ok(bank) => json!({ "ok": true, "questions": replace(bank) }),
+        Err(error) => error.to_json(),
+    })
+}
+
+/// Questions in the loaded bank matching a query.
+///
+/// `query_json` is an object with any of `topic`, `grade`,
+/// `min_difficulty`, `max_difficulty` (inclusive), and `tag`; a
+/// question must match all of them. `offset` and `limit` page through
+/// the results. Returns `{"total":n,"questions":[…]}` — `total` counts
+/// every match, `questions` holds the requested page in bundle order —
+/// or `{"error":"invalid_query"}`.
+#[wasm_bindgen]
+pub fn query_questions(query_json: &str) -> String {
+    let query = serde_json::from_str::<Value>(query_json)
+        .ok()
+        .and_then(|q| Query::from_json(&q));
+    let Some(query) = query else {
+        return version::result(json!({ "error": "invalid_query" }));
+    };
+    BANK.with(|b| {
+        let bank = b.borrow();
+        let matches = bank.query(&query);
+        let page: Vec<&Value> = matches
+            .iter()
+            .skip(query.offset)
+            .take(query.limit.unwrap_or(usize::MAX))
+            .map(|&i| &bank.questions[i])
+            .collect();
+        version::result(json!({ "total": matches.len(), "questions": page }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUNDLE: &str = r#"{"questions":[
+        {"id":"f-1","type":"fraction","problem":"1/2 + 1/4","answer":"3/4",
+         "topic":"fractions","grade":4,"difficulty":900,"tags":["addition"]},
+        {"id":"f-2","type":"fraction","problem":"3/4 - 1/8","answer":"5/8",
+         "topic":"fractions","grade":4,"difficulty":1100,"tags":["subtraction","unlike"]},
+        {"id":"t-1","type":"elapsed_time","problem":"9:00 to 11:30","answer":"2:30",
+         "topic":"time","grade":2,"difficulty":800},
+        {"id":"f-3","type":"fraction","problem":"2/3 + 1/6","answer":"5/6",
+         "topic":"fractions","grade":5,"difficulty":1200,"tags":["addition","unlike"]}]}"#;
+
+    fn parse(output: String) -> Value {
+        serde_json::from_str(&output).unwrap()
+    }
+
+    fn ids(query: &str) -> Vec<String> {
+        parse(query_questions(query))["questions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|q| q["id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_queries() {
+        assert_eq!(
+            parse(load_question_bank(BUNDLE)),
+            json!({"ok":true,"questions":4,"result_schema_version":2})
+        );
+        assert_eq!(ids("{}"), ["f-1", "f-2", "t-1", "f-3"]);
+        assert_eq!(ids(r#"{"topic":"fractions","grade":4}"#), ["f-1", "f-2"]);
+        assert_eq!(
+            ids(r#"{"min_difficulty":900,"max_difficulty":1100}"#),
+            ["f-1", "f-2"]
+        );
+        assert_eq!(ids(r#"{"tag":"unlike"}"#), ["f-2", "f-3"]);
+        assert_eq!(ids(r#"{"tag":"addition","grade":5}"#), ["f-3"]);
+        assert!(ids(r#"{"topic":"geometry"}"#).is_empty());
+
+        let page = parse(query_questions(
+            r#"{"topic":"fractions","offset":1,"limit":1}"#,
+        ));
+        assert_eq!(page["total"], 3);
+        assert_eq!(page["questions"][0]["answer"], "5/8");
+    }
+
+    #[test]
+    fn test_rejected_bundles_keep_the_bank() {
+        load_question_bank(BUNDLE);
+        assert_eq!(
+            parse(load_question_bank(
+                r#"{"questions":[{"id":"x","topic":"t","grade":13,"difficulty":1}]}"#
+            )),
+            json!({"ok":false,"error":"invalid_question","index":0,"result_schema_version":2})
+        );
+        let duplicate = r#"{"questions":[
+            {"id":"x","topic":"t","grade":1,"difficulty":1},
+            {"id":"x","topic":"t","grade":1,"difficulty":2}]}"#;
+        assert_eq!(
+            parse(load_question_bank(duplicate))["error"],
+            "duplicate_id"
+        );
+        assert_eq!(parse(load_question_bank("[]"))["error"], "invalid_bundle");
+        assert_eq!(parse(query_questions("{}"))["total"], 4);
+
+        assert_eq!(
+            parse(query_questions(r#"{"grade":"4"}"#))["error"],
+            "invalid_query"
+        );
+        assert_eq!(parse(query_questions("[]"))["error"], "invalid_query");
+    }
+}
@@ -0,0 +1,302 @@
+// ─── Nemeth Braille ──────────────────────────────────────────────────
+//
+// Nemeth Code for expressions and statements, as Unicode braille cells
+// (U+2800–U+28FF) that a refreshable display driver can send as-is:
+// "2x^2 + 5" → ⠼⠆⠭⠘⠆⠐⠬⠢.
+//
+// Covered: digits (with the numeric indicator where a side begins with
+// a number), decimals, letters and π, + − ×, fractions (simple and
+// complex), superscripts at any level, square roots, grouping and
+// absolute-value bars, the six comparison signs (spaced, as Nemeth
+// requires), and the omission symbol for "?" / "__" blanks. Grouping
+// follows the parse tree, as in render.rs.
+
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+use crate::compare::{self, Relation};
+use crate::expr::{self, BinOp, Expr, Func};
+use crate::latex;
+use crate::render::{precedence, starts_with_letter, Precedence};
+use crate::version;
+
+/// Nemeth numerals are dropped (lower-cell) digits, 0–9.
+const DIGITS: [char; 10] = ['⠴', '⠂', '⠆', '⠒', '⠲', '⠢', '⠖', '⠶', '⠦', '⠔'];
+
+/// a–z.
+const LETTERS: [char; 26] = [
+    '⠁', '⠃', '⠉', '⠙', '⠑', '⠋', '⠛', '⠓', '⠊', '⠚', '⠅', '⠇', '⠍', '⠝', '⠕', '⠏', '⠟', '⠗', '⠎',
+    '⠞', '⠥', '⠧', '⠺', '⠭', '⠽', '⠵',
+];
+
+const NUMERIC_INDICATOR: char = '⠼';
+const DECIMAL_POINT: char = '⠨';
+const CAPITAL: char = '⠠';
+const PI: &str = "⠨⠏";
+const PLUS: &str = "⠬";
+const MINUS: &str = "⠤";
+const TIMES: &str = "⠈⠡";
+const FRACTION_OPEN: &str = "⠹";
+const FRACTION_LINE: &str = "⠌";
+const FRACTION_CLOSE: &str = "⠼";
+/// Repeated before each fraction indicator once per level of nesting.
+const COMPLEX_FRACTION: &str = "⠠";
+const SUPERSCRIPT: &str = "⠘";
+const BASELINE: &str = "⠐";
+const RADICAL: &str = "⠜";
+const RADICAL_END: &str = "⠻";
+const GROUP_OPEN: &str = "⠷";
+const GROUP_CLOSE: &str = "⠾";
+const BAR: &str = "⠳";
+const OMISSION: char = '⠿';
+/// Blank cell around comparison signs.
+const SPACE: char = '⠀';
+
+fn relation_sign(relation: Relation) -> &'static str {
+    match relation {
+        Relation::Less => "⠐⠅",
+        Relation::LessOrEqual => "⠐⠅⠱",
+        Relation::Equal => "⠨⠅",
+        Relation::NotEqual => "⠌⠨⠅",
+        Relation::GreaterOrEqual => "⠨⠂⠱",
+        Relation::Greater => "⠨⠂",
+    }
+}
+
+fn number(value: &crate::rational::Rational) -> String {
+    // Literals are never negative (a minus sign parses as `Neg`) and
+    // non-integers come from terminating decimals.
+    let text = value
+        .to_exact_decimal()
+        .unwrap_or_else(|| value.to_string());
+    text.chars()
+        .map(|c| match c.to_digit(10) {
+            Some(d) => DIGITS[d as usize],
+            None => DECIMAL_POINT,
+        })
+        .collect()
+}
+
+fn variable(name: &str) -> String {
+    if name == "pi" {
+        return PI.to_string();
+    }
+    let mut cells = String::new();
+    for c in name.chars() {
+        if let Some(d) = c.to_digit(10) {
+            cells.push(DIGITS[d as usize]);
+        } else if c.is_ascii_alphabetic() {
+            if c.is_ascii_uppercase() {
+                cells.push(CAPITAL);
+            }
+            cells.push(LETTERS[(c.to_ascii_lowercase() as u8 - b'a') as usize]);
+        } else {
+            // No Nemeth cell here for letters outside a–z.
+            cells.push(OMISSION);
+        }
+    }
+    cells
+}
+
+/// How deeply fractions nest inside `expr` (a simple fraction is 1).
+fn fraction_depth(expr: &Expr) -> usize {
+    match expr {
+        Expr::Bin(BinOp::Div, l, r) => 1 + fraction_depth(l).max(fraction_depth(r)),
+        Expr::Bin(_, l, r) => fraction_depth(l).max(fraction_depth(r)),
+        Expr::Neg(inner) | Expr::Call(_, inner) => fraction_depth(inner),
+        Expr::Num(_) | Expr::Var(_) => 0,
+    }
+}
+
+fn group(cells: String) -> String {
+    format!("{}{}{}", GROUP_OPEN, cells, GROUP_CLOSE)
+}
+
+/// `expr` at superscript `level` (0 is the baseline), and whether it
+/// ends above that level (in an exponent). No level indicator is written
+/// after a trailing exponent: `settled` adds one only when something at
+/// `level` follows, so leaving nested exponents takes a single indicator.
+fn transcribe(expr: &Expr, level: usize) -> (String, bool) {
+    match expr {
+        Expr::Num(n) => (number(n), false),
+        Expr::Var(name) => (variable(name), false),
+        Expr::Neg(inner) if precedence(inner) < Precedence::Negation => {
+            (format!("{}{}", MINUS, group(settled(inner, level))), false)
+        }
+        Expr::Neg(inner) => {
+            let (cells, raised) = transcribe(inner, level);
+            (format!("{}{}", MINUS, cells), raised)
+        }
+        Expr::Call(Func::Sqrt, arg) => (
+            format!("{}{}{}", RADICAL, settled(arg, level), RADICAL_END),
+            false,
+        ),
+        Expr::Call(Func::Abs, arg) => (format!("{}{}{}", BAR, settled(arg, level), BAR), false),
+        Expr::Bin(BinOp::Div, left, right) => {
+            let complex = COMPLEX_FRACTION.repeat(fraction_depth(expr) - 1);
+            let cells = format!(
+                "{c}{}{}{c}{}{}{c}{}",
+                FRACTION_OPEN,
+                settled(left, level),
+                FRACTION_LINE,
+                settled(right, level),
+                FRACTION_CLOSE,
+                c = complex
+            );
+            (cells, false)
+        }
+        Expr::Bin(BinOp::Pow, base, exponent) => {
+            let cells = settled(base, level);
+            let cells = if precedence(base) < Precedence::Atom {
+                group(cells)
+            } else {
+                cells
+            };
+            let (exponent, _) = transcribe(exponent, level + 1);
+            (
+                format!("{}{}{}", cells, SUPERSCRIPT.repeat(level + 1), exponent),
+                true,
+            )
+        }
+        Expr::Bin(op, left, right) => {
+            let own = precedence(expr);
+            let l = settled(left, level);
+            let l = if precedence(left) < own { group(l) } else { l };
+            let group_right = precedence(right) <= own || matches!(**right, Expr::Neg(_));
+            let (r, raised) = if group_right {
+                (group(settled(right, level)), false)
+            } else {
+                transcribe(right, level)
+            };
+            let sign = match op {
+                BinOp::Add => PLUS,
+                BinOp::Sub => MINUS,
+                // "2x", "3⠷x⠬⠂⠾": numbers juxtapose with what follows.
+                _ if matches!(**left, Expr::Num(_))
+                    && (group_right || starts_with_letter(right)) =>
+                {
+                    ""
+                }
+                _ => TIMES,
+            };
+            (format!("{}{}{}", l, sign, r), raised)
+        }
+    }
+}
+
+/// `expr` at `level`, followed by the indicator back to `level` if it
+/// ends in an exponent — for when more cells at `level` follow.
+fn settled(expr: &Expr, level: usize) -> String {
+    let (mut cells, raised) = transcribe(expr, level);
+    if raised {
+        if level == 0 {
+            cells.push_str(BASELINE);
+        } else {
+            cells.push_str(&SUPERSCRIPT.repeat(level));
+        }
+    }
+    cells
+}
+
+/// One side of a statement, with the numeric indicator a leading
+/// numeral needs (also after a leading minus sign).
+fn side(text: &str) -> Result<String, expr::ParseError> {
+    let text = text.trim();
+    if text == "?" || (!text.is_empty() && text.chars().all(|c| c == '_')) {
+        return Ok(OMISSION.to_string());
+    }
+    // A return to the baseline at the very end is implied.
+    let (cells, _) = transcribe(&expr::parse(text)?, 0);
+    let sign_len = if cells.starts_with(MINUS) {
+        MINUS.len()
+    } else {
+        0
+    };
+    let (sign, rest) = cells.split_at(sign_len);
+    let numeral = rest.starts_with(DIGITS) || rest.starts_with(DECIMAL_POINT);
+    Ok(if numeral {
+        format!("{}{}{}", sign, NUMERIC_INDICATOR, rest)
+    } else {
+        cells.to_string()
+    })
+}
+
+/// Transcribe an expression or statement into Nemeth braille.
+///
+/// Takes the same input as `read_aloud` ("2/3 + 1/2 = ?", LaTeX
+/// accepted). Returns `{"braille"}` — Unicode braille cells, with blank
+/// cells (U+2800) around comparison signs — or
+/// `{"error":"parse_error","message","position"}`.
+#[wasm_bindgen]
+pub fn to_nemeth(expr: &str) -> String {
+    let plain = latex::to_plain(expr);
+    let braille = match compare::split_statement(&plain) {
+        Some((left, relation, right)) => side(left).and_then(|l| {
+            Ok(format!(
+                "{}{}{}{}{}",
+                l,
+                SPACE,
+                relation_sign(relation),
+                SPACE,
+                side(right)?
+            ))
+        }),
+        None => side(&plain),
+    };
+    version::result(match braille {
+        Ok(braille) => json!({ "braille": braille }),
+        Err(error) => json!({
+            "error": "parse_error",
+            "message": error.message,
+            "position": error.position,
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn braille(expr: &str) -> String {
+        let result: Value = serde_json::from_str(&to_nemeth(expr)).unwrap();
+        result["braille"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_transcriptions() {
+        let cases = [
+            ("1/2 + 3/4", "⠹⠂⠌⠆⠼⠬⠹⠒⠌⠲⠼"),
+            ("2x^2 + 5", "⠼⠆⠭⠘⠆⠐⠬⠢"),
+            ("x^2", "⠭⠘⠆"),
+            ("sqrt(16) = 4", "⠜⠂⠖⠻⠀⠨⠅⠀⠼⠲"),
+            ("-3 + x", "⠤⠼⠒⠬⠭"),
+            ("3(x + 1)", "⠼⠒⠷⠭⠬⠂⠾"),
+            ("7 * 8", "⠼⠶⠈⠡⠦"),
+            ("0.25", "⠼⠴⠨⠆⠢"),
+            ("2 + 3 = ?", "⠼⠆⠬⠒⠀⠨⠅⠀⠿"),
+            ("3/4 > 2/3", "⠹⠒⠌⠲⠼⠀⠨⠂⠀⠹⠆⠌⠒⠼"),
+            ("(1/2)/3", "⠠⠹⠹⠂⠌⠆⠼⠠⠌⠒⠠⠼"),
+            ("x^(y^2) + 1", "⠭⠘⠽⠘⠘⠆⠐⠬⠂"),
+            ("2^3^2", "⠼⠆⠘⠒⠘⠘⠆"),
+            ("2^3^2 + 1", "⠼⠆⠘⠒⠘⠘⠆⠐⠬⠂"),
+            ("x^(y^2 + 1) - 3", "⠭⠘⠽⠘⠘⠆⠘⠬⠂⠐⠤⠒"),
+            ("x^(y^(z^2)) * 2", "⠭⠘⠽⠘⠘⠵⠘⠘⠘⠆⠐⠈⠡⠆"),
+            ("(x^2 + 1)/2", "⠹⠭⠘⠆⠐⠬⠂⠌⠆⠼"),
+            ("2 pi", "⠼⠆⠨⠏"),
+            ("abs(-4)", "⠳⠤⠲⠳"),
+            (r"\frac{1}{2}", "⠹⠂⠌⠆⠼"),
+        ];
+        for (expr, cells) in cases {
+            assert_eq!(braille(expr), cells, "{}", expr);
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            to_nemeth("2 *"),
+            r#"{"error":"parse_error","message":"unexpected end of input","position":3,"result_schema_version":2}"#
+        );
+    }
+}
@@ -0,0 +1,214 @@
+// ─── Canonical Form ──────────────────────────────────────────────────
+//
+// Re-prints an expression in the engine's own syntax with one spelling
+// per parse tree: single spaces around + and −, "*" for explicit
+// products, "/" without spaces, juxtaposition for coefficients ("2x",
+// "3(x + 1)"), and parentheses only where the tree needs them. So
+// "2*x^2+ 3 ( x+1 )", "2x^{2} + 3(x + 1)" and "2 x^2 + 3*(x+1)" all
+// print "2x^2 + 3(x + 1)".
+//
+// The result parses back to the same tree — never nested deeper than
+// the input, so it stays within the parser's limits — which makes it
+// safe to store in place of what the student typed: progress records compare
+// and group answers by their canonical form. Values are not simplified
+// — "2/4" stays "2/4", since the form of an answer matters to format
+// feedback.
+
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+use crate::compare;
+use crate::expr::{self, BinOp, Expr, Func};
+use crate::latex;
+use crate::rational::Rational;
+use crate::render::{starts_with_letter, Precedence};
+use crate::version;
+
+/// Binding strength in plain syntax, where a quotient is a product
+/// rather than a fraction bar.
+fn precedence(expr: &Expr) -> Precedence {
+    match expr {
+        Expr::Bin(BinOp::Div, _, _) => Precedence::Product,
+        _ => crate::render::precedence(expr),
+    }
+}
+
+/// A number (or negated number) written before what it multiplies.
+fn is_coefficient(expr: &Expr) -> bool {
+    match expr {
+        Expr::Num(_) => true,
+        Expr::Neg(inner) => matches!(**inner, Expr::Num(_)),
+        _ => false,
+    }
+}
+
+fn print(expr: &Expr) -> String {
+    let grouped = |inner: &Expr, wrap: bool| {
+        if wrap {
+            format!("({})", print(inner))
+        } else {
+            print(inner)
+        }
+    };
+    match expr {
+        Expr::Num(n) if n.is_integer() => n.to_string(),
+        // Non-integer literals come from decimals, which terminate.
+        Expr::Num(n) => n.to_exact_decimal().unwrap_or_else(|| n.to_string()),
+        Expr::Var(name) => name.clone(),
+        Expr::Neg(inner) => format!(
+            "-{}",
+            grouped(inner, precedence(inner) < Precedence::Negation)
+        ),
+        Expr::Call(func, arg) => {
+            let name = match func {
+                Func::Sqrt => "sqrt",
+                Func::Abs => "abs",
+            };
+            format!("{}({})", name, print(arg))
+        }
+        Expr::Bin(BinOp::Pow, base, exponent) => format!(
+            "{}^{}",
+            grouped(base, precedence(base) < Precedence::Atom),
+            grouped(exponent, precedence(exponent) < Precedence::Negation)
+        ),
+        Expr::Bin(op, left, right) => {
+            let own = precedence(expr);
+            let l = grouped(left, precedence(left) < own);
+            // A negated right operand needs no parentheses: "3 - -2".
+            let group_right = precedence(right) <= own;
+            let r = grouped(right, group_right);
+            match op {
+                BinOp::Add => format!("{} + {}", l, r),
+                BinOp::Sub => format!("{} - {}", l, r),
+                BinOp::Div => format!("{}/{}", l, r),
+                BinOp::Mul
+                    if is_coefficient(left) && (group_right || starts_with_letter(right)) =>
+                {
+                    format!("{}{}", l, r)
+                }
+                _ => format!("{} * {}", l, r),
+            }
+        }
+    }
+}
+
+/// A mixed number ("2  1/2" → "2 1/2"), kept as written: the expression
+/// parser would read it as a product.
+fn mixed_number(text: &str) -> Option<String> {
+    let (whole, fraction) = text.split_once(' ')?;
+    Rational::parse(text)?;
+    let (numerator, denominator) = fraction.split_once('/')?;
+    Some(format!(
+        "{} {}/{}",
+        whole.trim(),
+        numerator.trim(),
+        denominator.trim()
+    ))
+}
+
+/// One side of a statement; "?" and "__" blanks become "?".
+fn side(text: &str) -> Result<String, expr::ParseError> {
+    let text = text.trim();
+    if text == "?" || (!text.is_empty() && text.chars().all(|c| c == '_')) {
+        return Ok("?".to_string());
+    }
+    if let Some(mixed) = mixed_number(text) {
+        return Ok(mixed);
+    }
+    Ok(print(&expr::parse(text)?))
+}
+
+/// `text` (an expression or statement, LaTeX accepted) in canonical form.
+pub(crate) fn canonical(text: &str) -> Result<String, expr::ParseError> {
+    let plain = latex::to_plain(text);
+    match compare::split_statement(&plain) {
+        Some((left, relation, right)) => Ok(format!(
+            "{} {} {}",
+            side(left)?,
+            relation.symbol(),
+            side(right)?
+        )),
+        None => side(&plain),
+    }
+}
+
+/// Re-print an expression or statement in canonical form.
+///
+/// Returns `{"canonical"}` — normalized spacing, operator symbols and
+/// parentheses, parsing back to the same tree — or
+/// `{"error":"parse_error","message","position"}`.
+#[wasm_bindgen]
+pub fn format_canonical(expr: &str) -> String {
+    version::result(match canonical(expr) {
+        Ok(canonical) => json!({ "canonical": canonical }),
+        Err(error) => json!({
+            "error": "parse_error",
+            "message": error.message,
+            "position": error.position,
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_forms() {
+        let cases = [
+            ("2*x^2+ 3 ( x+1 )", "2x^2 + 3(x + 1)"),
+            (r"2x^{2} + 3(x + 1)", "2x^2 + 3(x + 1)"),
+            ("1 /2+3/ 4", "1/2 + 3/4"),
+            ("(1 + 2)/(3 - x)", "(1 + 2)/(3 - x)"),
+            ("1/(2/3)", "1/(2/3)"),
+            ("1/2x", "1/2 * x"),
+            ("7×8", "7 * 8"),
+            ("3 - (-2)", "3 - -2"),
+            ("-(-3)", "--3"),
+            ("-2 * x", "-2x"),
+            ("(x^2)^3", "(x^2)^3"),
+            ("x^(1/2) + 2^(-1)", "x^(1/2) + 2^-1"),
+            ("√9 + abs(-4)", "sqrt(9) + abs(-4)"),
+            ("0.50", "0.5"),
+            ("2/4", "2/4"),
+            ("2  1/2", "2 1/2"),
+            ("x+1=3", "x + 1 = 3"),
+            ("3/4 ≥ 2/3", "3/4 >= 2/3"),
+            ("2 + 3 = __", "2 + 3 = ?"),
+        ];
+        for (input, canonical) in cases {
+            assert_eq!(
+                format_canonical(input),
+                json!({ "canonical": canonical, "result_schema_version": 2 }).to_string(),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_canonical_parses_back_to_same_tree() {
+        for input in [
+            "2x^2 - 3(x + 1)",
+            "1/2x + 4/(2y)",
+            "-x^2 * -3",
+            "2^3^2 / (1 - -1)",
+            "(a + b)(a - b) - sqrt(x + 1)",
+            "-(2 + 3) * x / 5",
+            // Near the parser's nesting limit: no parentheses added.
+            &format!("s - {}0{} * e", "-(".repeat(20), ")".repeat(20)),
+        ] {
+            let printed = canonical(input).unwrap();
+            assert_eq!(expr::parse(&printed), expr::parse(input), "{}", input);
+            assert_eq!(canonical(&printed).unwrap(), printed);
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            format_canonical("2 +"),
+            r#"{"error":"parse_error","message":"unexpected end of input","position":3,"result_schema_version":2}"#
+        );
+    }
+}
@@ -0,0 +1,188 @@
+// ─── Exact Comparisons ───────────────────────────────────────────────
+//
+// Statements like "3/4 > 2/3" are decided with exact rational
+// arithmetic — both sides are evaluated as fractions, never as f64 —
+// so "1/3 + 1/3 = 2/3" is true and "0.1 + 0.2 = 0.3" is true too.
+
+use std::cmp::Ordering;
+
+use wasm_bindgen::prelude::*;
+
+use crate::expr;
+use crate::rational::Rational;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Relation {
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    GreaterOrEqual,
+    Greater,
+}
+
+/// Relation symbols, two-character forms first so "<=" is not read as "<".
+const SYMBOLS: [(&str, Relation); 10] = [
+    ("<=", Relation::LessOrEqual),
+    (">=", Relation::GreaterOrEqual),
+    ("!=", Relation::NotEqual),
+    ("≤", Relation::LessOrEqual),
+    ("≥", Relation::GreaterOrEqual),
+    ("≠", Relation::NotEqual),
+    ("==", Relation::Equal),
+    ("<", Relation::Less),
+    (">", Relation::Greater),
+    ("=", Relation::Equal),
+];
+
+impl Relation {
+    pub(crate) fn parse(symbol: &str) -> Option<Relation> {
+        let symbol = symbol.trim();
+        SYMBOLS
+            .iter()
+            .find(|(text, _)| *text == symbol)
+            .map(|&(_, relation)| relation)
+    }
+
+    /// The symbol in plain ASCII: "<", "<=", "=", "!=", ">=", ">".
+    pub(crate) fn symbol(self) -> &'static str {
+        match self {
+            Relation::Less => "<",
+            Relation::LessOrEqual => "<=",
+            Relation::Equal => "=",
+            Relation::NotEqual => "!=",
+            Relation::GreaterOrEqual => ">=",
+            Relation::Greater => ">",
+        }
+    }
+
+    pub(crate) fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Relation::Less => ordering.is_lt(),
+            Relation::LessOrEqual => ordering.is_le(),
+            Relation::Equal => ordering.is_eq(),
+            Relation::NotEqual => ordering.is_ne(),
+            Relation::GreaterOrEqual => ordering.is_ge(),
+            Relation::Greater => ordering.is_gt(),
+        }
+    }
+}
+
+/// Evaluate a constant expression exactly: "3/4", "2 1/2", "1/3 + 1/6".
+pub(crate) fn exact_value(text: &str) -> Option<Rational> {
+    Rational::parse(text).or_else(|| expr::eval_rational(text, &|_| None).ok().flatten())
+}
+
+/// Evaluate a constant expression in floating point — the independent
+/// second path for dual-path grading (see `dual`).
+pub(crate) fn float_value(text: &str) -> Option<f64> {
+    // Plain literals ("2 1/2") first, as in `exact_value`.
+    Rational::parse(text)
+        .map(|r| r.to_f64())
+        .or_else(|| expr::eval_f64(text, &|_| None).ok().flatten())
+}
+
+/// Order two floats, treating a relative difference below 1e-9 as equal.
+pub(crate) fn float_ordering(a: f64, b: f64) -> Ordering {
+    if (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0) {
+        Ordering::Equal
+    } else {
+        a.total_cmp(&b)
+    }
+}
+
+/// Split "3/4 > 2/3" into its sides and relation.
+pub(crate) fn split_statement(statement: &str) -> Option<(&str, Relation, &str)> {
+    SYMBOLS.iter().find_map(|&(symbol, relation)| {
+        let (left, right) = statement.split_once(symbol)?;
+        Some((left, relation, right))
+    })
+}
+
+/// Is the statement true? `None` if it has no relation or a side
+/// cannot be evaluated exactly.
+pub(crate) fn evaluate_statement(statement: &str) -> Option<bool> {
+    let (left, relation, right) = split_statement(statement)?;
+    let ordering = exact_value(left)?.cmp(&exact_value(right)?);
+    Some(relation.holds(ordering))
+}
+
+/// `evaluate_statement` in floating point.
+pub(crate) fn evaluate_statement_f64(statement: &str) -> Option<bool> {
+    let (left, relation, right) = split_statement(statement)?;
+    let ordering = float_ordering(float_value(left)?, float_value(right)?);
+    Some(relation.holds(ordering))
+}
+
+/// Read "true"/"false" (also "T"/"F", "yes"/"no").
+pub(crate) fn parse_truth(answer: &str) -> Option<bool> {
+    match answer.trim().to_ascii_lowercase().as_str() {
+        "true" | "t" | "yes" | "y" => Some(true),
+        "false" | "f" | "no" | "n" => Some(false),
+        _ => None,
+    }
+}
+
+/// Validate a comparison: is `student_symbol` ("<", ">", "=") the
+/// relation between `left` and `right`?
+/// "3/4", "2/3", ">" → true
+#[wasm_bindgen]
+pub fn validate_comparison(left: &str, right: &str, student_symbol: &str) -> bool {
+    let (Some(relation), Some(a), Some(b)) = (
+        Relation::parse(student_symbol),
+        exact_value(left),
+        exact_value(right),
+    ) else {
+        return false;
+    };
+    // Only the strict relation counts: "≤" for 3/4 vs 2/3 is true but
+    // is not the answer to "which symbol goes here?".
+    matches!(
+        (relation, a.cmp(&b)),
+        (Relation::Less, Ordering::Less)
+            | (Relation::Equal, Ordering::Equal)
+            | (Relation::Greater, Ordering::Greater)
+    )
+}
+
+/// Validate a true/false answer to a statement like "3/4 > 2/3".
+#[wasm_bindgen]
+pub fn validate_true_false(statement: &str, student_answer: &str) -> bool {
+    match (evaluate_statement(statement), parse_truth(student_answer)) {
+        (Some(truth), Some(answer)) => truth == answer,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statements_are_exact() {
+        assert_eq!(evaluate_statement("3/4 > 2/3"), Some(true));
+        assert_eq!(evaluate_statement("0.1 + 0.2 = 0.3"), Some(true));
+        assert_eq!(evaluate_statement("1/3 + 1/3 <= 2/3"), Some(true));
+        assert_eq!(evaluate_statement("-1/2 ≥ 0"), Some(false));
+        assert_eq!(evaluate_statement("3/4"), None);
+        assert_eq!(evaluate_statement("x > 1"), None);
+    }
+
+    #[test]
+    fn test_float_path_agrees() {
+        assert_eq!(evaluate_statement_f64("0.1 + 0.2 = 0.3"), Some(true));
+        assert_eq!(evaluate_statement_f64("1/3 + 1/3 <= 2/3"), Some(true));
+        assert_eq!(evaluate_statement_f64("sqrt(2) > 1.41"), Some(true));
+        assert_eq!(float_value("2 1/2"), Some(2.5));
+    }
+
+    #[test]
+    fn test_comparison_symbols() {
+        assert!(validate_comparison("3/4", "2/3", ">"));
+        assert!(validate_comparison("2 1/2", "5/2", "="));
+        assert!(!validate_comparison("3/4", "2/3", ">="));
+        assert!(!validate_comparison("3/4", "2/3", "<"));
+        assert!(validate_true_false("3/4 > 2/3", "True"));
+        assert!(!validate_true_false("3/4 > 2/3", "maybe"));
+    }
+}
@@ -0,0 +1,293 @@
+// ─── Curriculum Graph ────────────────────────────────────────────────
+//
+// The academy's skills and their prerequisites, as a directed acyclic
+// graph: `{"skills":[{"id":"add","requires":[]},
+//                    {"id":"multiply","requires":["add"]},…]}`.
+//
+// Every query re-validates the graph, so a skills file with a typo or
+// a prerequisite loop is reported instead of silently locking skills
+// forever. Orders are deterministic: among skills that are ready at
+// the same time, file order wins.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::version;
+
+/// A validated skills graph.
+#[derive(Debug, Clone, PartialEq)]
+struct Curriculum {
+    /// Skill ids in file order.
+    skills: Vec<String>,
+    /// Prerequisites of each skill, by index into `skills`.
+    requires: Vec<Vec<usize>>,
+}
+
+/// Why a skills graph was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum GraphError {
+    InvalidGraph,
+    DuplicateSkill(String),
+    UnknownPrerequisite(String),
+    /// Skills on a prerequisite loop, in order.
+    Cycle(Vec<String>),
+}
+
+impl GraphError {
+    fn to_json(&self) -> Value {
+        match self {
+            GraphError::InvalidGraph => json!({ "error": "invalid_graph" }),
+            GraphError::DuplicateSkill(id) => json!({ "error": "duplicate_skill", "skill": id }),
+            GraphError::UnknownPrerequisite(id) => {
+                json!({ "error": "unknown_prerequisite", "skill": id })
+            }
+            GraphError::Cycle(ids) => json!({ "error": "cycle", "skills": ids }),
+        }
+    }
+}
+
+impl Curriculum {
+    fn parse(graph_json: &str) -> Result<Curriculum, GraphError> {
+        let graph: Value =
+            serde_json::from_str(graph_json).map_err(|_| GraphError::InvalidGraph)?;
+        let entries = graph
+            .get("skills")
+            .and_then(Value::as_array)
+            .ok_or(GraphError::InvalidGraph)?;
+
+        let mut skills = Vec::with_capacity(entries.len());
+        let mut index = BTreeMap::new();
+        let mut prerequisites = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let id = entry
+                .get("id")
+                .and_then(Value::as_str)
+                .ok_or(GraphError::InvalidGraph)?;
+            if index.insert(id.to_string(), skills.len()).is_some() {
+                return Err(GraphError::DuplicateSkill(id.to_string()));
+            }
+            let requires: Vec<&str> = match entry.get("requires") {
+                None => Vec::new(),
+                Some(list) => list
+                    .as_array()
+                    .and_then(|list| list.iter().map(Value::as_str).collect())
+                    .ok_or(GraphError::InvalidGraph)?,
+            };
+            skills.push(id.to_string());
+            prerequisites.push(requires);
+        }
+        let requires = prerequisites
+            .iter()
+            .map(|list| {
+                list.iter()
+                    .map(|id| {
+                        index
+                            .get(*id)
+                            .copied()
+                            .ok_or_else(|| GraphError::UnknownPrerequisite(id.to_string()))
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        let curriculum = Curriculum { skills, requires };
+        curriculum.order()?;
+        Ok(curriculum)
+    }
+
+    /// Kahn's algorithm; a prerequisite loop leaves skills unplaced.
+    fn order(&self) -> Result<Vec<usize>, GraphError> {
+        let mut waiting: Vec<usize> = self.requires.iter().map(Vec::len).collect();
+        let mut unlocks = vec![Vec::new(); self.skills.len()];
+        for (skill, requires) in self.requires.iter().enumerate() {
+            for &prerequisite in requires {
+                unlocks[prerequisite].push(skill);
+            }
+        }
+        let mut ready: BTreeSet<usize> = (0..self.skills.len())
+            .filter(|&s| waiting[s] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.skills.len());
+        while let Some(skill) = ready.pop_first() {
+            order.push(skill);
+            for &next in &unlocks[skill] {
+                waiting[next] -= 1;
+                if waiting[next] == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+        if order.len() == self.skills.len() {
+            Ok(order)
+        } else {
+            Err(GraphError::Cycle(self.find_cycle(&waiting)))
+        }
+    }
+
+    /// One prerequisite loop among the skills Kahn could not place
+    /// (`waiting` > 0): follow unplaced prerequisites until a skill
+    /// repeats.
+    fn find_cycle(&self, waiting: &[usize]) -> Vec<String> {
+        let Some(start) = (0..self.skills.len()).find(|&s| waiting[s] > 0) else {
+            return Vec::new();
+        };
+        let mut path = vec![start];
+        let mut current = start;
+        loop {
+            current = self.requires[current]
+                .iter()
+                .copied()
+                .find(|&p| waiting[p] > 0)
+                .expect("an unplaced skill has an unplaced prerequisite");
+            if let Some(at) = path.iter().position(|&s| s == current) {
+                // Prerequisite-first, like the topological order.
+                return path[at..]
+                    .iter()
+                    .rev()
+                    .map(|&s| self.skills[s].clone())
+                    .collect();
+            }
+            path.push(current);
+        }
+    }
+
+    fn ids(&self, indices: impl IntoIterator<Item = usize>) -> Vec<&str> {
+        indices
+            .into_iter()
+            .map(|i| self.skills[i].as_str())
+            .collect()
+    }
+}
+
+/// Check a skills graph: every id unique, every prerequisite a known
+/// skill, no prerequisite loops.
+///
+/// Returns `{"valid":true,"skills":n}`, or `{"error":code,…}`:
+/// "invalid_graph", "duplicate_skill" / "unknown_prerequisite" (with
+/// the offending `skill`), or "cycle" (with the loop's `skills`).
+#[wasm_bindgen]
+pub fn validate_curriculum(graph_json: &str) -> String {
+    version::result(match Curriculum::parse(graph_json) {
+        Ok(curriculum) => json!({ "valid": true, "skills": curriculum.skills.len() }),
+        Err(error) => error.to_json(),
+    })
+}
+
+/// Every skill, each after all of its prerequisites.
+///
+/// Returns `{"order":["add","multiply","fractions",…]}` (ties in file
+/// order), or the error from `validate_curriculum`.
+#[wasm_bindgen]
+pub fn curriculum_order(graph_json: &str) -> String {
+    let result =
+        Curriculum::parse(graph_json).and_then(|c| Ok(json!({ "order": c.ids(c.order()?) })));
+    version::result(result.unwrap_or_else(|error| error.to_json()))
+}
+
+/// Skills a student can start: not yet mastered, with every
+/// prerequisite mastered.
+///
+/// `mastered_json` is an array of skill ids; ids not in the graph are
+/// ignored (retired skills in old progress data). Returns
+/// `{"unlocked":[…]}` in curriculum order, or the error from
+/// `validate_curriculum` (`{"error":"invalid_mastered"}` for a
+/// malformed list).
+#[wasm_bindgen]
+pub fn unlocked_skills(graph_json: &str, mastered_json: &str) -> String {
+    let curriculum = match Curriculum::parse(graph_json) {
+        Ok(curriculum) => curriculum,
+        Err(error) => return version::result(error.to_json()),
+    };
+    let mastered: Option<BTreeSet<String>> = match serde_json::from_str::<Value>(mastered_json) {
+        Ok(Value::Array(ids)) => ids
+            .iter()
+            .map(|id| id.as_str().map(str::to_string))
+            .collect(),
+        _ => None,
+    };
+    let Some(mastered) = mastered else {
+        return version::result(json!({ "error": "invalid_mastered" }));
+    };
+    let is_mastered = |skill: usize| mastered.contains(&curriculum.skills[skill]);
+    let order = curriculum.order().expect("validated by parse");
+    let unlocked = order.into_iter().filter(|&skill| {
+        !is_mastered(skill) && curriculum.requires[skill].iter().all(|&p| is_mastered(p))
+    });
+    version::result(json!({ "unlocked": curriculum.ids(unlocked) }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRAPH: &str = r#"{"skills":[
+        {"id":"multiply","requires":["add"]},
+        {"id":"add"},
+        {"id":"fractions","requires":["multiply","subtract"]},
+        {"id":"subtract","requires":["add"]},
+        {"id":"decimals","requires":["fractions"]}]}"#;
+
+    fn parse(output: String) -> Value {
+        serde_json::from_str(&output).unwrap()
+    }
+
+    #[test]
+    fn test_order_and_validation() {
+        assert_eq!(
+            parse(validate_curriculum(GRAPH)),
+            json!({"valid":true,"skills":5,"result_schema_version":2})
+        );
+        assert_eq!(
+            parse(curriculum_order(GRAPH))["order"],
+            json!(["add", "multiply", "subtract", "fractions", "decimals"])
+        );
+    }
+
+    #[test]
+    fn test_unlocked_skills() {
+        let unlocked = |mastered: &str| parse(unlocked_skills(GRAPH, mastered))["unlocked"].clone();
+        assert_eq!(unlocked("[]"), json!(["add"]));
+        assert_eq!(unlocked(r#"["add"]"#), json!(["multiply", "subtract"]));
+        assert_eq!(
+            unlocked(r#"["add","subtract","retired"]"#),
+            json!(["multiply"])
+        );
+        assert_eq!(
+            unlocked(r#"["add","multiply","subtract"]"#),
+            json!(["fractions"])
+        );
+        assert_eq!(
+            parse(unlocked_skills(GRAPH, "{}"))["error"],
+            "invalid_mastered"
+        );
+    }
+
+    #[test]
+    fn test_rejected_graphs() {
+        let cyclic = r#"{"skills":[{"id":"a","requires":["c"]},{"id":"b","requires":["a"]},
+            {"id":"c","requires":["b"]},{"id":"d","requires":["a"]}]}"#;
+        let error = parse(validate_curriculum(cyclic));
+        assert_eq!(error["error"], "cycle");
+        assert_eq!(error["skills"].as_array().unwrap().len(), 3);
+        assert_eq!(parse(curriculum_order(cyclic))["error"], "cycle");
+
+        let self_loop = r#"{"skills":[{"id":"a","requires":["a"]}]}"#;
+        assert_eq!(
+            parse(validate_curriculum(self_loop))["skills"],
+            json!(["a"])
+        );
+        assert_eq!(
+            parse(validate_curriculum(r#"{"skills":[{"id":"a"},{"id":"a"}]}"#)),
+            json!({"error":"duplicate_skill","skill":"a","result_schema_version":2})
+        );
+        assert_eq!(
+            parse(validate_curriculum(
+                r#"{"skills":[{"id":"a","requires":["z"]}]}"#
+            )),
+            json!({"error":"unknown_prerequisite","skill":"z","result_schema_version":2})
+        );
+        assert_eq!(parse(validate_curriculum("[]"))["error"], "invalid_graph");
+    }
+}
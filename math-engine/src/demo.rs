@@ -0,0 +1,53 @@
+// ─── Demo Curriculum ─────────────────────────────────────────────────
+//
+// A small, complete sample curriculum — one unit per grade band (K-2,
+// 3-5, 6-8, 9-12) — compiled into the WASM module behind the
+// `demo-content` feature, so the desktop app and docs site can run
+// fully offline demos without downloading a content pack.
+//
+// The bank lives in `demo/bank.json`. Every exercise uses a built-in
+// problem type, and its `answer` is checked against `check_answer` in
+// the tests below, so the demo can never ship a wrong answer key.
+
+use wasm_bindgen::prelude::*;
+
+const DEMO_BANK: &str = include_str!("../demo/bank.json");
+
+/// The embedded demo curriculum as JSON:
+/// `{"version":1,"title","units":[{"id","grade_band","title","lesson",
+/// "exercises":[{"id","type","problem","answer"}…]}…]}`.
+/// Each exercise can be graded with `check_answer(type, problem, answer)`.
+#[wasm_bindgen]
+pub fn demo_bank() -> String {
+    DEMO_BANK.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn test_demo_answers_are_correct() {
+        let bank: Value = serde_json::from_str(&demo_bank()).unwrap();
+        let units = bank["units"].as_array().unwrap();
+        let bands: Vec<&str> = units
+            .iter()
+            .map(|u| u["grade_band"].as_str().unwrap())
+            .collect();
+        assert_eq!(bands, ["K-2", "3-5", "6-8", "9-12"]);
+        for exercise in units
+            .iter()
+            .flat_map(|u| u["exercises"].as_array().unwrap())
+        {
+            let field = |name: &str| exercise[name].as_str().unwrap();
+            let result = crate::check_answer(field("type"), field("problem"), field("answer"));
+            assert!(
+                result.contains("\"correct\":true"),
+                "{}: {}",
+                field("id"),
+                result
+            );
+        }
+    }
+}
@@ -0,0 +1,259 @@
+// ─── Multiple-Choice Distractors ─────────────────────────────────────
+//
+// Plausible wrong answers built from common error patterns: sign
+// flips, off-by-one, forgotten carries and borrows, the wrong
+// operation, place-value slips, and inverted fractions. Each carries a
+// misconception ID so a chosen distractor can be explained. Selection
+// and order depend only on the seed.
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::expr::{self, BinOp, Expr};
+use crate::rational::Rational;
+use crate::rng::SplitMix64;
+use crate::version;
+
+/// How many distractors `generate_distractors` returns.
+pub(crate) const DISTRACTOR_COUNT: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Distractor {
+    pub answer: String,
+    pub misconception: &'static str,
+}
+
+/// Add column by column, dropping every carry: 27 + 45 → 62.
+fn sum_without_carry(a: u64, b: u64) -> Option<u64> {
+    let (mut a, mut b, mut place, mut total) = (a, b, 1u64, 0u64);
+    while a > 0 || b > 0 {
+        total = total.checked_add(((a % 10 + b % 10) % 10).checked_mul(place)?)?;
+        a /= 10;
+        b /= 10;
+        place = place.checked_mul(10)?;
+    }
+    Some(total)
+}
+
+/// Subtract the smaller digit from the larger in every column instead
+/// of borrowing: 52 − 27 → 35.
+fn difference_without_borrow(a: u64, b: u64) -> Option<u64> {
+    let (mut a, mut b, mut place, mut total) = (a, b, 1u64, 0u64);
+    while a > 0 || b > 0 {
+        total = total.checked_add((a % 10).abs_diff(b % 10).checked_mul(place)?)?;
+        a /= 10;
+        b /= 10;
+        place = place.checked_mul(10)?;
+    }
+    Some(total)
+}
+
+fn non_negative_integer(r: Rational) -> Option<u64> {
+    if r.is_integer() {
+        u64::try_from(r.numer()).ok()
+    } else {
+        None
+    }
+}
+
+/// Errors specific to the operation in "a ∘ b" problems.
+fn operation_errors(problem: &str) -> Vec<(Option<Rational>, &'static str)> {
+    let Ok(Expr::Bin(op, l, r)) = expr::parse(problem) else {
+        return Vec::new();
+    };
+    let constant = |_: &str| None;
+    let (Some(a), Some(b)) = (l.eval_rational(&constant), r.eval_rational(&constant)) else {
+        return Vec::new();
+    };
+    let digits = non_negative_integer(a).zip(non_negative_integer(b));
+    let from_u64 = |n: u64| i64::try_from(n).ok().map(Rational::from_integer);
+
+    match op {
+        BinOp::Add => vec![
+            (
+                digits
+                    .and_then(|(x, y)| sum_without_carry(x, y))
+                    .and_then(from_u64),
+                "forgotten_carry",
+            ),
+            (a.checked_sub(b), "wrong_operation"),
+        ],
+        BinOp::Sub => vec![
+            (
+                digits
+                    .and_then(|(x, y)| difference_without_borrow(x, y))
+                    .and_then(from_u64),
+                "borrow_error",
+            ),
+            (a.checked_add(b), "wrong_operation"),
+        ],
+        BinOp::Mul => vec![
+            (a.checked_add(b), "wrong_operation"),
+            (
+                a.checked_mul(b).and_then(|p| p.checked_add(a)),
+                "off_by_one_group",
+            ),
+        ],
+        BinOp::Div => vec![
+            (a.checked_mul(b), "wrong_operation"),
+            (b.checked_div(a), "reversed_division"),
+        ],
+        BinOp::Pow => vec![(a.checked_mul(b), "power_as_product")],
+    }
+}
+
+/// Candidate wrong answers for `correct`, most specific first.
+fn candidates(problem: &str, correct: Rational) -> Vec<(Option<Rational>, &'static str)> {
+    let mut out = operation_errors(problem);
+    let ten = Rational::from_integer(10);
+    out.extend([
+        (correct.checked_neg(), "sign_flip"),
+        (correct.checked_add(Rational::ONE), "off_by_one"),
+        (correct.checked_sub(Rational::ONE), "off_by_one"),
+        (correct.checked_mul(ten), "place_value"),
+        (correct.checked_div(ten), "place_value"),
+    ]);
+    if !correct.is_integer() {
+        out.push((
+            Rational::new(correct.denom(), correct.numer()),
+            "inverted_fraction",
+        ));
+    }
+    out
+}
+
+/// The error pattern that explains a wrong `answer`, if any: the most
+/// specific candidate equal to it.
+pub(crate) fn classify_error(
+    problem: &str,
+    correct: Rational,
+    answer: Rational,
+) -> Option<&'static str> {
+    if answer == correct {
+        return None;
+    }
+    candidates(problem, correct)
+        .into_iter()
+        .find(|(value, _)| *value == Some(answer))
+        .map(|(_, misconception)| misconception)
+}
+
+/// Write `value` the way the correct answer was written: decimals keep
+/// the same number of places; otherwise fraction/integer form.
+fn render_like(value: Rational, correct_text: &str) -> Option<String> {
+    match correct_text.trim().split_once('.') {
+        Some((_, frac)) => {
+            let places = frac.len() as u32;
+            (value.round_to(places)? == value)
+                .then(|| value.to_decimal_string(places))
+                .flatten()
+        }
+        None => Some(value.to_string()),
+    }
+}
+
+/// Up to `DISTRACTOR_COUNT` distinct wrong answers for `problem`.
+pub(crate) fn distractors_for(problem: &str, correct_text: &str, seed: u64) -> Vec<Distractor> {
+    let Some(correct) = Rational::parse(correct_text) else {
+        return Vec::new();
+    };
+    let mut pool: Vec<Distractor> = Vec::new();
+    for (value, misconception) in candidates(problem, correct) {
+        let Some(value) = value.filter(|v| *v != correct) else {
+            continue;
+        };
+        let Some(answer) = render_like(value, correct_text) else {
+            continue;
+        };
+        if Rational::parse(&answer) == Some(correct) || pool.iter().any(|d| d.answer == answer) {
+            continue;
+        }
+        pool.push(Distractor {
+            answer,
+            misconception,
+        });
+    }
+
+    // Always keep the most specific error; the seed picks the rest and
+    // the final order.
+    if pool.is_empty() {
+        return pool;
+    }
+    let mut rng = SplitMix64::new(seed);
+    let first = pool.remove(0);
+    rng.shuffle(&mut pool);
+    pool.truncate(DISTRACTOR_COUNT - 1);
+    pool.push(first);
+    rng.shuffle(&mut pool);
+    pool
+}
+
+/// Generate plausible wrong answers for a multiple-choice question.
+///
+/// `problem`: the question, e.g. "27 + 45" (operation-specific errors
+/// such as a forgotten carry need a two-operand expression)
+/// `correct_answer`: "72", "3/4", or "1.25" — distractors use the same form
+///
+/// Returns `{"distractors":[{"answer":"62","misconception":"forgotten_carry"},…]}`;
+/// the same seed always gives the same list.
+#[wasm_bindgen]
+pub fn generate_distractors(problem: &str, correct_answer: &str, seed: u64) -> String {
+    let list: Vec<Value> = distractors_for(problem, correct_answer, seed)
+        .into_iter()
+        .map(|d| json!({ "answer": d.answer, "misconception": d.misconception }))
+        .collect();
+    version::result(version::list("distractors", Value::Array(list)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_error() {
+        let classify = |problem: &str, answer: &str| {
+            let correct = crate::compare::exact_value(problem).unwrap();
+            classify_error(problem, correct, Rational::parse(answer).unwrap())
+        };
+        assert_eq!(classify("27 + 45", "62"), Some("forgotten_carry"));
+        assert_eq!(classify("8 - 3", "-5"), Some("sign_flip"));
+        assert_eq!(classify("6 * 7", "43"), Some("off_by_one"));
+        assert_eq!(classify("6 * 7", "42"), None);
+        assert_eq!(classify("6 * 7", "17"), None);
+    }
+
+    #[test]
+    fn test_error_patterns() {
+        assert_eq!(sum_without_carry(27, 45), Some(62));
+        assert_eq!(difference_without_borrow(52, 27), Some(35));
+        let all = distractors_for("27 + 45", "72", 0);
+        assert_eq!(all.len(), DISTRACTOR_COUNT);
+        assert!(all.iter().all(|d| d.answer != "72"));
+        assert!(all.iter().any(|d| d.misconception == "forgotten_carry"));
+    }
+
+    #[test]
+    fn test_deterministic_per_seed() {
+        assert_eq!(
+            generate_distractors("27 + 45", "72", 7),
+            generate_distractors("27 + 45", "72", 7)
+        );
+        let seeds: Vec<String> = (0..8)
+            .map(|s| generate_distractors("27 + 45", "72", s))
+            .collect();
+        assert!(seeds.iter().any(|s| s != &seeds[0]));
+    }
+
+    #[test]
+    fn test_answer_forms_are_preserved() {
+        for d in distractors_for("1.5 * 2", "3.0", 3) {
+            assert!(d.answer.contains('.'), "{}", d.answer);
+        }
+        let fractions = distractors_for("", "3/4", 1);
+        assert!(fractions.iter().all(|d| !d.answer.contains('.')));
+        assert_eq!(
+            generate_distractors("", "abc", 1),
+            r#"{"distractors":[],"result_schema_version":2}"#
+        );
+    }
+}
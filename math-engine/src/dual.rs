@@ -0,0 +1,186 @@
+// ─── Dual-Path Grading ───────────────────────────────────────────────
+//
+// For high-stakes assessments every answer can be graded twice: once
+// by the problem type's validator, and once by an independent code
+// path — floating point for the exact validators, exact or full-parser
+// arithmetic for the float ones. If the two disagree the answer is not
+// marked right or wrong; it is reported as an engine inconsistency for
+// a teacher to review, so logic drift in either path never silently
+// decides a grade.
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::hints;
+use crate::registry::{self, Verdict};
+use crate::version;
+
+/// Agreement between the two grading paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CrossCheck {
+    Agree,
+    Disagree {
+        primary: bool,
+        secondary: bool,
+    },
+    /// The type has no second path, or it could not grade this input.
+    Unavailable,
+}
+
+impl CrossCheck {
+    fn code(self) -> &'static str {
+        match self {
+            CrossCheck::Agree => "agree",
+            CrossCheck::Disagree { .. } => "disagree",
+            CrossCheck::Unavailable => "unavailable",
+        }
+    }
+}
+
+/// Grade by both paths; `None` for an unknown problem type.
+pub(crate) fn grade_both(
+    problem_type: &str,
+    problem: &str,
+    student_answer: &str,
+) -> Option<(Verdict, CrossCheck)> {
+    let (verdict, secondary) =
+        registry::check_registered_both(problem_type, problem, student_answer)?;
+    let cross_check = match secondary {
+        None => CrossCheck::Unavailable,
+        Some(secondary) if secondary == verdict.correct => CrossCheck::Agree,
+        Some(secondary) => CrossCheck::Disagree {
+            primary: verdict.correct,
+            secondary,
+        },
+    };
+    Some((verdict, cross_check))
+}
+
+/// Like `check_answer`, grading each answer by two independent paths.
+///
+/// Adds `"cross_check"`: "agree", "unavailable" (no second path for
+/// this type or input — the validator's result stands), or "disagree".
+/// On disagreement `correct` is `null`, `hint_id` is
+/// "engine.inconsistency", and `"paths":{"primary","secondary"}` holds
+/// both results for review.
+#[wasm_bindgen]
+pub fn check_answer_dual(problem_type: &str, problem: &str, student_answer: &str) -> String {
+    let Some((verdict, cross_check)) = grade_both(problem_type, problem, student_answer) else {
+        let verdict = Verdict::new(false, &hints::UNKNOWN_TYPE, &[("type", problem_type)]);
+        return result_json(&verdict, problem, student_answer, CrossCheck::Unavailable);
+    };
+    result_json(&verdict, problem, student_answer, cross_check)
+}
+
+fn result_json(verdict: &Verdict, problem: &str, answer: &str, cross_check: CrossCheck) -> String {
+    let mut result = json!({
+        "correct": verdict.correct,
+        "hint": verdict.hint,
+        "hint_id": verdict.hint_id,
+        "hint_data": verdict.hint_data(),
+        "problem": problem,
+        "answer": answer,
+        "cross_check": cross_check.code(),
+    });
+    if let CrossCheck::Disagree { primary, secondary } = cross_check {
+        let review = Verdict::new(false, &hints::ENGINE_INCONSISTENCY, &[]);
+        result["correct"] = Value::Null;
+        result["hint"] = Value::from(review.hint.as_str());
+        result["hint_id"] = Value::from(review.hint_id.as_str());
+        result["hint_data"] = review.hint_data();
+        result["paths"] = json!({ "primary": primary, "secondary": secondary });
+    } else if let Some(misconception) = &verdict.misconception {
+        result["misconception"] = Value::from(misconception.as_str());
+    }
+    version::result(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cross_check(problem_type: &str, problem: &str, answer: &str) -> CrossCheck {
+        grade_both(problem_type, problem, answer).unwrap().1
+    }
+
+    #[test]
+    fn test_paths_agree_on_builtins() {
+        assert_eq!(cross_check("arithmetic", "15 / 3", "5"), CrossCheck::Agree);
+        assert_eq!(cross_check("arithmetic", "2 + 3", "6"), CrossCheck::Agree);
+        assert_eq!(cross_check("fraction", "2/4", "1/2"), CrossCheck::Agree);
+        assert_eq!(
+            cross_check("true_false", "0.1 + 0.2 = 0.3", "true"),
+            CrossCheck::Agree
+        );
+        assert_eq!(
+            cross_check("comparison", "1/3 ? 0.33", ">"),
+            CrossCheck::Agree
+        );
+        assert_eq!(
+            cross_check("fill_blank", "__ + __ = 1", "1/3, 2/3"),
+            CrossCheck::Agree
+        );
+        assert_eq!(
+            cross_check("money", "$1.00 + $2.00", "$3.00"),
+            CrossCheck::Unavailable
+        );
+    }
+
+    #[test]
+    fn test_custom_types_cross_check() {
+        crate::register_custom_type(
+            r#"{"name":"dual_circle","formula":"3.14159 * r^2","answer":{"mode":"rounded","places":2}}"#,
+        );
+        assert_eq!(
+            cross_check("dual_circle", "r = 3", "28.27"),
+            CrossCheck::Agree
+        );
+        assert_eq!(
+            cross_check("dual_circle", "r = 3", "28.3"),
+            CrossCheck::Agree
+        );
+        crate::register_custom_type(
+            r#"{"name":"dual_side","formula":"sqrt(a)","answer":{"mode":"tolerance","tolerance":0.01}}"#,
+        );
+        // sqrt(2) has no exact value, so there is no second path.
+        assert_eq!(
+            cross_check("dual_side", "a = 2", "1.414"),
+            CrossCheck::Unavailable
+        );
+        assert_eq!(
+            cross_check("dual_side", "a = 9/4", "1.5"),
+            CrossCheck::Agree
+        );
+    }
+
+    /// A validator whose two paths always disagree: the primary path
+    /// accepts every answer, the secondary rejects it.
+    struct SplitPaths;
+
+    impl registry::ProblemValidator for SplitPaths {
+        fn check(&self, _problem: &str, _student_answer: &str) -> Verdict {
+            Verdict::graded(true, &hints::CORRECT, &[])
+        }
+
+        fn cross_check(&self, _problem: &str, _student_answer: &str) -> Option<bool> {
+            Some(false)
+        }
+    }
+
+    #[test]
+    fn test_disagreement_is_surfaced() {
+        registry::register_validator("dual_split", Box::new(SplitPaths));
+        assert_eq!(
+            cross_check("dual_split", "1 + 1", "2"),
+            CrossCheck::Disagree {
+                primary: true,
+                secondary: false
+            }
+        );
+        let result = check_answer_dual("dual_split", "1 + 1", "2");
+        assert!(result.contains("\"correct\":null"));
+        assert!(result.contains("\"hint_id\":\"engine.inconsistency\""));
+        assert!(result.contains("\"paths\":{\"primary\":true,\"secondary\":false}"));
+        assert!(check_answer_dual("nope", "", "").contains("\"cross_check\":\"unavailable\""));
+    }
+}
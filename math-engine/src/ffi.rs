@@ -0,0 +1,177 @@
+// ─── C ABI ───────────────────────────────────────────────────────────
+//
+// The same engine as the WASM build, exported over the C ABI for hosts
+// that load the native library (libmath_validator.so / .dylib /
+// math_validator.dll) instead of instantiating WASM: the desktop
+// launcher and server-side Deno.
+//
+// Conventions match native/src/lib.rs: text goes in as a UTF-8
+// (pointer, length) pair, which Deno passes as a "buffer" parameter,
+// and results are i32 codes. `math_check_answer` returns a JSON string
+// the library allocated; the caller must hand it back to
+// `math_free_string`.
+//
+//   const lib = Deno.dlopen("math-engine/target/release/libmath_validator.so", {
+//     math_check_answer: {
+//       parameters: ["buffer", "u32", "buffer", "u32", "buffer", "u32"],
+//       result: "pointer",
+//     },
+//     math_free_string: { parameters: ["pointer"], result: "void" },
+//   });
+//   const ptr = lib.symbols.math_check_answer(type, type.length, …);
+//   const json = Deno.UnsafePointerView.getCString(ptr);
+//   lib.symbols.math_free_string(ptr);
+//
+// Engine state (locale, custom types) is per thread, as in WASM: call
+// from one thread, as Deno does, to share it between calls.
+
+use std::ffi::{c_char, CString};
+use std::ptr;
+
+/// The UTF-8 text at `ptr`, or `None` if it is not valid UTF-8. A null
+/// `ptr` is the empty string when `len` is 0 (Deno passes empty buffers
+/// that way) and invalid otherwise.
+///
+/// # Safety
+/// A non-null `ptr` must point to `len` readable bytes that outlive `'a`.
+unsafe fn text<'a>(ptr: *const u8, len: u32) -> Option<&'a str> {
+    if ptr.is_null() {
+        return (len == 0).then_some("");
+    }
+    std::str::from_utf8(std::slice::from_raw_parts(ptr, len as usize)).ok()
+}
+
+/// `validate_arithmetic` over the C ABI.
+/// Returns 1 if `expression` evaluates to `student_answer`, 0 if not,
+/// -1 for invalid UTF-8.
+///
+/// # Safety
+/// `expr_ptr` must point to `expr_len` readable bytes (or be null with
+/// `expr_len` 0).
+#[no_mangle]
+pub unsafe extern "C" fn math_validate_arithmetic(
+    expr_ptr: *const u8,
+    expr_len: u32,
+    student_answer: f64,
+) -> i32 {
+    match text(expr_ptr, expr_len) {
+        Some(expression) => crate::validate_arithmetic(expression, student_answer) as i32,
+        None => -1,
+    }
+}
+
+/// `validate_equation` over the C ABI.
+/// Returns 1 if the equation balances at `variable_value`, 0 if not,
+/// -1 for invalid UTF-8.
+///
+/// # Safety
+/// `equation_ptr` must point to `equation_len` readable bytes (or be
+/// null with `equation_len` 0).
+#[no_mangle]
+pub unsafe extern "C" fn math_validate_equation(
+    equation_ptr: *const u8,
+    equation_len: u32,
+    variable_value: f64,
+) -> i32 {
+    match text(equation_ptr, equation_len) {
+        Some(equation) => crate::validate_equation(equation, variable_value) as i32,
+        None => -1,
+    }
+}
+
+/// `check_answer` over the C ABI: the same JSON, as a NUL-terminated
+/// string owned by the caller until passed to `math_free_string`.
+/// Returns null for invalid UTF-8.
+///
+/// # Safety
+/// Each pointer must point to its length's readable bytes (or be null
+/// with length 0).
+#[no_mangle]
+pub unsafe extern "C" fn math_check_answer(
+    type_ptr: *const u8,
+    type_len: u32,
+    problem_ptr: *const u8,
+    problem_len: u32,
+    answer_ptr: *const u8,
+    answer_len: u32,
+) -> *mut c_char {
+    let (Some(problem_type), Some(problem), Some(answer)) = (
+        text(type_ptr, type_len),
+        text(problem_ptr, problem_len),
+        text(answer_ptr, answer_len),
+    ) else {
+        return ptr::null_mut();
+    };
+    // JSON escapes control characters, so the text never contains NUL.
+    CString::new(crate::check_answer(problem_type, problem, answer))
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Free a string returned by this library. Null is ignored.
+///
+/// # Safety
+/// `s` must be null or a pointer this library returned that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn math_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn check(problem_type: &str, problem: &str, answer: &str) -> Option<String> {
+        unsafe {
+            let s = math_check_answer(
+                problem_type.as_ptr(),
+                problem_type.len() as u32,
+                problem.as_ptr(),
+                problem.len() as u32,
+                answer.as_ptr(),
+                answer.len() as u32,
+            );
+            if s.is_null() {
+                return None;
+            }
+            let json = CStr::from_ptr(s).to_str().unwrap().to_string();
+            math_free_string(s);
+            Some(json)
+        }
+    }
+
+    #[test]
+    fn test_validate_over_c_abi() {
+        let expr = "12 * 34";
+        let equation = "x * 3 = 12";
+        unsafe {
+            assert_eq!(math_validate_arithmetic(expr.as_ptr(), 7, 408.0), 1);
+            assert_eq!(math_validate_arithmetic(expr.as_ptr(), 7, 409.0), 0);
+            assert_eq!(math_validate_equation(equation.as_ptr(), 10, 4.0), 1);
+            assert_eq!(math_validate_equation(equation.as_ptr(), 10, 3.0), 0);
+            assert_eq!(math_validate_arithmetic([0xff, 0xfe].as_ptr(), 2, 1.0), -1);
+            assert_eq!(math_validate_arithmetic(ptr::null(), 3, 1.0), -1);
+            assert_eq!(math_validate_arithmetic(ptr::null(), 0, 1.0), 0);
+        }
+    }
+
+    #[test]
+    fn test_check_answer_over_c_abi() {
+        assert_eq!(
+            check("arithmetic", "2 + 3", "5"),
+            Some(crate::check_answer("arithmetic", "2 + 3", "5"))
+        );
+        let wrong = check("fraction", "2/4", "1/3").unwrap();
+        assert!(wrong.contains(r#""correct":false"#), "{}", wrong);
+        assert!(check("arithmetic", "2 + 3", "\u{0}5").is_some());
+        unsafe {
+            let bad = [0xc3, 0x28];
+            let s = math_check_answer(bad.as_ptr(), 2, ptr::null(), 0, ptr::null(), 0);
+            assert!(s.is_null());
+            math_free_string(ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,109 @@
+// ─── Fill-in-the-Blank ───────────────────────────────────────────────
+//
+// Templates like "7 × __ = 56" or "__ + __ = 10": the student's values
+// are substituted into the blanks and the resulting statement is
+// decided exactly. Any values that make it true are accepted, so
+// "__ + __ = 10" has many right answers.
+//
+// A blank is two or more underscores, or "□". Several answers are
+// separated by commas or semicolons, in blank order.
+
+use wasm_bindgen::prelude::*;
+
+use crate::compare::{evaluate_statement, evaluate_statement_f64, exact_value, float_value};
+use crate::rational::Rational;
+
+/// Split a template at its blanks: "7 × __ = 56" → ["7 × ", " = 56"].
+pub(crate) fn template_parts(template: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut chars = template.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let end = match c {
+            '□' => i + c.len_utf8(),
+            '_' if chars.peek().is_some_and(|&(_, next)| next == '_') => {
+                let mut end = i;
+                while let Some(&(j, '_')) = chars.peek() {
+                    end = j + 1;
+                    chars.next();
+                }
+                end
+            }
+            _ => continue,
+        };
+        parts.push(&template[start..i]);
+        start = end;
+    }
+    parts.push(&template[start..]);
+    parts
+}
+
+/// The student's values, one per blank; `None` if any is not a number.
+pub(crate) fn blank_values(answer: &str) -> Option<Vec<Rational>> {
+    answer.split([',', ';']).map(exact_value).collect()
+}
+
+/// Fill the blanks with `values` and decide the statement. `None` if
+/// the counts differ or the filled statement cannot be evaluated.
+pub(crate) fn fill_and_check(template: &str, values: &[Rational]) -> Option<bool> {
+    let parts = template_parts(template);
+    if parts.len() != values.len() + 1 {
+        return None;
+    }
+    let mut statement = parts[0].to_string();
+    for (value, rest) in values.iter().zip(&parts[1..]) {
+        statement.push_str(&format!("({}){}", value, rest));
+    }
+    evaluate_statement(&statement)
+}
+
+/// `fill_and_check` in floating point, reading the values from the
+/// answer text directly (the second path for dual-path grading).
+pub(crate) fn fill_and_check_f64(template: &str, answer: &str) -> Option<bool> {
+    let parts = template_parts(template);
+    let values = answer
+        .split([',', ';'])
+        .map(float_value)
+        .collect::<Option<Vec<f64>>>()?;
+    if parts.len() != values.len() + 1 {
+        return None;
+    }
+    let mut statement = parts[0].to_string();
+    for (value, rest) in values.iter().zip(&parts[1..]) {
+        statement.push_str(&format!("({}){}", value, rest));
+    }
+    evaluate_statement_f64(&statement)
+}
+
+/// Validate a fill-in-the-blank answer: "7 × __ = 56", "8" → true.
+/// For several blanks, give the values in order: "__ + __ = 10", "3, 7".
+#[wasm_bindgen]
+pub fn validate_fill_blank(template: &str, student_answer: &str) -> bool {
+    blank_values(student_answer)
+        .and_then(|values| fill_and_check(template, &values))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_parts() {
+        assert_eq!(template_parts("7 × __ = 56"), ["7 × ", " = 56"]);
+        assert_eq!(template_parts("□ + ___ = 10"), ["", " + ", " = 10"]);
+        assert_eq!(template_parts("a_b = 1"), ["a_b = 1"]);
+    }
+
+    #[test]
+    fn test_fill_blank() {
+        assert!(validate_fill_blank("7 × __ = 56", "8"));
+        assert!(!validate_fill_blank("7 × __ = 56", "9"));
+        assert!(validate_fill_blank("__ + __ = 10", "3, 7"));
+        assert!(validate_fill_blank("__ + __ = 10", "2 1/2; 7.5"));
+        assert!(validate_fill_blank("3/__ = 1/2", "6"));
+        assert!(validate_fill_blank("__ - 5 < 0", "-1"));
+        assert!(!validate_fill_blank("__ + __ = 10", "10"));
+        assert!(!validate_fill_blank("7 × __ = 56", "eight"));
+    }
+}
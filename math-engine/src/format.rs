@@ -0,0 +1,226 @@
+// ─── Answer Format Feedback ──────────────────────────────────────────
+//
+// Correctness and presentation are graded separately: "4/8" for 1/2 is
+// mathematically right but not simplified. A format spec names what
+// the exercise asks for; violations produce feedback and partial
+// credit instead of a flat "wrong".
+//
+// Format spec (JSON, every field optional):
+//   { "simplified": true, "decimals": 2, "unit": "cm", "partial_credit": 0.5 }
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::hints;
+use crate::registry::check_registered;
+use crate::version;
+
+const DEFAULT_PARTIAL_CREDIT: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+struct FormatSpec {
+    simplified: bool,
+    decimals: Option<usize>,
+    unit: Option<String>,
+    partial_credit: f64,
+}
+
+impl FormatSpec {
+    fn from_json(text: &str) -> Option<FormatSpec> {
+        let spec: Value = if text.trim().is_empty() {
+            json!({})
+        } else {
+            serde_json::from_str(text).ok()?
+        };
+        Some(FormatSpec {
+            simplified: spec
+                .get("simplified")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            decimals: spec
+                .get("decimals")
+                .and_then(Value::as_u64)
+                .map(|d| d as usize),
+            unit: spec
+                .get("unit")
+                .and_then(Value::as_str)
+                .map(|u| u.trim().to_string()),
+            partial_credit: spec
+                .get("partial_credit")
+                .and_then(Value::as_f64)
+                .unwrap_or(DEFAULT_PARTIAL_CREDIT)
+                .clamp(0.0, 1.0),
+        })
+    }
+}
+
+/// Split "12.5 cm" into ("12.5", "cm"). Units are trailing letters plus
+/// °, ², ³, and %.
+fn split_unit(text: &str) -> (&str, &str) {
+    let t = text.trim();
+    let is_unit_char = |c: char| c.is_alphabetic() || matches!(c, '°' | '²' | '³' | '%');
+    let split = t
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_unit_char(*c))
+        .last()
+        .map(|(i, _)| i);
+    match split {
+        Some(i) if i > 0 => (t[..i].trim_end(), &t[i..]),
+        _ => (t, ""),
+    }
+}
+
+/// Is a written fraction ("4/8", "2 2/4", "3/1") in lowest terms?
+fn is_simplified(answer: &str) -> bool {
+    let fraction = answer.rsplit(' ').next().unwrap_or(answer);
+    let Some((n, d)) = fraction.split_once('/') else {
+        return true;
+    };
+    match (n.trim().parse::<i64>(), d.trim().parse::<i64>()) {
+        (Ok(n), Ok(d)) => d.unsigned_abs() != 1 && gcd(n.unsigned_abs(), d.unsigned_abs()) == 1,
+        _ => true, // Not a plain fraction; nothing to simplify
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Format problems with `answer`, as (code, message) pairs. Also
+/// returns the answer with its unit removed, for grading.
+fn format_issues<'a>(spec: &FormatSpec, answer: &'a str) -> (Vec<(&'static str, String)>, &'a str) {
+    let mut issues = Vec::new();
+    let (value, unit) = split_unit(answer);
+
+    let graded = match &spec.unit {
+        Some(expected) if unit.is_empty() => {
+            issues.push(("missing_unit", format!("Include the unit ({}).", expected)));
+            value
+        }
+        Some(expected) if !unit.eq_ignore_ascii_case(expected) => {
+            issues.push(("wrong_unit", format!("Give your answer in {}.", expected)));
+            value
+        }
+        Some(_) => value,
+        None => answer.trim(),
+    };
+
+    if spec.simplified && !is_simplified(graded) {
+        issues.push((
+            "not_simplified",
+            "Simplify your answer to lowest terms.".to_string(),
+        ));
+    }
+
+    if let Some(places) = spec.decimals {
+        let written = graded.split_once('.').map_or(0, |(_, f)| f.len());
+        if written != places {
+            issues.push((
+                "wrong_decimals",
+                format!("Round your answer to {} decimal places.", places),
+            ));
+        }
+    }
+
+    (issues, graded)
+}
+
+/// Like `check_answer`, plus a separate format channel.
+///
+/// `format_json` is a format spec (see module docs; "" for none). The
+/// answer is graded for correctness with any unit stripped, then
+/// checked against the spec.
+///
+/// Returns `{"correct","hint","hint_id","hint_data","format_ok","format_feedback":[{"code","message"}…],"score"}`
+/// where `score` is 1 for a correct, well-formatted answer,
+/// `partial_credit` for a correct answer with format problems, and 0
+/// otherwise.
+#[wasm_bindgen]
+pub fn check_answer_formatted(
+    problem_type: &str,
+    problem: &str,
+    student_answer: &str,
+    format_json: &str,
+) -> String {
+    let Some(spec) = FormatSpec::from_json(format_json) else {
+        return version::result(json!({ "correct": false, "error": "invalid_format_spec" }));
+    };
+    let (issues, graded) = format_issues(&spec, student_answer);
+    let Some(verdict) = check_registered(problem_type, problem, graded) else {
+        return version::result(json!({
+            "correct": false,
+            "hint": hints::UNKNOWN_TYPE.render(&[("type", problem_type)]),
+            "hint_id": hints::UNKNOWN_TYPE.id,
+        }));
+    };
+    let correct = verdict.correct;
+
+    let score = match (correct, issues.is_empty()) {
+        (true, true) => 1.0,
+        (true, false) => spec.partial_credit,
+        (false, _) => 0.0,
+    };
+    let feedback: Vec<Value> = issues
+        .iter()
+        .map(|(code, message)| json!({ "code": code, "message": message }))
+        .collect();
+
+    let mut result = json!({
+        "correct": correct,
+        "hint": verdict.hint,
+        "hint_id": verdict.hint_id,
+        "hint_data": verdict.hint_data(),
+        "format_ok": issues.is_empty(),
+        "format_feedback": feedback,
+        "score": score,
+    });
+    if let Some(misconception) = verdict.misconception {
+        result["misconception"] = Value::from(misconception);
+    }
+    version::result(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsimplified_fraction_gets_partial_credit() {
+        let result = check_answer_formatted("fraction", "1/2", "4/8", r#"{"simplified":true}"#);
+        assert!(result.contains("\"correct\":true"));
+        assert!(result.contains("\"format_ok\":false"));
+        assert!(result.contains("not_simplified"));
+        assert!(result.contains("\"score\":0.5"));
+
+        let result = check_answer_formatted("fraction", "1/2", "1/2", r#"{"simplified":true}"#);
+        assert!(result.contains("\"score\":1.0"));
+    }
+
+    #[test]
+    fn test_units_and_decimals() {
+        let spec = r#"{"unit":"cm","decimals":1,"partial_credit":0.25}"#;
+        let result = check_answer_formatted("arithmetic", "2.5 * 3", "7.5 cm", spec);
+        assert!(result.contains("\"format_ok\":true"));
+        let result = check_answer_formatted("arithmetic", "2.5 * 3", "7.5", spec);
+        assert!(result.contains("missing_unit"));
+        assert!(result.contains("\"score\":0.25"));
+        let result = check_answer_formatted("arithmetic", "2 * 3", "6 cm", spec);
+        assert!(result.contains("wrong_decimals"));
+        let result = check_answer_formatted("arithmetic", "2 * 3", "7.0 cm", spec);
+        assert!(result.contains("\"score\":0.0"));
+    }
+
+    #[test]
+    fn test_simplified_detection() {
+        assert!(is_simplified("3/4"));
+        assert!(is_simplified("2 1/2"));
+        assert!(!is_simplified("2 2/4"));
+        assert!(!is_simplified("3/1"));
+        assert!(is_simplified("0.75"));
+    }
+}
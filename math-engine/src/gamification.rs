@@ -0,0 +1,257 @@
+// ─── Gamification ────────────────────────────────────────────────────
+//
+// XP and daily-streak arithmetic. The UI only displays these numbers;
+// computing them here keeps every platform (web, desktop, the teacher's
+// replay) in agreement, and lets a school retune the rules without
+// touching the UI.
+
+use std::collections::BTreeSet;
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::version;
+
+/// How answers turn into XP.
+#[derive(Debug, Clone, PartialEq)]
+struct XpRules {
+    /// XP for a correct medium answer before multipliers.
+    base: f64,
+    /// Difficulty name → multiplier on `base`.
+    difficulty: Vec<(String, f64)>,
+    /// XP taken off a correct answer per hint used.
+    hint_penalty: u64,
+    /// Least XP a correct answer earns, however many hints.
+    min_xp: u64,
+    /// Every `streak_every` correct answers in a row earn `streak_xp`.
+    streak_every: u64,
+    streak_xp: u64,
+}
+
+impl Default for XpRules {
+    fn default() -> Self {
+        XpRules {
+            base: 10.0,
+            difficulty: [("easy", 1.0), ("medium", 1.5), ("hard", 2.0)]
+                .iter()
+                .map(|(name, m)| (name.to_string(), *m))
+                .collect(),
+            hint_penalty: 2,
+            min_xp: 1,
+            streak_every: 5,
+            streak_xp: 10,
+        }
+    }
+}
+
+impl XpRules {
+    /// Override the defaults with the fields present in `spec`.
+    fn from_json(spec: &Value) -> Option<XpRules> {
+        let mut rules = XpRules::default();
+        let fields = spec.as_object()?;
+        let count =
+            |name: &str, default: u64| fields.get(name).map_or(Some(default), Value::as_u64);
+        if let Some(base) = fields.get("base") {
+            rules.base = base.as_f64().filter(|b| *b >= 0.0)?;
+        }
+        if let Some(difficulty) = fields.get("difficulty") {
+            rules.difficulty = difficulty
+                .as_object()?
+                .iter()
+                .map(|(name, m)| Some((name.clone(), m.as_f64().filter(|m| *m >= 0.0)?)))
+                .collect::<Option<_>>()?;
+        }
+        rules.hint_penalty = count("hint_penalty", rules.hint_penalty)?;
+        rules.min_xp = count("min_xp", rules.min_xp)?;
+        rules.streak_every = count("streak_every", rules.streak_every)?;
+        rules.streak_xp = count("streak_xp", rules.streak_xp)?;
+        Some(rules)
+    }
+
+    /// XP for one answer; `None` for an unknown difficulty.
+    fn answer_xp(&self, correct: bool, difficulty: &str, hints: u64) -> Option<u64> {
+        let (_, multiplier) = self
+            .difficulty
+            .iter()
+            .find(|(name, _)| name == difficulty)?;
+        if !correct {
+            return Some(0);
+        }
+        let earned = (self.base * multiplier).round() as u64;
+        Some(
+            earned
+                .saturating_sub(hints.saturating_mul(self.hint_penalty))
+                .max(self.min_xp),
+        )
+    }
+}
+
+/// Total the XP for a sequence of answers.
+///
+/// `results_json`: `[{"correct":true,"difficulty":"hard","hints":1},…]`
+/// in answer order (difficulty defaults to "medium", hints to 0).
+/// `rules_json` overrides any of the defaults, or is empty:
+/// `{"base":10,"difficulty":{"easy":1,"medium":1.5,"hard":2},
+///   "hint_penalty":2,"min_xp":1,"streak_every":5,"streak_xp":10}`.
+///
+/// Returns `{"xp","items":[{"xp","bonus"}…],"streak","best_streak"}` —
+/// `bonus` is the streak bonus earned on that answer, `streak` the
+/// correct answers in a row at the end. Errors give
+/// `{"error":"invalid_results"}` or `{"error":"invalid_rules"}`.
+#[wasm_bindgen]
+pub fn compute_xp(results_json: &str, rules_json: &str) -> String {
+    let rules = if rules_json.trim().is_empty() {
+        Some(XpRules::default())
+    } else {
+        serde_json::from_str::<Value>(rules_json)
+            .ok()
+            .and_then(|spec| XpRules::from_json(&spec))
+    };
+    let Some(rules) = rules else {
+        return version::result(json!({ "error": "invalid_rules" }));
+    };
+    let Ok(Value::Array(results)) = serde_json::from_str::<Value>(results_json) else {
+        return version::result(json!({ "error": "invalid_results" }));
+    };
+
+    let (mut total, mut streak, mut best_streak) = (0u64, 0u64, 0u64);
+    let mut items = Vec::with_capacity(results.len());
+    for result in &results {
+        let correct = result.get("correct").and_then(Value::as_bool);
+        let difficulty = result
+            .get("difficulty")
+            .map_or(Some("medium"), Value::as_str);
+        let hints = result.get("hints").map_or(Some(0), Value::as_u64);
+        let (Some(correct), Some(difficulty), Some(hints)) = (correct, difficulty, hints) else {
+            return version::result(json!({ "error": "invalid_results" }));
+        };
+        let Some(xp) = rules.answer_xp(correct, difficulty, hints) else {
+            return version::result(json!({ "error": "invalid_results" }));
+        };
+
+        streak = if correct { streak + 1 } else { 0 };
+        best_streak = best_streak.max(streak);
+        let bonus = if correct && rules.streak_every > 0 && streak % rules.streak_every == 0 {
+            rules.streak_xp
+        } else {
+            0
+        };
+        total = total.saturating_add(xp).saturating_add(bonus);
+        items.push(json!({ "xp": xp, "bonus": bonus }));
+    }
+    version::result(json!({
+        "xp": total,
+        "items": items,
+        "streak": streak,
+        "best_streak": best_streak,
+    }))
+}
+
+/// Record practice on `today_index` and recompute the daily streak.
+///
+/// `history_json` is the array of day indices (days since any fixed
+/// epoch, chosen by the caller) the student has practised on, in any
+/// order. Returns `{"days","current","longest","new_day"}`: the sorted
+/// history including today, the streak ending today, the longest
+/// streak ever, and whether today was not yet recorded (so the caller
+/// celebrates once). A day after `today_index`, or anything but an
+/// array of day indices, gives `{"error":"invalid_history"}`.
+#[wasm_bindgen]
+pub fn update_streak(history_json: &str, today_index: u32) -> String {
+    let days: Option<BTreeSet<u32>> = match serde_json::from_str::<Value>(history_json) {
+        Ok(Value::Array(days)) => days
+            .iter()
+            .map(|day| {
+                let day = u32::try_from(day.as_u64()?).ok()?;
+                (day <= today_index).then_some(day)
+            })
+            .collect(),
+        _ => None,
+    };
+    let Some(mut days) = days else {
+        return version::result(json!({ "error": "invalid_history" }));
+    };
+
+    let new_day = days.insert(today_index);
+    // Today is the latest day, so the last run is the current streak.
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<u32> = None;
+    for &day in &days {
+        run = if previous.and_then(|p| p.checked_add(1)) == Some(day) {
+            run + 1
+        } else {
+            1
+        };
+        longest = longest.max(run);
+        previous = Some(day);
+    }
+    version::result(json!({
+        "days": days,
+        "current": run,
+        "longest": longest,
+        "new_day": new_day,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(output: String) -> Value {
+        serde_json::from_str(&output).unwrap()
+    }
+
+    #[test]
+    fn test_compute_xp() {
+        let results = r#"[{"correct":true,"difficulty":"hard"},{"correct":true,"hints":2},
+            {"correct":false},{"correct":true,"difficulty":"easy","hints":9}]"#;
+        let xp = parse(compute_xp(results, ""));
+        // 20 + (15 - 4) + 0 + max(10 - 18, 1)
+        assert_eq!(xp["xp"], 32);
+        assert_eq!(xp["items"][1]["xp"], 11);
+        assert_eq!(xp["streak"], 1);
+        assert_eq!(xp["best_streak"], 2);
+
+        // Custom rules: a bonus every 2 in a row.
+        let streaky = r#"[{"correct":true},{"correct":true},{"correct":true},{"correct":true}]"#;
+        let xp = parse(compute_xp(
+            streaky,
+            r#"{"base":4,"streak_every":2,"streak_xp":3}"#,
+        ));
+        assert_eq!(xp["xp"], 4 * 6 + 2 * 3);
+        assert_eq!(xp["items"][1]["bonus"], 3);
+        assert_eq!(xp["items"][2]["bonus"], 0);
+    }
+
+    #[test]
+    fn test_compute_xp_errors() {
+        assert!(compute_xp("{}", "").contains("invalid_results"));
+        assert!(compute_xp(r#"[{"difficulty":"hard"}]"#, "").contains("invalid_results"));
+        assert!(
+            compute_xp(r#"[{"correct":true,"difficulty":"epic"}]"#, "").contains("invalid_results")
+        );
+        assert!(compute_xp("[]", r#"{"base":-1}"#).contains("invalid_rules"));
+        assert!(compute_xp("[]", "not json").contains("invalid_rules"));
+    }
+
+    #[test]
+    fn test_update_streak() {
+        let streak = parse(update_streak("[10, 11, 3, 4, 5, 6]", 12));
+        assert_eq!(streak["current"], 3);
+        assert_eq!(streak["longest"], 4);
+        assert_eq!(streak["new_day"], true);
+        assert_eq!(streak["days"], json!([3, 4, 5, 6, 10, 11, 12]));
+
+        let again = parse(update_streak("[10, 11, 12]", 12));
+        assert_eq!(again["new_day"], false);
+        assert_eq!(again["current"], 3);
+
+        // A missed day restarts the streak.
+        assert_eq!(parse(update_streak("[8, 9]", 11))["current"], 1);
+        assert_eq!(parse(update_streak("[]", 0))["current"], 1);
+
+        assert!(update_streak("[13]", 12).contains("invalid_history"));
+        assert!(update_streak(r#"["x"]"#, 12).contains("invalid_history"));
+    }
+}
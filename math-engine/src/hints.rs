@@ -0,0 +1,300 @@
+// ─── Hint Catalog ────────────────────────────────────────────────────
+//
+// Every hint the engine can return, keyed by a stable ID. Results carry
+// both `hint_id` and the `hint` text in the engine's locale (see
+// `i18n`): the UI keys its own translations on the ID and tests assert
+// on it, so hint copy can be edited without breaking the purity suite.
+//
+// IDs are append-only — never rename or reuse one. Text may contain
+// `{placeholders}` filled in by `Hint::render`.
+
+use serde_json::{Map, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::i18n;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Hint {
+    pub id: &'static str,
+    pub text: &'static str,
+}
+
+/// What a hint is about, so the UI can choose how to present it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HintKind {
+    Correct,
+    /// How to enter the answer (its shape, not its value).
+    Input,
+    /// A method for working the problem out.
+    Strategy,
+    /// Names the error pattern behind a wrong answer.
+    Misconception,
+    /// The problem itself could not be graded.
+    Error,
+    /// Set aside for a teacher to review.
+    Review,
+}
+
+impl HintKind {
+    pub(crate) fn code(self) -> &'static str {
+        match self {
+            HintKind::Correct => "correct",
+            HintKind::Input => "input",
+            HintKind::Strategy => "strategy",
+            HintKind::Misconception => "misconception",
+            HintKind::Error => "error",
+            HintKind::Review => "review",
+        }
+    }
+}
+
+impl Hint {
+    /// The hint's kind. Pack-supplied hints are strategies.
+    pub(crate) fn kind(&self) -> HintKind {
+        match self.id {
+            "correct" => HintKind::Correct,
+            "invalid_format" | "unknown_type" | "budget_exceeded" => HintKind::Error,
+            "engine.inconsistency" => HintKind::Review,
+            "fraction.enter_as_fraction"
+            | "multiple_choice.choose_option"
+            | "true_false.answer"
+            | "comparison.choose_symbol"
+            | "list.separate_items"
+            | "fill_blank.one_per_blank" => HintKind::Input,
+            id if id.starts_with("misconception.") => HintKind::Misconception,
+            _ => HintKind::Strategy,
+        }
+    }
+
+    /// The text in the current locale (see `i18n`), with `{name}`
+    /// placeholders filled in.
+    pub(crate) fn render(&self, args: &[(&str, &str)]) -> String {
+        args.iter()
+            .fold(i18n::translate(self).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), value)
+            })
+    }
+}
+
+pub(crate) const CORRECT: Hint = Hint {
+    id: "correct",
+    text: "Correct!",
+};
+pub(crate) const INVALID_FORMAT: Hint = Hint {
+    id: "invalid_format",
+    text: "Invalid problem format.",
+};
+pub(crate) const UNKNOWN_TYPE: Hint = Hint {
+    id: "unknown_type",
+    text: "Unknown problem type: {type}",
+};
+pub(crate) const ARITHMETIC_STEP_BY_STEP: Hint = Hint {
+    id: "arithmetic.step_by_step",
+    text: "Try evaluating {problem} step by step.",
+};
+pub(crate) const FRACTION_ENTER_AS_FRACTION: Hint = Hint {
+    id: "fraction.enter_as_fraction",
+    text: "Enter your answer as a fraction: numerator/denominator",
+};
+pub(crate) const FRACTION_SIMPLIFY: Hint = Hint {
+    id: "fraction.simplify",
+    text: "Try simplifying the fraction to its lowest terms.",
+};
+pub(crate) const ELAPSED_TIME_COUNT_UP: Hint = Hint {
+    id: "elapsed_time.count_up",
+    text: "Count the minutes up to the next hour, then the whole hours.",
+};
+pub(crate) const TIME_ADDITION_CARRY: Hint = Hint {
+    id: "time_addition.carry",
+    text: "Add the minutes first — 60 minutes carry over as 1 hour.",
+};
+pub(crate) const MONEY_WORK_IN_CENTS: Hint = Hint {
+    id: "money.work_in_cents",
+    text: "Line up the decimal points and work in cents.",
+};
+pub(crate) const UNIT_PRICE_DIVIDE: Hint = Hint {
+    id: "unit_price.divide",
+    text: "Divide the total price by the number of items.",
+};
+pub(crate) const TEMPERATURE_FORMULA: Hint = Hint {
+    id: "temperature.formula",
+    text: "Use F = C × 9/5 + 32 (or K = C + 273.15).",
+};
+pub(crate) const CUSTOM_SUBSTITUTE: Hint = Hint {
+    id: "custom.substitute",
+    text: "Substitute the given values into the formula.",
+};
+pub(crate) const BUDGET_EXCEEDED: Hint = Hint {
+    id: "budget_exceeded",
+    text: "This problem is too complex to check automatically.",
+};
+pub(crate) const MULTIPLE_CHOICE_CHOOSE_OPTION: Hint = Hint {
+    id: "multiple_choice.choose_option",
+    text: "Choose one of the options.",
+};
+pub(crate) const MULTIPLE_CHOICE_TRY_AGAIN: Hint = Hint {
+    id: "multiple_choice.try_again",
+    text: "Not quite — work the problem out, then compare each option.",
+};
+pub(crate) const TRUE_FALSE_ANSWER: Hint = Hint {
+    id: "true_false.answer",
+    text: "Answer true or false.",
+};
+pub(crate) const TRUE_FALSE_EACH_SIDE: Hint = Hint {
+    id: "true_false.each_side",
+    text: "Work out each side exactly, then compare them.",
+};
+pub(crate) const COMPARISON_CHOOSE_SYMBOL: Hint = Hint {
+    id: "comparison.choose_symbol",
+    text: "Answer with <, > or =.",
+};
+pub(crate) const COMPARISON_COMMON_DENOMINATOR: Hint = Hint {
+    id: "comparison.common_denominator",
+    text: "Rewrite both numbers with a common denominator, then compare the numerators.",
+};
+pub(crate) const LIST_SEPARATE_ITEMS: Hint = Hint {
+    id: "list.separate_items",
+    text: "Give every answer, separated by commas.",
+};
+pub(crate) const LIST_CHECK_EACH: Hint = Hint {
+    id: "list.check_each",
+    text: "Some of your answers are not right — check each one.",
+};
+pub(crate) const LIST_FIND_MORE: Hint = Hint {
+    id: "list.find_more",
+    text: "Everything you gave is right, but some answers are missing.",
+};
+pub(crate) const LIST_ORDER: Hint = Hint {
+    id: "list.order",
+    text: "You have the right answers — check the order they go in.",
+};
+pub(crate) const FILL_BLANK_ONE_PER_BLANK: Hint = Hint {
+    id: "fill_blank.one_per_blank",
+    text: "Give one number for each blank ({count}), separated by commas.",
+};
+pub(crate) const FILL_BLANK_CHECK: Hint = Hint {
+    id: "fill_blank.check",
+    text: "Put your answer into the blank and check that the statement is true.",
+};
+pub(crate) const ENGINE_INCONSISTENCY: Hint = Hint {
+    id: "engine.inconsistency",
+    text: "This answer could not be graded automatically and has been set aside for review.",
+};
+
+// Misconception hints, keyed by the IDs `generate_distractors` assigns.
+pub(crate) const MISCONCEPTION_FORGOTTEN_CARRY: Hint = Hint {
+    id: "misconception.forgotten_carry",
+    text: "When a column adds up to 10 or more, carry the extra ten to the next column.",
+};
+pub(crate) const MISCONCEPTION_BORROW_ERROR: Hint = Hint {
+    id: "misconception.borrow_error",
+    text:
+        "When the top digit is smaller, borrow from the next column instead of swapping the digits.",
+};
+pub(crate) const MISCONCEPTION_SIGN_FLIP: Hint = Hint {
+    id: "misconception.sign_flip",
+    text: "Check the sign of your answer — should it be positive or negative?",
+};
+pub(crate) const MISCONCEPTION_OFF_BY_ONE: Hint = Hint {
+    id: "misconception.off_by_one",
+    text: "You're very close — recount carefully.",
+};
+pub(crate) const MISCONCEPTION_PLACE_VALUE: Hint = Hint {
+    id: "misconception.place_value",
+    text: "Check the place value — the decimal point or a zero is out of place.",
+};
+pub(crate) const MISCONCEPTION_WRONG_OPERATION: Hint = Hint {
+    id: "misconception.wrong_operation",
+    text: "Look again at the operation sign before you calculate.",
+};
+
+/// The hint explaining a distractor's misconception, if one exists.
+pub(crate) fn for_misconception(misconception: &str) -> Option<Hint> {
+    let hint = match misconception {
+        "forgotten_carry" => MISCONCEPTION_FORGOTTEN_CARRY,
+        "borrow_error" => MISCONCEPTION_BORROW_ERROR,
+        "sign_flip" => MISCONCEPTION_SIGN_FLIP,
+        "off_by_one" => MISCONCEPTION_OFF_BY_ONE,
+        "place_value" => MISCONCEPTION_PLACE_VALUE,
+        "wrong_operation" => MISCONCEPTION_WRONG_OPERATION,
+        _ => return None,
+    };
+    Some(hint)
+}
+
+/// The full built-in catalog, in ID order of introduction.
+pub(crate) const CATALOG: &[Hint] = &[
+    CORRECT,
+    INVALID_FORMAT,
+    UNKNOWN_TYPE,
+    ARITHMETIC_STEP_BY_STEP,
+    FRACTION_ENTER_AS_FRACTION,
+    FRACTION_SIMPLIFY,
+    ELAPSED_TIME_COUNT_UP,
+    TIME_ADDITION_CARRY,
+    MONEY_WORK_IN_CENTS,
+    UNIT_PRICE_DIVIDE,
+    TEMPERATURE_FORMULA,
+    CUSTOM_SUBSTITUTE,
+    BUDGET_EXCEEDED,
+    MULTIPLE_CHOICE_CHOOSE_OPTION,
+    MULTIPLE_CHOICE_TRY_AGAIN,
+    MISCONCEPTION_FORGOTTEN_CARRY,
+    MISCONCEPTION_BORROW_ERROR,
+    MISCONCEPTION_SIGN_FLIP,
+    MISCONCEPTION_OFF_BY_ONE,
+    MISCONCEPTION_PLACE_VALUE,
+    MISCONCEPTION_WRONG_OPERATION,
+    TRUE_FALSE_ANSWER,
+    TRUE_FALSE_EACH_SIDE,
+    COMPARISON_CHOOSE_SYMBOL,
+    COMPARISON_COMMON_DENOMINATOR,
+    LIST_SEPARATE_ITEMS,
+    LIST_CHECK_EACH,
+    LIST_FIND_MORE,
+    LIST_ORDER,
+    FILL_BLANK_ONE_PER_BLANK,
+    FILL_BLANK_CHECK,
+    ENGINE_INCONSISTENCY,
+];
+
+/// All built-in hints as a JSON object `{ id: text }`, for building
+/// translation tables. Placeholders are left unfilled.
+#[wasm_bindgen]
+pub fn hint_catalog() -> String {
+    let map: Map<String, Value> = CATALOG
+        .iter()
+        .map(|h| (h.id.to_string(), Value::from(h.text)))
+        .collect();
+    Value::Object(map).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_unique() {
+        for (i, a) in CATALOG.iter().enumerate() {
+            assert!(CATALOG[i + 1..].iter().all(|b| b.id != a.id), "{}", a.id);
+        }
+    }
+
+    #[test]
+    fn test_kinds() {
+        assert_eq!(CORRECT.kind(), HintKind::Correct);
+        assert_eq!(FILL_BLANK_ONE_PER_BLANK.kind(), HintKind::Input);
+        assert_eq!(MISCONCEPTION_SIGN_FLIP.kind(), HintKind::Misconception);
+        assert_eq!(BUDGET_EXCEEDED.kind(), HintKind::Error);
+        assert_eq!(LIST_ORDER.kind(), HintKind::Strategy);
+    }
+
+    #[test]
+    fn test_render_fills_placeholders() {
+        assert_eq!(
+            ARITHMETIC_STEP_BY_STEP.render(&[("problem", "2 + 3")]),
+            "Try evaluating 2 + 3 step by step."
+        );
+        assert!(hint_catalog().contains("\"fraction.simplify\":"));
+    }
+}
@@ -0,0 +1,270 @@
+// ─── Localized Hints ─────────────────────────────────────────────────
+//
+// Translations of the hint catalog, compiled into the WASM module so
+// hints stay pure and work offline. Each catalog maps hint IDs to text
+// with the same `{placeholders}`; an ID a catalog lacks falls back to
+// the English text in `hints`.
+//
+// The locale is engine state: `set_locale("th")` applies to every later
+// result, and `check_answer_localized` takes a locale for a single call.
+// Tags match on their primary subtag, so "th-TH" selects "th".
+
+use std::cell::Cell;
+
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+use crate::hints::Hint;
+
+/// Hint ID → translated text.
+type Messages = &'static [(&'static str, &'static str)];
+
+const ENGLISH: &str = "en";
+
+const THAI: Messages = &[
+    ("correct", "ถูกต้อง!"),
+    ("invalid_format", "รูปแบบโจทย์ไม่ถูกต้อง"),
+    ("unknown_type", "ไม่รู้จักประเภทโจทย์: {type}"),
+    ("arithmetic.step_by_step", "ลองคิด {problem} ทีละขั้น"),
+    ("fraction.enter_as_fraction", "ตอบเป็นเศษส่วน: ตัวเศษ/ตัวส่วน"),
+    ("fraction.simplify", "ลองทำเศษส่วนให้เป็นเศษส่วนอย่างต่ำ"),
+    (
+        "elapsed_time.count_up",
+        "นับนาทีไปจนถึงชั่วโมงถัดไป แล้วนับชั่วโมงเต็ม",
+    ),
+    ("time_addition.carry", "บวกนาทีก่อน — ครบ 60 นาทีทดเป็น 1 ชั่วโมง"),
+    ("money.work_in_cents", "ตั้งจุดทศนิยมให้ตรงกัน แล้วคิดเป็นหน่วยสตางค์"),
+    ("unit_price.divide", "นำราคารวมหารด้วยจำนวนสิ่งของ"),
+    (
+        "temperature.formula",
+        "ใช้ F = C × 9/5 + 32 (หรือ K = C + 273.15)",
+    ),
+    ("custom.substitute", "แทนค่าที่กำหนดลงในสูตร"),
+    ("budget_exceeded", "โจทย์นี้ซับซ้อนเกินกว่าจะตรวจอัตโนมัติได้"),
+    ("multiple_choice.choose_option", "เลือกหนึ่งตัวเลือก"),
+    (
+        "multiple_choice.try_again",
+        "ยังไม่ถูก — คิดหาคำตอบก่อน แล้วเทียบกับแต่ละตัวเลือก",
+    ),
+    ("true_false.answer", "ตอบ true (จริง) หรือ false (เท็จ)"),
+    ("true_false.each_side", "คิดค่าแต่ละข้างให้แน่นอน แล้วเปรียบเทียบกัน"),
+    ("comparison.choose_symbol", "ตอบด้วย <, > หรือ ="),
+    (
+        "comparison.common_denominator",
+        "ทำให้ทั้งสองจำนวนมีตัวส่วนเท่ากัน แล้วเปรียบเทียบตัวเศษ",
+    ),
+    ("list.separate_items", "ตอบให้ครบทุกคำตอบ คั่นด้วยจุลภาค"),
+    ("list.check_each", "คำตอบบางข้อยังไม่ถูก — ตรวจทีละข้อ"),
+    ("list.find_more", "ที่ตอบมาถูกทั้งหมด แต่ยังขาดบางคำตอบ"),
+    ("list.order", "คำตอบถูกแล้ว — ตรวจลำดับอีกครั้ง"),
+    (
+        "fill_blank.one_per_blank",
+        "ใส่ตัวเลขช่องละหนึ่งตัว ({count} ช่อง) คั่นด้วยจุลภาค",
+    ),
+    (
+        "fill_blank.check",
+        "ใส่คำตอบลงในช่องว่าง แล้วตรวจว่าประโยคเป็นจริง",
+    ),
+    (
+        "engine.inconsistency",
+        "ระบบตรวจคำตอบนี้อัตโนมัติไม่ได้ จึงส่งให้ครูตรวจอีกครั้ง",
+    ),
+    (
+        "misconception.forgotten_carry",
+        "เมื่อผลบวกในหลักใดได้ 10 หรือมากกว่า ให้ทดสิบไปยังหลักถัดไป",
+    ),
+    (
+        "misconception.borrow_error",
+        "เมื่อเลขตัวบนน้อยกว่า ให้ยืมจากหลักถัดไป อย่าสลับตัวเลข",
+    ),
+    (
+        "misconception.sign_flip",
+        "ตรวจเครื่องหมายของคำตอบ — ควรเป็นบวกหรือลบ?",
+    ),
+    ("misconception.off_by_one", "ใกล้แล้ว — ลองนับใหม่อย่างระมัดระวัง"),
+    (
+        "misconception.place_value",
+        "ตรวจค่าประจำหลัก — จุดทศนิยมหรือเลขศูนย์อยู่ผิดตำแหน่ง",
+    ),
+    (
+        "misconception.wrong_operation",
+        "ดูเครื่องหมายการคำนวณอีกครั้งก่อนคิด",
+    ),
+];
+
+const SPANISH: Messages = &[
+    ("correct", "¡Correcto!"),
+    ("invalid_format", "El formato del problema no es válido."),
+    ("unknown_type", "Tipo de problema desconocido: {type}"),
+    ("arithmetic.step_by_step", "Intenta resolver {problem} paso a paso."),
+    ("fraction.enter_as_fraction", "Escribe tu respuesta como fracción: numerador/denominador"),
+    ("fraction.simplify", "Intenta simplificar la fracción a su mínima expresión."),
+    ("elapsed_time.count_up", "Cuenta los minutos hasta la siguiente hora y luego las horas completas."),
+    ("time_addition.carry", "Suma primero los minutos: 60 minutos se llevan como 1 hora."),
+    ("money.work_in_cents", "Alinea los puntos decimales y trabaja en centavos."),
+    ("unit_price.divide", "Divide el precio total entre el número de artículos."),
+    ("temperature.formula", "Usa F = C × 9/5 + 32 (o K = C + 273.15)."),
+    ("custom.substitute", "Sustituye los valores dados en la fórmula."),
+    ("budget_exceeded", "Este problema es demasiado complejo para revisarlo automáticamente."),
+    ("multiple_choice.choose_option", "Elige una de las opciones."),
+    ("multiple_choice.try_again", "Casi: resuelve el problema y luego compara cada opción."),
+    ("true_false.answer", "Responde true (verdadero) o false (falso)."),
+    ("true_false.each_side", "Calcula cada lado exactamente y luego compáralos."),
+    ("comparison.choose_symbol", "Responde con <, > o =."),
+    ("comparison.common_denominator", "Escribe ambos números con un denominador común y luego compara los numeradores."),
+    ("list.separate_items", "Da todas las respuestas, separadas por comas."),
+    ("list.check_each", "Algunas de tus respuestas no son correctas: revisa cada una."),
+    ("list.find_more", "Todo lo que diste es correcto, pero faltan algunas respuestas."),
+    ("list.order", "Tienes las respuestas correctas: revisa el orden."),
+    ("fill_blank.one_per_blank", "Da un número para cada espacio ({count}), separados por comas."),
+    ("fill_blank.check", "Pon tu respuesta en el espacio y comprueba que la afirmación sea verdadera."),
+    ("engine.inconsistency", "Esta respuesta no se pudo calificar automáticamente y quedó pendiente de revisión."),
+    ("misconception.forgotten_carry", "Cuando una columna suma 10 o más, lleva la decena a la siguiente columna."),
+    ("misconception.borrow_error", "Cuando el dígito de arriba es menor, pide prestado a la siguiente columna en lugar de intercambiar los dígitos."),
+    ("misconception.sign_flip", "Revisa el signo de tu respuesta: ¿debe ser positiva o negativa?"),
+    ("misconception.off_by_one", "Estás muy cerca: vuelve a contar con cuidado."),
+    ("misconception.place_value", "Revisa el valor posicional: el punto decimal o un cero está fuera de lugar."),
+    ("misconception.wrong_operation", "Vuelve a mirar el signo de la operación antes de calcular."),
+];
+
+/// Every compiled-in locale other than English, by primary subtag.
+const CATALOGS: [(&str, Messages); 2] = [("es", SPANISH), ("th", THAI)];
+
+/// A supported locale: English, or one of `CATALOGS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Locale {
+    tag: &'static str,
+    messages: Messages,
+}
+
+const DEFAULT_LOCALE: Locale = Locale {
+    tag: ENGLISH,
+    messages: &[],
+};
+
+impl Locale {
+    /// Find the locale for a tag like "th" or "es-MX".
+    fn find(tag: &str) -> Option<Locale> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        if primary == ENGLISH {
+            return Some(DEFAULT_LOCALE);
+        }
+        CATALOGS
+            .iter()
+            .find(|(tag, _)| *tag == primary)
+            .map(|&(tag, messages)| Locale { tag, messages })
+    }
+}
+
+thread_local! {
+    // WASM is single-threaded; one locale per engine instance.
+    static LOCALE: Cell<Locale> = const { Cell::new(DEFAULT_LOCALE) };
+}
+
+/// The hint's text in the current locale (English if untranslated).
+pub(crate) fn translate(hint: &Hint) -> &'static str {
+    LOCALE.with(|locale| {
+        locale
+            .get()
+            .messages
+            .iter()
+            .find(|(id, _)| *id == hint.id)
+            .map_or(hint.text, |&(_, text)| text)
+    })
+}
+
+/// Run `f` with `tag` as the locale, then restore the previous one.
+/// An unsupported tag runs `f` in English.
+pub(crate) fn with_locale<R>(tag: &str, f: impl FnOnce() -> R) -> R {
+    let locale = Locale::find(tag).unwrap_or(DEFAULT_LOCALE);
+    let previous = LOCALE.with(|current| current.replace(locale));
+    let result = f();
+    LOCALE.with(|current| current.set(previous));
+    result
+}
+
+/// Whether `set_locale` would accept `tag`.
+pub(crate) fn is_supported(tag: &str) -> bool {
+    Locale::find(tag).is_some()
+}
+
+/// Set the locale for all later hints. Returns `false` (and keeps the
+/// current locale) if the tag is not supported.
+#[wasm_bindgen]
+pub fn set_locale(tag: &str) -> bool {
+    match Locale::find(tag) {
+        Some(locale) => {
+            LOCALE.with(|current| current.set(locale));
+            true
+        }
+        None => false,
+    }
+}
+
+/// The current locale's tag ("en", "th", …).
+#[wasm_bindgen]
+pub fn get_locale() -> String {
+    LOCALE.with(|current| current.get().tag.to_string())
+}
+
+/// Supported locale tags as a JSON array, e.g. `["en","es","th"]`.
+#[wasm_bindgen]
+pub fn supported_locales() -> String {
+    let tags = std::iter::once(ENGLISH).chain(CATALOGS.iter().map(|(tag, _)| *tag));
+    Value::from(tags.collect::<Vec<_>>()).to_string()
+}
+
+/// `check_answer` with hints in `locale` for this call only.
+#[wasm_bindgen]
+pub fn check_answer_localized(
+    problem_type: &str,
+    problem: &str,
+    student_answer: &str,
+    locale: &str,
+) -> String {
+    with_locale(locale, || {
+        crate::check_answer(problem_type, problem, student_answer)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hints::{self, CATALOG};
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogs_match_hint_ids_and_placeholders() {
+        for (tag, messages) in CATALOGS {
+            for (id, text) in messages {
+                let hint = CATALOG.iter().find(|h| h.id == *id);
+                let hint = hint.unwrap_or_else(|| panic!("{tag}: unknown hint {id}"));
+                assert_eq!(placeholders(text), placeholders(hint.text), "{tag}: {id}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_locale_selection() {
+        assert!(set_locale("th-TH"));
+        assert_eq!(get_locale(), "th");
+        assert_eq!(
+            hints::UNKNOWN_TYPE.render(&[("type", "x")]),
+            "ไม่รู้จักประเภทโจทย์: x"
+        );
+        assert!(!set_locale("xx"));
+        assert_eq!(get_locale(), "th");
+        let spanish = with_locale("es", || hints::CORRECT.render(&[]));
+        assert_eq!(spanish, "¡Correcto!");
+        assert_eq!(get_locale(), "th");
+        assert!(set_locale("en"));
+        assert_eq!(hints::CORRECT.render(&[]), "Correct!");
+        assert_eq!(supported_locales(), r#"["en","es","th"]"#);
+    }
+}
@@ -0,0 +1,786 @@
+// ─── Question-Bank Import ────────────────────────────────────────────
+//
+// Converts question banks from other tools into native exercises
+// (`{"id","type","problem","answer"}`, as in the demo bank), so schools
+// can reuse what they already have. Supported:
+//
+//   - "gift":       Moodle GIFT — multiple choice, true/false, numerical,
+//                   short answer, and missing-word questions
+//   - "moodle_xml": the same question types in Moodle XML
+//   - "csv":        a header row naming `type`, `problem` (or
+//                   `question`), `answer`, and optionally `id`/`options`
+//
+// Every question is read into one intermediate form and mapped onto a
+// built-in problem type; the converted answer key is then graded by
+// that type's validator, so an import never yields an exercise whose
+// own key is marked wrong. Anything that cannot be converted is listed
+// in the report instead of being dropped silently.
+
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+use crate::compare::{evaluate_statement, exact_value};
+use crate::fill_blank;
+use crate::registry;
+use crate::version;
+
+/// A question's answer, as the source format states it.
+#[derive(Debug, Clone, PartialEq)]
+enum Answer {
+    /// Options, each marked right or wrong.
+    Choices(Vec<(String, bool)>),
+    /// One number, with the tolerance the source allows.
+    Numeric(String, f64),
+    Truth(bool),
+    /// Accepted spellings of a typed answer.
+    Short(Vec<String>),
+    /// Essays, matching, and other types with no native equivalent.
+    Unsupported,
+}
+
+/// One question read from the source, before conversion.
+#[derive(Debug, Clone, PartialEq)]
+struct Question {
+    title: Option<String>,
+    stem: String,
+    answer: Answer,
+}
+
+/// A native exercise.
+#[derive(Debug, Clone, PartialEq)]
+struct Exercise {
+    id: String,
+    problem_type: &'static str,
+    problem: String,
+    answer: String,
+}
+
+/// Why a question was not imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Issue {
+    Unparseable,
+    UnsupportedQuestion,
+    NoCorrectAnswer,
+    MultipleCorrectAnswers,
+    NumericTolerance,
+    /// The converted answer key is graded wrong by its own validator.
+    AnswerKeyRejected,
+}
+
+impl Issue {
+    fn code(self) -> &'static str {
+        match self {
+            Issue::Unparseable => "unparseable",
+            Issue::UnsupportedQuestion => "unsupported_question",
+            Issue::NoCorrectAnswer => "no_correct_answer",
+            Issue::MultipleCorrectAnswers => "multiple_correct_answers",
+            Issue::NumericTolerance => "numeric_tolerance",
+            Issue::AnswerKeyRejected => "answer_key_rejected",
+        }
+    }
+}
+
+// ─── GIFT ────────────────────────────────────────────────────────────
+
+/// Remove GIFT's backslash escapes (`\:`, `\{`, `\}`, `\=`, `\~`, `\#`).
+fn gift_unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(next)) if ":{}=~#\\".contains(next) => {
+                out.push(next);
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Byte offsets of unescaped occurrences of `targets` in `text`.
+fn gift_split_points(text: &str, targets: &[char]) -> Vec<usize> {
+    let mut points = Vec::new();
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if targets.contains(&c) {
+            points.push(i);
+        }
+    }
+    points
+}
+
+/// Parse the text between a GIFT question's braces.
+fn gift_answer(body: &str) -> Answer {
+    let body = body.trim();
+    let upper = body.to_ascii_uppercase();
+    match upper.as_str() {
+        "T" | "TRUE" => return Answer::Truth(true),
+        "F" | "FALSE" => return Answer::Truth(false),
+        "" => return Answer::Unsupported,
+        _ => {}
+    }
+
+    if let Some(numeric) = body.strip_prefix('#') {
+        // "#12", "#12:0.5", or "#=12 =11:1" — the first full-credit entry.
+        let first = numeric.trim().trim_start_matches('=');
+        let first = first.split(['=', '~', '#']).next().unwrap_or("").trim();
+        let (value, tolerance) = first.split_once(':').unwrap_or((first, "0"));
+        return match tolerance.trim().parse::<f64>() {
+            Ok(tolerance) if !value.trim().is_empty() => {
+                Answer::Numeric(gift_unescape(value.trim()), tolerance)
+            }
+            _ => Answer::Unsupported,
+        };
+    }
+
+    // Options: "=right ~wrong ~%50%half" (feedback after '#' dropped).
+    let points = gift_split_points(body, &['=', '~']);
+    if points.first() != Some(&0) {
+        return Answer::Unsupported;
+    }
+    let mut options = Vec::new();
+    for (n, &start) in points.iter().enumerate() {
+        let end = points.get(n + 1).copied().unwrap_or(body.len());
+        let correct = body[start..].starts_with('=');
+        let mut text = &body[start + 1..end];
+        if let Some(&feedback) = gift_split_points(text, &['#']).first() {
+            text = &text[..feedback];
+        }
+        // "%50%" weights: only full credit counts as correct.
+        let mut correct = correct;
+        if let Some(rest) = text.trim_start().strip_prefix('%') {
+            if let Some((weight, option)) = rest.split_once('%') {
+                correct = weight.trim() == "100";
+                text = option;
+            }
+        }
+        if text.contains("->") {
+            return Answer::Unsupported; // matching question
+        }
+        options.push((gift_unescape(text.trim()), correct));
+    }
+    if options.iter().all(|(_, correct)| *correct) {
+        Answer::Short(options.into_iter().map(|(text, _)| text).collect())
+    } else {
+        Answer::Choices(options)
+    }
+}
+
+/// Split a GIFT file into questions; an `Err` marks one that could
+/// not be read.
+fn parse_gift(data: &str) -> Vec<Result<Question, Issue>> {
+    let mut blocks: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for line in data.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("//") {
+            continue;
+        }
+        if trimmed.is_empty() {
+            if !current.trim().is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+            current.clear();
+        } else {
+            current.push_str(trimmed);
+            current.push(' ');
+        }
+    }
+    if !current.trim().is_empty() {
+        blocks.push(current);
+    }
+
+    blocks
+        .iter()
+        .map(|block| block.trim())
+        .filter(|block| !block.starts_with("$CATEGORY:"))
+        .map(|block| {
+            let (title, rest) = match block.strip_prefix("::") {
+                Some(rest) => {
+                    let end = rest.find("::").ok_or(Issue::Unparseable)?;
+                    (Some(gift_unescape(rest[..end].trim())), &rest[end + 2..])
+                }
+                None => (None, block),
+            };
+            let open = *gift_split_points(rest, &['{'])
+                .first()
+                .ok_or(Issue::Unparseable)?;
+            let close = *gift_split_points(rest, &['}'])
+                .iter()
+                .find(|&&i| i > open)
+                .ok_or(Issue::Unparseable)?;
+            let before = rest[..open].trim();
+            let before = before
+                .strip_prefix("[html]")
+                .or_else(|| before.strip_prefix("[plain]"))
+                .or_else(|| before.strip_prefix("[markdown]"))
+                .unwrap_or(before);
+            let after = rest[close + 1..].trim();
+            // Text after the braces makes it a missing-word question.
+            let stem = if after.is_empty() {
+                gift_unescape(before.trim())
+            } else {
+                format!(
+                    "{} __ {}",
+                    gift_unescape(before.trim()),
+                    gift_unescape(after)
+                )
+            };
+            Ok(Question {
+                title,
+                stem,
+                answer: gift_answer(&rest[open + 1..close]),
+            })
+        })
+        .collect()
+}
+
+// ─── Moodle XML ──────────────────────────────────────────────────────
+
+/// Decode the five predefined XML entities and numeric references.
+fn xml_unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';').filter(|&i| i <= 10) else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            "times" => Some('×'),
+            "divide" => Some('÷'),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#')?.parse().ok())
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Text content of an XML fragment: CDATA kept verbatim, entities
+/// decoded, and (HTML) tags removed.
+fn xml_text(fragment: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = fragment;
+    while let Some(start) = rest.find("<![CDATA[") {
+        decoded.push_str(&xml_unescape(&rest[..start]));
+        let body = &rest[start + 9..];
+        let end = body.find("]]>").unwrap_or(body.len());
+        decoded.push_str(&body[..end]);
+        rest = body.get(end + 3..).unwrap_or("");
+    }
+    decoded.push_str(&xml_unescape(rest));
+
+    // Question text is usually HTML; keep only its text.
+    let mut text = String::with_capacity(decoded.len());
+    let mut in_tag = false;
+    for c in decoded.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    xml_unescape(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The inner XML of each `<tag …>…</tag>` element in `xml`, with its
+/// opening tag's attributes.
+fn xml_elements<'a>(xml: &'a str, tag: &str) -> Vec<(&'a str, &'a str)> {
+    let (open, close) = (format!("<{tag}"), format!("</{tag}>"));
+    let mut elements = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        // Skip longer tag names that share the prefix (<answer> vs <answernumbering>).
+        if !after.starts_with(['>', ' ', '\t', '\n', '\r', '/']) {
+            rest = after;
+            continue;
+        }
+        let Some(head_end) = after.find('>') else {
+            break;
+        };
+        let attributes = &after[..head_end];
+        if attributes.ends_with('/') {
+            elements.push((attributes, ""));
+            rest = &after[head_end + 1..];
+            continue;
+        }
+        let body = &after[head_end + 1..];
+        let Some(end) = body.find(&close) else {
+            break;
+        };
+        elements.push((attributes, &body[..end]));
+        rest = &body[end + close.len()..];
+    }
+    elements
+}
+
+/// Value of `name="…"` in an opening tag's attributes.
+fn xml_attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!("{name}=\"");
+    let start = attributes.find(&pattern)? + pattern.len();
+    let end = attributes[start..].find('"')?;
+    Some(&attributes[start..start + end])
+}
+
+/// The text of the first `<tag><text>…</text></tag>` in `xml`.
+fn xml_child_text(xml: &str, tag: &str) -> Option<String> {
+    let (_, body) = xml_elements(xml, tag).into_iter().next()?;
+    let (_, text) = xml_elements(body, "text").into_iter().next()?;
+    Some(xml_text(text))
+}
+
+fn parse_moodle_xml(data: &str) -> Vec<Result<Question, Issue>> {
+    xml_elements(data, "question")
+        .into_iter()
+        .filter(|(attributes, _)| xml_attribute(attributes, "type") != Some("category"))
+        .map(|(attributes, body)| {
+            let stem = xml_child_text(body, "questiontext").ok_or(Issue::Unparseable)?;
+            let answers: Vec<(String, bool)> = xml_elements(body, "answer")
+                .into_iter()
+                .map(|(attributes, answer)| {
+                    let full = xml_attribute(attributes, "fraction")
+                        .and_then(|f| f.parse::<f64>().ok())
+                        .is_some_and(|f| f >= 100.0);
+                    let text = xml_elements(answer, "text")
+                        .first()
+                        .map_or_else(String::new, |(_, text)| xml_text(text));
+                    (text, full)
+                })
+                .collect();
+            let correct = || answers.iter().filter(|(_, full)| *full);
+
+            let answer = match xml_attribute(attributes, "type") {
+                Some("multichoice") => Answer::Choices(answers.clone()),
+                Some("truefalse") => match correct().next() {
+                    Some((text, _)) => Answer::Truth(text.eq_ignore_ascii_case("true")),
+                    None => Answer::Choices(Vec::new()),
+                },
+                Some("numerical") => {
+                    let tolerance = xml_elements(body, "tolerance")
+                        .first()
+                        .and_then(|(_, t)| xml_text(t).parse::<f64>().ok())
+                        .unwrap_or(0.0);
+                    match correct().next() {
+                        Some((text, _)) => Answer::Numeric(text.clone(), tolerance),
+                        None => Answer::Choices(Vec::new()),
+                    }
+                }
+                Some("shortanswer") => {
+                    Answer::Short(correct().map(|(text, _)| text.clone()).collect())
+                }
+                _ => Answer::Unsupported,
+            };
+            Ok(Question {
+                title: xml_child_text(body, "name").filter(|name| !name.is_empty()),
+                stem,
+                answer,
+            })
+        })
+        .collect()
+}
+
+// ─── CSV ─────────────────────────────────────────────────────────────
+
+/// Split one CSV record, honouring double-quoted fields ("" = a quote).
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+/// A CSV row with a `type` column is already native. Without one, the
+/// question is converted like any other imported question.
+enum CsvRow {
+    Native(Exercise),
+    Question(Question),
+}
+
+fn parse_csv(data: &str) -> Vec<Result<CsvRow, Issue>> {
+    let mut lines = data.lines().filter(|line| !line.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let header: Vec<String> = csv_fields(header)
+        .into_iter()
+        .map(|h| h.to_ascii_lowercase())
+        .collect();
+    let column = |names: &[&str]| header.iter().position(|h| names.contains(&h.as_str()));
+    let (id, kind) = (column(&["id"]), column(&["type"]));
+    let (problem, answer) = (column(&["problem", "question"]), column(&["answer"]));
+    let options = column(&["options"]);
+    let (Some(problem), Some(answer)) = (problem, answer) else {
+        return vec![Err(Issue::Unparseable)];
+    };
+
+    lines
+        .map(|line| {
+            let fields = csv_fields(line);
+            let field = |index: Option<usize>| {
+                index
+                    .and_then(|i| fields.get(i))
+                    .filter(|f| !f.is_empty())
+                    .cloned()
+            };
+            let stem = field(Some(problem)).ok_or(Issue::Unparseable)?;
+            let key = field(Some(answer)).ok_or(Issue::Unparseable)?;
+
+            if let Some(kind) = field(kind) {
+                let problem_type = registry::BUILTIN_TYPES
+                    .iter()
+                    .find(|t| **t == kind)
+                    .ok_or(Issue::UnsupportedQuestion)?;
+                return Ok(CsvRow::Native(Exercise {
+                    id: field(id).unwrap_or_default(),
+                    problem_type,
+                    problem: stem,
+                    answer: key,
+                }));
+            }
+            // "options" = "62|72|82" with the answer naming the right one.
+            let answer = match field(options) {
+                Some(options) => Answer::Choices(
+                    options
+                        .split('|')
+                        .map(|o| (o.trim().to_string(), o.trim() == key))
+                        .collect(),
+                ),
+                None => Answer::Short(vec![key]),
+            };
+            Ok(CsvRow::Question(Question {
+                title: field(id),
+                stem,
+                answer,
+            }))
+        })
+        .collect()
+}
+
+// ─── Conversion ──────────────────────────────────────────────────────
+
+/// The arithmetic expression a stem asks about: "What is 7 + 5?" →
+/// "7 + 5". It is the longest run of math characters ending the stem.
+fn trailing_expression(stem: &str) -> Option<String> {
+    let stem = stem.trim().trim_end_matches(['?', '=', ' ', '.']);
+    let is_math = |c: char| c.is_ascii_digit() || " +-*/×÷^().".contains(c);
+    let start = stem
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_math(*c))
+        .last()?
+        .0;
+    let expression = stem[start..]
+        .replace('×', "*")
+        .replace('÷', "/")
+        .trim()
+        .to_string();
+    let has_operator = expression
+        .trim_start_matches('-')
+        .contains(['+', '-', '*', '/', '^']);
+    (has_operator && exact_value(&expression).is_some()).then_some(expression)
+}
+
+/// Map a question onto a built-in problem type.
+fn convert(question: &Question) -> Result<(&'static str, String, String), Issue> {
+    let stem = question.stem.trim();
+    match &question.answer {
+        Answer::Choices(options) => {
+            let mut correct = options.iter().enumerate().filter(|(_, (_, right))| *right);
+            let (index, (text, _)) = correct.next().ok_or(Issue::NoCorrectAnswer)?;
+            if correct.next().is_some() {
+                return Err(Issue::MultipleCorrectAnswers);
+            }
+            let texts: Vec<&str> = options.iter().map(|(text, _)| text.as_str()).collect();
+            let problem = json!({ "question": stem, "options": texts, "correct": index });
+            Ok(("multiple_choice", problem.to_string(), text.clone()))
+        }
+        Answer::Truth(truth) => {
+            if evaluate_statement(stem).is_some() {
+                return Ok(("true_false", stem.to_string(), truth.to_string()));
+            }
+            let problem = json!({
+                "question": stem,
+                "options": ["true", "false"],
+                "correct": usize::from(!truth),
+            });
+            Ok(("multiple_choice", problem.to_string(), truth.to_string()))
+        }
+        Answer::Numeric(_, tolerance) if *tolerance != 0.0 => Err(Issue::NumericTolerance),
+        Answer::Numeric(value, _) => numeric(stem, value),
+        Answer::Short(answers) => {
+            let value = answers
+                .iter()
+                .find(|a| exact_value(a).is_some())
+                .ok_or(Issue::UnsupportedQuestion)?;
+            numeric(stem, value)
+        }
+        Answer::Unsupported => Err(Issue::UnsupportedQuestion),
+    }
+}
+
+/// A numeric answer: a fill-in-the-blank when the stem has a blank,
+/// otherwise arithmetic on the expression the stem ends with.
+fn numeric(stem: &str, value: &str) -> Result<(&'static str, String, String), Issue> {
+    if fill_blank::template_parts(stem).len() > 1 {
+        return Ok(("fill_blank", stem.to_string(), value.to_string()));
+    }
+    let expression = trailing_expression(stem).ok_or(Issue::UnsupportedQuestion)?;
+    Ok(("arithmetic", expression, value.to_string()))
+}
+
+/// Convert a question and check its answer key against the validator.
+fn to_exercise(question: &Question, item: usize) -> Result<Exercise, Issue> {
+    let (problem_type, problem, answer) = convert(question)?;
+    verified(
+        Exercise {
+            id: question.title.clone().unwrap_or_default(),
+            problem_type,
+            problem,
+            answer,
+        },
+        item,
+    )
+}
+
+/// Fill in a missing ID and reject an answer key its own validator
+/// grades wrong.
+fn verified(mut exercise: Exercise, item: usize) -> Result<Exercise, Issue> {
+    if exercise.id.is_empty() {
+        exercise.id = format!("q{item}");
+    }
+    let verdict =
+        registry::check_registered(exercise.problem_type, &exercise.problem, &exercise.answer);
+    match verdict {
+        Some(verdict) if verdict.correct => Ok(exercise),
+        _ => Err(Issue::AnswerKeyRejected),
+    }
+}
+
+/// Convert a question bank from another tool into native exercises.
+///
+/// `format`: "gift", "moodle_xml", or "csv" (see the module docs).
+///
+/// Returns `{"exercises":[{"id","type","problem","answer"}…],
+/// "report":{"format","questions","imported","skipped",
+/// "issues":[{"item","code"}…]}}`, where `item` is the question's
+/// 1-based position in the source and `code` one of "unparseable",
+/// "unsupported_question", "no_correct_answer",
+/// "multiple_correct_answers", "numeric_tolerance", or
+/// "answer_key_rejected". Every imported answer key is graded correct
+/// by `check_answer`. An unknown format gives `{"error":"unknown_format"}`.
+#[wasm_bindgen]
+pub fn import_bank(format: &str, data: &str) -> String {
+    let results: Vec<Result<Exercise, Issue>> = match format {
+        "gift" => parse_gift(data)
+            .into_iter()
+            .enumerate()
+            .map(|(i, q)| q.and_then(|q| to_exercise(&q, i + 1)))
+            .collect(),
+        "moodle_xml" => parse_moodle_xml(data)
+            .into_iter()
+            .enumerate()
+            .map(|(i, q)| q.and_then(|q| to_exercise(&q, i + 1)))
+            .collect(),
+        "csv" => parse_csv(data)
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| match row? {
+                CsvRow::Native(exercise) => verified(exercise, i + 1),
+                CsvRow::Question(question) => to_exercise(&question, i + 1),
+            })
+            .collect(),
+        _ => return version::result(json!({ "error": "unknown_format" })),
+    };
+
+    let mut exercises = Vec::new();
+    let mut issues = Vec::new();
+    for (i, result) in results.iter().enumerate() {
+        match result {
+            Ok(exercise) => exercises.push(json!({
+                "id": exercise.id,
+                "type": exercise.problem_type,
+                "problem": exercise.problem,
+                "answer": exercise.answer,
+            })),
+            Err(issue) => issues.push(json!({ "item": i + 1, "code": issue.code() })),
+        }
+    }
+    version::result(json!({
+        "exercises": exercises,
+        "report": {
+            "format": format,
+            "questions": results.len(),
+            "imported": exercises.len(),
+            "skipped": issues.len(),
+            "issues": issues,
+        },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn import(format: &str, data: &str) -> Value {
+        serde_json::from_str(&import_bank(format, data)).unwrap()
+    }
+
+    #[test]
+    fn test_gift() {
+        let bank = import(
+            "gift",
+            r#"
+// Week 3
+$CATEGORY: arithmetic
+
+::carry::What is 27 + 45? {=72 ~62#Check the carry ~82}
+
+::sum:: What is 7 + 5? {#12}
+
+2/5 = 4/10 {T}
+
+7 × {=8} = 56
+
+Nine minus {=4} is five.
+
+Describe your method. {}
+
+Pi to two places {#3.14:0.01}
+"#,
+        );
+        let exercises = bank["exercises"].as_array().unwrap();
+        assert_eq!(exercises.len(), 4);
+        assert_eq!(exercises[0]["id"], "carry");
+        assert_eq!(exercises[0]["type"], "multiple_choice");
+        assert_eq!(exercises[0]["answer"], "72");
+        assert_eq!(exercises[1]["problem"], "7 + 5");
+        assert_eq!(exercises[2]["type"], "true_false");
+        assert_eq!(exercises[3]["id"], "q4");
+        assert_eq!(exercises[3]["type"], "fill_blank");
+        // "Nine minus __ is five." has no math to grade the blank against.
+        let codes: Vec<&str> = bank["report"]["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["code"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                "answer_key_rejected",
+                "unsupported_question",
+                "numeric_tolerance"
+            ]
+        );
+        assert_eq!(bank["report"]["questions"], 7);
+    }
+
+    #[test]
+    fn test_moodle_xml() {
+        let bank = import(
+            "moodle_xml",
+            r#"<?xml version="1.0"?>
+<quiz>
+  <question type="category"><category><text>$course$/Fractions</text></category></question>
+  <question type="multichoice">
+    <name><text>halves</text></name>
+    <questiontext format="html"><text><![CDATA[<p>Which equals <b>1/2</b>?</p>]]></text></questiontext>
+    <answernumbering>abc</answernumbering>
+    <answer fraction="100"><text>2/4</text></answer>
+    <answer fraction="0"><text>2/3</text></answer>
+  </question>
+  <question type="numerical">
+    <questiontext format="html"><text>&lt;p&gt;Compute 6 &amp;times; 7&lt;/p&gt;</text></questiontext>
+    <answer fraction="100"><text>42</text></answer>
+    <tolerance>0</tolerance>
+  </question>
+  <question type="truefalse">
+    <questiontext><text>3/4 &gt; 2/3</text></questiontext>
+    <answer fraction="100"><text>true</text></answer>
+    <answer fraction="0"><text>false</text></answer>
+  </question>
+  <question type="essay">
+    <questiontext><text>Explain.</text></questiontext>
+  </question>
+</quiz>"#,
+        );
+        let exercises = bank["exercises"].as_array().unwrap();
+        assert_eq!(exercises.len(), 3);
+        assert_eq!(exercises[0]["id"], "halves");
+        assert_eq!(exercises[0]["answer"], "2/4");
+        assert_eq!(exercises[1]["problem"], "6 * 7");
+        assert_eq!(exercises[2]["problem"], "3/4 > 2/3");
+        assert_eq!(bank["report"]["issues"][0]["item"], 4);
+    }
+
+    #[test]
+    fn test_csv() {
+        let bank = import(
+            "csv",
+            "id,type,problem,answer\n\
+             a1,fraction,2/4,1/2\n\
+             a2,money,\"$3.07 + $1.95\",$5.02\n\
+             a3,arithmetic,2 + 2,5\n\
+             a4,essay,Why?,Because\n",
+        );
+        assert_eq!(bank["report"]["imported"], 2);
+        assert_eq!(bank["report"]["issues"][0]["code"], "answer_key_rejected");
+        assert_eq!(bank["report"]["issues"][1]["code"], "unsupported_question");
+
+        let bank = import(
+            "csv",
+            "question,answer,options\nWhat is 9 - 4?,5,\nPick the even one,4,3|4|5\n",
+        );
+        assert_eq!(bank["exercises"][0]["problem"], "9 - 4");
+        assert_eq!(bank["exercises"][1]["type"], "multiple_choice");
+        assert!(import_bank("qti", "").contains("unknown_format"));
+    }
+}
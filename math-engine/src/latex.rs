@@ -0,0 +1,285 @@
+// ─── LaTeX Input ─────────────────────────────────────────────────────
+//
+// Lesson content is authored in LaTeX, so problems and answers may
+// arrive as `\frac{1}{2} + \frac{1}{4}`, `x^{2}`, or `\sqrt{8}`. Before
+// dispatch, the registry rewrites such input into the engine's plain
+// syntax — `1/2 + 1/4`, `x^2`, `sqrt(8)` — so every validator accepts
+// it without knowing LaTeX exists.
+//
+// Only text that looks like LaTeX (a backslash, or `^{` / `_{`) is
+// touched, and JSON problems (multiple choice, lists) never are. Input
+// using a command the converter does not know is passed through
+// unchanged, so the validator reports it as malformed rather than
+// grading a lossy guess.
+//
+// Supported: \frac \dfrac \tfrac, \sqrt and \sqrt[n], ^{…}, {…} groups,
+// \cdot \times \div, \left \right, \le \ge \ne (and \leq \geq \neq \lt
+// \gt), \pi, spacing commands, and $…$ or \(…\) delimiters. A digit
+// directly before a fraction is a mixed number: 2\frac{1}{2} → "2 1/2".
+// Groups nested deeper than the parser accepts are passed through too.
+
+use std::borrow::Cow;
+
+use crate::budget::MAX_NESTING_DEPTH;
+
+/// Commands that become plain text.
+const SYMBOLS: [(&str, &str); 17] = [
+    ("cdot", " * "),
+    ("times", " * "),
+    ("div", " / "),
+    ("le", " ≤ "),
+    ("leq", " ≤ "),
+    ("ge", " ≥ "),
+    ("geq", " ≥ "),
+    ("ne", " ≠ "),
+    ("neq", " ≠ "),
+    ("lt", " < "),
+    ("gt", " > "),
+    ("pi", "pi"),
+    ("left", ""),
+    ("right", ""),
+    ("quad", " "),
+    ("qquad", " "),
+    ("displaystyle", ""),
+];
+
+/// Does `text` need converting?
+fn looks_like_latex(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        return false;
+    }
+    text.contains('\\') || text.contains("^{") || text.contains("_{") || trimmed.starts_with('$')
+}
+
+/// `text` in the engine's plain syntax; unchanged if it is not LaTeX or
+/// uses a command the converter does not support.
+pub(crate) fn to_plain(text: &str) -> Cow<'_, str> {
+    if !looks_like_latex(text) {
+        return Cow::Borrowed(text);
+    }
+    let chars: Vec<char> = text.trim().chars().collect();
+    let chars = strip_delimiters(&chars);
+    let mut converter = Converter {
+        chars,
+        at: 0,
+        depth: 0,
+    };
+    match converter.sequence(None) {
+        Some(plain) if converter.at == chars.len() => {
+            Cow::Owned(plain.split_whitespace().collect::<Vec<_>>().join(" "))
+        }
+        _ => Cow::Borrowed(text),
+    }
+}
+
+/// Drop a surrounding `$…$`, `$$…$$`, or `\(…\)`.
+fn strip_delimiters(chars: &[char]) -> &[char] {
+    for (open, close) in [("$$", "$$"), ("$", "$"), ("\\(", "\\)")] {
+        let (open, close): (Vec<char>, Vec<char>) =
+            (open.chars().collect(), close.chars().collect());
+        if chars.len() >= open.len() + close.len()
+            && chars.starts_with(&open)
+            && chars.ends_with(&close)
+        {
+            return &chars[open.len()..chars.len() - close.len()];
+        }
+    }
+    chars
+}
+
+/// `text` as an operand: bare if it is a single number or name,
+/// otherwise parenthesized.
+fn operand(text: &str) -> String {
+    let text = text.trim();
+    let atomic = !text.is_empty() && text.chars().all(|c| c.is_alphanumeric() || c == '.');
+    if atomic {
+        text.to_string()
+    } else {
+        format!("({})", text)
+    }
+}
+
+/// A converted command.
+enum Piece {
+    Text(String),
+    /// Kept apart so a preceding digit can make it a mixed number.
+    Fraction(String),
+}
+
+struct Converter<'a> {
+    chars: &'a [char],
+    at: usize,
+    /// Open groups; bounded so hostile input cannot overflow the stack.
+    depth: usize,
+}
+
+impl Converter<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.at).copied()
+    }
+
+    /// Convert until `close` (consumed) or the end of input.
+    fn sequence(&mut self, close: Option<char>) -> Option<String> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let out = self.sequence_rule(close);
+        self.depth -= 1;
+        out
+    }
+
+    fn sequence_rule(&mut self, close: Option<char>) -> Option<String> {
+        let mut out = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return close.is_none().then_some(out);
+            };
+            self.at += 1;
+            match c {
+                _ if Some(c) == close => return Some(out),
+                '}' | ']' => return None,
+                '\\' => match self.command()? {
+                    Piece::Text(text) => out.push_str(&text),
+                    Piece::Fraction(fraction) => {
+                        // A digit right before a fraction makes a mixed number.
+                        if out.ends_with(|c: char| c.is_ascii_digit()) {
+                            out.push(' ');
+                        }
+                        out.push_str(&fraction);
+                    }
+                },
+                '{' => out.push_str(&operand(&self.sequence(Some('}'))?)),
+                '^' | '_' => {
+                    let exponent = self.argument()?;
+                    if c == '_' {
+                        // Subscripts are part of a name: x_{1} → x1.
+                        out.push_str(&exponent);
+                    } else {
+                        out.push('^');
+                        out.push_str(&operand(&exponent));
+                    }
+                }
+                '~' => out.push(' '),
+                _ => out.push(c),
+            }
+        }
+    }
+
+    /// A `{…}` group, or a single character: `x^2`, `\frac12`.
+    fn argument(&mut self) -> Option<String> {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.at += 1;
+        }
+        let c = self.peek()?;
+        self.at += 1;
+        match c {
+            '{' => self.sequence(Some('}')),
+            '\\' | '}' | '[' | ']' | '^' | '_' => None,
+            _ => Some(c.to_string()),
+        }
+    }
+
+    /// After a backslash.
+    fn command(&mut self) -> Option<Piece> {
+        let start = self.at;
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.at += 1;
+        }
+        let name: String = self.chars[start..self.at].iter().collect();
+        if name.is_empty() {
+            // Control symbols: spacing, or an escaped brace/paren.
+            let c = self.peek()?;
+            self.at += 1;
+            let text = match c {
+                ',' | ';' | ':' | '!' | ' ' => " ",
+                '{' => "(",
+                '}' => ")",
+                _ => return None,
+            };
+            return Some(Piece::Text(text.to_string()));
+        }
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.argument()?;
+                let denominator = self.argument()?;
+                Some(Piece::Fraction(format!(
+                    "{}/{}",
+                    operand(&numerator),
+                    operand(&denominator)
+                )))
+            }
+            "sqrt" => {
+                if self.peek() == Some('[') {
+                    self.at += 1;
+                    let index = self.sequence(Some(']'))?;
+                    let radicand = self.argument()?;
+                    let root = format!("({})^(1/{})", radicand.trim(), operand(&index));
+                    return Some(Piece::Text(root));
+                }
+                let root = format!("sqrt({})", self.argument()?.trim());
+                Some(Piece::Text(root))
+            }
+            _ => SYMBOLS
+                .iter()
+                .find(|(command, _)| *command == name)
+                .map(|(_, plain)| Piece::Text(plain.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        let cases = [
+            (r"\frac{1}{2} + \frac{1}{4}", "1/2 + 1/4"),
+            (r"$\dfrac{3}{4}$", "3/4"),
+            ("x^{2} + 1", "x^2 + 1"),
+            (r"\sqrt{8}", "sqrt(8)"),
+            (r"\frac{x+1}{2}", "(x+1)/2"),
+            (r"2\frac{1}{2}", "2 1/2"),
+            (r"7 \times 8", "7 * 8"),
+            (r"12 \div 4", "12 / 4"),
+            (r"\left(1 + 2\right)^{3}", "(1 + 2)^3"),
+            (r"\sqrt[3]{27}", "(27)^(1/3)"),
+            (r"\frac12", "1/2"),
+            (r"\le", "≤"),
+            (r"10\,000", "10 000"),
+        ];
+        for (latex, plain) in cases {
+            assert_eq!(to_plain(latex), plain, "{}", latex);
+        }
+    }
+
+    #[test]
+    fn test_non_latex_is_untouched() {
+        for text in [
+            "1/2 + 1/4",
+            r#"{"options":["\\frac{1}{2}","1/3"],"correct":0}"#,
+            r"\unknown{1}",
+            r"\frac{1}{2",
+            r"\frac{1}",
+            &format!("{}1{}", "x^{".repeat(100_000), "}".repeat(100_000)),
+        ] {
+            assert!(matches!(to_plain(text), Cow::Borrowed(t) if t == text));
+        }
+    }
+
+    #[test]
+    fn test_check_answer_accepts_latex() {
+        let verdict = |problem_type, problem, answer| {
+            let result: serde_json::Value =
+                serde_json::from_str(&crate::check_answer(problem_type, problem, answer)).unwrap();
+            result["correct"].as_bool().unwrap()
+        };
+        assert!(verdict("fraction", r"\frac{2}{4}", r"\frac{1}{2}"));
+        assert!(verdict("fraction", "2/4", r"$\frac{1}{2}$"));
+        assert!(verdict("arithmetic", r"7 \times 8", "56"));
+        assert!(verdict("comparison", r"\frac{3}{4} ? \frac{2}{3}", r"\gt"));
+        assert!(!verdict("fraction", r"\frac{2}{4}", r"\frac{1}{3}"));
+    }
+}
@@ -3,8 +3,94 @@
 // This module compiles to WebAssembly and runs inside Fresh Islands.
 // It provides mathematically indestructible validation for exercises.
 
+use std::fmt;
+
 use wasm_bindgen::prelude::*;
 
+// Exact arithmetic and the expression parser (no_std, no-panic).
+pub(crate) use math_core::{budget, expr};
+
+mod bank;
+mod braille;
+mod canonical;
+mod compare;
+mod curriculum;
+#[cfg(feature = "demo-content")]
+mod demo;
+mod distractors;
+mod dual;
+#[cfg(not(target_arch = "wasm32"))]
+mod ffi;
+mod fill_blank;
+mod format;
+mod gamification;
+mod hints;
+mod i18n;
+mod import;
+mod latex;
+mod list;
+mod mastery;
+mod matrix;
+mod money;
+mod msgpack;
+mod parallel;
+mod quiz;
+mod rational;
+mod registry;
+mod render;
+mod review;
+mod rng;
+mod schema;
+mod scoring;
+mod session;
+mod simd;
+mod snapshot;
+mod speech;
+mod temperature;
+mod template;
+mod time;
+mod validators;
+mod vector;
+mod version;
+
+pub use bank::{load_question_bank, query_questions};
+pub use braille::to_nemeth;
+pub use canonical::format_canonical;
+pub use compare::{validate_comparison, validate_true_false};
+pub use curriculum::{curriculum_order, unlocked_skills, validate_curriculum};
+#[cfg(feature = "demo-content")]
+pub use demo::demo_bank;
+pub use distractors::generate_distractors;
+pub use dual::check_answer_dual;
+pub use fill_blank::validate_fill_blank;
+pub use format::*;
+pub use gamification::{compute_xp, update_streak};
+pub use hints::hint_catalog;
+pub use i18n::{check_answer_localized, get_locale, set_locale, supported_locales};
+pub use import::import_bank;
+pub use list::check_list_answer;
+pub use mastery::{decay_mastery, select_next, update_mastery};
+pub use matrix::*;
+pub use money::*;
+pub use quiz::QuizSession;
+pub use rational::{JsRational, Rational};
+pub use registry::{register_custom_type, supported_types};
+pub use render::render_expression;
+pub use review::schedule_review;
+pub use schema::validate_exercises;
+pub use scoring::{batch_score, batch_score_msgpack, score_quiz, score_timed_quiz};
+pub use session::{Attempt, Problem, Session};
+pub use snapshot::{export_state, import_state};
+pub use speech::read_aloud;
+pub use temperature::*;
+pub use template::instantiate;
+pub use time::*;
+pub use vector::*;
+pub use version::{
+    engine_version, get_result_schema_version, set_result_schema_version,
+    supported_result_schema_versions,
+};
+
 // ─── Arithmetic Validation ───────────────────────────────────────────
 
 /// Validate an arithmetic expression: "2 + 3 = 5" → true
@@ -19,35 +105,34 @@ pub fn validate_arithmetic(expression: &str, student_answer: f64) -> bool {
 /// Evaluate a simple arithmetic expression.
 /// Supports: +, -, *, / with two operands.
 fn evaluate_expression(expr: &str) -> Option<f64> {
+    let (op, left, right) = split_expression(expr)?;
+    op.apply(left, right)
+}
+
+/// Split a simple expression into its operation and operands:
+/// "12 / 4" → `(Op::Div, 12.0, 4.0)`, "7" → `(Op::Value, 7.0, 0.0)`.
+fn split_expression(expr: &str) -> Option<(simd::Op, f64, f64)> {
     let expr = expr.trim();
 
     // Try each operator
-    for op in ['+', '-', '*', '/'] {
-        if let Some(pos) = expr.rfind(op) {
+    for (symbol, op) in [
+        ('+', simd::Op::Add),
+        ('-', simd::Op::Sub),
+        ('*', simd::Op::Mul),
+        ('/', simd::Op::Div),
+    ] {
+        if let Some(pos) = expr.rfind(symbol) {
             if pos == 0 {
                 continue; // Skip leading negative sign
             }
             let left = expr[..pos].trim().parse::<f64>().ok()?;
             let right = expr[pos + 1..].trim().parse::<f64>().ok()?;
-
-            return match op {
-                '+' => Some(left + right),
-                '-' => Some(left - right),
-                '*' => Some(left * right),
-                '/' => {
-                    if right.abs() < 1e-15 {
-                        None // Division by zero
-                    } else {
-                        Some(left / right)
-                    }
-                }
-                _ => None,
-            };
+            return Some((op, left, right));
         }
     }
 
     // Single number
-    expr.parse::<f64>().ok()
+    Some((simd::Op::Value, expr.parse::<f64>().ok()?, 0.0))
 }
 
 // ─── Equation Validation ─────────────────────────────────────────────
@@ -55,13 +140,15 @@ fn evaluate_expression(expr: &str) -> Option<f64> {
 /// Check if an equation is balanced: "2x + 3 = 7" with x=2 → true
 #[wasm_bindgen]
 pub fn validate_equation(equation: &str, variable_value: f64) -> bool {
-    let parts: Vec<&str> = equation.split('=').collect();
-    if parts.len() != 2 {
+    let Some((left, right)) = equation.split_once('=') else {
+        return false;
+    };
+    if right.contains('=') {
         return false;
     }
 
-    let left = evaluate_side(parts[0].trim(), variable_value);
-    let right = evaluate_side(parts[1].trim(), variable_value);
+    let left = evaluate_side(left.trim(), variable_value);
+    let right = evaluate_side(right.trim(), variable_value);
 
     match (left, right) {
         (Some(l), Some(r)) => (l - r).abs() < 1e-9,
@@ -73,13 +160,60 @@ pub fn validate_equation(equation: &str, variable_value: f64) -> bool {
 fn evaluate_side(side: &str, x: f64) -> Option<f64> {
     let side = side.trim();
 
-    // Replace 'x' with the numeric value and evaluate
-    let substituted = side.replace('x', &format!("{}", x));
-
-    // Simple evaluation of substituted expression
+    // Replace 'x' with the numeric value and evaluate, on the stack
+    // unless the result is too long for it
+    let mut inline = InlineText::default();
+    if substitute(&mut inline, side, x).is_ok() {
+        return evaluate_expression(inline.as_str());
+    }
+    let mut substituted = String::new();
+    substitute(&mut substituted, side, x).ok()?;
     evaluate_expression(&substituted)
 }
 
+/// Write `side` with every 'x' replaced by `x`.
+fn substitute(out: &mut impl fmt::Write, side: &str, x: f64) -> fmt::Result {
+    for (i, piece) in side.split('x').enumerate() {
+        if i > 0 {
+            write!(out, "{}", x)?;
+        }
+        out.write_str(piece)?;
+    }
+    Ok(())
+}
+
+/// Text in a fixed stack buffer; writing past its end fails.
+struct InlineText {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl Default for InlineText {
+    fn default() -> InlineText {
+        InlineText {
+            bytes: [0; 64],
+            len: 0,
+        }
+    }
+}
+
+impl InlineText {
+    fn as_str(&self) -> &str {
+        // Only whole `str`s are ever written, so this is valid UTF-8.
+        std::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl fmt::Write for InlineText {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        let slot = self.bytes.get_mut(self.len..end).ok_or(fmt::Error)?;
+        slot.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
 // ─── Fraction Validation ─────────────────────────────────────────────
 
 /// Validate a fraction answer: numerator/denominator
@@ -122,74 +256,118 @@ fn gcd(a: u64, b: u64) -> u64 {
 // ─── Validation Result ───────────────────────────────────────────────
 
 /// Detailed validation result returned as JSON string.
+/// Dispatches to the validator registered for `problem_type`; `hint_id`
+/// is the hint's stable catalog ID (see `hint_catalog`), and `hint_data`
+/// describes the hint as `{"kind","step_index","referenced_operands",
+/// "template_id","params"}` for interactive rendering. A wrong
+/// multiple-choice pick adds `misconception`. An unknown `problem_type`
+/// adds `supported_types`, the registry's types as in `supported_types()`.
+/// `problem` and `student_answer` may be LaTeX (`\frac{1}{2}`; see latex.rs).
 #[wasm_bindgen]
 pub fn check_answer(problem_type: &str, problem: &str, student_answer: &str) -> String {
-    let (is_correct, hint) = match problem_type {
-        "arithmetic" => {
-            let answer: f64 = student_answer.parse().unwrap_or(f64::NAN);
-            let correct = validate_arithmetic(problem, answer);
-            let hint = if correct {
-                "Correct!".to_string()
-            } else {
-                format!("Try evaluating {} step by step.", problem)
-            };
-            (correct, hint)
-        }
-        "fraction" => {
-            let parts: Vec<&str> = student_answer.split('/').collect();
-            if parts.len() == 2 {
-                let num = parts[0].trim().parse::<i64>().unwrap_or(0);
-                let den = parts[1].trim().parse::<i64>().unwrap_or(0);
-                let prob_parts: Vec<&str> = problem.split('/').collect();
-                if prob_parts.len() == 2 {
-                    let exp_num = prob_parts[0].trim().parse::<i64>().unwrap_or(0);
-                    let exp_den = prob_parts[1].trim().parse::<i64>().unwrap_or(0);
-                    let correct = validate_fraction(exp_num, exp_den, num, den);
-                    let hint = if correct {
-                        "Correct!".to_string()
-                    } else {
-                        "Try simplifying the fraction to its lowest terms.".to_string()
-                    };
-                    (correct, hint)
-                } else {
-                    (false, "Invalid problem format.".to_string())
-                }
-            } else {
-                (false, "Enter your answer as a fraction: numerator/denominator".to_string())
-            }
-        }
-        _ => (false, format!("Unknown problem type: {}", problem_type)),
+    let (verdict, supported) =
+        match registry::check_registered(problem_type, problem, student_answer) {
+            Some(verdict) => (verdict, String::new()),
+            None => (
+                registry::Verdict::new(false, &hints::UNKNOWN_TYPE, &[("type", problem_type)]),
+                format!(r#","supported_types":{}"#, registry::capabilities()),
+            ),
+        };
+
+    let misconception = match &verdict.misconception {
+        Some(m) => format!(r#","misconception":{}"#, json_string(m)),
+        None => String::new(),
     };
+    version::stamp_text(format!(
+        r#"{{"correct":{},"hint":{},"hint_id":{},"hint_data":{},"problem":{},"answer":{}{}{}}}"#,
+        verdict.correct,
+        json_string(&verdict.hint),
+        json_string(&verdict.hint_id),
+        verdict.hint_data(),
+        json_string(problem),
+        json_string(student_answer),
+        misconception,
+        supported
+    ))
+}
+
+/// Quote and escape a string for embedding in JSON output.
+fn json_string(text: &str) -> String {
+    serde_json::Value::from(text).to_string()
+}
+
+// ─── Batch Validation ────────────────────────────────────────────────
 
-    format!(
-        r#"{{"correct":{},"hint":"{}","problem":"{}","answer":"{}"}}"#,
-        is_correct, hint, problem, student_answer
-    )
+/// Why one batch item could not be graded.
+fn batch_error(problem: Option<&str>, answer: Option<&str>) -> Option<&'static str> {
+    match (problem, answer) {
+        (None, _) => Some("missing_problem"),
+        (_, None) => Some("missing_answer"),
+        (Some(problem), _) if evaluate_expression(problem).is_none() => Some("invalid_problem"),
+        (_, Some(answer)) if answer.parse::<f64>().is_err() => Some("invalid_answer"),
+        _ => None,
+    }
 }
 
-// ─── Performance Benchmarks ──────────────────────────────────────────
+/// Validate a batch of ';'-separated arithmetic problems and answers,
+/// e.g. a whole quiz for its review screen.
+///
+/// Returns `{"results":[…]}` with one entry per position:
+/// `{"problem","answer","correct","error","hint","hint_id"}`. `answer`
+/// is the answer normalized ("5.0" → "5"), or `null` if it is not a
+/// number; `error` is `null` or one of "invalid_problem",
+/// "invalid_answer", "missing_problem", "missing_answer" (the lists
+/// differ in length). `batch_score` grades the same input with partial
+/// credit.
+///
+/// Items are graded in vectorized chunks (see `simd.rs`); only wrong or
+/// unparseable ones go through the validator one by one, in parallel
+/// with the `parallel` feature (see `parallel.rs`).
+#[wasm_bindgen]
+pub fn batch_validate(problems: &str, answers: &str) -> String {
+    version::result(version::list("results", batch_results(problems, answers)))
+}
 
-/// Batch validate multiple arithmetic problems. Returns count of correct answers.
+/// `batch_validate`'s results, MessagePack-encoded (see msgpack.rs).
 #[wasm_bindgen]
-pub fn batch_validate(problems: &str, answers: &str) -> u32 {
-    let probs: Vec<&str> = problems.split(';').collect();
-    let ans: Vec<&str> = answers.split(';').collect();
-
-    if probs.len() != ans.len() {
-        return 0;
-    }
-
-    probs
-        .iter()
-        .zip(ans.iter())
-        .filter(|(p, a)| {
-            if let Ok(answer) = a.trim().parse::<f64>() {
-                validate_arithmetic(p.trim(), answer)
-            } else {
-                false
+pub fn batch_validate_msgpack(problems: &str, answers: &str) -> Vec<u8> {
+    msgpack::encode(&version::stamp(version::list(
+        "results",
+        batch_results(problems, answers),
+    )))
+}
+
+fn batch_results(problems: &str, answers: &str) -> serde_json::Value {
+    let probs: Vec<&str> = problems.split(';').map(str::trim).collect();
+    let ans: Vec<&str> = answers.split(';').map(str::trim).collect();
+    let items: Vec<(Option<&str>, Option<&str>)> = (0..probs.len().max(ans.len()))
+        .map(|i| (probs.get(i).copied(), ans.get(i).copied()))
+        .collect();
+    let fast_correct = simd::correct_items(&items);
+    let graded: Vec<_> = items.into_iter().zip(fast_correct).collect();
+
+    let results = parallel::map_items(&graded, |&((problem, answer), fast_correct)| {
+        let error = batch_error(problem, answer);
+        let value = answer.and_then(|a| a.parse::<f64>().ok());
+        let verdict = match problem.filter(|_| error != Some("invalid_problem")) {
+            Some(_) if fast_correct => registry::Verdict::new(true, &hints::CORRECT, &[]),
+            Some(problem) => {
+                registry::check_registered("arithmetic", problem, answer.unwrap_or_default())
+                    .unwrap_or_else(|| registry::Verdict::new(false, &hints::INVALID_FORMAT, &[]))
             }
+            None => registry::Verdict::new(false, &hints::INVALID_FORMAT, &[]),
+        };
+        serde_json::json!({
+            "problem": problem,
+            // + 0.0 turns -0 into 0
+            "answer": value.map(|v| (v + 0.0).to_string()),
+            "correct": error.is_none() && verdict.correct,
+            "error": error,
+            "hint": verdict.hint,
+            "hint_id": verdict.hint_id,
         })
-        .count() as u32
+    });
+    serde_json::Value::Array(results)
 }
 
 // ─── Tests ───────────────────────────────────────────────────────────
@@ -237,15 +415,76 @@ mod tests {
         assert_eq!(simplify_fraction(6, 9), vec![2, 3]);
     }
 
+    fn batch(problems: &str, answers: &str) -> Vec<serde_json::Value> {
+        let result: serde_json::Value =
+            serde_json::from_str(&batch_validate(problems, answers)).unwrap();
+        serde_json::from_value(result["results"].clone()).unwrap()
+    }
+
     #[test]
     fn test_batch_validate() {
-        assert_eq!(batch_validate("2 + 3;4 * 5;10 / 2", "5;20;5"), 3);
-        assert_eq!(batch_validate("2 + 3;4 * 5", "5;21"), 1);
+        let results = batch("2 + 3;4 * 5;10 / 2", "5;21; 5.0 ");
+        let correct: Vec<bool> = results.iter().map(|r| r["correct"] == true).collect();
+        assert_eq!(correct, [true, false, true]);
+        assert_eq!(results[1]["hint_id"], "arithmetic.step_by_step");
+        assert_eq!(results[2]["answer"], "5");
+        assert!(results[2]["error"].is_null());
+
+        let results = batch("2 + 3;two + 2;1 + 1", "five;4");
+        let errors: Vec<&str> = results
+            .iter()
+            .map(|r| r["error"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            errors,
+            ["invalid_answer", "invalid_problem", "missing_answer"]
+        );
+        assert!(results[0]["answer"].is_null());
+        assert_eq!(batch("1 + 1", "2;3")[1]["error"], "missing_problem");
+    }
+
+    #[test]
+    fn test_validate_equation() {
+        assert!(validate_equation("x * 3 = 12", 4.0));
+        assert!(!validate_equation("x * 3 = 12", 3.0));
+        assert!(!validate_equation("x * 3 = 12 = 12", 4.0));
+        assert!(!validate_equation("x * 3", 4.0));
+        // 1e300 prints as 301 digits, too long to substitute on the stack.
+        assert!(validate_equation("x * 1 = x", 1e300));
+    }
+
+    #[test]
+    fn test_batch_validate_msgpack() {
+        let problems = vec!["12 * 34"; 1_000].join(";") + ";two + 2";
+        let answers = vec!["408"; 999].join(";") + ";409;4";
+        let json = batch_validate(&problems, &answers);
+        let msgpack = batch_validate_msgpack(&problems, &answers);
+        let decoded: serde_json::Value = rmp_serde::from_slice(&msgpack).unwrap();
+        assert_eq!(
+            decoded,
+            serde_json::from_str::<serde_json::Value>(&json).unwrap()
+        );
+        assert!(
+            msgpack.len() < json.len() * 4 / 5,
+            "{} vs {}",
+            msgpack.len(),
+            json.len()
+        );
     }
 
     #[test]
     fn test_check_answer_json() {
         let result = check_answer("arithmetic", "2 + 3", "5");
         assert!(result.contains("\"correct\":true"));
+        assert!(!result.contains("supported_types"));
+    }
+
+    #[test]
+    fn test_unknown_type_lists_supported_types() {
+        let result: serde_json::Value =
+            serde_json::from_str(&check_answer("geometry", "x", "y")).unwrap();
+        assert_eq!(result["hint_id"], "unknown_type");
+        let types = result["supported_types"].as_array().unwrap();
+        assert!(types.contains(&serde_json::json!({ "type": "arithmetic", "builtin": true })));
     }
 }
@@ -0,0 +1,283 @@
+// ─── Multi-Part List Answers ─────────────────────────────────────────
+//
+// Answers made of several values: "list all factors of 12", "give both
+// roots". Each element is graded on its own, so the student sees which
+// ones were right instead of a flat "wrong".
+//
+// Problem (JSON): { "items": ["1", "2", "3", "4", "6", "12"], "ordered": false }
+// Answer: "1, 2, 3, 4, 6, 12" (commas or semicolons), or a JSON array.
+// Numeric items compare by exact value ("0.5" matches "1/2"); others by
+// case-insensitive text.
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::compare::exact_value;
+use crate::hints::{self, Hint};
+use crate::registry::Verdict;
+use crate::version;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ItemStatus {
+    Correct,
+    /// Belongs in the list, but at another position (ordered lists).
+    Misplaced,
+    /// Matches an item the student already gave (unordered lists).
+    Duplicate,
+    Wrong,
+}
+
+impl ItemStatus {
+    fn code(self) -> &'static str {
+        match self {
+            ItemStatus::Correct => "correct",
+            ItemStatus::Misplaced => "misplaced",
+            ItemStatus::Duplicate => "duplicate",
+            ItemStatus::Wrong => "wrong",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ListGrade {
+    pub items: Vec<(String, ItemStatus)>,
+    /// Expected items the student did not give.
+    pub missing: usize,
+}
+
+impl ListGrade {
+    pub(crate) fn correct(&self) -> bool {
+        self.missing == 0 && self.items.iter().all(|(_, s)| *s == ItemStatus::Correct)
+    }
+
+    /// Fraction of the list graded correct, penalising extra items.
+    pub(crate) fn score(&self) -> f64 {
+        let right = self
+            .items
+            .iter()
+            .filter(|(_, s)| *s == ItemStatus::Correct)
+            .count();
+        let out_of = self.items.len().max(right + self.missing);
+        if out_of == 0 {
+            return 0.0;
+        }
+        right as f64 / out_of as f64
+    }
+
+    /// The hint for this grade.
+    pub(crate) fn hint(&self) -> Hint {
+        let status = |s| self.items.iter().any(|(_, status)| *status == s);
+        if self.correct() {
+            hints::CORRECT
+        } else if status(ItemStatus::Misplaced) && !status(ItemStatus::Wrong) {
+            hints::LIST_ORDER
+        } else if status(ItemStatus::Wrong) || status(ItemStatus::Duplicate) {
+            hints::LIST_CHECK_EACH
+        } else {
+            hints::LIST_FIND_MORE
+        }
+    }
+
+    /// The verdict for this grade; its step is the first item that is
+    /// not correct.
+    pub(crate) fn verdict(&self) -> Verdict {
+        let verdict = Verdict::new(self.correct(), &self.hint(), &[]);
+        match self
+            .items
+            .iter()
+            .position(|(_, status)| *status != ItemStatus::Correct)
+        {
+            Some(index) => verdict
+                .at_step(index)
+                .with_operands(&[self.items[index].0.as_str()]),
+            None => verdict,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ListProblem {
+    items: Vec<String>,
+    ordered: bool,
+}
+
+impl ListProblem {
+    pub(crate) fn from_json(text: &str) -> Option<ListProblem> {
+        let payload: Value = serde_json::from_str(text).ok()?;
+        let items = payload
+            .get("items")?
+            .as_array()?
+            .iter()
+            .map(item_text)
+            .collect::<Option<Vec<_>>>()?;
+        if items.is_empty() {
+            return None;
+        }
+        let ordered = payload
+            .get("ordered")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        Some(ListProblem { items, ordered })
+    }
+
+    pub(crate) fn grade(&self, given: &[String]) -> ListGrade {
+        let mut used = vec![false; self.items.len()];
+        let mut items = Vec::with_capacity(given.len());
+        for (position, answer) in given.iter().enumerate() {
+            let matches: Vec<usize> = (0..self.items.len())
+                .filter(|&i| same_item(&self.items[i], answer))
+                .collect();
+            let status = if self.ordered {
+                if matches.contains(&position) {
+                    used[position] = true;
+                    ItemStatus::Correct
+                } else if matches.is_empty() {
+                    ItemStatus::Wrong
+                } else {
+                    ItemStatus::Misplaced
+                }
+            } else if let Some(&i) = matches.iter().find(|&&i| !used[i]) {
+                used[i] = true;
+                ItemStatus::Correct
+            } else if matches.is_empty() {
+                ItemStatus::Wrong
+            } else {
+                ItemStatus::Duplicate
+            };
+            items.push((answer.clone(), status));
+        }
+        ListGrade {
+            items,
+            missing: used.iter().filter(|u| !**u).count(),
+        }
+    }
+}
+
+fn item_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn same_item(expected: &str, given: &str) -> bool {
+    match (exact_value(expected), exact_value(given)) {
+        (Some(a), Some(b)) => a == b,
+        _ => expected.trim().eq_ignore_ascii_case(given.trim()),
+    }
+}
+
+/// Split the student's answer into items; `None` if it is empty.
+pub(crate) fn split_answer(answer: &str) -> Option<Vec<String>> {
+    let items: Vec<String> = if answer.trim_start().starts_with('[') {
+        serde_json::from_str::<Vec<Value>>(answer)
+            .ok()?
+            .iter()
+            .map(item_text)
+            .collect::<Option<_>>()?
+    } else {
+        answer
+            .split([',', ';'])
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    };
+    (!items.is_empty()).then_some(items)
+}
+
+/// Grade a list answer element by element.
+///
+/// `problem`: `{"items":["-2","3"],"ordered":false}`
+/// `student_answer`: "3, -2" or `["3","-2"]`
+///
+/// Returns `{"correct","hint","hint_id","score","items":[{"answer","status"}…],"missing"}`
+/// where `status` is "correct", "misplaced", "duplicate", or "wrong",
+/// and `missing` counts expected items not given.
+#[wasm_bindgen]
+pub fn check_list_answer(problem: &str, student_answer: &str) -> String {
+    let Some(list) = ListProblem::from_json(problem) else {
+        return version::result(json!({ "correct": false, "error": "invalid_problem" }));
+    };
+    let Some(given) = split_answer(student_answer) else {
+        let verdict = Verdict::new(false, &hints::LIST_SEPARATE_ITEMS, &[]);
+        return version::result(json!({
+            "correct": false,
+            "hint": verdict.hint,
+            "hint_id": verdict.hint_id,
+            "hint_data": verdict.hint_data(),
+            "score": 0.0,
+            "items": [],
+            "missing": list.items.len(),
+        }));
+    };
+    let grade = list.grade(&given);
+    let items: Vec<Value> = grade
+        .items
+        .iter()
+        .map(|(answer, status)| json!({ "answer": answer, "status": status.code() }))
+        .collect();
+    let verdict = grade.verdict();
+    version::result(json!({
+        "correct": verdict.correct,
+        "hint": verdict.hint,
+        "hint_id": verdict.hint_id,
+        "hint_data": verdict.hint_data(),
+        "score": grade.score(),
+        "items": items,
+        "missing": grade.missing,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grade(problem: &str, answer: &str) -> ListGrade {
+        let list = ListProblem::from_json(problem).unwrap();
+        list.grade(&split_answer(answer).unwrap())
+    }
+
+    #[test]
+    fn test_unordered_factors() {
+        let factors = r#"{"items":[1,2,3,4,6,12]}"#;
+        assert!(grade(factors, "12, 6, 4, 3, 2, 1").correct());
+
+        let partial = grade(factors, "1, 2, 2, 5");
+        let statuses: Vec<ItemStatus> = partial.items.iter().map(|(_, s)| *s).collect();
+        assert_eq!(
+            statuses,
+            [
+                ItemStatus::Correct,
+                ItemStatus::Correct,
+                ItemStatus::Duplicate,
+                ItemStatus::Wrong
+            ]
+        );
+        assert_eq!(partial.missing, 4);
+        assert_eq!(partial.hint().id, "list.check_each");
+    }
+
+    #[test]
+    fn test_ordered_and_exact_values() {
+        let roots = r#"{"items":["-1/2","3"],"ordered":true}"#;
+        assert!(grade(roots, r#"["-0.5", "3"]"#).correct());
+        let swapped = grade(roots, "3; -1/2");
+        assert!(swapped
+            .items
+            .iter()
+            .all(|(_, s)| *s == ItemStatus::Misplaced));
+        assert_eq!(swapped.hint().id, "list.order");
+        assert_eq!(swapped.score(), 0.0);
+    }
+
+    #[test]
+    fn test_check_list_answer_json() {
+        let result = check_list_answer(r#"{"items":["a","b"]}"#, "B");
+        assert!(result.contains("\"correct\":false"));
+        assert!(result.contains("\"missing\":1"));
+        assert!(result.contains("\"score\":0.5"));
+        assert!(result.contains("\"hint_id\":\"list.find_more\""));
+        assert!(check_list_answer("[]", "1").contains("invalid_problem"));
+    }
+}
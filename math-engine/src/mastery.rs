@@ -0,0 +1,355 @@
+// ─── Mastery Estimation ──────────────────────────────────────────────
+//
+// An ELO rating per student (and per skill), on the same scale as the
+// problems' difficulty ratings. Each answer is a "match" between the
+// student and the problem: the rating moves by how surprising the
+// result was — a correct answer on a hard problem counts for much
+// more than one on an easy problem.
+//
+// Ratings are rounded to hundredths so every platform stores and
+// compares the same numbers.
+//
+// Adaptive sequencing picks the problem the student should get right
+// about TARGET_SUCCESS of the time — hard enough to learn from, easy
+// enough not to discourage — with a seeded choice among the closest
+// few so a session doesn't repeat the same problem.
+//
+// Between sessions a rating fades: retention halves every half-life,
+// and the part of the rating above the floor shrinks with it. A skill
+// is due for review once retention drops below the review threshold.
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::rng::SplitMix64;
+use crate::version;
+
+/// Largest rating change from one answer.
+const K_FACTOR: f64 = 32.0;
+
+/// Rating gap at which the stronger side is expected to win 10 : 1.
+const SCALE: f64 = 400.0;
+
+/// Rating of a new student, and of a skill with no rating yet.
+const INITIAL_RATING: f64 = 1000.0;
+
+/// Chance of a correct answer adaptive practice aims for.
+const TARGET_SUCCESS: f64 = 0.7;
+
+/// How many of the best-matched problems the seed chooses among.
+const CANDIDATES: usize = 3;
+
+/// Chance (0–1) that a student rated `rating` answers a problem of
+/// `difficulty` correctly.
+pub(crate) fn expected_score(rating: f64, difficulty: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((difficulty - rating) / SCALE))
+}
+
+/// Update a student's rating after one answer.
+///
+/// `student_rating` and `problem_difficulty` share one scale (a new
+/// student and an average problem both start at `INITIAL_RATING`,
+/// 1000). Returns the new
+/// rating, rounded to hundredths; non-finite inputs return
+/// `student_rating` unchanged.
+#[wasm_bindgen]
+pub fn update_mastery(student_rating: f64, problem_difficulty: f64, correct: bool) -> f64 {
+    if !student_rating.is_finite() || !problem_difficulty.is_finite() {
+        return student_rating;
+    }
+    let actual = if correct { 1.0 } else { 0.0 };
+    let rating =
+        student_rating + K_FACTOR * (actual - expected_score(student_rating, problem_difficulty));
+    (rating * 100.0).round() / 100.0
+}
+
+/// Pick the next problem for a student.
+///
+/// `mastery_json` maps skills to ratings, `{"fractions":1040,"time":980}`
+/// (a skill missing from it counts as a new student's 1000).
+/// `available_problems_json` is
+/// `[{"id":"f-12","skill":"fractions","difficulty":1100},…]`.
+/// The problems whose predicted success is closest to 70% are the
+/// candidates, and `seed` chooses among the best three, so the same
+/// inputs always give the same pick.
+///
+/// Returns `{"id","index","skill","expected"}` — `index` into the
+/// problem list and the predicted chance of success — or
+/// `{"error":code}`: "invalid_mastery", "invalid_problems", or
+/// "no_problems".
+#[wasm_bindgen]
+pub fn select_next(mastery_json: &str, available_problems_json: &str, seed: u64) -> String {
+    let mastery: Option<BTreeMap<String, f64>> = match serde_json::from_str::<Value>(mastery_json) {
+        Ok(Value::Object(skills)) => skills
+            .into_iter()
+            .map(|(skill, rating)| Some((skill, rating.as_f64()?)))
+            .collect(),
+        _ => None,
+    };
+    let Some(mastery) = mastery else {
+        return version::result(json!({ "error": "invalid_mastery" }));
+    };
+    let problems: Option<Vec<(String, String, f64)>> =
+        match serde_json::from_str::<Value>(available_problems_json) {
+            Ok(Value::Array(problems)) => problems
+                .iter()
+                .map(|p| {
+                    let id = match p.get("id")? {
+                        Value::String(id) => id.clone(),
+                        Value::Number(id) => id.to_string(),
+                        _ => return None,
+                    };
+                    let skill = p.get("skill")?.as_str()?.to_string();
+                    let difficulty = p.get("difficulty")?.as_f64()?;
+                    Some((id, skill, difficulty))
+                })
+                .collect(),
+            _ => None,
+        };
+    let Some(problems) = problems else {
+        return version::result(json!({ "error": "invalid_problems" }));
+    };
+    if problems.is_empty() {
+        return version::result(json!({ "error": "no_problems" }));
+    }
+
+    let mut ranked: Vec<(usize, f64)> = problems
+        .iter()
+        .enumerate()
+        .map(|(index, (_, skill, difficulty))| {
+            let rating = mastery.get(skill).copied().unwrap_or(INITIAL_RATING);
+            (index, expected_score(rating, *difficulty))
+        })
+        .collect();
+    // Stable sort: equally good matches keep list order.
+    ranked.sort_by(|a, b| {
+        (a.1 - TARGET_SUCCESS)
+            .abs()
+            .total_cmp(&(b.1 - TARGET_SUCCESS).abs())
+    });
+    let pool = ranked.len().min(CANDIDATES);
+    let (index, expected) = ranked[SplitMix64::new(seed).below(pool as u64) as usize];
+    let (id, skill, _) = &problems[index];
+    version::result(json!({
+        "id": id,
+        "index": index,
+        "skill": skill,
+        "expected": (expected * 100.0).round() / 100.0,
+    }))
+}
+
+// ─── Forgetting ──────────────────────────────────────────────────────
+
+/// How fast ratings fade; see `decay_mastery`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Decay {
+    /// Days for retention to halve.
+    half_life_days: f64,
+    /// Rating the decay approaches and never passes.
+    floor: f64,
+    /// Retention below which a skill is due for review.
+    review_threshold: f64,
+}
+
+impl Default for Decay {
+    fn default() -> Self {
+        Decay {
+            half_life_days: 30.0,
+            floor: INITIAL_RATING,
+            review_threshold: 0.8,
+        }
+    }
+}
+
+impl Decay {
+    /// `{"half_life_days","floor","review_threshold"}`; a missing field
+    /// keeps its default, and "" means all defaults.
+    fn from_json(params_json: &str) -> Option<Decay> {
+        let mut decay = Decay::default();
+        if params_json.trim().is_empty() {
+            return Some(decay);
+        }
+        let params = serde_json::from_str::<Value>(params_json).ok()?;
+        let fields = params.as_object()?;
+        let number = |name: &str, default: f64| {
+            fields
+                .get(name)
+                .map_or(Some(default), |v| v.as_f64().filter(|n| n.is_finite()))
+        };
+        decay.half_life_days = number("half_life_days", decay.half_life_days)?;
+        decay.floor = number("floor", decay.floor)?;
+        decay.review_threshold = number("review_threshold", decay.review_threshold)?;
+        let valid = decay.half_life_days > 0.0
+            && decay.review_threshold > 0.0
+            && decay.review_threshold < 1.0;
+        valid.then_some(decay)
+    }
+
+    /// Fraction remembered after `days`: 1 at first, ½ after one half-life.
+    fn retention(&self, days: f64) -> f64 {
+        0.5f64.powf(days / self.half_life_days)
+    }
+
+    /// Days after the last practice at which retention reaches the
+    /// review threshold.
+    fn due_after_days(&self) -> f64 {
+        self.half_life_days * (1.0 / self.review_threshold).log2()
+    }
+}
+
+/// Fade a skill's rating over the days since it was last practised.
+///
+/// Retention halves every `half_life_days` (default 30), and the part of
+/// `mastery` above `floor` (default 1000, a new student's rating) is
+/// scaled by it; a rating at or below the floor does not change. The
+/// skill is due for review once retention is below `review_threshold`
+/// (default 0.8, about 9.7 days at the default half-life). `params_json`
+/// overrides any of the three, or is "" for the defaults.
+///
+/// Returns `{"mastery","retention","due","days_until_due"}` — the faded
+/// rating and retention rounded to hundredths, and whole days left until
+/// review (0 once due) — or `{"error":code}`: "invalid_input" for a
+/// non-finite rating or negative days, "invalid_params".
+#[wasm_bindgen]
+pub fn decay_mastery(mastery: f64, days_elapsed: f64, params_json: &str) -> String {
+    if !mastery.is_finite() || !days_elapsed.is_finite() || days_elapsed < 0.0 {
+        return version::result(json!({ "error": "invalid_input" }));
+    }
+    let Some(decay) = Decay::from_json(params_json) else {
+        return version::result(json!({ "error": "invalid_params" }));
+    };
+    let retention = decay.retention(days_elapsed);
+    let faded = if mastery > decay.floor {
+        decay.floor + (mastery - decay.floor) * retention
+    } else {
+        mastery
+    };
+    let days_until_due = (decay.due_after_days() - days_elapsed).ceil().max(0.0);
+    version::result(json!({
+        "mastery": (faded * 100.0).round() / 100.0,
+        "retention": (retention * 100.0).round() / 100.0,
+        "due": retention < decay.review_threshold,
+        "days_until_due": days_until_due as u64,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_even_match_moves_half_k() {
+        assert_eq!(expected_score(1000.0, 1000.0), 0.5);
+        assert_eq!(update_mastery(1000.0, 1000.0, true), 1016.0);
+        assert_eq!(update_mastery(1000.0, 1000.0, false), 984.0);
+    }
+
+    #[test]
+    fn test_surprise_weighs_more() {
+        // 400 points apart: expected 10/11.
+        let upset = update_mastery(1000.0, 1400.0, true);
+        let routine = update_mastery(1000.0, 600.0, true);
+        assert_eq!(upset, 1029.09);
+        assert_eq!(routine, 1002.91);
+        assert!(update_mastery(1400.0, 1000.0, false) < 1371.0);
+    }
+
+    #[test]
+    fn test_select_next_targets_seventy_percent() {
+        let mastery = r#"{"fractions":1000,"time":1300}"#;
+        let problems = r#"[{"id":"f-easy","skill":"fractions","difficulty":400},
+            {"id":"f-mid","skill":"fractions","difficulty":850},
+            {"id":"f-hard","skill":"fractions","difficulty":1500},
+            {"id":"t-mid","skill":"time","difficulty":1150},
+            {"id":"t-hard","skill":"time","difficulty":1700},
+            {"id":"a-new","skill":"angles","difficulty":860}]"#;
+        let picks: std::collections::BTreeSet<String> = (0..50)
+            .map(|seed| {
+                let pick: Value =
+                    serde_json::from_str(&select_next(mastery, problems, seed)).unwrap();
+                pick["id"].as_str().unwrap().to_string()
+            })
+            .collect();
+        // 150 points below the rating ≈ 70%; the seed varies the pick
+        // among the three closest.
+        let expected: std::collections::BTreeSet<String> = ["f-mid", "t-mid", "a-new"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(picks, expected);
+        assert_eq!(
+            select_next(mastery, problems, 9),
+            select_next(mastery, problems, 9)
+        );
+    }
+
+    #[test]
+    fn test_select_next_errors() {
+        assert!(select_next("[]", "[]", 0).contains("invalid_mastery"));
+        assert!(select_next("{}", r#"[{"id":"x"}]"#, 0).contains("invalid_problems"));
+        assert!(select_next("{}", "[]", 0).contains("no_problems"));
+        let single = select_next("{}", r#"[{"id":7,"skill":"time","difficulty":1000}]"#, 3);
+        assert!(single.contains(r#""expected":0.5"#) && single.contains(r#""id":"7""#));
+    }
+
+    #[test]
+    fn test_non_finite_inputs() {
+        assert_eq!(update_mastery(1200.0, f64::NAN, true), 1200.0);
+        assert_eq!(update_mastery(1200.0, f64::INFINITY, false), 1200.0);
+    }
+
+    fn decay(mastery: f64, days: f64, params: &str) -> Value {
+        serde_json::from_str(&decay_mastery(mastery, days, params)).unwrap()
+    }
+
+    #[test]
+    fn test_decay_halves_above_the_floor() {
+        let fresh = decay(1400.0, 0.0, "");
+        assert_eq!(fresh["mastery"], 1400.0);
+        assert_eq!(fresh["retention"], 1.0);
+        assert_eq!(fresh["due"], false);
+        // log2(1 / 0.8) × 30 ≈ 9.66 days.
+        assert_eq!(fresh["days_until_due"], 10);
+
+        let month = decay(1400.0, 30.0, "");
+        assert_eq!(month["mastery"], 1200.0);
+        assert_eq!(month["retention"], 0.5);
+        assert_eq!(
+            (month["due"].clone(), month["days_until_due"].clone()),
+            (json!(true), json!(0))
+        );
+        assert_eq!(decay(1400.0, 9.0, "")["days_until_due"], 1);
+        assert_eq!(decay(1400.0, 9.0, "")["due"], false);
+        assert_eq!(decay(1400.0, 10.0, "")["due"], true);
+        // At or below the floor, nothing fades.
+        assert_eq!(decay(950.0, 365.0, "")["mastery"], 950.0);
+    }
+
+    #[test]
+    fn test_decay_params_and_errors() {
+        let custom = decay(1200.0, 7.0, r#"{"half_life_days":7,"floor":800}"#);
+        assert_eq!(custom["mastery"], 1000.0);
+        let strict = decay(1200.0, 0.0, r#"{"review_threshold":0.99}"#);
+        assert_eq!(strict["days_until_due"], 1);
+        assert_eq!(decay(1200.0, 1.0, "{}"), decay(1200.0, 1.0, ""));
+
+        for params in [
+            r#"{"half_life_days":0}"#,
+            r#"{"review_threshold":1}"#,
+            r#"{"floor":"low"}"#,
+            "[]",
+            "nope",
+        ] {
+            assert_eq!(
+                decay(1200.0, 1.0, params)["error"],
+                "invalid_params",
+                "{}",
+                params
+            );
+        }
+        assert_eq!(decay(f64::NAN, 1.0, "")["error"], "invalid_input");
+        assert_eq!(decay(1200.0, -1.0, "")["error"], "invalid_input");
+    }
+}
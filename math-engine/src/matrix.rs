@@ -0,0 +1,243 @@
+// ─── Matrix Operations ───────────────────────────────────────────────
+//
+// Add, subtract, multiply, and 2×2 / 3×3 determinants over exact
+// rationals. Matrices arrive as JSON arrays of rows; entries may be
+// numbers (3, 0.5) or fraction strings ("1/2"). Results name every
+// wrong entry so the UI can highlight cells instead of failing the
+// whole grid.
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::rational::Rational;
+use crate::version;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<Rational>,
+}
+
+/// Parse a JSON number or fraction string into an exact rational.
+pub(crate) fn rational_from_json(value: &Value) -> Option<Rational> {
+    match value {
+        Value::Number(n) => Rational::parse(&n.to_string()),
+        Value::String(s) => Rational::parse(s),
+        _ => None,
+    }
+}
+
+impl Matrix {
+    pub(crate) fn from_json(value: &Value) -> Option<Matrix> {
+        let rows = value.as_array()?;
+        let cols = rows.first()?.as_array()?.len();
+        if cols == 0 {
+            return None;
+        }
+        let mut data = Vec::with_capacity(rows.len() * cols);
+        for row in rows {
+            let row = row.as_array()?;
+            if row.len() != cols {
+                return None; // Ragged rows
+            }
+            for entry in row {
+                data.push(rational_from_json(entry)?);
+            }
+        }
+        Some(Matrix {
+            rows: rows.len(),
+            cols,
+            data,
+        })
+    }
+
+    fn get(&self, r: usize, c: usize) -> Rational {
+        self.data[r * self.cols + c]
+    }
+
+    fn zip_with(
+        &self,
+        other: &Matrix,
+        op: fn(Rational, Rational) -> Option<Rational>,
+    ) -> Option<Matrix> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return None;
+        }
+        let data = self
+            .data
+            .iter()
+            .zip(&other.data)
+            .map(|(&a, &b)| op(a, b))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Matrix { data, ..*self })
+    }
+
+    pub(crate) fn add(&self, other: &Matrix) -> Option<Matrix> {
+        self.zip_with(other, Rational::checked_add)
+    }
+
+    pub(crate) fn sub(&self, other: &Matrix) -> Option<Matrix> {
+        self.zip_with(other, Rational::checked_sub)
+    }
+
+    pub(crate) fn mul(&self, other: &Matrix) -> Option<Matrix> {
+        if self.cols != other.rows {
+            return None;
+        }
+        let mut data = Vec::with_capacity(self.rows * other.cols);
+        for r in 0..self.rows {
+            for c in 0..other.cols {
+                let mut sum = Rational::ZERO;
+                for k in 0..self.cols {
+                    sum = sum.checked_add(self.get(r, k).checked_mul(other.get(k, c))?)?;
+                }
+                data.push(sum);
+            }
+        }
+        Some(Matrix {
+            rows: self.rows,
+            cols: other.cols,
+            data,
+        })
+    }
+
+    /// Determinant of a 2×2 or 3×3 matrix.
+    pub(crate) fn determinant(&self) -> Option<Rational> {
+        let m = |r, c| self.get(r, c);
+        match (self.rows, self.cols) {
+            (2, 2) => m(0, 0)
+                .checked_mul(m(1, 1))?
+                .checked_sub(m(0, 1).checked_mul(m(1, 0))?),
+            (3, 3) => {
+                let minor = |c0: usize, c1: usize| {
+                    m(1, c0)
+                        .checked_mul(m(2, c1))?
+                        .checked_sub(m(1, c1).checked_mul(m(2, c0))?)
+                };
+                m(0, 0)
+                    .checked_mul(minor(1, 2)?)?
+                    .checked_sub(m(0, 1).checked_mul(minor(0, 2)?)?)?
+                    .checked_add(m(0, 2).checked_mul(minor(0, 1)?)?)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn matrix_error(error: &str) -> String {
+    version::result(json!({ "correct": false, "error": error }))
+}
+
+/// Validate a matrix operation and report wrong entries as JSON.
+///
+/// `operation`: "add" | "subtract" | "multiply" | "determinant"
+/// `operands_json`: `[A, B]` (or `[A]` for determinant)
+/// `student_json`: the student's matrix, or a scalar for determinant
+///
+/// Returns `{"correct":bool,"wrong_entries":[[row,col],…],"expected_shape":[r,c]}`
+/// or `{"correct":false,"error":"…"}`.
+#[wasm_bindgen]
+pub fn validate_matrix_operation(
+    operation: &str,
+    operands_json: &str,
+    student_json: &str,
+) -> String {
+    let Ok(Value::Array(operands)) = serde_json::from_str::<Value>(operands_json) else {
+        return matrix_error("invalid_operands");
+    };
+    let Some(operands) = operands
+        .iter()
+        .map(Matrix::from_json)
+        .collect::<Option<Vec<_>>>()
+    else {
+        return matrix_error("invalid_operands");
+    };
+    let Ok(student) = serde_json::from_str::<Value>(student_json) else {
+        return matrix_error("invalid_answer");
+    };
+
+    if operation == "determinant" {
+        let [a] = operands.as_slice() else {
+            return matrix_error("invalid_operands");
+        };
+        let Some(expected) = a.determinant() else {
+            return matrix_error("unsupported_size");
+        };
+        return match rational_from_json(&student) {
+            Some(answer) => version::result(json!({ "correct": answer == expected })),
+            None => matrix_error("invalid_answer"),
+        };
+    }
+
+    let [a, b] = operands.as_slice() else {
+        return matrix_error("invalid_operands");
+    };
+    let expected = match operation {
+        "add" => a.add(b),
+        "subtract" => a.sub(b),
+        "multiply" => a.mul(b),
+        _ => return matrix_error("unknown_operation"),
+    };
+    let Some(expected) = expected else {
+        return matrix_error("dimension_mismatch");
+    };
+    let Some(answer) = Matrix::from_json(&student) else {
+        return matrix_error("invalid_answer");
+    };
+    let shape = [expected.rows, expected.cols];
+    if answer.rows != expected.rows || answer.cols != expected.cols {
+        return version::result(
+            json!({ "correct": false, "wrong_entries": [], "expected_shape": shape, "error": "wrong_shape" }),
+        );
+    }
+
+    let wrong: Vec<[usize; 2]> = (0..expected.rows)
+        .flat_map(|r| (0..expected.cols).map(move |c| (r, c)))
+        .filter(|&(r, c)| answer.get(r, c) != expected.get(r, c))
+        .map(|(r, c)| [r, c])
+        .collect();
+
+    version::result(
+        json!({ "correct": wrong.is_empty(), "wrong_entries": wrong, "expected_shape": shape }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_multiply_reports_wrong_entries() {
+        let result = validate_matrix_operation(
+            "multiply",
+            "[[[1,2],[3,4]], [[5,6],[7,8]]]",
+            "[[19,22],[43,51]]",
+        );
+        assert!(result.contains("\"correct\":false"));
+        assert!(result.contains("\"wrong_entries\":[[1,1]]"));
+    }
+
+    #[test]
+    fn test_matrix_add_with_fractions() {
+        let result =
+            validate_matrix_operation("add", r#"[[["1/2",1]], [[0.5,"2/3"]]]"#, "[[1,\"5/3\"]]");
+        assert!(result.contains("\"correct\":true"));
+    }
+
+    #[test]
+    fn test_determinants() {
+        let result = validate_matrix_operation("determinant", "[[[1,2],[3,4]]]", "-2");
+        assert!(result.contains("\"correct\":true"));
+        let result = validate_matrix_operation("determinant", "[[[2,0,1],[1,3,2],[1,1,1]]]", "0");
+        assert!(result.contains("\"correct\":true"));
+    }
+
+    #[test]
+    fn test_matrix_errors() {
+        let result = validate_matrix_operation("multiply", "[[[1,2]], [[1,2]]]", "[[1]]");
+        assert!(result.contains("dimension_mismatch"));
+        let result = validate_matrix_operation("add", "not json", "[[1]]");
+        assert!(result.contains("invalid_operands"));
+    }
+}
@@ -0,0 +1,166 @@
+// ─── Money Arithmetic ────────────────────────────────────────────────
+//
+// Amounts are integer cents (i64). Student input such as "$3.07" is
+// parsed digit by digit — never through f64 — so 0.1 + 0.2 problems
+// are graded exactly.
+
+use wasm_bindgen::prelude::*;
+
+/// Parse a money amount into cents: "$3.07", "3.07", "$3", "3.5",
+/// "-$1.25", "1,250.00", "85¢". More than two decimals is rejected.
+pub(crate) fn parse_cents(text: &str) -> Option<i64> {
+    let mut s = text.trim();
+    let negative = s.starts_with('-');
+    if negative {
+        s = s[1..].trim_start();
+    }
+
+    if let Some(cents) = s.strip_suffix('¢').or_else(|| s.strip_suffix('c')) {
+        let cents = cents.trim();
+        if cents.is_empty() || !cents.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let value = cents.parse::<i64>().ok()?;
+        return Some(if negative { -value } else { value });
+    }
+
+    s = s.strip_prefix('$').unwrap_or(s).trim_start();
+    let (whole, frac) = match s.split_once('.') {
+        Some((w, f)) => (w, f),
+        None => (s, ""),
+    };
+    let whole: String = whole.chars().filter(|&c| c != ',').collect();
+    if whole.is_empty() && frac.is_empty() {
+        return None;
+    }
+    if !whole.bytes().all(|b| b.is_ascii_digit()) || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if frac.len() > 2 {
+        return None;
+    }
+
+    let dollars = if whole.is_empty() {
+        0
+    } else {
+        whole.parse::<i64>().ok()?
+    };
+    let cents = match frac.len() {
+        0 => 0,
+        1 => frac.parse::<i64>().ok()? * 10,
+        _ => frac.parse::<i64>().ok()?,
+    };
+    let value = dollars.checked_mul(100)?.checked_add(cents)?;
+    Some(if negative { -value } else { value })
+}
+
+/// Evaluate a sum of prices like "$3.07 + $1.95 - $0.50" in cents.
+pub(crate) fn evaluate_cents(expr: &str) -> Option<i64> {
+    let mut total = 0i64;
+    let mut term = String::new();
+    let mut sign = 1i64;
+
+    for c in expr.chars() {
+        if c == '+' || c == '-' {
+            if term.trim().is_empty() {
+                // Unary sign before the next term
+                if c == '-' {
+                    sign = -sign;
+                }
+                continue;
+            }
+            total = total.checked_add(parse_cents(&term)?.checked_mul(sign)?)?;
+            term.clear();
+            sign = if c == '-' { -1 } else { 1 };
+        } else {
+            term.push(c);
+        }
+    }
+
+    if term.trim().is_empty() {
+        return None; // Empty input or trailing operator
+    }
+    total.checked_add(parse_cents(&term)?.checked_mul(sign)?)
+}
+
+/// Validate the total of a list of prices: "$3.07 + $1.95" = "$5.02".
+#[wasm_bindgen]
+pub fn validate_money_sum(prices: &str, student_answer: &str) -> bool {
+    match (evaluate_cents(prices), parse_cents(student_answer)) {
+        (Some(expected), Some(answer)) => expected == answer,
+        _ => false,
+    }
+}
+
+/// Validate change-making: paid "$10", cost "$3.07" → "$6.93".
+/// Negative amounts paid or owed are never valid.
+#[wasm_bindgen]
+pub fn validate_change(paid: &str, cost: &str, student_answer: &str) -> bool {
+    match (
+        parse_cents(paid),
+        parse_cents(cost),
+        parse_cents(student_answer),
+    ) {
+        (Some(p), Some(c), Some(answer)) if c >= 0 && p >= c => p.checked_sub(c) == Some(answer),
+        _ => false,
+    }
+}
+
+/// Validate a unit price: "$3.60" for 4 items → "$0.90".
+/// When the total doesn't split evenly, the price rounded half-up to
+/// the nearest cent is expected.
+#[wasm_bindgen]
+pub fn validate_unit_price(total: &str, quantity: u32, student_answer: &str) -> bool {
+    if quantity == 0 {
+        return false;
+    }
+    match (parse_cents(total), parse_cents(student_answer)) {
+        (Some(t), Some(answer)) if t >= 0 => unit_price_cents(t, quantity as i64) == answer,
+        _ => false,
+    }
+}
+
+/// Integer division rounded half-up (for non-negative totals).
+fn unit_price_cents(total: i64, quantity: i64) -> i64 {
+    let (total, quantity) = (total as i128, quantity as i128);
+    ((total * 2 + quantity) / (quantity * 2)) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cents_without_floats() {
+        assert_eq!(parse_cents("$3.07"), Some(307));
+        assert_eq!(parse_cents("3.5"), Some(350));
+        assert_eq!(parse_cents("$1,250.00"), Some(125_000));
+        assert_eq!(parse_cents("85¢"), Some(85));
+        assert_eq!(parse_cents("-$1.25"), Some(-125));
+        assert_eq!(parse_cents("$3.071"), None);
+        assert_eq!(parse_cents("three"), None);
+    }
+
+    #[test]
+    fn test_money_sum_is_exact() {
+        assert!(validate_money_sum("$0.10 + $0.20", "$0.30"));
+        assert!(validate_money_sum("$3.07 + $1.95 - $0.50", "4.52"));
+        assert!(!validate_money_sum("$3.07 + $1.95", "$5.12"));
+    }
+
+    #[test]
+    fn test_change_and_unit_price() {
+        assert!(validate_change("$10", "$3.07", "$6.93"));
+        assert!(!validate_change("$1", "$3.07", "-$2.07"));
+        assert!(!validate_change(
+            "$90000000000000000",
+            "-$90000000000000000",
+            "0"
+        ));
+        assert!(!validate_change("-$1", "-$3", "$2"));
+        assert!(validate_unit_price("$3.60", 4, "$0.90"));
+        assert!(validate_unit_price("$1.00", 3, "$0.33"));
+        assert!(validate_unit_price("$0.05", 2, "$0.03"));
+        assert!(!validate_unit_price("$3.60", 0, "$0"));
+    }
+}
@@ -0,0 +1,167 @@
+// ─── MessagePack Results ─────────────────────────────────────────────
+//
+// A quiz can send thousands of results across the WASM boundary at
+// once. The `*_msgpack` variants of the result APIs return the same
+// values as their JSON counterparts, MessagePack-encoded into a
+// `Uint8Array`: smaller than the JSON text, with no JSON.parse on the
+// JS side. The frontend already decodes MessagePack (exercise packs):
+//
+//   import { decode } from "@msgpack/msgpack";
+//   const results = decode(batch_validate_msgpack(problems, answers));
+//
+// Encoding follows the MessagePack spec with the smallest form of each
+// value. Integers stay integers and floats are float 64, so a decoded
+// result equals `JSON.parse` of the JSON variant.
+
+use serde_json::Value;
+
+/// `value`, MessagePack-encoded.
+pub(crate) fn encode(value: &Value) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Null => out.push(0xc0),
+        Value::Bool(b) => out.push(if *b { 0xc3 } else { 0xc2 }),
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                write_uint(out, u);
+            } else if let Some(i) = n.as_i64() {
+                write_int(out, i);
+            } else {
+                out.push(0xcb);
+                out.extend_from_slice(&n.as_f64().unwrap_or(f64::NAN).to_be_bytes());
+            }
+        }
+        Value::String(s) => write_str(out, s),
+        Value::Array(items) => {
+            write_header(out, items.len(), 0x90, 0xdc, 0xdd);
+            for item in items {
+                write_value(out, item);
+            }
+        }
+        Value::Object(map) => {
+            write_header(out, map.len(), 0x80, 0xde, 0xdf);
+            for (key, item) in map {
+                write_str(out, key);
+                write_value(out, item);
+            }
+        }
+    }
+}
+
+fn write_uint(out: &mut Vec<u8>, u: u64) {
+    match u {
+        0..=0x7f => out.push(u as u8),
+        0x80..=0xff => out.extend_from_slice(&[0xcc, u as u8]),
+        0x100..=0xffff => {
+            out.push(0xcd);
+            out.extend_from_slice(&(u as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(0xce);
+            out.extend_from_slice(&(u as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(0xcf);
+            out.extend_from_slice(&u.to_be_bytes());
+        }
+    }
+}
+
+/// A negative integer (non-negative ones go through `write_uint`).
+fn write_int(out: &mut Vec<u8>, i: i64) {
+    if i >= -32 {
+        out.push(i as i8 as u8);
+    } else if i >= i64::from(i8::MIN) {
+        out.extend_from_slice(&[0xd0, i as i8 as u8]);
+    } else if i >= i64::from(i16::MIN) {
+        out.push(0xd1);
+        out.extend_from_slice(&(i as i16).to_be_bytes());
+    } else if i >= i64::from(i32::MIN) {
+        out.push(0xd2);
+        out.extend_from_slice(&(i as i32).to_be_bytes());
+    } else {
+        out.push(0xd3);
+        out.extend_from_slice(&i.to_be_bytes());
+    }
+}
+
+fn write_str(out: &mut Vec<u8>, s: &str) {
+    match s.len() {
+        len @ 0..=31 => out.push(0xa0 | len as u8),
+        len @ 32..=0xff => out.extend_from_slice(&[0xd9, len as u8]),
+        len => write_size(out, len, 0xda, 0xdb),
+    }
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// An array or map header: the fix form below 16 entries, else the
+/// 16- or 32-bit form.
+fn write_header(out: &mut Vec<u8>, len: usize, fix: u8, marker16: u8, marker32: u8) {
+    if len < 16 {
+        out.push(fix | len as u8);
+    } else {
+        write_size(out, len, marker16, marker32);
+    }
+}
+
+fn write_size(out: &mut Vec<u8>, len: usize, marker16: u8, marker32: u8) {
+    if len <= 0xffff {
+        out.push(marker16);
+        out.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        out.push(marker32);
+        out.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn round_trip(value: &Value) -> Value {
+        rmp_serde::from_slice(&encode(value)).unwrap()
+    }
+
+    #[test]
+    fn test_smallest_forms() {
+        assert_eq!(encode(&json!(null)), [0xc0]);
+        assert_eq!(encode(&json!(true)), [0xc3]);
+        assert_eq!(encode(&json!(7)), [0x07]);
+        assert_eq!(encode(&json!(-1)), [0xff]);
+        assert_eq!(encode(&json!(200)), [0xcc, 200]);
+        assert_eq!(encode(&json!(-200)), [0xd1, 0xff, 0x38]);
+        assert_eq!(encode(&json!("ab")), [0xa2, b'a', b'b']);
+        assert_eq!(encode(&json!([1, 2])), [0x92, 0x01, 0x02]);
+        assert_eq!(encode(&json!({"a": 1})), [0x81, 0xa1, b'a', 0x01]);
+        let mut half = vec![0xcb];
+        half.extend_from_slice(&0.5f64.to_be_bytes());
+        assert_eq!(encode(&json!(0.5)), half);
+    }
+
+    #[test]
+    fn test_round_trips_every_size_class() {
+        for value in [
+            json!([0, 127, 128, 255, 256, 65_535, 65_536, u32::MAX, u64::MAX]),
+            json!([-32, -33, -128, -129, -32_768, -32_769, i32::MIN, i64::MIN]),
+            json!([1.5, -0.25, 1e300]),
+            json!([
+                "",
+                "x".repeat(31),
+                "x".repeat(32),
+                "x".repeat(256),
+                "x".repeat(70_000)
+            ]),
+            json!((0..70_000).collect::<Vec<_>>()),
+            Value::Object((0..20).map(|i| (i.to_string(), json!(i))).collect()),
+            json!({"nested": [{"ok": true, "hint": "¡Correcto!"}, null]}),
+        ] {
+            assert_eq!(round_trip(&value), value);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// ─── Parallel Batches ────────────────────────────────────────────────
+//
+// A teacher dashboard grading a whole class runs the engine natively,
+// where the batch APIs can use every core. With the `parallel` feature
+// on a native target, `map_items` spreads items over rayon's thread
+// pool; on wasm32, or without the feature, it is a plain sequential
+// map. Results come back in input order either way.
+//
+// Hints are translated through the thread-local locale (see i18n.rs),
+// so workers grade in the caller's locale. Anything order-sensitive,
+// like summing float credits, stays with the caller.
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+use rayon::prelude::*;
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+use crate::i18n;
+
+/// `items.iter().map(f)`, collected in order, in parallel where
+/// supported.
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+pub(crate) fn map_items<T, R>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let locale = i18n::get_locale();
+    items
+        .par_iter()
+        .map(|item| i18n::with_locale(&locale, || f(item)))
+        .collect()
+}
+
+/// `items.iter().map(f)`, collected in order, in parallel where
+/// supported.
+#[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+pub(crate) fn map_items<T, R>(items: &[T], f: impl Fn(&T) -> R) -> Vec<R> {
+    items.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{batch_score, batch_validate, set_locale};
+    use serde_json::Value;
+
+    /// A class's worth of worksheets: right, wrong, near-miss, LaTeX,
+    /// and malformed items.
+    fn worksheet(len: usize) -> (String, String) {
+        let (mut problems, mut answers) = (Vec::new(), Vec::new());
+        for i in 0..len {
+            let (a, b) = (i as i64 % 97, i as i64 % 13 + 1);
+            let (problem, answer) = match i % 6 {
+                0 => (format!("{} + {}", a, b), (a + b).to_string()),
+                1 => (format!("{} * {}", a, b), (a * b + 1).to_string()),
+                2 => (format!("{} - {}", a, b), (b - a).to_string()),
+                3 => (format!(r"\frac{{{}}}{{{}}}", a, b), "x".to_string()),
+                4 => (format!("{} / 0", a), "0".to_string()),
+                _ => (format!("{} +", a), a.to_string()),
+            };
+            problems.push(problem);
+            answers.push(answer);
+        }
+        (problems.join(";"), answers.join(";"))
+    }
+
+    #[test]
+    fn test_map_items_keeps_order() {
+        let items: Vec<u64> = (0..10_000).collect();
+        let expected: Vec<u64> = items.iter().map(|n| n * n).collect();
+        assert_eq!(map_items(&items, |n| n * n), expected);
+    }
+
+    #[test]
+    fn test_batches_match_item_by_item() {
+        set_locale("th");
+        let (problems, answers) = worksheet(3_000);
+        let whole: Value = serde_json::from_str(&batch_validate(&problems, &answers)).unwrap();
+        let one_by_one: Vec<Value> = problems
+            .split(';')
+            .zip(answers.split(';'))
+            .map(|(p, a)| {
+                serde_json::from_str::<Value>(&batch_validate(p, a)).unwrap()["results"][0].clone()
+            })
+            .collect();
+        assert_eq!(whole["results"], Value::Array(one_by_one));
+        assert!(
+            whole.to_string().contains("ถูกต้อง"),
+            "workers use the caller's locale"
+        );
+
+        let scored: Value = serde_json::from_str(&batch_score(&problems, &answers)).unwrap();
+        let mut total = 0.0;
+        for (item, (p, a)) in scored["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .zip(problems.split(';').zip(answers.split(';')))
+        {
+            let single: Value = serde_json::from_str(&batch_score(p, a)).unwrap();
+            assert_eq!(item, &single["items"][0]);
+            total += single["score"].as_f64().unwrap();
+        }
+        assert_eq!(scored["score"].as_f64(), Some(total));
+        set_locale("en");
+    }
+
+    #[test]
+    fn test_batches_are_deterministic() {
+        let (problems, answers) = worksheet(5_000);
+        let (validated, scored) = (
+            batch_validate(&problems, &answers),
+            batch_score(&problems, &answers),
+        );
+        for _ in 0..20 {
+            assert_eq!(batch_validate(&problems, &answers), validated);
+            assert_eq!(batch_score(&problems, &answers), scored);
+        }
+    }
+}
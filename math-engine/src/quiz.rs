@@ -0,0 +1,432 @@
+// ─── Quiz Sessions ───────────────────────────────────────────────────
+//
+// `QuizSession` runs one quiz as a state machine:
+//
+//   ready ──start──▶ active ──finish──▶ finished
+//                    │    ▲
+//                    └────┘ answer, useHint
+//
+// While active the student works through the questions in order: a
+// right answer moves on to the next question, a wrong one may be
+// retried, and `useHint` marks the current question as helped. Each
+// transition depends only on the state and the call, and the whole
+// state round-trips through JSON, so a quiz interrupted by a closed tab
+// resumes exactly where it stopped:
+//
+//   const quiz = new QuizSession('[{"problem_type":"fraction","problem":"2/4"}]');
+//   quiz.start();
+//   quiz.answer("1/2");   // {"correct":true,…,"next":null}
+//   localStorage.quiz = quiz.toJson();
+//   QuizSession.fromJson(localStorage.quiz).finish();   // {"score":1,…}
+//
+// A call the current phase does not allow (answering before `start`,
+// finishing twice) returns {"error":"invalid_transition","phase":…}
+// and changes nothing.
+
+use std::cmp::Ordering;
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::session::Problem;
+use crate::version;
+
+/// Credit for a question solved after using a hint.
+const HINTED_CREDIT: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Ready,
+    Active,
+    Finished,
+}
+
+impl Phase {
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Ready => "ready",
+            Phase::Active => "active",
+            Phase::Finished => "finished",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Phase> {
+        [Phase::Ready, Phase::Active, Phase::Finished]
+            .into_iter()
+            .find(|phase| phase.name() == name)
+    }
+}
+
+/// One question and what the student did with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Question {
+    problem_type: String,
+    problem: String,
+    /// Every answer given, in order; only the last can be right.
+    answers: Vec<String>,
+    hints_used: u32,
+}
+
+impl Question {
+    /// `{"problem_type","problem"}`, with `"answers"` and `"hints_used"`
+    /// when restoring saved state.
+    fn from_json(item: &Value) -> Option<Question> {
+        let text = |name: &str| Some(item.get(name)?.as_str()?.to_string());
+        let answers = match item.get("answers") {
+            Some(answers) => answers
+                .as_array()?
+                .iter()
+                .map(|a| Some(a.as_str()?.to_string()))
+                .collect::<Option<_>>()?,
+            None => Vec::new(),
+        };
+        let hints_used = match item.get("hints_used") {
+            Some(hints) => u32::try_from(hints.as_u64()?).ok()?,
+            None => 0,
+        };
+        Some(Question {
+            problem_type: text("problem_type")?,
+            problem: text("problem")?,
+            answers,
+            hints_used,
+        })
+    }
+
+    fn untouched(&self) -> bool {
+        self.answers.is_empty() && self.hints_used == 0
+    }
+}
+
+/// One quiz in progress; see the module comment for its transitions.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuizSession {
+    phase: Phase,
+    questions: Vec<Question>,
+    /// The question being worked on; every earlier one is solved, and
+    /// `questions.len()` once all are.
+    current: usize,
+}
+
+#[wasm_bindgen]
+impl QuizSession {
+    /// A quiz over `problems_json`, a non-empty array of
+    /// `{"problem_type","problem"}`, ready to start. Throws
+    /// "invalid_problems" otherwise.
+    #[wasm_bindgen(constructor)]
+    pub fn new(problems_json: &str) -> Result<QuizSession, String> {
+        let invalid = || "invalid_problems".to_string();
+        let problems = serde_json::from_str::<Value>(problems_json).map_err(|_| invalid())?;
+        let questions = problems
+            .as_array()
+            .filter(|items| !items.is_empty())
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|item| {
+                Question::from_json(item)
+                    .filter(Question::untouched)
+                    .ok_or_else(invalid)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(QuizSession {
+            phase: Phase::Ready,
+            questions,
+            current: 0,
+        })
+    }
+
+    /// Restore a quiz saved with `toJson`. Throws "invalid_state" for
+    /// anything `toJson` could not have produced.
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(state_json: &str) -> Result<QuizSession, String> {
+        serde_json::from_str::<Value>(state_json)
+            .ok()
+            .and_then(|state| QuizSession::from_value(&state))
+            .ok_or_else(|| "invalid_state".to_string())
+    }
+
+    /// The whole state as JSON:
+    /// `{"phase","current","questions":[{"problem_type","problem",
+    /// "answers","hints_used"}…]}`.
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        let questions: Vec<Value> = self
+            .questions
+            .iter()
+            .map(|q| {
+                json!({
+                    "problem_type": q.problem_type,
+                    "problem": q.problem,
+                    "answers": q.answers,
+                    "hints_used": q.hints_used,
+                })
+            })
+            .collect();
+        json!({
+            "phase": self.phase.name(),
+            "current": self.current,
+            "questions": questions,
+        })
+        .to_string()
+    }
+
+    /// "ready", "active", or "finished".
+    #[wasm_bindgen(getter)]
+    pub fn phase(&self) -> String {
+        self.phase.name().to_string()
+    }
+
+    /// Index of the question to answer next, while there is one.
+    #[wasm_bindgen(getter)]
+    pub fn current(&self) -> Option<usize> {
+        (self.phase == Phase::Active && self.current < self.questions.len()).then_some(self.current)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.questions.len()
+    }
+
+    /// ready → active. Returns `{"ok":true,"current":0}`.
+    pub fn start(&mut self) -> String {
+        if self.phase != Phase::Ready {
+            return self.invalid_transition();
+        }
+        self.phase = Phase::Active;
+        version::result(json!({ "ok": true, "current": self.current }))
+    }
+
+    /// Grade `answer` to the current question, as `check_answer` does.
+    /// Returns `{"correct","hint","hint_id","misconception","attempts",
+    /// "next"}`, where `next` is the question to answer now (`null`
+    /// once every question is solved).
+    pub fn answer(&mut self, answer: &str) -> String {
+        let Some(index) = self.current() else {
+            return self.invalid_transition();
+        };
+        let question = &mut self.questions[index];
+        let attempt = Problem::new(&question.problem_type, &question.problem).check(answer);
+        question.answers.push(answer.to_string());
+        let attempts = question.answers.len();
+        if attempt.correct() {
+            self.current += 1;
+        }
+        version::result(json!({
+            "correct": attempt.correct(),
+            "hint": attempt.hint(),
+            "hint_id": attempt.hint_id(),
+            "misconception": attempt.misconception(),
+            "attempts": attempts,
+            "next": self.current(),
+        }))
+    }
+
+    /// Record that the student asked for a hint on the current
+    /// question. Returns `{"ok":true,"question","hints_used"}`.
+    #[wasm_bindgen(js_name = useHint)]
+    pub fn use_hint(&mut self) -> String {
+        let Some(index) = self.current() else {
+            return self.invalid_transition();
+        };
+        let question = &mut self.questions[index];
+        question.hints_used += 1;
+        version::result(json!({
+            "ok": true,
+            "question": index,
+            "hints_used": question.hints_used,
+        }))
+    }
+
+    /// active → finished; questions not yet solved earn nothing. A
+    /// solved question earns 1, or 0.5 if a hint was used. Returns
+    /// `{"score","max_score","questions":[{"problem_type","problem",
+    /// "solved","attempts","hints_used","credit"}…]}`.
+    pub fn finish(&mut self) -> String {
+        if self.phase != Phase::Active {
+            return self.invalid_transition();
+        }
+        self.phase = Phase::Finished;
+        let mut score = 0.0;
+        let questions: Vec<Value> = self
+            .questions
+            .iter()
+            .enumerate()
+            .map(|(i, q)| {
+                let solved = i < self.current;
+                let credit = match (solved, q.hints_used) {
+                    (false, _) => 0.0,
+                    (true, 0) => 1.0,
+                    (true, _) => HINTED_CREDIT,
+                };
+                score += credit;
+                json!({
+                    "problem_type": q.problem_type,
+                    "problem": q.problem,
+                    "solved": solved,
+                    "attempts": q.answers.len(),
+                    "hints_used": q.hints_used,
+                    "credit": credit,
+                })
+            })
+            .collect();
+        version::result(json!({
+            "score": score,
+            "max_score": self.questions.len(),
+            "questions": questions,
+        }))
+    }
+}
+
+impl QuizSession {
+    fn invalid_transition(&self) -> String {
+        version::result(json!({ "error": "invalid_transition", "phase": self.phase.name() }))
+    }
+
+    /// Saved state, if it satisfies every invariant the transitions
+    /// keep: questions before `current` solved (so answered), none
+    /// after it touched, and nothing touched before `start`.
+    fn from_value(state: &Value) -> Option<QuizSession> {
+        let phase = Phase::from_name(state.get("phase")?.as_str()?)?;
+        let current = usize::try_from(state.get("current")?.as_u64()?).ok()?;
+        let questions: Vec<Question> = state
+            .get("questions")?
+            .as_array()?
+            .iter()
+            .map(Question::from_json)
+            .collect::<Option<_>>()?;
+        let consistent = !questions.is_empty()
+            && current <= questions.len()
+            && (phase != Phase::Ready || current == 0)
+            && questions
+                .iter()
+                .enumerate()
+                .all(|(i, q)| match i.cmp(&current) {
+                    Ordering::Less => !q.answers.is_empty(),
+                    Ordering::Equal => phase != Phase::Ready || q.untouched(),
+                    Ordering::Greater => q.untouched(),
+                });
+        consistent.then_some(QuizSession {
+            phase,
+            questions,
+            current,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROBLEMS: &str = r#"[
+        {"problem_type":"arithmetic","problem":"7 * 8"},
+        {"problem_type":"fraction","problem":"2/4"}
+    ]"#;
+
+    fn parse(json: &str) -> Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_transitions() {
+        let mut quiz = QuizSession::new(PROBLEMS).unwrap();
+        assert_eq!((quiz.phase(), quiz.current()), ("ready".to_string(), None));
+        assert_eq!(parse(&quiz.answer("56"))["error"], "invalid_transition");
+        assert_eq!(parse(&quiz.start())["current"], 0);
+
+        let wrong = parse(&quiz.answer("54"));
+        assert_eq!(wrong["correct"], false);
+        assert_eq!(wrong["next"], 0);
+        assert_eq!(parse(&quiz.use_hint())["hints_used"], 1);
+        let right = parse(&quiz.answer("56"));
+        assert_eq!(
+            (right["attempts"].clone(), right["next"].clone()),
+            (json!(2), json!(1))
+        );
+        let last = parse(&quiz.answer("1/2"));
+        assert_eq!(last["next"], Value::Null);
+        assert_eq!(quiz.current(), None);
+        assert_eq!(parse(&quiz.use_hint())["error"], "invalid_transition");
+
+        let summary = parse(&quiz.finish());
+        assert_eq!(summary["score"], 1.5);
+        assert_eq!(summary["max_score"], 2);
+        assert_eq!(summary["questions"][0]["credit"], HINTED_CREDIT);
+        assert_eq!(quiz.phase(), "finished");
+        assert_eq!(
+            parse(&quiz.finish()),
+            json!({"error":"invalid_transition","phase":"finished","result_schema_version":2})
+        );
+        assert_eq!(parse(&quiz.start())["error"], "invalid_transition");
+    }
+
+    #[test]
+    fn test_finishing_early() {
+        let mut quiz = QuizSession::new(PROBLEMS).unwrap();
+        quiz.start();
+        quiz.answer("55");
+        let summary = parse(&quiz.finish());
+        assert_eq!(summary["score"], 0.0);
+        assert_eq!(summary["questions"][0]["attempts"], 1);
+        assert_eq!(summary["questions"][1]["solved"], false);
+    }
+
+    #[test]
+    fn test_resume_exactly() {
+        let mut quiz = QuizSession::new(PROBLEMS).unwrap();
+        assert_eq!(QuizSession::from_json(&quiz.to_json()).unwrap(), quiz);
+        quiz.start();
+        quiz.answer("56");
+        quiz.use_hint();
+        let mut resumed = QuizSession::from_json(&quiz.to_json()).unwrap();
+        assert_eq!(resumed, quiz);
+        assert_eq!(resumed.answer("1/2"), quiz.answer("1/2"));
+        assert_eq!(resumed.finish(), quiz.finish());
+        assert_eq!(QuizSession::from_json(&quiz.to_json()).unwrap(), quiz);
+    }
+
+    #[test]
+    fn test_rejected_input() {
+        for problems in ["[]", "{}", "not json", r#"[{"problem":"2 + 2"}]"#] {
+            let error = QuizSession::new(problems).unwrap_err();
+            assert_eq!(error, "invalid_problems", "{}", problems);
+        }
+        let q = |answers: &str| {
+            format!(
+                r#"{{"problem_type":"arithmetic","problem":"1 + 1","answers":{}}}"#,
+                answers
+            )
+        };
+        for state in [
+            // Solved questions were answered; later ones are untouched.
+            format!(
+                r#"{{"phase":"active","current":1,"questions":[{}]}}"#,
+                q("[]")
+            ),
+            format!(
+                r#"{{"phase":"active","current":0,"questions":[{},{}]}}"#,
+                q("[]"),
+                q(r#"["2"]"#)
+            ),
+            format!(
+                r#"{{"phase":"ready","current":0,"questions":[{}]}}"#,
+                q(r#"["3"]"#)
+            ),
+            format!(
+                r#"{{"phase":"active","current":2,"questions":[{}]}}"#,
+                q(r#"["2"]"#)
+            ),
+            format!(
+                r#"{{"phase":"paused","current":0,"questions":[{}]}}"#,
+                q("[]")
+            ),
+            r#"{"phase":"active","current":0,"questions":[]}"#.to_string(),
+        ] {
+            assert_eq!(
+                QuizSession::from_json(&state).unwrap_err(),
+                "invalid_state",
+                "{}",
+                state
+            );
+        }
+    }
+}
@@ -0,0 +1,176 @@
+// ─── Exact Rational Numbers ──────────────────────────────────────────
+//
+// The arithmetic itself lives in math-core (`math_core::Rational`,
+// re-exported here for the rest of the engine). This module exports it
+// to JS as the `Rational` class, so islands can do exact fraction math
+// without round-tripping strings:
+//
+//   const sum = Rational.parse("1/2").add(new Rational(1n, 3n)); // 5/6
+//   sum.toExactDecimal(); // "0.8(3)"
+
+use wasm_bindgen::prelude::*;
+
+pub use math_core::Rational;
+
+/// `Rational` as seen from JS.
+#[wasm_bindgen(js_name = Rational)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JsRational(Rational);
+
+impl From<Rational> for JsRational {
+    fn from(value: Rational) -> JsRational {
+        JsRational(value)
+    }
+}
+
+fn overflow() -> String {
+    "overflow".to_string()
+}
+
+/// The JS-facing API. Values are always stored reduced; errors are
+/// "division_by_zero", "overflow", or "invalid_number".
+#[wasm_bindgen(js_class = Rational)]
+impl JsRational {
+    #[wasm_bindgen(constructor)]
+    pub fn from_parts(num: i64, den: i64) -> Result<JsRational, String> {
+        if den == 0 {
+            return Err("division_by_zero".to_string());
+        }
+        Rational::new(num, den).map(JsRational).ok_or_else(overflow)
+    }
+
+    /// Parse "7", "-3/4", "0.75", or "2 1/2".
+    #[wasm_bindgen(js_name = parse)]
+    pub fn parse_text(text: &str) -> Result<JsRational, String> {
+        Rational::parse(text)
+            .map(JsRational)
+            .ok_or_else(|| "invalid_number".to_string())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn numerator(&self) -> i64 {
+        self.0.numer()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn denominator(&self) -> i64 {
+        self.0.denom()
+    }
+
+    pub fn add(&self, other: &JsRational) -> Result<JsRational, String> {
+        self.0
+            .checked_add(other.0)
+            .map(JsRational)
+            .ok_or_else(overflow)
+    }
+
+    pub fn sub(&self, other: &JsRational) -> Result<JsRational, String> {
+        self.0
+            .checked_sub(other.0)
+            .map(JsRational)
+            .ok_or_else(overflow)
+    }
+
+    pub fn mul(&self, other: &JsRational) -> Result<JsRational, String> {
+        self.0
+            .checked_mul(other.0)
+            .map(JsRational)
+            .ok_or_else(overflow)
+    }
+
+    pub fn div(&self, other: &JsRational) -> Result<JsRational, String> {
+        if other.0 == Rational::ZERO {
+            return Err("division_by_zero".to_string());
+        }
+        self.0
+            .checked_div(other.0)
+            .map(JsRational)
+            .ok_or_else(overflow)
+    }
+
+    /// Lowest terms. Every `Rational` is already reduced, so this is a
+    /// copy; it exists so code written for unreduced fractions reads
+    /// naturally.
+    pub fn simplify(&self) -> JsRational {
+        *self
+    }
+
+    /// -1, 0, or 1 as `self` is less than, equal to, or greater than
+    /// `other`.
+    pub fn compare(&self, other: &JsRational) -> i32 {
+        self.0.cmp(&other.0) as i32
+    }
+
+    pub fn equals(&self, other: &JsRational) -> bool {
+        self == other
+    }
+
+    /// "3/4", or "2" for whole numbers.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_fraction_string(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// Nearest float; for display only.
+    #[wasm_bindgen(js_name = toNumber)]
+    pub fn to_number(&self) -> f64 {
+        self.0.to_f64()
+    }
+
+    /// Decimal text rounded to `places`, as `to_decimal_string`.
+    #[wasm_bindgen(js_name = toDecimal)]
+    pub fn to_decimal(&self, places: u32) -> Result<String, String> {
+        self.0.to_decimal_string(places).ok_or_else(overflow)
+    }
+
+    /// The exact decimal expansion, with any repeating block in
+    /// parentheses: 3/8 → "0.375", 1/3 → "0.(3)", -7/6 → "-1.1(6)".
+    /// Fails with "overflow" past `math_core::rational::MAX_DECIMAL_DIGITS`
+    /// digits.
+    #[wasm_bindgen(js_name = toExactDecimal)]
+    pub fn to_exact_decimal(&self) -> Result<String, String> {
+        self.0.to_exact_decimal().ok_or_else(overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_js_api() {
+        let half = JsRational::parse_text("0.5").unwrap();
+        let third = JsRational::from_parts(2, 6).unwrap();
+        assert_eq!((third.numerator(), third.denominator()), (1, 3));
+        assert_eq!(half.add(&third).unwrap().to_fraction_string(), "5/6");
+        assert_eq!(half.sub(&third).unwrap().to_fraction_string(), "1/6");
+        assert_eq!(half.mul(&third).unwrap().to_fraction_string(), "1/6");
+        assert_eq!(half.div(&third).unwrap().to_fraction_string(), "3/2");
+        assert_eq!((half.compare(&third), third.compare(&half)), (1, -1));
+        assert!(half.equals(&JsRational::from_parts(-3, -6).unwrap().simplify()));
+
+        assert_eq!(
+            half.div(&Rational::ZERO.into()),
+            Err("division_by_zero".to_string())
+        );
+        assert_eq!(
+            JsRational::from_parts(1, 0),
+            Err("division_by_zero".to_string())
+        );
+        assert_eq!(
+            JsRational::parse_text("x"),
+            Err("invalid_number".to_string())
+        );
+        let max = JsRational::from(Rational::from_integer(i64::MAX));
+        assert_eq!(max.add(&max), Err("overflow".to_string()));
+    }
+
+    #[test]
+    fn test_exact_decimal() {
+        let decimal = |n, d| JsRational::from_parts(n, d).unwrap().to_exact_decimal();
+        assert_eq!(decimal(3, 8).as_deref(), Ok("0.375"));
+        assert_eq!(decimal(1, 3).as_deref(), Ok("0.(3)"));
+        assert_eq!(decimal(-7, 6).as_deref(), Ok("-1.1(6)"));
+        assert_eq!(decimal(1, 1_000_003), Err("overflow".to_string()));
+    }
+}
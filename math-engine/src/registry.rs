@@ -0,0 +1,617 @@
+// ─── Validator Registry ──────────────────────────────────────────────
+//
+// `check_answer` dispatches through a registry of `ProblemValidator`s
+// keyed by problem type. Built-in types are compiled in; content packs
+// add their own at runtime with `register_custom_type`, describing a
+// formula and how to compare answers — no WASM rebuild needed.
+//
+// Custom type spec (JSON):
+//   {
+//     "name": "rectangle_area",
+//     "formula": "l * w",
+//     "answer": { "mode": "exact" },          // or "rounded" + "places",
+//                                             // or "tolerance" + "tolerance"
+//     "hint": "Multiply the length by the width.",
+//     "hint_id": "area.rectangle"             // optional; default "custom.<name>"
+//   }
+// Problems for a custom type list the formula's variables: "l = 3, w = 4/5".
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::budget::{Budget, BudgetExceeded};
+use crate::compare;
+use crate::expr::{self, Expr};
+use crate::hints::{self, Hint, HintKind};
+use crate::latex;
+use crate::rational::Rational;
+use crate::validators;
+use crate::version;
+
+/// Whether the answer is correct, plus the hint shown to the student
+/// and its stable catalog ID. `misconception` names the error pattern a
+/// chosen multiple-choice distractor represents.
+///
+/// The rest describes the hint as data (see `hint_data`), so the UI can
+/// render it interactively — e.g. highlight the operands it refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Verdict {
+    pub correct: bool,
+    pub hint_id: String,
+    pub hint: String,
+    pub misconception: Option<String>,
+    pub kind: HintKind,
+    /// Which part of the answer (list item, chosen option) the hint is
+    /// about, 0-based.
+    pub step_index: Option<usize>,
+    /// Parts of the problem the hint refers to.
+    pub operands: Vec<String>,
+    /// Placeholder values the hint text was rendered with.
+    pub params: Vec<(String, String)>,
+}
+
+impl Verdict {
+    pub(crate) fn new(correct: bool, hint: &Hint, args: &[(&str, &str)]) -> Verdict {
+        Verdict {
+            correct,
+            hint_id: hint.id.to_string(),
+            hint: hint.render(args),
+            misconception: None,
+            kind: hint.kind(),
+            step_index: None,
+            operands: Vec::new(),
+            params: args
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    /// `CORRECT` when right, otherwise `hint`.
+    pub(crate) fn graded(correct: bool, hint: &Hint, args: &[(&str, &str)]) -> Verdict {
+        if correct {
+            Verdict::new(true, &hints::CORRECT, &[])
+        } else {
+            Verdict::new(false, hint, args)
+        }
+    }
+
+    pub(crate) fn with_operands<S: AsRef<str>>(mut self, operands: &[S]) -> Verdict {
+        self.operands = operands
+            .iter()
+            .map(|o| o.as_ref().trim().to_string())
+            .collect();
+        self
+    }
+
+    pub(crate) fn at_step(mut self, index: usize) -> Verdict {
+        self.step_index = Some(index);
+        self
+    }
+
+    /// The hint as structured data:
+    /// `{"kind","step_index","referenced_operands","template_id","params"}`.
+    /// `template_id` is the hint ID; `params` fills its placeholders.
+    pub(crate) fn hint_data(&self) -> Value {
+        let params: serde_json::Map<String, Value> = self
+            .params
+            .iter()
+            .map(|(name, value)| (name.clone(), Value::from(value.as_str())))
+            .collect();
+        json!({
+            "kind": self.kind.code(),
+            "step_index": self.step_index,
+            "referenced_operands": self.operands,
+            "template_id": self.hint_id,
+            "params": params,
+        })
+    }
+}
+
+/// Grades one problem type.
+pub(crate) trait ProblemValidator {
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict;
+
+    /// Grade again by an independent code path (floating point for
+    /// exact validators, exact for float ones), for dual-path grading.
+    /// `None` if the type has no second path or it cannot grade this
+    /// input.
+    fn cross_check(&self, _problem: &str, _student_answer: &str) -> Option<bool> {
+        None
+    }
+}
+
+pub(crate) struct Registry {
+    validators: BTreeMap<String, Box<dyn ProblemValidator>>,
+    /// Source spec of every custom type, for `export_state`.
+    custom_specs: BTreeMap<String, String>,
+}
+
+pub(crate) const BUILTIN_TYPES: [&str; 12] = [
+    "arithmetic",
+    "fraction",
+    "elapsed_time",
+    "time_addition",
+    "money",
+    "unit_price",
+    "temperature",
+    "multiple_choice",
+    "true_false",
+    "comparison",
+    "list",
+    "fill_blank",
+];
+
+impl Registry {
+    fn with_builtins() -> Registry {
+        let mut registry = Registry {
+            validators: BTreeMap::new(),
+            custom_specs: BTreeMap::new(),
+        };
+        registry.insert("arithmetic", Box::new(validators::Arithmetic));
+        registry.insert("fraction", Box::new(validators::Fraction));
+        registry.insert("elapsed_time", Box::new(validators::ElapsedTime));
+        registry.insert("time_addition", Box::new(validators::TimeAddition));
+        registry.insert("money", Box::new(validators::Money));
+        registry.insert("unit_price", Box::new(validators::UnitPrice));
+        registry.insert("temperature", Box::new(validators::Temperature));
+        registry.insert("multiple_choice", Box::new(validators::MultipleChoice));
+        registry.insert("true_false", Box::new(validators::TrueFalse));
+        registry.insert("comparison", Box::new(validators::Comparison));
+        registry.insert("list", Box::new(validators::ListAnswer));
+        registry.insert("fill_blank", Box::new(validators::FillBlank));
+        registry
+    }
+
+    fn insert(&mut self, problem_type: &str, validator: Box<dyn ProblemValidator>) {
+        self.validators.insert(problem_type.to_string(), validator);
+    }
+
+    fn check(&self, problem_type: &str, problem: &str, student_answer: &str) -> Option<Verdict> {
+        let (problem, student_answer) = (latex::to_plain(problem), latex::to_plain(student_answer));
+        self.validators
+            .get(problem_type)
+            .map(|v| v.check(&problem, &student_answer))
+    }
+
+    fn check_both(
+        &self,
+        problem_type: &str,
+        problem: &str,
+        student_answer: &str,
+    ) -> Option<(Verdict, Option<bool>)> {
+        let (problem, student_answer) = (latex::to_plain(problem), latex::to_plain(student_answer));
+        self.validators.get(problem_type).map(|v| {
+            (
+                v.check(&problem, &student_answer),
+                v.cross_check(&problem, &student_answer),
+            )
+        })
+    }
+}
+
+thread_local! {
+    // WASM is single-threaded; one registry per engine instance.
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry::with_builtins());
+}
+
+/// Grade `student_answer` with the validator registered for
+/// `problem_type`; `None` if no such type exists.
+pub(crate) fn check_registered(
+    problem_type: &str,
+    problem: &str,
+    student_answer: &str,
+) -> Option<Verdict> {
+    REGISTRY.with(|r| r.borrow().check(problem_type, problem, student_answer))
+}
+
+/// Register a validator under `problem_type` directly, so tests can
+/// grade with hand-built validators.
+#[cfg(test)]
+pub(crate) fn register_validator(problem_type: &str, validator: Box<dyn ProblemValidator>) {
+    REGISTRY.with(|r| r.borrow_mut().insert(problem_type, validator));
+}
+
+/// Like `check_registered`, also returning the validator's independent
+/// second grading (see `ProblemValidator::cross_check`).
+pub(crate) fn check_registered_both(
+    problem_type: &str,
+    problem: &str,
+    student_answer: &str,
+) -> Option<(Verdict, Option<bool>)> {
+    REGISTRY.with(|r| r.borrow().check_both(problem_type, problem, student_answer))
+}
+
+// ─── Formula-driven custom types ─────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AnswerMode {
+    /// Exact rational equality ("3/4" = "0.75").
+    Exact,
+    /// Exact, or the expected value rounded to `places` decimals.
+    Rounded(u32),
+    /// Within an absolute tolerance; allows irrational results.
+    Tolerance(f64),
+}
+
+struct FormulaValidator {
+    formula: Expr,
+    mode: AnswerMode,
+    /// Pack-supplied `(hint_id, hint)`; `None` uses `CUSTOM_SUBSTITUTE`
+    /// in the current locale.
+    hint: Option<(String, String)>,
+}
+
+/// Parse "l = 3, w = 4/5" (',' or ';' separated) into variable values.
+fn parse_bindings(problem: &str) -> Option<BTreeMap<String, Rational>> {
+    let mut bindings = BTreeMap::new();
+    for pair in problem.split([',', ';']).filter(|p| !p.trim().is_empty()) {
+        let (name, value) = pair.split_once('=')?;
+        bindings.insert(name.trim().to_string(), Rational::parse(value)?);
+    }
+    Some(bindings)
+}
+
+impl FormulaValidator {
+    fn is_correct(
+        &self,
+        bindings: &BTreeMap<String, Rational>,
+        student_answer: &str,
+    ) -> Result<bool, BudgetExceeded> {
+        let lookup = |name: &str| bindings.get(name).copied();
+        let mut budget = Budget::default();
+        match self.mode {
+            AnswerMode::Exact | AnswerMode::Rounded(_) => {
+                let (Some(expected), Some(answer)) = (
+                    self.formula.try_eval_rational(&lookup, &mut budget)?,
+                    Rational::parse(student_answer),
+                ) else {
+                    return Ok(false);
+                };
+                Ok(match self.mode {
+                    AnswerMode::Rounded(places) => {
+                        answer == expected || expected.round_to(places) == Some(answer)
+                    }
+                    _ => answer == expected,
+                })
+            }
+            AnswerMode::Tolerance(tolerance) => {
+                let expected = self
+                    .formula
+                    .try_eval_f64(&|name| lookup(name).map(|r| r.to_f64()), &mut budget)?;
+                let answer = Rational::parse(student_answer).map(|r| r.to_f64());
+                Ok(match (expected, answer) {
+                    (Some(e), Some(a)) => (e - a).abs() <= tolerance,
+                    _ => false,
+                })
+            }
+        }
+    }
+}
+
+impl FormulaValidator {
+    /// `is_correct` by the other arithmetic: floating point for exact
+    /// and rounded answers, exact for tolerance answers. `None` when
+    /// that path cannot evaluate (e.g. an irrational exact result).
+    fn is_correct_other_path(
+        &self,
+        bindings: &BTreeMap<String, Rational>,
+        student_answer: &str,
+    ) -> Option<bool> {
+        let lookup = |name: &str| bindings.get(name).copied();
+        let mut budget = Budget::default();
+        let answer = Rational::parse(student_answer)?;
+        match self.mode {
+            AnswerMode::Exact | AnswerMode::Rounded(_) => {
+                let expected = self
+                    .formula
+                    .try_eval_f64(&|name| lookup(name).map(|r| r.to_f64()), &mut budget)
+                    .ok()??;
+                let same = |value: f64| compare::float_ordering(value, answer.to_f64()).is_eq();
+                Some(match self.mode {
+                    // Rounded: a whole number of 10^-places units, at
+                    // most half a unit from the expected value.
+                    AnswerMode::Rounded(places) => {
+                        let scale = 10f64.powi(places as i32);
+                        let units = answer.to_f64() * scale;
+                        same(expected)
+                            || (compare::float_ordering(units, units.round()).is_eq()
+                                && (expected * scale - units).abs() <= 0.5 + 1e-9)
+                    }
+                    _ => same(expected),
+                })
+            }
+            AnswerMode::Tolerance(tolerance) => {
+                let expected = self
+                    .formula
+                    .try_eval_rational(&lookup, &mut budget)
+                    .ok()??;
+                let error = expected.checked_sub(answer)?.to_f64().abs();
+                Some(error <= tolerance)
+            }
+        }
+    }
+}
+
+impl ProblemValidator for FormulaValidator {
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        let Some(bindings) = parse_bindings(problem) else {
+            return Verdict::new(false, &hints::INVALID_FORMAT, &[]);
+        };
+        if self
+            .formula
+            .variables()
+            .iter()
+            .any(|v| !bindings.contains_key(v))
+        {
+            return Verdict::new(false, &hints::INVALID_FORMAT, &[]);
+        }
+        let operands: Vec<String> = bindings
+            .iter()
+            .map(|(name, value)| format!("{name} = {value}"))
+            .collect();
+        let verdict = match self.is_correct(&bindings, student_answer) {
+            Ok(true) => Verdict::new(true, &hints::CORRECT, &[]),
+            Ok(false) => match &self.hint {
+                Some((hint_id, hint)) => Verdict {
+                    hint_id: hint_id.clone(),
+                    hint: hint.clone(),
+                    kind: HintKind::Strategy,
+                    ..Verdict::new(false, &hints::CUSTOM_SUBSTITUTE, &[])
+                },
+                None => Verdict::new(false, &hints::CUSTOM_SUBSTITUTE, &[]),
+            },
+            Err(BudgetExceeded) => return Verdict::new(false, &hints::BUDGET_EXCEEDED, &[]),
+        };
+        verdict.with_operands(&operands)
+    }
+
+    fn cross_check(&self, problem: &str, student_answer: &str) -> Option<bool> {
+        let bindings = parse_bindings(problem)?;
+        self.is_correct_other_path(&bindings, student_answer)
+    }
+}
+
+fn parse_answer_mode(spec: Option<&Value>) -> Result<AnswerMode, String> {
+    let Some(spec) = spec else {
+        return Ok(AnswerMode::Exact);
+    };
+    match spec.get("mode").and_then(Value::as_str) {
+        None | Some("exact") => Ok(AnswerMode::Exact),
+        Some("rounded") => spec
+            .get("places")
+            .and_then(Value::as_u64)
+            .filter(|&p| p <= 9)
+            .map(|p| AnswerMode::Rounded(p as u32))
+            .ok_or_else(|| "rounded answers need \"places\" (0-9)".to_string()),
+        Some("tolerance") => spec
+            .get("tolerance")
+            .and_then(Value::as_f64)
+            .filter(|t| t.is_finite() && *t >= 0.0)
+            .map(AnswerMode::Tolerance)
+            .ok_or_else(|| "tolerance answers need a non-negative \"tolerance\"".to_string()),
+        Some(other) => Err(format!("unknown answer mode: {}", other)),
+    }
+}
+
+fn is_valid_type_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+}
+
+fn build_from_spec(spec_json: &str) -> Result<(String, FormulaValidator), String> {
+    let spec: Value = serde_json::from_str(spec_json).map_err(|_| "invalid JSON".to_string())?;
+    let name = spec
+        .get("name")
+        .and_then(Value::as_str)
+        .filter(|n| is_valid_type_name(n))
+        .ok_or_else(|| "\"name\" must be lowercase letters, digits, or '_'".to_string())?;
+    if BUILTIN_TYPES.contains(&name) {
+        return Err(format!("cannot replace built-in type: {}", name));
+    }
+    let formula_text = spec
+        .get("formula")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "missing \"formula\"".to_string())?;
+    let formula = expr::parse(formula_text).map_err(|e| {
+        if e.is_budget_exceeded() {
+            "formula is too complex (operation budget exceeded)".to_string()
+        } else {
+            format!("formula: {}", e)
+        }
+    })?;
+    let mode = parse_answer_mode(spec.get("answer"))?;
+    // Pack-supplied hints get a namespaced ID unless the pack names one.
+    let hint = spec.get("hint").and_then(Value::as_str).map(|text| {
+        let hint_id = spec
+            .get("hint_id")
+            .and_then(Value::as_str)
+            .map_or_else(|| format!("custom.{}", name), str::to_string);
+        (hint_id, text.to_string())
+    });
+
+    let validator = FormulaValidator {
+        formula,
+        mode,
+        hint,
+    };
+    Ok((name.to_string(), validator))
+}
+
+fn register_from_spec(spec_json: &str) -> Result<String, String> {
+    let (name, validator) = build_from_spec(spec_json)?;
+    REGISTRY.with(|r| {
+        let mut registry = r.borrow_mut();
+        registry.insert(&name, Box::new(validator));
+        registry
+            .custom_specs
+            .insert(name.clone(), spec_json.to_string());
+    });
+    Ok(name)
+}
+
+/// Specs of all registered custom types, in name order.
+pub(crate) fn custom_type_specs() -> Vec<String> {
+    REGISTRY.with(|r| r.borrow().custom_specs.values().cloned().collect())
+}
+
+/// Replace every custom type with `specs`. All specs are checked
+/// first; on any error the registry is left unchanged.
+pub(crate) fn replace_custom_types(specs: &[String]) -> Result<usize, String> {
+    let mut registry = Registry::with_builtins();
+    for spec in specs {
+        let (name, validator) = build_from_spec(spec)?;
+        registry.insert(&name, Box::new(validator));
+        registry.custom_specs.insert(name, spec.clone());
+    }
+    let count = registry.custom_specs.len();
+    REGISTRY.with(|r| *r.borrow_mut() = registry);
+    Ok(count)
+}
+
+/// Every registered problem type, in name order:
+/// `[{"type":"arithmetic","builtin":true},…]`.
+pub(crate) fn capabilities() -> Value {
+    REGISTRY.with(|r| {
+        r.borrow()
+            .validators
+            .keys()
+            .map(|name| json!({ "type": name, "builtin": BUILTIN_TYPES.contains(&name.as_str()) }))
+            .collect()
+    })
+}
+
+/// The problem types `check_answer` can grade, built-in and custom,
+/// as `{"types":[{"type","builtin"}…]}` in name order.
+#[wasm_bindgen]
+pub fn supported_types() -> String {
+    version::result(version::list("types", capabilities()))
+}
+
+/// Register (or replace) a formula-driven problem type from a content
+/// pack. Returns `{"ok":true,"type":...}` or `{"ok":false,"error":...}`.
+/// Built-in types cannot be replaced.
+#[wasm_bindgen]
+pub fn register_custom_type(spec_json: &str) -> String {
+    match register_from_spec(spec_json) {
+        Ok(name) => version::result(json!({ "ok": true, "type": name })),
+        Err(error) => version::result(json!({ "ok": false, "error": error })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtins_are_registered() {
+        for problem_type in BUILTIN_TYPES {
+            assert!(check_registered(problem_type, "", "").is_some());
+        }
+        assert!(check_registered("no_such_type", "", "").is_none());
+        let types = capabilities();
+        let types = types.as_array().unwrap();
+        assert!(types.contains(&json!({ "type": "fraction", "builtin": true })));
+        assert!(types.len() >= BUILTIN_TYPES.len());
+    }
+
+    #[test]
+    fn test_hint_data() {
+        let verdict = check_registered("comparison", "3/4 ? 2/3", "<").unwrap();
+        assert_eq!(
+            verdict.hint_data(),
+            json!({
+                "kind": "strategy",
+                "step_index": null,
+                "referenced_operands": ["3/4", "2/3"],
+                "template_id": "comparison.common_denominator",
+                "params": {},
+            })
+        );
+        let problem =
+            r#"{"options":["72",{"text":"62","misconception":"forgotten_carry"}],"correct":0}"#;
+        let data = check_registered("multiple_choice", problem, "B")
+            .unwrap()
+            .hint_data();
+        assert_eq!(data["kind"], "misconception");
+        assert_eq!(data["step_index"], 1);
+        assert_eq!(data["referenced_operands"], json!(["62"]));
+        let data = check_registered("list", r#"{"items":["1","2"],"ordered":true}"#, "1, 3")
+            .unwrap()
+            .hint_data();
+        assert_eq!(data["step_index"], 1);
+        assert_eq!(data["referenced_operands"], json!(["3"]));
+        let data = check_registered("fill_blank", "__ + __ = 10", "3")
+            .unwrap()
+            .hint_data();
+        assert_eq!(data["kind"], "input");
+        assert_eq!(data["params"], json!({ "count": "2" }));
+    }
+
+    #[test]
+    fn test_custom_formula_type() {
+        let result = register_custom_type(
+            r#"{"name":"test_area","formula":"l * w","hint":"Multiply l by w."}"#,
+        );
+        assert_eq!(
+            result,
+            r#"{"ok":true,"result_schema_version":2,"type":"test_area"}"#
+        );
+        assert!(supported_types().contains(r#"{"builtin":false,"type":"test_area"}"#));
+        let verdict = check_registered("test_area", "l = 3, w = 4/5", "2.4").unwrap();
+        assert_eq!(
+            (verdict.correct, verdict.hint_id.as_str()),
+            (true, "correct")
+        );
+        let verdict = check_registered("test_area", "l = 3, w = 4/5", "12/4").unwrap();
+        assert_eq!(verdict.hint_id, "custom.test_area");
+        assert_eq!(verdict.hint, "Multiply l by w.");
+        assert_eq!(
+            check_registered("test_area", "l = 3", "3").map(|v| v.correct),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_custom_answer_modes() {
+        register_custom_type(
+            r#"{"name":"test_hyp","formula":"sqrt(a^2 + b^2)","answer":{"mode":"tolerance","tolerance":0.01}}"#,
+        );
+        assert_eq!(
+            check_registered("test_hyp", "a=1, b=1", "1.414").map(|v| v.correct),
+            Some(true)
+        );
+        register_custom_type(
+            r#"{"name":"test_third","formula":"x / 3","answer":{"mode":"rounded","places":2}}"#,
+        );
+        assert_eq!(
+            check_registered("test_third", "x=1", "0.33").map(|v| v.correct),
+            Some(true)
+        );
+        assert_eq!(
+            check_registered("test_third", "x=1", "1/3").map(|v| v.correct),
+            Some(true)
+        );
+        assert_eq!(
+            check_registered("test_third", "x=1", "0.3").map(|v| v.correct),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_register_rejects_bad_specs() {
+        assert!(register_custom_type(r#"{"name":"money","formula":"x"}"#).contains("built-in"));
+        assert!(
+            register_custom_type(r#"{"name":"Bad Name","formula":"x"}"#).contains("\"ok\":false")
+        );
+        assert!(register_custom_type(r#"{"name":"t","formula":"2 +"}"#).contains("formula"));
+        assert!(register_custom_type("not json").contains("invalid JSON"));
+        let huge = format!(r#"{{"name":"t","formula":"{}"}}"#, "(".repeat(5_000));
+        assert!(register_custom_type(&huge).contains("too complex"));
+    }
+}
@@ -0,0 +1,354 @@
+// ─── Expression Rendering ────────────────────────────────────────────
+//
+// Turns a parsed expression back into LaTeX or MathML for display, so
+// the UI shows exactly what the engine understood: if a student types
+// "1/2x" and sees ½x instead of 1/(2x), the mismatch is visible before
+// they submit.
+//
+// Output mirrors the parse tree: quotients become fractions, and
+// parentheses appear wherever the tree groups differently from plain
+// left-to-right reading (including around negative right operands, as
+// in 3 − (−2)). Statements ("x = 2", "3/4 > 2/3") render each side with
+// the relation between them, like `format_canonical`.
+
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+use crate::compare::{self, Relation};
+use crate::expr::{self, BinOp, Expr, Func};
+use crate::latex;
+use crate::version;
+
+/// Binding strength, loosest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Precedence {
+    Sum,
+    Product,
+    Negation,
+    Power,
+    Atom,
+}
+
+pub(crate) fn precedence(expr: &Expr) -> Precedence {
+    match expr {
+        Expr::Bin(BinOp::Add | BinOp::Sub, _, _) => Precedence::Sum,
+        Expr::Bin(BinOp::Mul, _, _) => Precedence::Product,
+        // Drawn as a fraction bar, which groups by itself.
+        Expr::Bin(BinOp::Div, _, _) => Precedence::Atom,
+        Expr::Neg(_) => Precedence::Negation,
+        Expr::Bin(BinOp::Pow, _, _) => Precedence::Power,
+        Expr::Num(_) | Expr::Var(_) | Expr::Call(_, _) => Precedence::Atom,
+    }
+}
+
+/// Does the expression start with a letter or radical when written out,
+/// so a number before it can be juxtaposed ("2x", "3√2")?
+pub(crate) fn starts_with_letter(expr: &Expr) -> bool {
+    match expr {
+        Expr::Var(_) | Expr::Call(_, _) => true,
+        Expr::Bin(BinOp::Pow | BinOp::Mul, base, _) => starts_with_letter(base),
+        _ => false,
+    }
+}
+
+/// One output notation.
+trait Notation {
+    fn number(&self, text: &str) -> String;
+    fn variable(&self, name: &str) -> String;
+    fn group(&self, inner: String) -> String;
+    /// Operator between two already rendered operands.
+    fn infix(&self, op: BinOp, left: String, right: String, implicit: bool) -> String;
+    fn negate(&self, inner: String) -> String;
+    fn call(&self, func: Func, arg: String) -> String;
+    /// A "?" or "__" side of a statement.
+    fn blank(&self) -> String;
+    /// Relation between the two rendered sides of a statement.
+    fn relation(&self, relation: Relation, left: String, right: String) -> String;
+    fn wrap(&self, body: String) -> String;
+}
+
+struct Latex;
+
+impl Notation for Latex {
+    fn number(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    fn variable(&self, name: &str) -> String {
+        match name {
+            "pi" => "\\pi".to_string(),
+            _ if name.chars().count() == 1 => name.to_string(),
+            _ => format!("\\mathrm{{{}}}", name),
+        }
+    }
+
+    fn group(&self, inner: String) -> String {
+        format!("\\left({}\\right)", inner)
+    }
+
+    fn infix(&self, op: BinOp, left: String, right: String, implicit: bool) -> String {
+        match op {
+            BinOp::Add => format!("{} + {}", left, right),
+            BinOp::Sub => format!("{} - {}", left, right),
+            BinOp::Mul if implicit => format!("{}{}", left, right),
+            BinOp::Mul => format!("{} \\times {}", left, right),
+            BinOp::Div => format!("\\frac{{{}}}{{{}}}", left, right),
+            BinOp::Pow => format!("{}^{{{}}}", left, right),
+        }
+    }
+
+    fn negate(&self, inner: String) -> String {
+        format!("-{}", inner)
+    }
+
+    fn call(&self, func: Func, arg: String) -> String {
+        match func {
+            Func::Sqrt => format!("\\sqrt{{{}}}", arg),
+            Func::Abs => format!("\\left|{}\\right|", arg),
+        }
+    }
+
+    fn blank(&self) -> String {
+        "?".to_string()
+    }
+
+    fn relation(&self, relation: Relation, left: String, right: String) -> String {
+        let symbol = match relation {
+            Relation::Less => "<",
+            Relation::LessOrEqual => "\\le",
+            Relation::Equal => "=",
+            Relation::NotEqual => "\\ne",
+            Relation::GreaterOrEqual => "\\ge",
+            Relation::Greater => ">",
+        };
+        format!("{} {} {}", left, symbol, right)
+    }
+
+    fn wrap(&self, body: String) -> String {
+        body
+    }
+}
+
+struct MathMl;
+
+impl Notation for MathMl {
+    fn number(&self, text: &str) -> String {
+        format!("<mn>{}</mn>", text)
+    }
+
+    fn variable(&self, name: &str) -> String {
+        match name {
+            "pi" => "<mi>π</mi>".to_string(),
+            _ => format!("<mi>{}</mi>", name),
+        }
+    }
+
+    fn group(&self, inner: String) -> String {
+        format!("<mrow><mo>(</mo>{}<mo>)</mo></mrow>", inner)
+    }
+
+    fn infix(&self, op: BinOp, left: String, right: String, implicit: bool) -> String {
+        let operator = match op {
+            BinOp::Div => return format!("<mfrac>{}{}</mfrac>", left, right),
+            BinOp::Pow => return format!("<msup>{}{}</msup>", left, right),
+            BinOp::Add => "+",
+            BinOp::Sub => "−",
+            // U+2062 INVISIBLE TIMES: read as multiplication, not drawn.
+            BinOp::Mul if implicit => "\u{2062}",
+            BinOp::Mul => "×",
+        };
+        format!("<mrow>{}<mo>{}</mo>{}</mrow>", left, operator, right)
+    }
+
+    fn negate(&self, inner: String) -> String {
+        format!("<mrow><mo>−</mo>{}</mrow>", inner)
+    }
+
+    fn call(&self, func: Func, arg: String) -> String {
+        match func {
+            Func::Sqrt => format!("<msqrt>{}</msqrt>", arg),
+            Func::Abs => format!("<mrow><mo>|</mo>{}<mo>|</mo></mrow>", arg),
+        }
+    }
+
+    fn blank(&self) -> String {
+        "<mi>?</mi>".to_string()
+    }
+
+    fn relation(&self, relation: Relation, left: String, right: String) -> String {
+        let symbol = match relation {
+            Relation::Less => "&lt;",
+            Relation::LessOrEqual => "≤",
+            Relation::Equal => "=",
+            Relation::NotEqual => "≠",
+            Relation::GreaterOrEqual => "≥",
+            Relation::Greater => "&gt;",
+        };
+        format!("<mrow>{}<mo>{}</mo>{}</mrow>", left, symbol, right)
+    }
+
+    fn wrap(&self, body: String) -> String {
+        format!(
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\">{}</math>",
+            body
+        )
+    }
+}
+
+fn render(expr: &Expr, notation: &dyn Notation) -> String {
+    match expr {
+        Expr::Num(n) if n.is_integer() => notation.number(&n.to_string()),
+        // Non-integer literals come from decimals ("0.75"), which are
+        // always terminating.
+        Expr::Num(n) => notation.number(&n.to_exact_decimal().unwrap_or_else(|| n.to_string())),
+        Expr::Var(name) => notation.variable(name),
+        Expr::Neg(inner) => {
+            let operand = render(inner, notation);
+            if precedence(inner) < Precedence::Negation {
+                notation.negate(notation.group(operand))
+            } else {
+                notation.negate(operand)
+            }
+        }
+        Expr::Call(func, arg) => notation.call(*func, render(arg, notation)),
+        Expr::Bin(op, left, right) => {
+            let (l, r) = (render(left, notation), render(right, notation));
+            // The fraction bar and the raised exponent group by
+            // themselves; other operators need parentheses for operands
+            // that bind more loosely.
+            let (group_left, group_right) = match op {
+                BinOp::Div => (false, false),
+                BinOp::Pow => (precedence(left) < Precedence::Atom, false),
+                _ => (
+                    precedence(left) < precedence(expr),
+                    precedence(right) <= precedence(expr) || matches!(**right, Expr::Neg(_)),
+                ),
+            };
+            // "2x", "3√2", "3(x + 1)"
+            let implicit = *op == BinOp::Mul
+                && matches!(**left, Expr::Num(_))
+                && (group_right || starts_with_letter(right));
+            let l = if group_left { notation.group(l) } else { l };
+            let r = if group_right { notation.group(r) } else { r };
+            notation.infix(*op, l, r, implicit)
+        }
+    }
+}
+
+/// One side of a statement; "?" and "__" blanks render as a blank.
+fn render_side(text: &str, notation: &dyn Notation) -> Result<String, expr::ParseError> {
+    let text = text.trim();
+    if text == "?" || (!text.is_empty() && text.chars().all(|c| c == '_')) {
+        return Ok(notation.blank());
+    }
+    Ok(render(&expr::parse(text)?, notation))
+}
+
+/// Render an expression or statement as LaTeX or MathML.
+///
+/// `expr` uses the engine's syntax (LaTeX input is accepted too) and may
+/// be a statement such as "x = 2" or "x <= 3"; `format` is "latex" or
+/// "mathml". Returns `{"format","output"}`, or
+/// `{"error":"invalid_format"}` /
+/// `{"error":"parse_error","message","position"}`.
+#[wasm_bindgen]
+pub fn render_expression(expr: &str, format: &str) -> String {
+    let notation: &dyn Notation = match format {
+        "latex" => &Latex,
+        "mathml" => &MathMl,
+        _ => return version::result(json!({ "error": "invalid_format" })),
+    };
+    let plain = latex::to_plain(expr);
+    let rendered = match compare::split_statement(&plain) {
+        Some((left, relation, right)) => render_side(left, notation)
+            .and_then(|l| Ok(notation.relation(relation, l, render_side(right, notation)?))),
+        None => render_side(&plain, notation),
+    };
+    version::result(match rendered {
+        Ok(body) => json!({
+            "format": format,
+            "output": notation.wrap(body),
+        }),
+        Err(error) => json!({
+            "error": "parse_error",
+            "message": error.message,
+            "position": error.position,
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn output(expr: &str, format: &str) -> String {
+        let result: Value = serde_json::from_str(&render_expression(expr, format)).unwrap();
+        result["output"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_latex() {
+        let cases = [
+            ("1/2 + 3/4", "\\frac{1}{2} + \\frac{3}{4}"),
+            ("2x^2 - 3(x + 1)", "2x^{2} - 3\\left(x + 1\\right)"),
+            ("(1 + 2)/(3 - x)", "\\frac{1 + 2}{3 - x}"),
+            ("-2^2", "-2^{2}"),
+            ("(-2)^2", "\\left(-2\\right)^{2}"),
+            ("3 - -2", "3 - \\left(-2\\right)"),
+            ("8 - (3 - 1)", "8 - \\left(3 - 1\\right)"),
+            ("7 * 8", "7 \\times 8"),
+            ("2√3 + abs(x)", "2\\sqrt{3} + \\left|x\\right|"),
+            ("0.75 pi r", "0.75\\pi \\times r"),
+            ("speed * 2", "\\mathrm{speed} \\times 2"),
+        ];
+        for (expr, latex) in cases {
+            assert_eq!(output(expr, "latex"), latex, "{}", expr);
+        }
+    }
+
+    #[test]
+    fn test_mathml() {
+        assert_eq!(
+            output("1/2 + x^2", "mathml"),
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow>\
+             <mfrac><mn>1</mn><mn>2</mn></mfrac><mo>+</mo>\
+             <msup><mi>x</mi><mn>2</mn></msup></mrow></math>"
+        );
+        assert!(output("2x", "mathml").contains("<mo>\u{2062}</mo>"));
+        assert!(output("sqrt(9) - 1", "mathml").contains("<msqrt><mn>9</mn></msqrt><mo>−</mo>"));
+    }
+
+    #[test]
+    fn test_statements() {
+        assert_eq!(output("x = 2", "latex"), "x = 2");
+        assert_eq!(output("x <= 3", "latex"), "x \\le 3");
+        assert_eq!(
+            output("1/2 != 2/3", "latex"),
+            "\\frac{1}{2} \\ne \\frac{2}{3}"
+        );
+        assert_eq!(
+            output("2/3 + 1/2 = ?", "latex"),
+            "\\frac{2}{3} + \\frac{1}{2} = ?"
+        );
+        assert_eq!(
+            output("x = 2", "mathml"),
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\">\
+             <mrow><mi>x</mi><mo>=</mo><mn>2</mn></mrow></math>"
+        );
+        assert!(output("x <= 3", "mathml").contains("<mi>x</mi><mo>≤</mo><mn>3</mn>"));
+        assert!(output("3/4 > 2/3", "mathml").contains("</mfrac><mo>&gt;</mo><mfrac>"));
+        assert!(output("x < 1", "mathml").contains("<mo>&lt;</mo>"));
+        assert!(render_expression("x = 2 +", "latex").contains("parse_error"));
+    }
+
+    #[test]
+    fn test_latex_round_trip_and_errors() {
+        assert_eq!(output(r"\frac{1}{2}", "latex"), "\\frac{1}{2}");
+        assert_eq!(
+            render_expression("2 +", "latex"),
+            r#"{"error":"parse_error","message":"unexpected end of input","position":3,"result_schema_version":2}"#
+        );
+        assert!(render_expression("1", "svg").contains("invalid_format"));
+    }
+}
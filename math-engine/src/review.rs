@@ -0,0 +1,179 @@
+// ─── Spaced Repetition ───────────────────────────────────────────────
+//
+// SM-2 review scheduling. After each review the student's recall is
+// graded 0–5; a good grade pushes the next review further out (1 day,
+// 6 days, then the last interval × the card's ease), a failed one
+// starts the card over tomorrow. The ease drifts with every grade, so
+// hard cards come back more often.
+//
+// The card state is plain JSON the caller stores between reviews.
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::version;
+
+/// Ease of a new card.
+const INITIAL_EASE: f64 = 2.5;
+
+/// SM-2's floor on the ease.
+const MIN_EASE: f64 = 1.3;
+
+/// Lowest grade that counts as recalled.
+const PASSING_QUALITY: u8 = 3;
+
+/// One card's scheduling state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Card {
+    /// Successful reviews in a row.
+    repetitions: u32,
+    /// Days from the last review to the next.
+    interval: u32,
+    ease: f64,
+}
+
+impl Default for Card {
+    fn default() -> Self {
+        Card {
+            repetitions: 0,
+            interval: 0,
+            ease: INITIAL_EASE,
+        }
+    }
+}
+
+impl Card {
+    /// `{"repetitions","interval","ease"}`; missing fields take the
+    /// new-card values.
+    fn from_json(state: &Value) -> Option<Card> {
+        let fields = state.as_object()?;
+        let count = |name: &str| {
+            fields
+                .get(name)
+                .map_or(Some(0), |v| u32::try_from(v.as_u64()?).ok())
+        };
+        let ease = match fields.get("ease") {
+            Some(ease) => ease.as_f64().filter(|e| *e >= MIN_EASE)?,
+            None => INITIAL_EASE,
+        };
+        Some(Card {
+            repetitions: count("repetitions")?,
+            interval: count("interval")?,
+            ease,
+        })
+    }
+
+    /// The state after a review graded `quality` (0–5), `elapsed_days`
+    /// after the previous one.
+    fn review(self, quality: u8, elapsed_days: u32) -> Card {
+        let miss = f64::from(5 - quality);
+        let ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+        let ease = (ease * 100.0).round() / 100.0;
+
+        if quality < PASSING_QUALITY {
+            return Card {
+                repetitions: 0,
+                interval: 1,
+                ease,
+            };
+        }
+        // Remembered after a late review: the card held longer than
+        // scheduled, so grow from the time that actually passed.
+        let held = self.interval.max(elapsed_days);
+        let interval = match self.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => (f64::from(held) * ease).round().min(f64::from(u32::MAX)) as u32,
+        };
+        Card {
+            repetitions: self.repetitions.saturating_add(1),
+            interval,
+            ease,
+        }
+    }
+}
+
+/// Schedule a card's next review with SM-2.
+///
+/// `card_state_json` is the state returned by the previous call, or
+/// empty for a new card. `quality` grades the recall: 5 perfect, 4
+/// after hesitation, 3 with difficulty, 0–2 not recalled.
+/// `elapsed_days` is the time since the previous review.
+///
+/// Returns `{"repetitions","interval","ease"}` — store it as the new
+/// card state; the next review is due `interval` days from now.
+/// Errors give `{"error":"invalid_card"}` or
+/// `{"error":"invalid_quality"}`.
+#[wasm_bindgen]
+pub fn schedule_review(card_state_json: &str, quality: u8, elapsed_days: u32) -> String {
+    if quality > 5 {
+        return version::result(json!({ "error": "invalid_quality" }));
+    }
+    let card = if card_state_json.trim().is_empty() {
+        Some(Card::default())
+    } else {
+        serde_json::from_str::<Value>(card_state_json)
+            .ok()
+            .and_then(|state| Card::from_json(&state))
+    };
+    let Some(card) = card else {
+        return version::result(json!({ "error": "invalid_card" }));
+    };
+    let next = card.review(quality, elapsed_days);
+    version::result(json!({
+        "repetitions": next.repetitions,
+        "interval": next.interval,
+        "ease": next.ease,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review(state: &str, quality: u8, elapsed: u32) -> Value {
+        serde_json::from_str(&schedule_review(state, quality, elapsed)).unwrap()
+    }
+
+    #[test]
+    fn test_sm2_intervals() {
+        let first = review("", 5, 0);
+        assert_eq!(
+            first,
+            json!({"repetitions":1,"interval":1,"ease":2.6,"result_schema_version":2})
+        );
+        let second = review(&first.to_string(), 4, 1);
+        assert_eq!(
+            second,
+            json!({"repetitions":2,"interval":6,"ease":2.6,"result_schema_version":2})
+        );
+        let third = review(&second.to_string(), 3, 6);
+        // ease 2.6 - 0.14 = 2.46; 6 × 2.46 = 14.76
+        assert_eq!(
+            third,
+            json!({"repetitions":3,"interval":15,"ease":2.46,"result_schema_version":2})
+        );
+
+        // Reviewed 20 days late and still recalled: 35 × 2.56, not 15 × 2.56.
+        let late = review(&third.to_string(), 5, 35);
+        assert_eq!(late["interval"], 90);
+    }
+
+    #[test]
+    fn test_lapse_and_ease_floor() {
+        let lapse = review(r#"{"repetitions":4,"interval":30,"ease":1.4}"#, 0, 30);
+        assert_eq!(
+            lapse,
+            json!({"repetitions":0,"interval":1,"ease":1.3,"result_schema_version":2})
+        );
+        assert_eq!(review("{}", 2, 0)["interval"], 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(schedule_review("", 6, 0).contains("invalid_quality"));
+        assert!(schedule_review("[]", 3, 0).contains("invalid_card"));
+        assert!(schedule_review(r#"{"ease":0.5}"#, 3, 0).contains("invalid_card"));
+        assert!(schedule_review(r#"{"interval":-1}"#, 3, 0).contains("invalid_card"));
+    }
+}
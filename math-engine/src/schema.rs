@@ -0,0 +1,432 @@
+// ─── Exercise File Validation ────────────────────────────────────────
+//
+// Lesson authors write exercises as JSON:
+//
+//   {"exercises":[
+//     {"id":"add-1","type":"arithmetic","problem":"27 + 45","answer":"72",
+//      "tolerance":0,"params":{"carry":true},"hints":["Add the ones first."]}]}
+//
+// `validate_exercises` reports every problem in the file at once, each
+// with the field's path and line, and grades each answer key with its
+// own validator — so a typo is caught before content ships, not by a
+// student marked wrong for a right answer.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::{json, Map, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::registry;
+use crate::version;
+
+/// Fields an exercise may have.
+const KNOWN_FIELDS: [&str; 7] = [
+    "id",
+    "type",
+    "problem",
+    "answer",
+    "tolerance",
+    "params",
+    "hints",
+];
+
+/// What is wrong with a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Problem {
+    MissingField,
+    WrongType,
+    Empty,
+    DuplicateId,
+    UnknownType,
+    NegativeTolerance,
+    AnswerKeyRejected,
+    /// A warning: the field is ignored.
+    UnknownField,
+}
+
+impl Problem {
+    fn code(self) -> &'static str {
+        match self {
+            Problem::MissingField => "missing_field",
+            Problem::WrongType => "wrong_type",
+            Problem::Empty => "empty",
+            Problem::DuplicateId => "duplicate_id",
+            Problem::UnknownType => "unknown_type",
+            Problem::NegativeTolerance => "negative_tolerance",
+            Problem::AnswerKeyRejected => "answer_key_rejected",
+            Problem::UnknownField => "unknown_field",
+        }
+    }
+
+    fn severity(self) -> &'static str {
+        match self {
+            Problem::UnknownField => "warning",
+            _ => "error",
+        }
+    }
+}
+
+/// Line (1-based) of every member and element, by path
+/// ("exercises[2].hints[0]"). Only run on text serde_json accepted.
+struct LineIndex<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    line: usize,
+    lines: BTreeMap<String, usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn build(source: &'a str) -> BTreeMap<String, usize> {
+        let mut index = LineIndex {
+            bytes: source.as_bytes(),
+            pos: 0,
+            line: 1,
+            lines: BTreeMap::new(),
+        };
+        index.value(String::new());
+        index.lines
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(byte) = self.peek().filter(u8::is_ascii_whitespace) {
+            if byte == b'\n' {
+                self.line += 1;
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Consume a string literal and return it decoded.
+    fn string(&mut self) -> String {
+        let start = self.pos;
+        self.pos += 1;
+        while let Some(byte) = self.peek() {
+            self.pos += 1;
+            match byte {
+                b'\\' => self.pos += 1,
+                b'"' => break,
+                _ => {}
+            }
+        }
+        let literal = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap_or("\"\"");
+        serde_json::from_str(literal).unwrap_or_default()
+    }
+
+    fn value(&mut self, path: String) {
+        self.skip_whitespace();
+        let line = self.line;
+        match self.peek() {
+            Some(b'{') => {
+                self.pos += 1;
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() != Some(b'"') {
+                        self.pos += 1; // '}'
+                        break;
+                    }
+                    let key_line = self.line;
+                    let key = self.string();
+                    let child = if path.is_empty() {
+                        key
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    self.lines.insert(child.clone(), key_line);
+                    self.skip_whitespace();
+                    self.pos += 1; // ':'
+                    self.value(child);
+                    self.skip_whitespace();
+                    if self.peek() == Some(b',') {
+                        self.pos += 1;
+                    }
+                }
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                let mut index = 0;
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() == Some(b']') {
+                        self.pos += 1;
+                        break;
+                    }
+                    self.value(format!("{path}[{index}]"));
+                    index += 1;
+                    self.skip_whitespace();
+                    if self.peek() == Some(b',') {
+                        self.pos += 1;
+                    }
+                }
+            }
+            Some(b'"') => {
+                self.string();
+            }
+            Some(_) => {
+                while self
+                    .peek()
+                    .is_some_and(|b| !b.is_ascii_whitespace() && !b",]}".contains(&b))
+                {
+                    self.pos += 1;
+                }
+            }
+            None => {}
+        }
+        self.lines.entry(path).or_insert(line);
+    }
+}
+
+/// Line of `path`, or of its nearest ancestor present in the file
+/// (a missing field is reported on its exercise's line).
+fn line_of(lines: &BTreeMap<String, usize>, path: &str) -> usize {
+    let mut path = path;
+    loop {
+        if let Some(&line) = lines.get(path) {
+            return line;
+        }
+        match path.rfind(['.', '[']) {
+            Some(cut) => path = &path[..cut],
+            None => return 1,
+        }
+    }
+}
+
+/// Problems with one exercise, as (field, problem) pairs.
+fn check_exercise(
+    exercise: &Map<String, Value>,
+    seen_ids: &mut BTreeSet<String>,
+) -> Vec<(String, Problem)> {
+    let mut found = Vec::new();
+    // Required, non-empty strings.
+    let mut text = |field: &str| match exercise.get(field) {
+        None => {
+            found.push((field.to_string(), Problem::MissingField));
+            None
+        }
+        Some(Value::String(s)) if s.trim().is_empty() => {
+            found.push((field.to_string(), Problem::Empty));
+            None
+        }
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(_) => {
+            found.push((field.to_string(), Problem::WrongType));
+            None
+        }
+    };
+    let (id, problem_type, problem, answer) =
+        (text("id"), text("type"), text("problem"), text("answer"));
+
+    if let Some(id) = id {
+        if !seen_ids.insert(id) {
+            found.push(("id".to_string(), Problem::DuplicateId));
+        }
+    }
+    match exercise.get("tolerance").map(Value::as_f64) {
+        Some(None) => found.push(("tolerance".to_string(), Problem::WrongType)),
+        Some(Some(t)) if t < 0.0 => {
+            found.push(("tolerance".to_string(), Problem::NegativeTolerance))
+        }
+        _ => {}
+    }
+    if exercise.get("params").is_some_and(|p| !p.is_object()) {
+        found.push(("params".to_string(), Problem::WrongType));
+    }
+    match exercise.get("hints") {
+        Some(Value::Array(hints)) => {
+            for (i, hint) in hints.iter().enumerate() {
+                match hint.as_str() {
+                    None => found.push((format!("hints[{i}]"), Problem::WrongType)),
+                    Some(h) if h.trim().is_empty() => {
+                        found.push((format!("hints[{i}]"), Problem::Empty))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        Some(_) => found.push(("hints".to_string(), Problem::WrongType)),
+        None => {}
+    }
+    for field in exercise.keys() {
+        if !KNOWN_FIELDS.contains(&field.as_str()) {
+            found.push((field.clone(), Problem::UnknownField));
+        }
+    }
+
+    // The answer key must satisfy its own validator.
+    if let (Some(problem_type), Some(problem), Some(answer)) = (problem_type, problem, answer) {
+        match registry::check_registered(&problem_type, &problem, &answer) {
+            None => found.push(("type".to_string(), Problem::UnknownType)),
+            Some(verdict) if !verdict.correct => {
+                found.push(("answer".to_string(), Problem::AnswerKeyRejected))
+            }
+            Some(_) => {}
+        }
+    }
+    found
+}
+
+/// Validate an exercise file before it ships.
+///
+/// `source` is the file's JSON text (see the module comment for the
+/// format). Returns `{"valid","exercises","diagnostics":[…]}` where each
+/// diagnostic is `{"severity","code","path","line"}` — e.g.
+/// `{"severity":"error","code":"answer_key_rejected",
+///   "path":"exercises[3].answer","line":12}`. `valid` is false when
+/// any diagnostic is an error; warnings ("unknown_field") only flag
+/// fields that will be ignored.
+///
+/// Error codes: "syntax" (with `column` and `message`), "missing_field",
+/// "wrong_type", "empty", "duplicate_id", "unknown_type",
+/// "negative_tolerance", "answer_key_rejected".
+#[wasm_bindgen]
+pub fn validate_exercises(source: &str) -> String {
+    let file: Value = match serde_json::from_str(source) {
+        Ok(file) => file,
+        Err(err) => {
+            return version::result(json!({
+                "valid": false,
+                "exercises": 0,
+                "diagnostics": [{
+                    "severity": "error",
+                    "code": "syntax",
+                    "path": "",
+                    "line": err.line(),
+                    "column": err.column(),
+                    "message": err.to_string(),
+                }],
+            }));
+        }
+    };
+
+    let mut found: Vec<(String, Problem)> = Vec::new();
+    let exercises = match file.get("exercises") {
+        Some(Value::Array(exercises)) => exercises.as_slice(),
+        Some(_) => {
+            found.push(("exercises".to_string(), Problem::WrongType));
+            &[]
+        }
+        None => {
+            found.push(("exercises".to_string(), Problem::MissingField));
+            &[]
+        }
+    };
+    let mut seen_ids = BTreeSet::new();
+    for (i, exercise) in exercises.iter().enumerate() {
+        let path = format!("exercises[{i}]");
+        match exercise.as_object() {
+            Some(fields) => found.extend(
+                check_exercise(fields, &mut seen_ids)
+                    .into_iter()
+                    .map(|(field, problem)| (format!("{path}.{field}"), problem)),
+            ),
+            None => found.push((path, Problem::WrongType)),
+        }
+    }
+
+    let lines = if found.is_empty() {
+        BTreeMap::new()
+    } else {
+        LineIndex::build(source)
+    };
+    let valid = found
+        .iter()
+        .all(|(_, problem)| problem.severity() != "error");
+    let diagnostics: Vec<Value> = found
+        .iter()
+        .map(|(path, problem)| {
+            json!({
+                "severity": problem.severity(),
+                "code": problem.code(),
+                "path": path,
+                "line": line_of(&lines, path),
+            })
+        })
+        .collect();
+    version::result(json!({
+        "valid": valid,
+        "exercises": exercises.len(),
+        "diagnostics": diagnostics,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(source: &str) -> Value {
+        serde_json::from_str(&validate_exercises(source)).unwrap()
+    }
+
+    #[test]
+    fn test_valid_file() {
+        let source = r#"{"exercises":[
+            {"id":"add-1","type":"arithmetic","problem":"27 + 45","answer":"72",
+             "hints":["Add the ones first."],"params":{"carry":true}},
+            {"id":"cmp-1","type":"comparison","problem":"1/2 ? 2/3","answer":"<","tolerance":0}
+        ]}"#;
+        assert_eq!(
+            validate(source),
+            json!({"valid":true,"exercises":2,"diagnostics":[],"result_schema_version":2})
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_have_paths_and_lines() {
+        let source = r#"{
+  "exercises": [
+    {"id": "a", "type": "arithmetic", "problem": "2 + 2", "answer": "5"},
+    {"id": "a", "type": "geometry", "problem": "x", "answer": "y",
+     "tolerance": -1,
+     "hints": ["ok", 3],
+     "level": 2},
+    {"type": "arithmetic", "problem": "", "answer": 4}
+  ]
+}"#;
+        let report = validate(source);
+        assert_eq!(report["valid"], false);
+        let found: Vec<(String, String, u64)> = report["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| {
+                (
+                    d["path"].as_str().unwrap().to_string(),
+                    d["code"].as_str().unwrap().to_string(),
+                    d["line"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        let expected = [
+            ("exercises[0].answer", "answer_key_rejected", 3),
+            ("exercises[1].id", "duplicate_id", 4),
+            ("exercises[1].tolerance", "negative_tolerance", 5),
+            ("exercises[1].hints[1]", "wrong_type", 6),
+            ("exercises[1].level", "unknown_field", 7),
+            ("exercises[1].type", "unknown_type", 4),
+            ("exercises[2].id", "missing_field", 8),
+            ("exercises[2].problem", "empty", 8),
+            ("exercises[2].answer", "wrong_type", 8),
+        ];
+        let expected: Vec<(String, String, u64)> = expected
+            .iter()
+            .map(|(p, c, l)| (p.to_string(), c.to_string(), *l))
+            .collect();
+        assert_eq!(found, expected);
+        assert_eq!(report["diagnostics"][4]["severity"], "warning");
+    }
+
+    #[test]
+    fn test_syntax_and_shape_errors() {
+        let report = validate("{\n  \"exercises\": [\n    {\"id\": }\n  ]\n}");
+        assert_eq!(report["diagnostics"][0]["code"], "syntax");
+        assert_eq!(report["diagnostics"][0]["line"], 3);
+        assert_eq!(validate("{}")["diagnostics"][0]["code"], "missing_field");
+        assert_eq!(
+            validate(r#"{"exercises":[1]}"#)["diagnostics"][0]["path"],
+            "exercises[0]"
+        );
+    }
+}
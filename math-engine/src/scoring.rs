@@ -0,0 +1,575 @@
+// ─── Scoring ─────────────────────────────────────────────────────────
+//
+// Batch scoring for arithmetic worksheets. A right answer earns full
+// credit; a wrong one earns partial credit when the misconception
+// classifier recognises it as a near miss — the right magnitude with
+// the wrong sign, the right digits in the wrong place — and nothing
+// when it points to the wrong method or no known pattern.
+//
+// Quiz scoring weights each item by its own weight and its difficulty,
+// totals per topic, and maps the percentage to a letter grade. The
+// difficulty multipliers and grade boundaries form a rubric the school
+// can replace.
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::compare::exact_value;
+use crate::distractors::classify_error;
+use crate::msgpack;
+use crate::parallel;
+use crate::rational::Rational;
+use crate::validate_arithmetic;
+use crate::version;
+
+/// Credit for a wrong answer explained by `misconception`.
+pub(crate) fn partial_credit(misconception: &str) -> f64 {
+    match misconception {
+        // Right magnitude, wrong sign; right digits, wrong place value.
+        "sign_flip" | "place_value" => 0.5,
+        // Right method, one slip in carrying out the steps.
+        "off_by_one" | "off_by_one_group" | "forgotten_carry" | "borrow_error" => 0.25,
+        // Wrong operation or operand order: the method itself is wrong.
+        _ => 0.0,
+    }
+}
+
+/// Credit for one problem, and the misconception behind a wrong answer.
+pub(crate) fn score_item(problem: &str, answer: &str) -> (f64, Option<&'static str>) {
+    let correct = answer
+        .parse::<f64>()
+        .is_ok_and(|value| validate_arithmetic(problem, value));
+    if correct {
+        return (1.0, None);
+    }
+    let misconception = exact_value(problem)
+        .zip(Rational::parse(answer))
+        .and_then(|(expected, given)| classify_error(problem, expected, given));
+    (misconception.map_or(0.0, partial_credit), misconception)
+}
+
+/// Score a batch of arithmetic problems with partial credit.
+///
+/// `problems` and `answers` are ';'-separated, as for `batch_validate`.
+/// Returns `{"score","max_score","items":[{"problem","answer","correct",
+/// "credit","misconception"}…]}`, where `misconception` is `null` unless
+/// the classifier recognised a wrong answer. Lists of different lengths
+/// give `{"error":"length_mismatch"}`.
+#[wasm_bindgen]
+pub fn batch_score(problems: &str, answers: &str) -> String {
+    version::result(score_batch(problems, answers))
+}
+
+/// `batch_score`'s result, MessagePack-encoded (see msgpack.rs).
+#[wasm_bindgen]
+pub fn batch_score_msgpack(problems: &str, answers: &str) -> Vec<u8> {
+    msgpack::encode(&version::stamp(score_batch(problems, answers)))
+}
+
+fn score_batch(problems: &str, answers: &str) -> Value {
+    let problems: Vec<&str> = problems.split(';').map(str::trim).collect();
+    let answers: Vec<&str> = answers.split(';').map(str::trim).collect();
+    if problems.len() != answers.len() {
+        return json!({ "error": "length_mismatch" });
+    }
+
+    let pairs: Vec<(&str, &str)> = problems.into_iter().zip(answers).collect();
+    let graded = parallel::map_items(&pairs, |&(problem, answer)| {
+        let (credit, misconception) = score_item(problem, answer);
+        let item = json!({
+            "problem": problem,
+            "answer": answer,
+            "correct": credit == 1.0,
+            "credit": credit,
+            "misconception": misconception,
+        });
+        (credit, item)
+    });
+    // Summed in input order, so the float total never depends on threads.
+    let score = graded.iter().fold(0.0, |score, (credit, _)| score + credit);
+    let items: Vec<Value> = graded.into_iter().map(|(_, item)| item).collect();
+    json!({
+        "score": score,
+        "max_score": items.len(),
+        "items": items,
+    })
+}
+
+// ─── Weighted Quiz Scoring ───────────────────────────────────────────
+
+/// Difficulty multipliers and grade boundaries for `score_quiz`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Rubric {
+    /// Difficulty name → weight multiplier.
+    difficulty: Vec<(String, f64)>,
+    /// (grade, minimum percentage), highest minimum first.
+    grades: Vec<(String, f64)>,
+}
+
+impl Default for Rubric {
+    fn default() -> Self {
+        let pairs = |list: &[(&str, f64)]| {
+            list.iter()
+                .map(|(name, value)| (name.to_string(), *value))
+                .collect()
+        };
+        Rubric {
+            difficulty: pairs(&[("easy", 1.0), ("medium", 1.5), ("hard", 2.0)]),
+            grades: pairs(&[
+                ("A", 90.0),
+                ("B", 80.0),
+                ("C", 70.0),
+                ("D", 60.0),
+                ("F", 0.0),
+            ]),
+        }
+    }
+}
+
+impl Rubric {
+    /// `{"difficulty":{"easy":1,…},"grades":[{"grade":"A","min":90},…]}`;
+    /// a missing part keeps its default.
+    fn from_json(spec: &Value) -> Option<Rubric> {
+        let mut rubric = Rubric::default();
+        if let Some(difficulty) = spec.get("difficulty") {
+            rubric.difficulty = difficulty
+                .as_object()?
+                .iter()
+                .map(|(name, m)| Some((name.clone(), m.as_f64().filter(|m| *m >= 0.0)?)))
+                .collect::<Option<_>>()?;
+        }
+        if let Some(grades) = spec.get("grades") {
+            let mut grades: Vec<(String, f64)> = grades
+                .as_array()?
+                .iter()
+                .map(|g| {
+                    Some((
+                        g.get("grade")?.as_str()?.to_string(),
+                        g.get("min")?.as_f64()?,
+                    ))
+                })
+                .collect::<Option<_>>()?;
+            if grades.is_empty() {
+                return None;
+            }
+            grades.sort_by(|a, b| b.1.total_cmp(&a.1));
+            rubric.grades = grades;
+        }
+        Some(rubric)
+    }
+
+    fn multiplier(&self, difficulty: &str) -> Option<f64> {
+        self.difficulty
+            .iter()
+            .find(|(name, _)| name == difficulty)
+            .map(|(_, m)| *m)
+    }
+
+    /// The grade for `percentage`; below every minimum, the lowest grade.
+    fn grade(&self, percentage: f64) -> &str {
+        let (lowest, _) = self.grades.last().expect("rubric has grades");
+        self.grades
+            .iter()
+            .find(|(_, min)| percentage >= *min)
+            .map_or(lowest, |(grade, _)| grade)
+    }
+}
+
+/// One scored quiz item.
+#[derive(Debug, Clone, PartialEq)]
+struct QuizItem {
+    topic: String,
+    /// Weight × difficulty multiplier.
+    weight: f64,
+    /// Earned fraction of the weight, 0–1.
+    credit: f64,
+}
+
+impl QuizItem {
+    /// `{"topic","weight","difficulty","credit"}` or `"correct":bool`
+    /// instead of `credit`. Weight defaults to 1, difficulty to "medium".
+    fn from_json(item: &Value, rubric: &Rubric) -> Option<QuizItem> {
+        let weight = item.get("weight").map_or(Some(1.0), Value::as_f64)?;
+        let difficulty = item
+            .get("difficulty")
+            .map_or(Some("medium"), Value::as_str)?;
+        let credit = item_credit(item)?;
+        (weight >= 0.0).then_some(())?;
+        Some(QuizItem {
+            topic: item
+                .get("topic")
+                .map_or(Some("general"), Value::as_str)?
+                .to_string(),
+            weight: weight * rubric.multiplier(difficulty)?,
+            credit,
+        })
+    }
+}
+
+/// An item's `"credit"` (0–1), or 1 / 0 for `"correct":true` / `false`.
+fn item_credit(item: &Value) -> Option<f64> {
+    match (item.get("credit"), item.get("correct")) {
+        (Some(credit), _) => credit.as_f64().filter(|c| (0.0..=1.0).contains(c)),
+        (None, Some(correct)) => Some(f64::from(u8::from(correct.as_bool()?))),
+        (None, None) => None,
+    }
+}
+
+/// Percentage rounded to two decimals; 0 for an empty total.
+fn percentage(score: f64, max_score: f64) -> f64 {
+    if max_score <= 0.0 {
+        return 0.0;
+    }
+    (score / max_score * 10_000.0).round() / 100.0
+}
+
+/// Score a quiz with weighted items, per-topic subscores, and a grade.
+///
+/// `items_json` is an array of items, or `{"items":[…],"rubric":{…}}`
+/// to replace the default rubric. An item is
+/// `{"topic":"fractions","weight":2,"difficulty":"hard","credit":0.5}`
+/// (`"correct":true` may replace `credit`; weight defaults to 1 and
+/// difficulty to "medium"). The default rubric multiplies weights by
+/// easy 1, medium 1.5, hard 2, and grades A ≥ 90, B ≥ 80, C ≥ 70,
+/// D ≥ 60, else F; a custom rubric is
+/// `{"difficulty":{"easy":1,…},"grades":[{"grade":"A","min":90},…]}`.
+///
+/// Returns `{"score","max_score","percentage","grade",
+/// "topics":{"fractions":{"score","max_score","percentage"}…}}`, or
+/// `{"error":"invalid_items"}` / `{"error":"invalid_rubric"}`.
+#[wasm_bindgen]
+pub fn score_quiz(items_json: &str) -> String {
+    let Ok(payload) = serde_json::from_str::<Value>(items_json) else {
+        return version::result(json!({ "error": "invalid_items" }));
+    };
+    let (items, rubric) = match &payload {
+        Value::Array(items) => (items, Some(Rubric::default())),
+        Value::Object(fields) => match fields.get("items").and_then(Value::as_array) {
+            Some(items) => (
+                items,
+                fields
+                    .get("rubric")
+                    .map_or(Some(Rubric::default()), Rubric::from_json),
+            ),
+            None => return version::result(json!({ "error": "invalid_items" })),
+        },
+        _ => return version::result(json!({ "error": "invalid_items" })),
+    };
+    let Some(rubric) = rubric else {
+        return version::result(json!({ "error": "invalid_rubric" }));
+    };
+    let Some(items) = items
+        .iter()
+        .map(|item| QuizItem::from_json(item, &rubric))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return version::result(json!({ "error": "invalid_items" }));
+    };
+
+    let mut topics: BTreeMap<&str, (f64, f64)> = BTreeMap::new();
+    for item in &items {
+        let totals = topics.entry(&item.topic).or_default();
+        totals.0 += item.weight * item.credit;
+        totals.1 += item.weight;
+    }
+    let score: f64 = topics.values().map(|(score, _)| score).sum();
+    let max_score: f64 = topics.values().map(|(_, max)| max).sum();
+    let overall = percentage(score, max_score);
+    let topics: serde_json::Map<String, Value> = topics
+        .into_iter()
+        .map(|(topic, (score, max_score))| {
+            let subscore = json!({
+                "score": score,
+                "max_score": max_score,
+                "percentage": percentage(score, max_score),
+            });
+            (topic.to_string(), subscore)
+        })
+        .collect();
+    version::result(json!({
+        "score": score,
+        "max_score": max_score,
+        "percentage": overall,
+        "grade": rubric.grade(overall),
+        "topics": topics,
+    }))
+}
+
+// ─── Timed Quiz Scoring ──────────────────────────────────────────────
+//
+// The caller times each item and passes the elapsed milliseconds in, so
+// scoring stays a pure function of its input: the same items always
+// earn the same bonus, whatever the clock said when they were scored.
+
+/// Time allowed per item unless the item or the quiz sets one.
+const DEFAULT_TARGET_MS: u64 = 30_000;
+
+/// Bonus for a right answer given instantly; it falls linearly to 0 at
+/// the item's target time.
+const MAX_SPEED_BONUS: f64 = 0.5;
+
+/// How an item's time compares with its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pace {
+    /// Within half the target time.
+    Fast,
+    /// Within the target time.
+    OnTime,
+    /// Over the target time.
+    Slow,
+    /// Fast and not fully right: worth slowing down.
+    Rushed,
+}
+
+impl Pace {
+    fn of(elapsed_ms: u64, target_ms: u64, credit: f64) -> Pace {
+        match () {
+            _ if elapsed_ms > target_ms => Pace::Slow,
+            _ if elapsed_ms.saturating_mul(2) > target_ms => Pace::OnTime,
+            _ if credit < 1.0 => Pace::Rushed,
+            _ => Pace::Fast,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Pace::Fast => "fast",
+            Pace::OnTime => "on_pace",
+            Pace::Slow => "slow",
+            Pace::Rushed => "rushed",
+        }
+    }
+}
+
+/// One timed item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TimedItem {
+    credit: f64,
+    elapsed_ms: u64,
+    target_ms: u64,
+}
+
+impl TimedItem {
+    /// `{"credit" or "correct","elapsed_ms","target_ms"}`; the target
+    /// defaults to `default_target_ms` and must be positive.
+    fn from_json(item: &Value, default_target_ms: u64) -> Option<TimedItem> {
+        let target_ms = item
+            .get("target_ms")
+            .map_or(Some(default_target_ms), Value::as_u64)
+            .filter(|&target| target > 0)?;
+        Some(TimedItem {
+            credit: item_credit(item)?,
+            elapsed_ms: item.get("elapsed_ms")?.as_u64()?,
+            target_ms,
+        })
+    }
+
+    /// Only fully right answers earn a bonus, so a fast guess never
+    /// pays; rounded to two decimals.
+    fn speed_bonus(&self) -> f64 {
+        if self.credit < 1.0 || self.elapsed_ms >= self.target_ms {
+            return 0.0;
+        }
+        let spare = (self.target_ms - self.elapsed_ms) as f64 / self.target_ms as f64;
+        (MAX_SPEED_BONUS * spare * 100.0).round() / 100.0
+    }
+}
+
+/// Score a timed quiz: credit plus a speed bonus per item, and pace
+/// feedback.
+///
+/// `items_json` is an array of `{"correct":true,"elapsed_ms":4200}`
+/// (`credit` may replace `correct`, as in `score_quiz`; `target_ms`
+/// defaults to 30 s), or `{"items":[…],"target_ms":n}` to set the
+/// default target. A right answer earns up to 0.5 extra, falling
+/// linearly to 0 at its target time. Each item's pace is "fast" (within
+/// half its target), "on_pace", "slow" (over target), or "rushed" (fast
+/// but not fully right).
+///
+/// Returns `{"score","bonus","total","max_score","elapsed_ms",
+/// "items":[{"credit","bonus","pace","elapsed_ms","target_ms"}…]}`, or
+/// `{"error":"invalid_items"}`.
+#[wasm_bindgen]
+pub fn score_timed_quiz(items_json: &str) -> String {
+    let invalid = || version::result(json!({ "error": "invalid_items" }));
+    let Ok(payload) = serde_json::from_str::<Value>(items_json) else {
+        return invalid();
+    };
+    let (items, target_ms) = match &payload {
+        Value::Array(items) => (items, Some(DEFAULT_TARGET_MS)),
+        Value::Object(fields) => match fields.get("items").and_then(Value::as_array) {
+            Some(items) => (
+                items,
+                fields
+                    .get("target_ms")
+                    .map_or(Some(DEFAULT_TARGET_MS), Value::as_u64),
+            ),
+            None => return invalid(),
+        },
+        _ => return invalid(),
+    };
+    let Some(items) = target_ms.and_then(|target_ms| {
+        items
+            .iter()
+            .map(|item| TimedItem::from_json(item, target_ms))
+            .collect::<Option<Vec<_>>>()
+    }) else {
+        return invalid();
+    };
+
+    let score = items.iter().fold(0.0, |score, item| score + item.credit);
+    let bonus = items
+        .iter()
+        .fold(0.0, |bonus, item| bonus + item.speed_bonus());
+    let elapsed_ms = items
+        .iter()
+        .fold(0u64, |total, item| total.saturating_add(item.elapsed_ms));
+    let items: Vec<Value> = items
+        .iter()
+        .map(|item| {
+            json!({
+                "credit": item.credit,
+                "bonus": item.speed_bonus(),
+                "pace": Pace::of(item.elapsed_ms, item.target_ms, item.credit).name(),
+                "elapsed_ms": item.elapsed_ms,
+                "target_ms": item.target_ms,
+            })
+        })
+        .collect();
+    version::result(json!({
+        "score": score,
+        "bonus": (bonus * 100.0).round() / 100.0,
+        "total": ((score + bonus) * 100.0).round() / 100.0,
+        "max_score": items.len(),
+        "elapsed_ms": elapsed_ms,
+        "items": items,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_credit() {
+        assert_eq!(score_item("2 + 3", "5"), (1.0, None));
+        assert_eq!(score_item("8 - 3", "-5"), (0.5, Some("sign_flip")));
+        assert_eq!(score_item("27 + 45", "62"), (0.25, Some("forgotten_carry")));
+        assert_eq!(score_item("20 / 4", "80"), (0.0, Some("wrong_operation")));
+        assert_eq!(score_item("2 + 3", "9"), (0.0, None));
+        assert_eq!(score_item("2 + 3", "five"), (0.0, None));
+    }
+
+    #[test]
+    fn test_batch_score() {
+        let result: Value =
+            serde_json::from_str(&batch_score("2 + 3;8 - 3;4 * 5", "5;-5;21")).unwrap();
+        assert_eq!(result["score"], 1.75);
+        assert_eq!(result["max_score"], 3);
+        assert_eq!(result["items"][1]["misconception"], "sign_flip");
+        assert_eq!(result["items"][2]["credit"], 0.25);
+        assert!(batch_score("2 + 3", "5;6").contains("length_mismatch"));
+    }
+
+    #[test]
+    fn test_batch_score_msgpack() {
+        for (problems, answers) in [("2 + 3;8 - 3;4 * 5", "5;-5;21"), ("2 + 3", "5;6")] {
+            let json: Value = serde_json::from_str(&batch_score(problems, answers)).unwrap();
+            let decoded: Value =
+                rmp_serde::from_slice(&batch_score_msgpack(problems, answers)).unwrap();
+            assert_eq!(decoded, json);
+        }
+    }
+
+    fn quiz(input: &str) -> Value {
+        serde_json::from_str(&score_quiz(input)).unwrap()
+    }
+
+    #[test]
+    fn test_score_quiz() {
+        let result = quiz(
+            r#"[{"topic":"fractions","weight":2,"difficulty":"hard","correct":true},
+                {"topic":"fractions","difficulty":"easy","credit":0.5},
+                {"topic":"time","correct":false}]"#,
+        );
+        // fractions: 4 of 4 + 0.5 of 1; time: 0 of 1.5
+        assert_eq!(result["score"], 4.5);
+        assert_eq!(result["max_score"], 6.5);
+        assert_eq!(result["percentage"], 69.23);
+        assert_eq!(result["grade"], "D");
+        assert_eq!(result["topics"]["fractions"]["percentage"], 90.0);
+        assert_eq!(result["topics"]["time"]["score"], 0.0);
+    }
+
+    #[test]
+    fn test_custom_rubric_and_errors() {
+        let result = quiz(
+            r#"{"items":[{"difficulty":"hard","correct":true},{"correct":false}],
+                "rubric":{"difficulty":{"medium":1,"hard":3},
+                          "grades":[{"grade":"Pass","min":70},{"grade":"Fail","min":0}]}}"#,
+        );
+        assert_eq!(result["percentage"], 75.0);
+        assert_eq!(result["grade"], "Pass");
+        assert_eq!(result["topics"]["general"]["max_score"], 4.0);
+
+        assert_eq!(quiz("[]")["grade"], "F");
+        assert_eq!(quiz(r#"[{"credit":2}]"#)["error"], "invalid_items");
+        assert_eq!(
+            quiz(r#"[{"difficulty":"extreme","correct":true}]"#)["error"],
+            "invalid_items"
+        );
+        assert_eq!(
+            quiz(r#"{"items":[],"rubric":{"grades":[]}}"#)["error"],
+            "invalid_rubric"
+        );
+    }
+
+    fn timed(input: &str) -> Value {
+        serde_json::from_str(&score_timed_quiz(input)).unwrap()
+    }
+
+    #[test]
+    fn test_timed_quiz_bonus_and_pace() {
+        let result = timed(
+            r#"[{"correct":true,"elapsed_ms":6000},
+                {"correct":true,"elapsed_ms":20000},
+                {"credit":0.5,"elapsed_ms":3000},
+                {"correct":true,"elapsed_ms":45000},
+                {"correct":true,"elapsed_ms":1000,"target_ms":4000}]"#,
+        );
+        // Bonuses: 0.5 × 24/30, 0.5 × 10/30, none, none, 0.5 × 3/4.
+        let bonuses: Vec<f64> = (0..5)
+            .map(|i| result["items"][i]["bonus"].as_f64().unwrap())
+            .collect();
+        assert_eq!(bonuses, [0.4, 0.17, 0.0, 0.0, 0.38]);
+        let paces: Vec<&str> = (0..5)
+            .map(|i| result["items"][i]["pace"].as_str().unwrap())
+            .collect();
+        assert_eq!(paces, ["fast", "on_pace", "rushed", "slow", "fast"]);
+        assert_eq!(result["score"], 4.5);
+        assert_eq!(result["bonus"], 0.95);
+        assert_eq!(result["total"], 5.45);
+        assert_eq!(result["max_score"], 5);
+        assert_eq!(result["elapsed_ms"], 75_000);
+    }
+
+    #[test]
+    fn test_timed_quiz_target_and_errors() {
+        let result = timed(r#"{"items":[{"correct":true,"elapsed_ms":0}],"target_ms":1000}"#);
+        assert_eq!(result["items"][0]["bonus"], MAX_SPEED_BONUS);
+        assert_eq!(result["items"][0]["target_ms"], 1000);
+        assert_eq!(timed("[]")["total"], 0.0);
+        for input in [
+            r#"[{"correct":true}]"#,
+            r#"[{"correct":true,"elapsed_ms":-5}]"#,
+            r#"[{"correct":true,"elapsed_ms":5,"target_ms":0}]"#,
+            r#"[{"elapsed_ms":5}]"#,
+            r#"{"items":[],"target_ms":"fast"}"#,
+            "{}",
+        ] {
+            assert_eq!(timed(input)["error"], "invalid_items", "{}", input);
+        }
+    }
+}
@@ -0,0 +1,322 @@
+// ─── Object API ──────────────────────────────────────────────────────
+//
+// `Problem`, `Attempt`, and `Session` as wasm-bindgen classes, so
+// TypeScript gets generated typings instead of parsing `check_answer`'s
+// JSON by hand:
+//
+//   const problem = new Problem("fraction", "2/4");
+//   const attempt = problem.check("1/2");
+//   if (!attempt.correct) show(attempt.hint);
+//
+//   const session = new Session();
+//   const i = session.add(problem);
+//   session.submit(i, "3/5");
+//   session.solvedCount;
+//
+// Grading goes through the same validator registry as `check_answer`,
+// so both APIs always agree.
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::canonical;
+use crate::hints;
+use crate::msgpack;
+use crate::registry::{self, Verdict};
+use crate::version;
+
+/// One exercise: a problem type and the problem text.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    problem_type: String,
+    text: String,
+}
+
+#[wasm_bindgen]
+impl Problem {
+    #[wasm_bindgen(constructor)]
+    pub fn new(problem_type: &str, text: &str) -> Problem {
+        Problem {
+            problem_type: problem_type.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = problemType)]
+    pub fn problem_type(&self) -> String {
+        self.problem_type.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    /// Is the type known to the registry (built-in or custom)?
+    #[wasm_bindgen(getter)]
+    pub fn supported(&self) -> bool {
+        registry::check_registered(&self.problem_type, "", "").is_some()
+    }
+
+    /// Grade an answer, as `check_answer` does.
+    pub fn check(&self, answer: &str) -> Attempt {
+        let verdict = registry::check_registered(&self.problem_type, &self.text, answer)
+            .unwrap_or_else(|| {
+                Verdict::new(false, &hints::UNKNOWN_TYPE, &[("type", &self.problem_type)])
+            });
+        Attempt {
+            answer: answer.to_string(),
+            canonical: canonical::canonical(answer).unwrap_or_else(|_| answer.trim().to_string()),
+            verdict,
+        }
+    }
+}
+
+/// One graded answer.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    answer: String,
+    /// `answer` as `format_canonical` prints it.
+    canonical: String,
+    verdict: Verdict,
+}
+
+#[wasm_bindgen]
+impl Attempt {
+    #[wasm_bindgen(getter)]
+    pub fn answer(&self) -> String {
+        self.answer.clone()
+    }
+
+    /// The answer in canonical form (see `format_canonical`), for
+    /// storing and comparing; trimmed but otherwise as typed if it is
+    /// not an expression (multiple-choice JSON, units).
+    #[wasm_bindgen(getter)]
+    pub fn canonical(&self) -> String {
+        self.canonical.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn correct(&self) -> bool {
+        self.verdict.correct
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn hint(&self) -> String {
+        self.verdict.hint.clone()
+    }
+
+    /// Stable catalog ID of the hint (see `hint_catalog`).
+    #[wasm_bindgen(getter, js_name = hintId)]
+    pub fn hint_id(&self) -> String {
+        self.verdict.hint_id.clone()
+    }
+
+    /// The error pattern a chosen multiple-choice distractor represents.
+    #[wasm_bindgen(getter)]
+    pub fn misconception(&self) -> Option<String> {
+        self.verdict.misconception.clone()
+    }
+
+    /// The hint as data, as `check_answer`'s `hint_data`.
+    #[wasm_bindgen(js_name = hintData)]
+    pub fn hint_data(&self) -> String {
+        self.verdict.hint_data().to_string()
+    }
+}
+
+/// A practice session: a list of problems and every answer given.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Session {
+    problems: Vec<Problem>,
+    /// Attempts per problem, in order.
+    attempts: Vec<Vec<Attempt>>,
+}
+
+#[wasm_bindgen]
+impl Session {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Session {
+        Session::default()
+    }
+
+    /// Add a problem; returns its index.
+    pub fn add(&mut self, problem: &Problem) -> usize {
+        self.problems.push(problem.clone());
+        self.attempts.push(Vec::new());
+        self.problems.len() - 1
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.problems.len()
+    }
+
+    pub fn problem(&self, index: usize) -> Option<Problem> {
+        self.problems.get(index).cloned()
+    }
+
+    /// Grade and record an answer to problem `index`; `undefined` if
+    /// there is no such problem.
+    pub fn submit(&mut self, index: usize, answer: &str) -> Option<Attempt> {
+        let attempt = self.problems.get(index)?.check(answer);
+        self.attempts[index].push(attempt.clone());
+        Some(attempt)
+    }
+
+    /// Answers given to problem `index` so far.
+    #[wasm_bindgen(js_name = attemptCount)]
+    pub fn attempt_count(&self, index: usize) -> usize {
+        self.attempts.get(index).map_or(0, Vec::len)
+    }
+
+    /// The latest answer to problem `index`, if any.
+    #[wasm_bindgen(js_name = lastAttempt)]
+    pub fn last_attempt(&self, index: usize) -> Option<Attempt> {
+        self.attempts.get(index)?.last().cloned()
+    }
+
+    #[wasm_bindgen(js_name = isSolved)]
+    pub fn is_solved(&self, index: usize) -> bool {
+        self.attempts
+            .get(index)
+            .is_some_and(|a| a.iter().any(Attempt::correct))
+    }
+
+    /// Problems answered correctly at least once.
+    #[wasm_bindgen(getter, js_name = solvedCount)]
+    pub fn solved_count(&self) -> usize {
+        (0..self.problems.len())
+            .filter(|&i| self.is_solved(i))
+            .count()
+    }
+
+    /// Problems answered correctly on the first try.
+    #[wasm_bindgen(getter, js_name = firstTryCount)]
+    pub fn first_try_count(&self) -> usize {
+        self.attempts
+            .iter()
+            .filter(|a| a.first().is_some_and(Attempt::correct))
+            .count()
+    }
+
+    /// Every problem with its attempts, for a results screen:
+    /// `{"problems":[{"problem_type","problem","solved","attempts":[{"answer",
+    /// "canonical","correct","hint","hint_id","misconception"}…]}…]}`.
+    pub fn results(&self) -> String {
+        version::result(version::list("problems", self.results_value()))
+    }
+
+    /// `results`, MessagePack-encoded (see msgpack.rs).
+    #[wasm_bindgen(js_name = resultsMsgpack)]
+    pub fn results_msgpack(&self) -> Vec<u8> {
+        msgpack::encode(&version::stamp(version::list(
+            "problems",
+            self.results_value(),
+        )))
+    }
+}
+
+impl Session {
+    fn results_value(&self) -> Value {
+        self.problems
+            .iter()
+            .zip(&self.attempts)
+            .enumerate()
+            .map(|(i, (problem, attempts))| {
+                let attempts: Vec<Value> = attempts
+                    .iter()
+                    .map(|a| {
+                        json!({
+                            "answer": a.answer,
+                            "canonical": a.canonical,
+                            "correct": a.verdict.correct,
+                            "hint": a.verdict.hint,
+                            "hint_id": a.verdict.hint_id,
+                            "misconception": a.verdict.misconception,
+                        })
+                    })
+                    .collect();
+                json!({
+                    "problem_type": problem.problem_type,
+                    "problem": problem.text,
+                    "solved": self.is_solved(i),
+                    "attempts": attempts,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_problem_check_matches_check_answer() {
+        let problem = Problem::new("fraction", "2/4");
+        assert!(problem.supported());
+        let attempt = problem.check("1/2");
+        assert!(attempt.correct());
+        assert_eq!(attempt.hint_id(), "correct");
+
+        let wrong = problem.check("3/5");
+        let json: serde_json::Value =
+            serde_json::from_str(&crate::check_answer("fraction", "2/4", "3/5")).unwrap();
+        assert_eq!(wrong.hint(), json["hint"]);
+        assert_eq!(wrong.hint_data(), json["hint_data"].to_string());
+
+        let unknown = Problem::new("geometry", "x");
+        assert!(!unknown.supported());
+        assert_eq!(unknown.check("y").hint_id(), "unknown_type");
+    }
+
+    #[test]
+    fn test_session_tracks_attempts() {
+        let mut session = Session::new();
+        let first = session.add(&Problem::new("arithmetic", "2 + 3"));
+        let second = session.add(&Problem::new("arithmetic", "7 * 8"));
+        assert_eq!((first, second, session.length()), (0, 1, 2));
+
+        assert!(session.submit(first, "5").unwrap().correct());
+        assert!(!session.submit(second, "54").unwrap().correct());
+        assert!(session.submit(second, "56").unwrap().correct());
+        assert!(session.submit(9, "1").is_none());
+
+        assert_eq!(session.attempt_count(second), 2);
+        assert_eq!(session.last_attempt(second).unwrap().answer(), "56");
+        assert_eq!(session.solved_count(), 2);
+        assert_eq!(session.first_try_count(), 1);
+        assert_eq!(session.problem(1).unwrap().text(), "7 * 8");
+
+        let attempt = session.submit(second, " 7*8 ").unwrap();
+        assert_eq!(attempt.canonical(), "7 * 8");
+        assert_eq!(attempt.answer(), " 7*8 ");
+    }
+
+    #[test]
+    fn test_session_results_in_both_encodings() {
+        let mut session = Session::new();
+        let i = session.add(&Problem::new("arithmetic", "7 * 8"));
+        session.add(&Problem::new("fraction", "2/4"));
+        session.submit(i, "54");
+        session.submit(i, "56");
+
+        let decoded: Value = serde_json::from_str(&session.results()).unwrap();
+        let results = &decoded["problems"];
+        assert_eq!(results[0]["solved"], true);
+        assert_eq!(
+            results[0]["attempts"][0]["hint_id"],
+            "arithmetic.step_by_step"
+        );
+        assert_eq!(results[0]["attempts"][1]["correct"], true);
+        assert_eq!(results[1]["attempts"], json!([]));
+
+        let unpacked: Value = rmp_serde::from_slice(&session.results_msgpack()).unwrap();
+        assert_eq!(unpacked, decoded);
+        assert!(session.results_msgpack().len() < session.results().len());
+    }
+}
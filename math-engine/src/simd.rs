@@ -0,0 +1,248 @@
+// ─── Vectorized Batch Grading ────────────────────────────────────────
+//
+// An end-of-unit test can hand `batch_validate` thousands of arithmetic
+// items at once. Each item is split into operands once (the same rules
+// as `evaluate_expression`), packed into lane arrays, and graded two at
+// a time with WASM SIMD (`f64x2`) when the module is built with
+// `-C target-feature=+simd128`. Every other build runs the same kernel
+// one lane at a time.
+//
+// Both paths perform the same IEEE operations and comparisons as
+// `validate_arithmetic`, so they agree bit for bit. Only items the
+// kernel marks correct skip the per-item validator; wrong answers still
+// go through it for their hint.
+
+use crate::latex;
+
+/// The operation `evaluate_expression` found, or `Value` for a single
+/// number (graded as its left operand).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Op {
+    Value,
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Op {
+    /// Lane code, as a float so the vector kernel can compare it
+    /// directly.
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    fn code(self) -> f64 {
+        self as u8 as f64
+    }
+
+    /// The scalar result; `None` for division by (nearly) zero.
+    pub(crate) fn apply(self, left: f64, right: f64) -> Option<f64> {
+        match self {
+            Op::Value => Some(left),
+            Op::Add => Some(left + right),
+            Op::Sub => Some(left - right),
+            Op::Mul => Some(left * right),
+            Op::Div if right.abs() < 1e-15 => None,
+            Op::Div => Some(left / right),
+        }
+    }
+}
+
+/// Split items in struct-of-arrays form for the kernel.
+#[derive(Debug, Default)]
+pub(crate) struct Lanes {
+    ops: Vec<Op>,
+    left: Vec<f64>,
+    right: Vec<f64>,
+    answer: Vec<f64>,
+}
+
+impl Lanes {
+    pub(crate) fn push(&mut self, op: Op, left: f64, right: f64, answer: f64) {
+        self.ops.push(op);
+        self.left.push(left);
+        self.right.push(right);
+        self.answer.push(answer);
+    }
+
+    /// Whether each lane's answer is within 1e-9 of its result.
+    pub(crate) fn grade(&self) -> Vec<bool> {
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        {
+            self.grade_simd()
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+        {
+            self.grade_scalar()
+        }
+    }
+
+    /// The fallback kernel: one lane at a time.
+    #[cfg_attr(
+        all(target_arch = "wasm32", target_feature = "simd128"),
+        allow(dead_code)
+    )]
+    pub(crate) fn grade_scalar(&self) -> Vec<bool> {
+        (0..self.ops.len())
+            .map(|i| grade_lane(self.ops[i], self.left[i], self.right[i], self.answer[i]))
+            .collect()
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    fn grade_simd(&self) -> Vec<bool> {
+        let pairs = self.ops.len() / 2;
+        let mut graded = Vec::with_capacity(self.ops.len());
+        for i in (0..pairs).map(|p| p * 2) {
+            let lanes = |v: &[f64]| [v[i], v[i + 1]];
+            graded.extend(grade_pair(
+                [self.ops[i].code(), self.ops[i + 1].code()],
+                lanes(&self.left),
+                lanes(&self.right),
+                lanes(&self.answer),
+            ));
+        }
+        for i in pairs * 2..self.ops.len() {
+            graded.push(grade_lane(
+                self.ops[i],
+                self.left[i],
+                self.right[i],
+                self.answer[i],
+            ));
+        }
+        graded
+    }
+}
+
+fn grade_lane(op: Op, left: f64, right: f64, answer: f64) -> bool {
+    match op.apply(left, right) {
+        Some(correct) => (correct - answer).abs() < 1e-9,
+        None => false,
+    }
+}
+
+/// Two lanes of `grade_lane`: every operation is computed and the one
+/// each lane asked for is selected by mask.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+fn grade_pair(ops: [f64; 2], left: [f64; 2], right: [f64; 2], answer: [f64; 2]) -> [bool; 2] {
+    use core::arch::wasm32::*;
+
+    let vector = |lanes: [f64; 2]| f64x2(lanes[0], lanes[1]);
+    let (ops, left, right, answer) = (vector(ops), vector(left), vector(right), vector(answer));
+    let is = |op: Op| f64x2_eq(ops, f64x2_splat(op.code()));
+
+    let mut result = left;
+    result = v128_bitselect(f64x2_add(left, right), result, is(Op::Add));
+    result = v128_bitselect(f64x2_sub(left, right), result, is(Op::Sub));
+    result = v128_bitselect(f64x2_mul(left, right), result, is(Op::Mul));
+    result = v128_bitselect(f64x2_div(left, right), result, is(Op::Div));
+
+    let by_zero = v128_and(is(Op::Div), f64x2_lt(f64x2_abs(right), f64x2_splat(1e-15)));
+    let close = f64x2_lt(f64x2_abs(f64x2_sub(result, answer)), f64x2_splat(1e-9));
+    let correct = v128_andnot(close, by_zero);
+    let bits = i64x2_bitmask(correct);
+    [bits & 1 != 0, bits & 2 != 0]
+}
+
+/// Grade each `(problem, answer)` as the arithmetic validator would,
+/// vectorized: `true` where the answer is certainly correct. `false`
+/// covers wrong answers and items the kernel cannot take (missing or
+/// unsplittable problems); callers grade those one by one.
+pub(crate) fn correct_items(items: &[(Option<&str>, Option<&str>)]) -> Vec<bool> {
+    let mut lanes = Lanes::default();
+    let mut lane_of = Vec::with_capacity(items.len());
+    for &(problem, answer) in items {
+        let split = problem.and_then(|p| crate::split_expression(&latex::to_plain(p)));
+        lane_of.push(split.map(|(op, left, right)| {
+            // As `validators::Arithmetic`: LaTeX converted, NaN if not a number.
+            let answer = latex::to_plain(answer.unwrap_or_default())
+                .parse()
+                .unwrap_or(f64::NAN);
+            lanes.push(op, left, right, answer);
+            lanes.ops.len() - 1
+        }));
+    }
+    let graded = lanes.grade();
+    lane_of
+        .into_iter()
+        .map(|lane| lane.is_some_and(|i| graded[i]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate_arithmetic;
+
+    /// Operands chosen to hit every edge: signed zeros, the division
+    /// cutoff, the 1e-9 tolerance, infinities, and NaN.
+    const EDGES: [f64; 16] = [
+        0.0,
+        -0.0,
+        1.0,
+        -1.0,
+        2.5,
+        1e-15,
+        9e-16,
+        1e-9,
+        3.0 + 1e-10,
+        0.1 + 0.2,
+        0.3,
+        1e300,
+        -1e300,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NAN,
+    ];
+
+    #[test]
+    fn test_kernels_agree_with_scalar_grading() {
+        let ops = [Op::Value, Op::Add, Op::Sub, Op::Mul, Op::Div];
+        let mut lanes = Lanes::default();
+        let mut expected = Vec::new();
+        for op in ops {
+            for left in EDGES {
+                for right in EDGES {
+                    for answer in EDGES {
+                        lanes.push(op, left, right, answer);
+                        expected.push(match op.apply(left, right) {
+                            Some(correct) => (correct - answer).abs() < 1e-9,
+                            None => false,
+                        });
+                    }
+                }
+            }
+        }
+        assert_eq!(lanes.grade(), expected);
+        assert_eq!(lanes.grade_scalar(), expected);
+        // An odd count leaves a scalar tail after the last pair.
+        lanes.push(Op::Add, 2.0, 3.0, 5.0);
+        expected.push(true);
+        assert_eq!(lanes.grade(), expected);
+    }
+
+    #[test]
+    fn test_correct_items_match_validate_arithmetic() {
+        let items = [
+            (Some("2 + 3"), Some("5")),
+            (Some("2 + 3"), Some("6")),
+            (Some("-4"), Some("-4")),
+            (Some("10 / 0"), Some("inf")),
+            (Some("0.1 + 0.2"), Some("0.3")),
+            (Some(r"\frac{6}{3}"), Some("2")),
+            (Some("7 * 8"), Some(r"$56$")),
+            (Some("two + 2"), Some("4")),
+            (Some("5 - 3"), Some("two")),
+            (Some("5 - 3"), None),
+            (None, Some("1")),
+        ];
+        let graded = correct_items(&items);
+        for (&(problem, answer), correct) in items.iter().zip(graded) {
+            let (problem, answer) = (problem.unwrap_or_default(), answer.unwrap_or_default());
+            let answer = latex::to_plain(answer).parse().unwrap_or(f64::NAN);
+            assert_eq!(
+                correct,
+                validate_arithmetic(&latex::to_plain(problem), answer),
+                "{:?}",
+                (problem, answer)
+            );
+        }
+    }
+}
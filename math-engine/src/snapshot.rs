@@ -0,0 +1,340 @@
+// ─── Engine State Snapshots ──────────────────────────────────────────
+//
+// `export_state` packs everything the engine holds between calls —
+// registered custom types, the loaded question bank, the hint locale,
+// and the result schema version — into one compact binary blob so the
+// desktop app can save a practice session and resume it instantly after
+// a restart; `import_state` restores it.
+//
+// Layout (all integers little-endian):
+//   magic "SAES" | format version u8 | section* | FNV-1a checksum u32
+//   section = tag u8 | payload length u32 | payload
+//
+// Unknown section tags are skipped, so older engines can read newer
+// blobs; state with no section in a blob (one from an older engine) is
+// left as it is. A blob is applied all-or-nothing: any error leaves the
+// current state untouched.
+
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+use crate::bank;
+use crate::i18n;
+use crate::registry;
+use crate::version;
+
+const MAGIC: &[u8; 4] = b"SAES";
+const FORMAT_VERSION: u8 = 1;
+
+/// Section tags. Never renumber; only append.
+const SECTION_CUSTOM_TYPES: u8 = 1;
+const SECTION_QUESTION_BANK: u8 = 2;
+const SECTION_LOCALE: u8 = 3;
+const SECTION_RESULT_SCHEMA: u8 = 4;
+
+fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811C_9DC5, |hash, &b| {
+        (hash ^ b as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[derive(Default)]
+struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn string(&mut self, text: &str) {
+        self.u32(text.len() as u32);
+        self.bytes.extend_from_slice(text.as_bytes());
+    }
+
+    fn section(&mut self, tag: u8, payload: Writer) {
+        self.bytes.push(tag);
+        self.u32(payload.bytes.len() as u32);
+        self.bytes.extend_from_slice(&payload.bytes);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if n > self.bytes.len() {
+            return Err("truncated".to_string());
+        }
+        let (head, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| "invalid UTF-8".to_string())
+    }
+}
+
+/// Everything restored from a blob, before it is applied.
+#[derive(Debug, Default, PartialEq)]
+struct EngineState {
+    custom_types: Vec<String>,
+    /// The bank as a `load_question_bank` bundle.
+    question_bank: Option<String>,
+    locale: Option<String>,
+    result_schema: Option<u32>,
+}
+
+fn encode(state: &EngineState) -> Vec<u8> {
+    let mut out = Writer::default();
+    out.bytes.extend_from_slice(MAGIC);
+    out.bytes.push(FORMAT_VERSION);
+
+    let mut custom = Writer::default();
+    custom.u32(state.custom_types.len() as u32);
+    for spec in &state.custom_types {
+        custom.string(spec);
+    }
+    out.section(SECTION_CUSTOM_TYPES, custom);
+
+    if let Some(bundle) = &state.question_bank {
+        let mut section = Writer::default();
+        section.string(bundle);
+        out.section(SECTION_QUESTION_BANK, section);
+    }
+    if let Some(tag) = &state.locale {
+        let mut section = Writer::default();
+        section.string(tag);
+        out.section(SECTION_LOCALE, section);
+    }
+    if let Some(schema) = state.result_schema {
+        let mut section = Writer::default();
+        section.u32(schema);
+        out.section(SECTION_RESULT_SCHEMA, section);
+    }
+
+    let checksum = fnv1a(&out.bytes);
+    out.u32(checksum);
+    out.bytes
+}
+
+fn decode(blob: &[u8]) -> Result<EngineState, String> {
+    if blob.len() < MAGIC.len() + 1 + 4 {
+        return Err("truncated".to_string());
+    }
+    let (body, checksum) = blob.split_at(blob.len() - 4);
+    if fnv1a(body).to_le_bytes() != checksum {
+        return Err("checksum_mismatch".to_string());
+    }
+
+    let mut reader = Reader { bytes: body };
+    if reader.take(4)? != MAGIC {
+        return Err("not_a_snapshot".to_string());
+    }
+    let version = reader.u8()?;
+    if version > FORMAT_VERSION {
+        return Err(format!("unsupported_version: {}", version));
+    }
+
+    let mut state = EngineState::default();
+    while !reader.bytes.is_empty() {
+        let tag = reader.u8()?;
+        let len = reader.u32()? as usize;
+        let mut section = Reader {
+            bytes: reader.take(len)?,
+        };
+        // Sections from newer engines are skipped.
+        match tag {
+            SECTION_CUSTOM_TYPES => {
+                let count = section.u32()?;
+                for _ in 0..count {
+                    state.custom_types.push(section.string()?);
+                }
+            }
+            SECTION_QUESTION_BANK => state.question_bank = Some(section.string()?),
+            SECTION_LOCALE => state.locale = Some(section.string()?),
+            SECTION_RESULT_SCHEMA => state.result_schema = Some(section.u32()?),
+            _ => {}
+        }
+    }
+    Ok(state)
+}
+
+/// Check every part of `state`, then replace the engine's state with
+/// it. Returns the number of custom types and questions restored.
+fn apply(state: EngineState) -> Result<(usize, Option<usize>), String> {
+    let bank = match &state.question_bank {
+        Some(bundle) => Some(bank::parse_bundle(bundle)?),
+        None => None,
+    };
+    if let Some(tag) = state
+        .locale
+        .as_deref()
+        .filter(|tag| !i18n::is_supported(tag))
+    {
+        return Err(format!("unsupported_locale: {}", tag));
+    }
+    if let Some(schema) = state.result_schema.filter(|s| !version::is_supported(*s)) {
+        return Err(format!("unsupported_result_schema: {}", schema));
+    }
+
+    // The last fallible step; everything after it only swaps state in.
+    let custom_types = registry::replace_custom_types(&state.custom_types)?;
+    let questions = bank.map(bank::replace);
+    if let Some(tag) = &state.locale {
+        i18n::set_locale(tag);
+    }
+    if let Some(schema) = state.result_schema {
+        version::set_result_schema_version(schema);
+    }
+    Ok((custom_types, questions))
+}
+
+/// Serialize the engine's state (registered custom types, question
+/// bank, locale, result schema version) to a compact binary blob.
+#[wasm_bindgen]
+pub fn export_state() -> Vec<u8> {
+    encode(&EngineState {
+        custom_types: registry::custom_type_specs(),
+        question_bank: Some(bank::bundle_json()),
+        locale: Some(i18n::get_locale()),
+        result_schema: Some(version::result_schema()),
+    })
+}
+
+/// Restore state saved by `export_state`, replacing the current state.
+/// Returns `{"ok":true,"custom_types":n,"questions":m}` (`questions` is
+/// null for a blob without a bank) or `{"ok":false,"error":…}`; on
+/// error nothing changes.
+#[wasm_bindgen]
+pub fn import_state(blob: &[u8]) -> String {
+    match decode(blob).and_then(apply) {
+        Ok((custom_types, questions)) => version::result(
+            json!({ "ok": true, "custom_types": custom_types, "questions": questions }),
+        ),
+        Err(error) => version::result(json!({ "ok": false, "error": error })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let state = EngineState {
+            custom_types: vec![r#"{"name":"a","formula":"x"}"#.to_string()],
+            question_bank: Some(r#"{"questions":[]}"#.to_string()),
+            locale: Some("th".to_string()),
+            result_schema: Some(1),
+        };
+        assert_eq!(decode(&encode(&state)), Ok(state));
+        assert_eq!(
+            decode(&encode(&EngineState::default())),
+            Ok(EngineState::default())
+        );
+    }
+
+    #[test]
+    fn test_corruption_is_rejected() {
+        let mut blob = encode(&EngineState::default());
+        assert!(decode(&blob).is_ok());
+        blob[5] ^= 0xFF;
+        assert_eq!(decode(&blob), Err("checksum_mismatch".to_string()));
+        assert!(decode(b"SAE").is_err());
+    }
+
+    #[test]
+    fn test_unknown_sections_are_skipped() {
+        let mut out = Writer::default();
+        out.bytes.extend_from_slice(MAGIC);
+        out.bytes.push(FORMAT_VERSION);
+        let mut future = Writer::default();
+        future.string("from a newer engine");
+        out.section(200, future);
+        let checksum = fnv1a(&out.bytes);
+        out.u32(checksum);
+        assert_eq!(decode(&out.bytes), Ok(EngineState::default()));
+    }
+
+    #[test]
+    fn test_export_import_restores_custom_types() {
+        crate::register_custom_type(r#"{"name":"snapshot_double","formula":"2n"}"#);
+        let blob = export_state();
+        assert!(import_state(&blob).contains("\"ok\":true"));
+        let result = crate::check_answer("snapshot_double", "n = 4", "8");
+        assert!(result.contains("\"correct\":true"));
+        assert!(import_state(&[1, 2, 3]).contains("\"ok\":false"));
+        // A failed import leaves the registered types in place.
+        let result = crate::check_answer("snapshot_double", "n = 4", "8");
+        assert!(result.contains("\"correct\":true"));
+    }
+
+    #[test]
+    fn test_export_import_restores_bank_locale_and_schema() {
+        let bundle = r#"{"questions":[
+            {"id":"s-1","topic":"fractions","grade":4,"difficulty":900,"answer":"3/4"},
+            {"id":"s-2","topic":"time","grade":2,"difficulty":800}]}"#;
+        assert!(crate::load_question_bank(bundle).contains("\"ok\":true"));
+        assert!(crate::set_locale("th"));
+        assert!(version::set_result_schema_version(1));
+        let blob = export_state();
+
+        crate::load_question_bank(r#"{"questions":[]}"#);
+        crate::set_locale("en");
+        version::set_result_schema_version(2);
+
+        let result: serde_json::Value = serde_json::from_str(&import_state(&blob)).unwrap();
+        assert_eq!(result["ok"], true);
+        assert_eq!(result["questions"], 2);
+        assert_eq!(crate::get_locale(), "th");
+        assert_eq!(version::get_result_schema_version(), 1);
+        let page: serde_json::Value =
+            serde_json::from_str(&crate::query_questions(r#"{"topic":"fractions"}"#)).unwrap();
+        assert_eq!(page["questions"][0]["answer"], "3/4");
+
+        crate::set_locale("en");
+        version::set_result_schema_version(2);
+    }
+
+    #[test]
+    fn test_invalid_sections_change_nothing() {
+        crate::load_question_bank(
+            r#"{"questions":[{"id":"keep","topic":"t","grade":1,"difficulty":1}]}"#,
+        );
+        let bad_bank = EngineState {
+            question_bank: Some("[]".to_string()),
+            ..EngineState::default()
+        };
+        assert!(import_state(&encode(&bad_bank)).contains("invalid_bundle"));
+        let bad_locale = EngineState {
+            locale: Some("xx".to_string()),
+            ..EngineState::default()
+        };
+        assert!(import_state(&encode(&bad_locale)).contains("unsupported_locale"));
+        let bad_schema = EngineState {
+            result_schema: Some(99),
+            ..EngineState::default()
+        };
+        assert!(import_state(&encode(&bad_schema)).contains("unsupported_result_schema"));
+        assert!(crate::query_questions("{}").contains("\"total\":1"));
+
+        // A blob with no bank section (an older engine's) keeps the bank.
+        assert!(import_state(&encode(&EngineState::default())).contains("\"questions\":null"));
+        assert!(crate::query_questions("{}").contains("\"total\":1"));
+    }
+}
@@ -0,0 +1,685 @@
+// ─── Read Aloud ──────────────────────────────────────────────────────
+//
+// Spoken readings of expressions and statements for screen readers and
+// the audio-support mode: "2/3 + 1/2 = ?" → "two thirds plus one half
+// equals what".
+//
+// The reading follows the parse tree, like render.rs: wherever the
+// display would need parentheses the reading says "the quantity …", and
+// compound fractions are read as "the fraction with numerator … and
+// denominator …", so the structure survives without seeing it.
+//
+// Each supported locale (as in i18n.rs) has its own phrases and number
+// words; an unsupported locale reads in English.
+
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+use crate::compare::{self, Relation};
+use crate::expr::{self, BinOp, Expr, Func};
+use crate::i18n;
+use crate::latex;
+use crate::render::{precedence, starts_with_letter, Precedence};
+use crate::version;
+
+/// One locale's phrases. Templates fill `{a}` and `{b}`.
+struct Phrases {
+    tag: &'static str,
+    /// Cardinal number words.
+    number: fn(u64) -> String,
+    /// "two thirds" for a simple fraction, or `None` to read it with
+    /// `over`.
+    fraction: fn(u64, u64) -> Option<String>,
+    /// Decimal separator: "zero point seven five".
+    point: &'static str,
+    plus: &'static str,
+    minus: &'static str,
+    times: &'static str,
+    over: &'static str,
+    compound_fraction: &'static str,
+    negative: &'static str,
+    squared: &'static str,
+    cubed: &'static str,
+    power: &'static str,
+    square_root: &'static str,
+    absolute_value: &'static str,
+    quantity: &'static str,
+    pi: &'static str,
+    /// An unknown side, "?" or "__".
+    unknown: &'static str,
+    /// Less, less or equal, equal, not equal, greater or equal, greater.
+    relations: [&'static str; 6],
+}
+
+const ENGLISH: Phrases = Phrases {
+    tag: "en",
+    number: english_number,
+    fraction: english_fraction,
+    point: "point",
+    plus: "{a} plus {b}",
+    minus: "{a} minus {b}",
+    times: "{a} times {b}",
+    over: "{a} over {b}",
+    compound_fraction: "the fraction with numerator {a} and denominator {b}",
+    negative: "negative {a}",
+    squared: "{a} squared",
+    cubed: "{a} cubed",
+    power: "{a} to the power of {b}",
+    square_root: "the square root of {a}",
+    absolute_value: "the absolute value of {a}",
+    quantity: "the quantity {a}",
+    pi: "pi",
+    unknown: "what",
+    relations: [
+        "is less than",
+        "is less than or equal to",
+        "equals",
+        "does not equal",
+        "is greater than or equal to",
+        "is greater than",
+    ],
+};
+
+const SPANISH: Phrases = Phrases {
+    tag: "es",
+    number: spanish_number,
+    fraction: spanish_fraction,
+    point: "coma",
+    plus: "{a} más {b}",
+    minus: "{a} menos {b}",
+    times: "{a} por {b}",
+    over: "{a} sobre {b}",
+    compound_fraction: "la fracción con numerador {a} y denominador {b}",
+    negative: "menos {a}",
+    squared: "{a} al cuadrado",
+    cubed: "{a} al cubo",
+    power: "{a} elevado a {b}",
+    square_root: "la raíz cuadrada de {a}",
+    absolute_value: "el valor absoluto de {a}",
+    quantity: "la cantidad {a}",
+    pi: "pi",
+    unknown: "cuánto",
+    relations: [
+        "es menor que",
+        "es menor o igual que",
+        "es igual a",
+        "no es igual a",
+        "es mayor o igual que",
+        "es mayor que",
+    ],
+};
+
+const THAI: Phrases = Phrases {
+    tag: "th",
+    number: thai_number,
+    fraction: |_, _| None,
+    point: "จุด",
+    plus: "{a} บวก {b}",
+    minus: "{a} ลบ {b}",
+    times: "{a} คูณ {b}",
+    over: "{a} ส่วน {b}",
+    compound_fraction: "เศษ {a} ส่วน {b}",
+    negative: "ติดลบ {a}",
+    squared: "{a} ยกกำลังสอง",
+    cubed: "{a} ยกกำลังสาม",
+    power: "{a} ยกกำลัง {b}",
+    square_root: "รากที่สองของ {a}",
+    absolute_value: "ค่าสัมบูรณ์ของ {a}",
+    quantity: "วงเล็บเปิด {a} วงเล็บปิด",
+    pi: "พาย",
+    unknown: "เท่าไร",
+    relations: [
+        "น้อยกว่า",
+        "น้อยกว่าหรือเท่ากับ",
+        "เท่ากับ",
+        "ไม่เท่ากับ",
+        "มากกว่าหรือเท่ากับ",
+        "มากกว่า",
+    ],
+};
+
+const LOCALES: [&Phrases; 3] = [&ENGLISH, &SPANISH, &THAI];
+
+fn fill(template: &str, a: &str, b: &str) -> String {
+    template.replace("{a}", a).replace("{b}", b)
+}
+
+// ─── Number words ────────────────────────────────────────────────────
+
+const ENGLISH_ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const ENGLISH_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const ENGLISH_SCALES: [&str; 7] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+fn english_below_thousand(n: u64) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let rest_words = match rest {
+        0 => String::new(),
+        1..=19 => ENGLISH_ONES[rest as usize].to_string(),
+        _ if rest % 10 == 0 => ENGLISH_TENS[(rest / 10) as usize].to_string(),
+        _ => format!(
+            "{}-{}",
+            ENGLISH_TENS[(rest / 10) as usize],
+            ENGLISH_ONES[(rest % 10) as usize]
+        ),
+    };
+    match (hundreds, rest) {
+        (0, _) => rest_words,
+        (_, 0) => format!("{} hundred", ENGLISH_ONES[hundreds as usize]),
+        _ => format!("{} hundred {}", ENGLISH_ONES[hundreds as usize], rest_words),
+    }
+}
+
+fn english_number(n: u64) -> String {
+    if n == 0 {
+        return ENGLISH_ONES[0].to_string();
+    }
+    let mut groups = Vec::new();
+    let mut rest = n;
+    for scale in ENGLISH_SCALES {
+        let group = rest % 1000;
+        if group > 0 {
+            let words = english_below_thousand(group);
+            groups.push(if scale.is_empty() {
+                words
+            } else {
+                format!("{} {}", words, scale)
+            });
+        }
+        rest /= 1000;
+    }
+    groups.reverse();
+    groups.join(" ")
+}
+
+/// "one half", "two thirds", "three twenty-fifths".
+fn english_fraction(numerator: u64, denominator: u64) -> Option<String> {
+    if denominator < 2 {
+        return None;
+    }
+    let plural = numerator != 1;
+    let ordinal = match denominator {
+        2 if plural => "halves".to_string(),
+        2 => "half".to_string(),
+        _ => {
+            let cardinal = english_number(denominator);
+            let split = cardinal.rfind([' ', '-']).map_or(0, |i| i + 1);
+            let (head, last) = cardinal.split_at(split);
+            let last = match last {
+                "one" => "first".to_string(),
+                "two" => "second".to_string(),
+                "three" => "third".to_string(),
+                "five" => "fifth".to_string(),
+                "eight" => "eighth".to_string(),
+                "nine" => "ninth".to_string(),
+                "twelve" => "twelfth".to_string(),
+                _ if last.ends_with('y') => format!("{}ieth", &last[..last.len() - 1]),
+                _ => format!("{}th", last),
+            };
+            format!("{}{}{}", head, last, if plural { "s" } else { "" })
+        }
+    };
+    Some(format!("{} {}", english_number(numerator), ordinal))
+}
+
+const SPANISH_UNITS: [&str; 30] = [
+    "cero",
+    "uno",
+    "dos",
+    "tres",
+    "cuatro",
+    "cinco",
+    "seis",
+    "siete",
+    "ocho",
+    "nueve",
+    "diez",
+    "once",
+    "doce",
+    "trece",
+    "catorce",
+    "quince",
+    "dieciséis",
+    "diecisiete",
+    "dieciocho",
+    "diecinueve",
+    "veinte",
+    "veintiuno",
+    "veintidós",
+    "veintitrés",
+    "veinticuatro",
+    "veinticinco",
+    "veintiséis",
+    "veintisiete",
+    "veintiocho",
+    "veintinueve",
+];
+const SPANISH_TENS: [&str; 10] = [
+    "",
+    "",
+    "",
+    "treinta",
+    "cuarenta",
+    "cincuenta",
+    "sesenta",
+    "setenta",
+    "ochenta",
+    "noventa",
+];
+const SPANISH_HUNDREDS: [&str; 10] = [
+    "",
+    "ciento",
+    "doscientos",
+    "trescientos",
+    "cuatrocientos",
+    "quinientos",
+    "seiscientos",
+    "setecientos",
+    "ochocientos",
+    "novecientos",
+];
+
+fn spanish_below_thousand(n: u64) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let rest_words = match rest {
+        0 => String::new(),
+        1..=29 => SPANISH_UNITS[rest as usize].to_string(),
+        _ if rest % 10 == 0 => SPANISH_TENS[(rest / 10) as usize].to_string(),
+        _ => format!(
+            "{} y {}",
+            SPANISH_TENS[(rest / 10) as usize],
+            SPANISH_UNITS[(rest % 10) as usize]
+        ),
+    };
+    match (hundreds, rest) {
+        (0, _) => rest_words,
+        (1, 0) => "cien".to_string(),
+        (_, 0) => SPANISH_HUNDREDS[hundreds as usize].to_string(),
+        _ => format!("{} {}", SPANISH_HUNDREDS[hundreds as usize], rest_words),
+    }
+}
+
+/// "uno" shortens before a noun: "veintiún mil", "un millón".
+fn spanish_apocope(words: String) -> String {
+    if let Some(stem) = words.strip_suffix("veintiuno") {
+        format!("{}veintiún", stem)
+    } else if let Some(stem) = words.strip_suffix("uno") {
+        format!("{}un", stem)
+    } else {
+        words
+    }
+}
+
+/// Below a million: "dos mil trescientos".
+fn spanish_below_million(n: u64) -> String {
+    let (thousands, rest) = (n / 1000, n % 1000);
+    let thousands_words = match thousands {
+        0 => String::new(),
+        1 => "mil".to_string(),
+        _ => format!("{} mil", spanish_apocope(spanish_below_thousand(thousands))),
+    };
+    match (thousands, rest) {
+        (0, _) => spanish_below_thousand(rest),
+        (_, 0) => thousands_words,
+        _ => format!("{} {}", thousands_words, spanish_below_thousand(rest)),
+    }
+}
+
+fn spanish_number(n: u64) -> String {
+    if n == 0 {
+        return SPANISH_UNITS[0].to_string();
+    }
+    // Long scale: each name is a million times the last.
+    const SCALES: [(u64, &str, &str); 3] = [
+        (1_000_000_000_000_000_000, "trillón", "trillones"),
+        (1_000_000_000_000, "billón", "billones"),
+        (1_000_000, "millón", "millones"),
+    ];
+    let mut words = Vec::new();
+    let mut rest = n;
+    for (scale, singular, plural) in SCALES {
+        let count = rest / scale;
+        rest %= scale;
+        match count {
+            0 => {}
+            1 => words.push(format!("un {}", singular)),
+            _ => words.push(format!(
+                "{} {}",
+                spanish_apocope(spanish_number(count)),
+                plural
+            )),
+        }
+    }
+    if rest > 0 {
+        words.push(spanish_below_million(rest));
+    }
+    words.join(" ")
+}
+
+/// "un medio", "dos tercios"; denominators above ten read with "sobre".
+fn spanish_fraction(numerator: u64, denominator: u64) -> Option<String> {
+    const NAMES: [&str; 11] = [
+        "", "", "medio", "tercio", "cuarto", "quinto", "sexto", "séptimo", "octavo", "noveno",
+        "décimo",
+    ];
+    let name = NAMES.get(denominator as usize).filter(|n| !n.is_empty())?;
+    let plural = if numerator == 1 { "" } else { "s" };
+    Some(format!(
+        "{} {}{}",
+        spanish_apocope(spanish_number(numerator)),
+        name,
+        plural
+    ))
+}
+
+const THAI_DIGITS: [&str; 10] = [
+    "ศูนย์",
+    "หนึ่ง",
+    "สอง",
+    "สาม",
+    "สี่",
+    "ห้า",
+    "หก",
+    "เจ็ด",
+    "แปด",
+    "เก้า",
+];
+const THAI_PLACES: [&str; 6] = ["", "สิบ", "ร้อย", "พัน", "หมื่น", "แสน"];
+
+/// Below a million, without "ศูนย์" for zero.
+fn thai_below_million(n: u64) -> String {
+    let mut words = String::new();
+    for place in (0..THAI_PLACES.len()).rev() {
+        let digit = (n / 10u64.pow(place as u32) % 10) as usize;
+        let word = match (place, digit) {
+            (_, 0) => continue,
+            (1, 1) => "",
+            (1, 2) => "ยี่",
+            // A final one after higher digits is "เอ็ด": สิบเอ็ด, ร้อยเอ็ด.
+            (0, 1) if n > 1 => "เอ็ด",
+            _ => THAI_DIGITS[digit],
+        };
+        words.push_str(word);
+        words.push_str(THAI_PLACES[place]);
+    }
+    words
+}
+
+fn thai_number(n: u64) -> String {
+    if n == 0 {
+        return THAI_DIGITS[0].to_string();
+    }
+    let (millions, rest) = (n / 1_000_000, n % 1_000_000);
+    if millions == 0 {
+        return thai_below_million(rest);
+    }
+    format!("{}ล้าน{}", thai_number(millions), thai_below_million(rest))
+}
+
+// ─── Reading ─────────────────────────────────────────────────────────
+
+fn read_number(value: &crate::rational::Rational, phrases: &Phrases) -> String {
+    // Literals are never negative: a minus sign parses as `Neg`.
+    let text = value
+        .to_exact_decimal()
+        .unwrap_or_else(|| value.to_string());
+    let (whole, digits) = text.split_once('.').unwrap_or((&text, ""));
+    let whole = (phrases.number)(whole.parse().unwrap_or(0));
+    if digits.is_empty() {
+        return whole;
+    }
+    let digits: Vec<String> = digits
+        .chars()
+        .filter_map(|d| d.to_digit(10))
+        .map(|d| (phrases.number)(u64::from(d)))
+        .collect();
+    format!("{} {} {}", whole, phrases.point, digits.join(" "))
+}
+
+/// A non-negative integer literal's value.
+fn whole_number(expr: &Expr) -> Option<u64> {
+    match expr {
+        Expr::Num(n) if n.is_integer() => u64::try_from(n.numer()).ok(),
+        _ => None,
+    }
+}
+
+fn read(expr: &Expr, phrases: &Phrases) -> String {
+    // An operand that would be parenthesized on screen.
+    let grouped = |inner: &Expr, group: bool| {
+        let text = read(inner, phrases);
+        if group {
+            fill(phrases.quantity, &text, "")
+        } else {
+            text
+        }
+    };
+    match expr {
+        Expr::Num(n) => read_number(n, phrases),
+        Expr::Var(name) if name == "pi" => phrases.pi.to_string(),
+        Expr::Var(name) => name.clone(),
+        Expr::Neg(inner) => fill(
+            phrases.negative,
+            &grouped(inner, precedence(inner) < Precedence::Negation),
+            "",
+        ),
+        Expr::Call(func, arg) => {
+            let template = match func {
+                Func::Sqrt => phrases.square_root,
+                Func::Abs => phrases.absolute_value,
+            };
+            fill(
+                template,
+                &grouped(arg, precedence(arg) < Precedence::Atom),
+                "",
+            )
+        }
+        Expr::Bin(BinOp::Div, left, right) => {
+            if let (Some(n), Some(d)) = (whole_number(left), whole_number(right)) {
+                if let Some(words) = (phrases.fraction)(n, d) {
+                    return words;
+                }
+            }
+            let simple = |e: &Expr| matches!(e, Expr::Num(_) | Expr::Var(_));
+            let template = if simple(left) && simple(right) {
+                phrases.over
+            } else {
+                phrases.compound_fraction
+            };
+            fill(template, &read(left, phrases), &read(right, phrases))
+        }
+        Expr::Bin(BinOp::Pow, base, exponent) => {
+            let base_text = grouped(base, precedence(base) < Precedence::Atom);
+            match whole_number(exponent) {
+                Some(2) => fill(phrases.squared, &base_text, ""),
+                Some(3) => fill(phrases.cubed, &base_text, ""),
+                _ => fill(phrases.power, &base_text, &read(exponent, phrases)),
+            }
+        }
+        Expr::Bin(op, left, right) => {
+            let own = precedence(expr);
+            let l = grouped(left, precedence(left) < own);
+            let r = grouped(
+                right,
+                precedence(right) <= own || matches!(**right, Expr::Neg(_)),
+            );
+            // "two x", not "two times x".
+            if *op == BinOp::Mul && matches!(**left, Expr::Num(_)) && starts_with_letter(right) {
+                return format!("{} {}", l, r);
+            }
+            let template = match op {
+                BinOp::Add => phrases.plus,
+                BinOp::Sub => phrases.minus,
+                _ => phrases.times,
+            };
+            fill(template, &l, &r)
+        }
+    }
+}
+
+/// One side of a statement; "?" and blanks are the unknown.
+fn read_side(text: &str, phrases: &Phrases) -> Result<String, expr::ParseError> {
+    let text = text.trim();
+    if text == "?" || (!text.is_empty() && text.chars().all(|c| c == '_')) {
+        return Ok(phrases.unknown.to_string());
+    }
+    expr::parse(text).map(|parsed| read(&parsed, phrases))
+}
+
+fn relation_index(relation: Relation) -> usize {
+    match relation {
+        Relation::Less => 0,
+        Relation::LessOrEqual => 1,
+        Relation::Equal => 2,
+        Relation::NotEqual => 3,
+        Relation::GreaterOrEqual => 4,
+        Relation::Greater => 5,
+    }
+}
+
+/// Read an expression or statement aloud.
+///
+/// `expr` is an expression ("2/3 + 1/2") or a statement with one
+/// relation ("2/3 + 1/2 = ?", "3/4 > 2/3"); a side that is "?" or a
+/// blank ("__") is read as the unknown. LaTeX input is accepted.
+/// `locale` is a tag like "es" or "th-TH", or empty for the current
+/// locale (see `set_locale`); unsupported locales read in English.
+///
+/// Returns `{"text","locale"}` or
+/// `{"error":"parse_error","message","position"}` (position within the
+/// side that failed).
+#[wasm_bindgen]
+pub fn read_aloud(expr: &str, locale: &str) -> String {
+    let tag = if locale.trim().is_empty() {
+        i18n::get_locale()
+    } else {
+        locale.to_string()
+    };
+    let primary = tag
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let phrases = LOCALES
+        .iter()
+        .find(|p| p.tag == primary)
+        .copied()
+        .unwrap_or(&ENGLISH);
+
+    let plain = latex::to_plain(expr);
+    let reading = match compare::split_statement(&plain) {
+        Some((left, relation, right)) => read_side(left, phrases).and_then(|l| {
+            let r = read_side(right, phrases)?;
+            Ok(format!(
+                "{} {} {}",
+                l,
+                phrases.relations[relation_index(relation)],
+                r
+            ))
+        }),
+        None => read_side(&plain, phrases),
+    };
+    version::result(match reading {
+        Ok(text) => json!({ "text": text, "locale": phrases.tag }),
+        Err(error) => json!({
+            "error": "parse_error",
+            "message": error.message,
+            "position": error.position,
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn text(expr: &str, locale: &str) -> String {
+        let result: Value = serde_json::from_str(&read_aloud(expr, locale)).unwrap();
+        result["text"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_english_readings() {
+        let cases = [
+            ("2/3 + 1/2 = ?", "two thirds plus one half equals what"),
+            ("3/4 > 2/3", "three fourths is greater than two thirds"),
+            ("2x^2 - 5", "two x squared minus five"),
+            (
+                "(x + 1)/2",
+                "the fraction with numerator x plus one and denominator two",
+            ),
+            ("3(x + 1)", "three times the quantity x plus one"),
+            ("-(4 - 7)", "negative the quantity four minus seven"),
+            (
+                "sqrt(16) * 0.25",
+                "the square root of sixteen times zero point two five",
+            ),
+            ("x/y", "x over y"),
+            ("2^10", "two to the power of ten"),
+            ("1/21 + 5/12", "one twenty-first plus five twelfths"),
+            ("7 - 2 = __", "seven minus two equals what"),
+            (r"\frac{1}{2}", "one half"),
+        ];
+        for (expr, reading) in cases {
+            assert_eq!(text(expr, "en"), reading, "{}", expr);
+        }
+    }
+
+    #[test]
+    fn test_number_words() {
+        assert_eq!(english_number(0), "zero");
+        assert_eq!(english_number(115), "one hundred fifteen");
+        assert_eq!(english_number(2_000_042), "two million forty-two");
+        assert_eq!(spanish_number(21_000), "veintiún mil");
+        assert_eq!(spanish_number(1_101), "mil ciento uno");
+        assert_eq!(spanish_number(2_500_000), "dos millones quinientos mil");
+        assert_eq!(thai_number(21), "ยี่สิบเอ็ด");
+        assert_eq!(thai_number(111), "หนึ่งร้อยสิบเอ็ด");
+        assert_eq!(thai_number(1_000_001), "หนึ่งล้านหนึ่ง");
+        assert_eq!(thai_number(10), "สิบ");
+    }
+
+    #[test]
+    fn test_other_locales() {
+        assert_eq!(
+            text("2/3 + 1/2 = ?", "es-MX"),
+            "dos tercios más un medio es igual a cuánto"
+        );
+        assert_eq!(text("5/12", "es"), "cinco sobre doce");
+        assert_eq!(text("1/2 + 3", "th"), "หนึ่ง ส่วน สอง บวก สาม");
+        // Unsupported locales read in English.
+        assert!(read_aloud("1 + 1", "fr").contains(r#""locale":"en""#));
+        assert!(read_aloud("1 + ", "en").contains("parse_error"));
+    }
+}
@@ -0,0 +1,163 @@
+// ─── Temperature Conversion ──────────────────────────────────────────
+//
+// Celsius ↔ Fahrenheit ↔ Kelvin with exact rational arithmetic:
+//   F = C × 9/5 + 32        K = C + 273.15
+// Equivalent fraction and decimal answers ("-110/9", "-12 2/9") are
+// both accepted; non-terminating results also accept a decimal rounded
+// to the precision the student wrote (at least one place).
+
+use wasm_bindgen::prelude::*;
+
+use crate::rational::Rational;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+impl TemperatureUnit {
+    fn parse(text: &str) -> Option<TemperatureUnit> {
+        let t = text
+            .trim()
+            .trim_start_matches('°')
+            .trim()
+            .to_ascii_lowercase();
+        match t.as_str() {
+            "c" | "celsius" => Some(TemperatureUnit::Celsius),
+            "f" | "fahrenheit" => Some(TemperatureUnit::Fahrenheit),
+            "k" | "kelvin" => Some(TemperatureUnit::Kelvin),
+            _ => None,
+        }
+    }
+
+    /// Split an answer like "77°F" into its value and (optional) unit.
+    fn split_suffix(text: &str) -> (&str, Option<TemperatureUnit>) {
+        let (value, unit) = split_temperature(text);
+        match TemperatureUnit::parse(unit) {
+            Some(parsed) => (value, Some(parsed)),
+            None => (text.trim(), None),
+        }
+    }
+}
+
+/// Split "77°F" / "77 F" / "300 K" into ("77", "°F").
+pub(crate) fn split_temperature(text: &str) -> (&str, &str) {
+    let t = text.trim();
+    let split = t
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphabetic() || *c == '°')
+        .last()
+        .map(|(i, _)| i);
+    match split {
+        Some(i) if i > 0 => (t[..i].trim_end(), &t[i..]),
+        _ => (t, ""),
+    }
+}
+
+/// 273.15 as an exact rational.
+fn kelvin_offset() -> Option<Rational> {
+    Rational::new(27315, 100)
+}
+
+fn to_celsius(value: Rational, unit: TemperatureUnit) -> Option<Rational> {
+    match unit {
+        TemperatureUnit::Celsius => Some(value),
+        TemperatureUnit::Fahrenheit => value
+            .checked_sub(Rational::from_integer(32))?
+            .checked_mul(Rational::new(5, 9)?),
+        TemperatureUnit::Kelvin => value.checked_sub(kelvin_offset()?),
+    }
+}
+
+fn from_celsius(celsius: Rational, unit: TemperatureUnit) -> Option<Rational> {
+    match unit {
+        TemperatureUnit::Celsius => Some(celsius),
+        TemperatureUnit::Fahrenheit => celsius
+            .checked_mul(Rational::new(9, 5)?)?
+            .checked_add(Rational::from_integer(32)),
+        TemperatureUnit::Kelvin => celsius.checked_add(kelvin_offset()?),
+    }
+}
+
+/// Exact converted value, or `None` for bad input / below absolute zero.
+fn convert(value: &str, from: &str, to: &str) -> Option<(Rational, TemperatureUnit)> {
+    let from = TemperatureUnit::parse(from)?;
+    let to = TemperatureUnit::parse(to)?;
+    let celsius = to_celsius(Rational::parse(value)?, from)?;
+    if celsius < kelvin_offset()?.checked_neg()? {
+        return None;
+    }
+    Some((from_celsius(celsius, to)?, to))
+}
+
+/// Number of digits after the decimal point in a plain decimal answer.
+fn decimal_places(text: &str) -> Option<u32> {
+    let (_, frac) = text.split_once('.')?;
+    Some(frac.len() as u32)
+}
+
+/// Validate a temperature conversion: "25" °C → °F is "77".
+/// The answer may carry the target unit ("77°F"); any other unit fails.
+#[wasm_bindgen]
+pub fn validate_temperature_conversion(
+    value: &str,
+    from: &str,
+    to: &str,
+    student_answer: &str,
+) -> bool {
+    let Some((expected, target)) = convert(value, from, to) else {
+        return false;
+    };
+
+    let (answer_text, unit) = TemperatureUnit::split_suffix(student_answer);
+    if unit.is_some_and(|u| u != target) {
+        return false;
+    }
+    let Some(answer) = Rational::parse(answer_text) else {
+        return false;
+    };
+
+    if answer == expected {
+        return true;
+    }
+    if expected.is_terminating() {
+        return false;
+    }
+    match decimal_places(answer_text) {
+        Some(places) if places >= 1 => expected.round_to(places) == Some(answer),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_celsius_fahrenheit_exact() {
+        assert!(validate_temperature_conversion("25", "C", "F", "77"));
+        assert!(validate_temperature_conversion("77", "°F", "°C", "25°C"));
+        assert!(validate_temperature_conversion("-40", "C", "F", "-40"));
+        assert!(!validate_temperature_conversion("25", "C", "F", "77°C"));
+    }
+
+    #[test]
+    fn test_equivalent_forms() {
+        // 10 °F = -110/9 °C = -12.222…
+        assert!(validate_temperature_conversion("10", "F", "C", "-110/9"));
+        assert!(validate_temperature_conversion("10", "F", "C", "-12 2/9"));
+        assert!(validate_temperature_conversion("10", "F", "C", "-12.22"));
+        assert!(!validate_temperature_conversion("10", "F", "C", "-12"));
+        assert!(validate_temperature_conversion("36.6", "C", "F", "97.88"));
+    }
+
+    #[test]
+    fn test_kelvin() {
+        assert!(validate_temperature_conversion("0", "C", "K", "273.15"));
+        assert!(validate_temperature_conversion("300", "K", "C", "26.85"));
+        assert!(!validate_temperature_conversion("-300", "C", "K", "-26.85"));
+    }
+}
@@ -0,0 +1,314 @@
+// ─── Problem Templates ───────────────────────────────────────────────
+//
+// A template is one quiz question with placeholders for its numbers.
+// `instantiate` fills them from a student's seed, so classmates get
+// different numbers for the same question — sharing answers doesn't
+// help — while the teacher regenerates every student's exact variant,
+// and its answer key, from the same seeds.
+//
+// The seed is mixed with a hash of the template, so one student seed
+// gives unrelated numbers on every question of a quiz.
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::compare::{evaluate_statement, exact_value};
+use crate::rational::Rational;
+use crate::registry;
+use crate::rng::SplitMix64;
+use crate::version;
+
+/// Draws tried before the `where` constraints count as unsatisfiable.
+const MAX_DRAWS: usize = 100;
+
+/// Widest integer range a variable may draw from.
+const MAX_RANGE: i64 = 1_000_000;
+
+/// How one placeholder gets its value.
+#[derive(Debug, Clone, PartialEq)]
+enum Variable {
+    /// Integers `min..=max` in steps of `step`, optionally skipping 0.
+    Range {
+        min: i64,
+        max: i64,
+        step: i64,
+        nonzero: bool,
+    },
+    /// One of a fixed list of values ("1/2", "0.75", …).
+    Choices(Vec<String>),
+}
+
+impl Variable {
+    fn from_json(spec: &Value) -> Option<Variable> {
+        if let Some(choices) = spec.get("choices") {
+            let choices: Vec<String> = choices
+                .as_array()?
+                .iter()
+                .map(|c| match c {
+                    Value::String(text) => Rational::parse(text).map(|_| text.clone()),
+                    Value::Number(n) => Some(n.to_string()),
+                    _ => None,
+                })
+                .collect::<Option<_>>()?;
+            return (!choices.is_empty()).then_some(Variable::Choices(choices));
+        }
+        let min = spec.get("min")?.as_i64()?;
+        let max = spec.get("max")?.as_i64()?;
+        let step = spec.get("step").map_or(Some(1), Value::as_i64)?;
+        let nonzero = spec
+            .get("nonzero")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        (min <= max && step > 0 && max.checked_sub(min)? <= MAX_RANGE).then_some(Variable::Range {
+            min,
+            max,
+            step,
+            nonzero,
+        })
+    }
+
+    /// Draw a value; `None` when a nonzero range drew 0.
+    fn draw(&self, rng: &mut SplitMix64) -> Option<String> {
+        match self {
+            Variable::Range {
+                min,
+                max,
+                step,
+                nonzero,
+            } => {
+                let count = (max - min) / step + 1;
+                let value = min + step * rng.below(count as u64) as i64;
+                (!nonzero || value != 0).then(|| value.to_string())
+            }
+            Variable::Choices(choices) => {
+                Some(choices[rng.below(choices.len() as u64) as usize].clone())
+            }
+        }
+    }
+}
+
+/// A parsed template.
+#[derive(Debug, Clone, PartialEq)]
+struct Template {
+    problem_type: String,
+    problem: String,
+    answer: String,
+    variables: BTreeMap<String, Variable>,
+    constraints: Vec<String>,
+}
+
+impl Template {
+    fn from_json(payload: &Value) -> Option<Template> {
+        let text = |field: &str| payload.get(field)?.as_str().map(str::to_string);
+        let variables = payload
+            .get("vars")?
+            .as_object()?
+            .iter()
+            .map(|(name, spec)| Some((name.clone(), Variable::from_json(spec)?)))
+            .collect::<Option<BTreeMap<_, _>>>()?;
+        let constraints = match payload.get("where") {
+            None => Vec::new(),
+            Some(list) => list
+                .as_array()?
+                .iter()
+                .map(|c| c.as_str().map(str::to_string))
+                .collect::<Option<_>>()?,
+        };
+        Some(Template {
+            problem_type: text("type")?,
+            problem: text("problem")?,
+            answer: text("answer")?,
+            variables,
+            constraints,
+        })
+    }
+}
+
+/// Replace every `{name}` in `text`. In expressions (`wrap`), negative
+/// values are parenthesized so "{a} - {b}" stays well-formed.
+fn substitute(text: &str, values: &BTreeMap<String, String>, wrap: bool) -> String {
+    let mut out = text.to_string();
+    for (name, value) in values {
+        let value = if wrap && value.starts_with('-') {
+            format!("({value})")
+        } else {
+            value.clone()
+        };
+        out = out.replace(&format!("{{{name}}}"), &value);
+    }
+    out
+}
+
+/// FNV-1a, to mix the template into the student's seed.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+/// Why a template could not be instantiated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TemplateError {
+    InvalidTemplate,
+    UnknownType,
+    /// No draw within `MAX_DRAWS` met the `where` constraints.
+    Unsatisfiable,
+    /// The generated answer key is graded wrong by its validator.
+    AnswerKeyRejected,
+}
+
+impl TemplateError {
+    fn code(self) -> &'static str {
+        match self {
+            TemplateError::InvalidTemplate => "invalid_template",
+            TemplateError::UnknownType => "unknown_type",
+            TemplateError::Unsatisfiable => "constraints_unsatisfiable",
+            TemplateError::AnswerKeyRejected => "answer_key_rejected",
+        }
+    }
+}
+
+/// A filled-in template.
+#[derive(Debug, Clone, PartialEq)]
+struct Variant {
+    problem_type: String,
+    problem: String,
+    answer: String,
+    values: BTreeMap<String, String>,
+}
+
+fn variant(payload: &Value, student_seed: u64) -> Result<Variant, TemplateError> {
+    let template = Template::from_json(payload).ok_or(TemplateError::InvalidTemplate)?;
+    // Canonical JSON (sorted keys), so formatting never changes the numbers.
+    let mut rng = SplitMix64::new(student_seed ^ fnv1a(&payload.to_string()));
+
+    for _ in 0..MAX_DRAWS {
+        let values: Option<BTreeMap<String, String>> = template
+            .variables
+            .iter()
+            .map(|(name, variable)| Some((name.clone(), variable.draw(&mut rng)?)))
+            .collect();
+        let Some(values) = values else {
+            continue;
+        };
+        let satisfied = template
+            .constraints
+            .iter()
+            .all(|c| evaluate_statement(&substitute(c, &values, true)) == Some(true));
+        if !satisfied {
+            continue;
+        }
+
+        let problem = substitute(&template.problem, &values, false);
+        // An expression answer is computed; anything else ("<", "true")
+        // is taken as written.
+        let answer_text = substitute(&template.answer, &values, true);
+        let answer = match exact_value(&answer_text) {
+            Some(value) => value.to_string(),
+            None => substitute(&template.answer, &values, false),
+        };
+        let verdict = registry::check_registered(&template.problem_type, &problem, &answer)
+            .ok_or(TemplateError::UnknownType)?;
+        if !verdict.correct {
+            return Err(TemplateError::AnswerKeyRejected);
+        }
+        return Ok(Variant {
+            problem_type: template.problem_type,
+            problem,
+            answer,
+            values,
+        });
+    }
+    Err(TemplateError::Unsatisfiable)
+}
+
+/// Fill a problem template with numbers derived from a student's seed.
+///
+/// `problem_template`:
+/// `{"type":"arithmetic","problem":"{a} + {b}","answer":"{a} + {b}",
+///   "vars":{"a":{"min":10,"max":99},"b":{"min":10,"max":99,"step":5}},
+///   "where":["{a} > {b}"]}`
+/// A variable is an integer range (`min`, `max`, optional `step` and
+/// `nonzero`) or `{"choices":["1/2","3/4"]}`. `answer` is evaluated
+/// exactly when it is an expression, and used as written otherwise.
+///
+/// Returns `{"type","problem","answer","values":{"a":"42",…}}` — the
+/// same template and seed always give the same variant, and its answer
+/// key is checked with `check_answer`. Errors give `{"error":code}`:
+/// "invalid_template", "unknown_type", "constraints_unsatisfiable", or
+/// "answer_key_rejected".
+#[wasm_bindgen]
+pub fn instantiate(problem_template: &str, student_seed: u64) -> String {
+    let result = serde_json::from_str::<Value>(problem_template)
+        .map_err(|_| TemplateError::InvalidTemplate)
+        .and_then(|payload| variant(&payload, student_seed));
+    version::result(match result {
+        Ok(variant) => json!({
+            "type": variant.problem_type,
+            "problem": variant.problem,
+            "answer": variant.answer,
+            "values": variant.values,
+        }),
+        Err(error) => json!({ "error": error.code() }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUBTRACTION: &str = r#"{"type":"arithmetic","problem":"{a} - {b}","answer":"{a} - {b}",
+        "vars":{"a":{"min":10,"max":99},"b":{"min":10,"max":99}},"where":["{a} > {b}"]}"#;
+
+    fn instance(template: &str, seed: u64) -> Value {
+        serde_json::from_str(&instantiate(template, seed)).unwrap()
+    }
+
+    #[test]
+    fn test_variants_differ_by_seed_but_regenerate() {
+        let first = instance(SUBTRACTION, 7);
+        assert_eq!(instance(SUBTRACTION, 7), first);
+        let answer: i64 = first["answer"].as_str().unwrap().parse().unwrap();
+        assert!(answer > 0, "{first}");
+        let problems: std::collections::BTreeSet<String> = (0..20)
+            .map(|seed| instance(SUBTRACTION, seed)["problem"].to_string())
+            .collect();
+        assert!(problems.len() > 10);
+        // Formatting the template differently keeps every variant.
+        let compact: Value = serde_json::from_str(SUBTRACTION).unwrap();
+        assert_eq!(instance(&compact.to_string(), 7), first);
+    }
+
+    #[test]
+    fn test_choices_literal_answers_and_errors() {
+        let fraction = r#"{"type":"comparison","problem":"{x} ? {y}","answer":"<",
+            "vars":{"x":{"choices":["1/3","1/4"]},"y":{"choices":["1/2","2/3"]}}}"#;
+        assert_eq!(instance(fraction, 3)["answer"], "<");
+        let negative = r#"{"type":"arithmetic","problem":"{a} + {b}","answer":"{a} + {b}",
+            "vars":{"a":{"min":-9,"max":-1},"b":{"min":1,"max":9,"nonzero":true}}}"#;
+        assert!(instance(negative, 1).get("error").is_none());
+
+        let error = |template: &str| instance(template, 0)["error"].clone();
+        assert_eq!(error("{}"), "invalid_template");
+        assert_eq!(
+            error(
+                r#"{"type":"arithmetic","problem":"{a}","answer":"{a}","vars":{"a":{"min":1,"max":2}},"where":["{a} > 5"]}"#
+            ),
+            "constraints_unsatisfiable"
+        );
+        assert_eq!(
+            error(
+                r#"{"type":"arithmetic","problem":"{a} + 1","answer":"{a}","vars":{"a":{"min":1,"max":2}}}"#
+            ),
+            "answer_key_rejected"
+        );
+        assert_eq!(
+            error(
+                r#"{"type":"nope","problem":"{a}","answer":"{a}","vars":{"a":{"min":1,"max":2}}}"#
+            ),
+            "unknown_type"
+        );
+    }
+}
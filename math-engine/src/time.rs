@@ -0,0 +1,239 @@
+// ─── Time Arithmetic ─────────────────────────────────────────────────
+//
+// Elapsed time, clock reading, and hour/minute addition with carrying.
+// Every value is converted to whole minutes (i64) before comparing,
+// so there is no floating point anywhere in this module.
+
+use wasm_bindgen::prelude::*;
+
+const MINUTES_PER_HOUR: i64 = 60;
+const HALF_DAY: i64 = 12 * MINUTES_PER_HOUR;
+const FULL_DAY: i64 = 24 * MINUTES_PER_HOUR;
+
+/// A clock time in minutes after midnight, plus whether it was written
+/// with an explicit am/pm marker (which pins it to the 24-hour day).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ClockTime {
+    pub minutes: i64,
+    pub has_meridiem: bool,
+}
+
+/// Parse a clock time: "9:45", "21:05", "11:30 am", "1:15pm".
+pub(crate) fn parse_clock_time(text: &str) -> Option<ClockTime> {
+    let lower = text.trim().to_ascii_lowercase();
+    let (body, meridiem) = if let Some(rest) = lower.strip_suffix("am") {
+        (rest.trim_end(), Some(false))
+    } else if let Some(rest) = lower.strip_suffix("pm") {
+        (rest.trim_end(), Some(true))
+    } else {
+        (lower.as_str(), None)
+    };
+
+    let (h, m) = body.split_once(':')?;
+    let hours = h.trim().parse::<i64>().ok()?;
+    let mins = m.trim();
+    if mins.len() != 2 {
+        return None;
+    }
+    let mins = mins.parse::<i64>().ok()?;
+    if !(0..60).contains(&mins) {
+        return None;
+    }
+
+    let hours = match meridiem {
+        Some(pm) => {
+            if !(1..=12).contains(&hours) {
+                return None;
+            }
+            (hours % 12) + if pm { 12 } else { 0 }
+        }
+        None => {
+            if !(0..24).contains(&hours) {
+                return None;
+            }
+            hours
+        }
+    };
+
+    Some(ClockTime {
+        minutes: hours * MINUTES_PER_HOUR + mins,
+        has_meridiem: meridiem.is_some(),
+    })
+}
+
+/// Parse a duration into whole minutes: "1:35", "1h 35m", "1 hr 35 min",
+/// "95 min", or a bare "95" (minutes).
+pub(crate) fn parse_duration(text: &str) -> Option<i64> {
+    let lower = text.trim().to_ascii_lowercase();
+    if lower.is_empty() {
+        return None;
+    }
+
+    if let Some((h, m)) = lower.split_once(':') {
+        let hours = h.trim().parse::<i64>().ok()?;
+        let mins = m.trim().parse::<i64>().ok()?;
+        if hours < 0 || !(0..60).contains(&mins) {
+            return None;
+        }
+        return hours.checked_mul(MINUTES_PER_HOUR)?.checked_add(mins);
+    }
+
+    if let Ok(mins) = lower.parse::<i64>() {
+        return if mins >= 0 { Some(mins) } else { None };
+    }
+
+    // Unit form: a sequence of <number><unit> pairs.
+    let mut total = 0i64;
+    let mut number = String::new();
+    let mut unit = String::new();
+
+    let mut flush = |number: &mut String, unit: &mut String| -> Option<()> {
+        if number.is_empty() {
+            return None;
+        }
+        let value = number.parse::<i64>().ok()?;
+        let scale = match unit.as_str() {
+            "h" | "hr" | "hrs" | "hour" | "hours" => MINUTES_PER_HOUR,
+            "m" | "min" | "mins" | "minute" | "minutes" => 1,
+            _ => return None,
+        };
+        total = total.checked_add(value.checked_mul(scale)?)?;
+        number.clear();
+        unit.clear();
+        Some(())
+    };
+
+    for c in lower.chars() {
+        if c.is_ascii_digit() {
+            if !unit.is_empty() {
+                flush(&mut number, &mut unit)?;
+            }
+            number.push(c);
+        } else if c.is_ascii_alphabetic() {
+            unit.push(c);
+        } else if !c.is_whitespace() {
+            return None;
+        }
+    }
+    flush(&mut number, &mut unit)?;
+
+    Some(total)
+}
+
+/// Minutes from `start` to `end`, wrapping past midnight (or noon, for
+/// 12-hour clock times written without am/pm).
+fn elapsed_minutes(start: ClockTime, end: ClockTime) -> i64 {
+    if end.minutes >= start.minutes {
+        return end.minutes - start.minutes;
+    }
+    let twelve_hour = !start.has_meridiem
+        && !end.has_meridiem
+        && start.minutes < 13 * MINUTES_PER_HOUR
+        && end.minutes < 13 * MINUTES_PER_HOUR;
+    let wrap = if twelve_hour { HALF_DAY } else { FULL_DAY };
+    (end.minutes + wrap - start.minutes) % wrap
+}
+
+/// Does `answer` name the clock time `expected_minutes`?
+/// A 12-hour reading without am/pm ("1:20" for 13:20) is accepted.
+fn clock_matches(expected_minutes: i64, answer: &str) -> bool {
+    let Some(answer) = parse_clock_time(answer) else {
+        return false;
+    };
+    let expected = expected_minutes.rem_euclid(FULL_DAY);
+    if answer.minutes == expected {
+        return true;
+    }
+    !answer.has_meridiem
+        && (1..=12).contains(&(answer.minutes / MINUTES_PER_HOUR))
+        && answer.minutes % HALF_DAY == expected % HALF_DAY
+}
+
+/// Validate an elapsed-time answer: from "9:45" to "11:20" is "1:35".
+#[wasm_bindgen]
+pub fn validate_elapsed_time(start: &str, end: &str, student_answer: &str) -> bool {
+    match (
+        parse_clock_time(start),
+        parse_clock_time(end),
+        parse_duration(student_answer),
+    ) {
+        (Some(s), Some(e), Some(answer)) => elapsed_minutes(s, e) == answer,
+        _ => false,
+    }
+}
+
+/// Validate a clock reading from hand positions, both given in minute
+/// ticks (0–59) around the dial: minute hand on 9 and hour hand just
+/// before 4 (tick 18) reads "3:45".
+#[wasm_bindgen]
+pub fn validate_clock_reading(
+    hour_hand_tick: u32,
+    minute_hand_tick: u32,
+    student_answer: &str,
+) -> bool {
+    if hour_hand_tick >= 60 || minute_hand_tick >= 60 {
+        return false;
+    }
+    // The hour hand advances one tick every 12 minutes.
+    let offset = minute_hand_tick / 12;
+    if hour_hand_tick % 5 != offset {
+        return false; // Hands are inconsistent — not a real clock face
+    }
+    let hour = match (hour_hand_tick - offset) / 5 {
+        0 => 12,
+        h => h,
+    };
+    let expected = hour as i64 * MINUTES_PER_HOUR + minute_hand_tick as i64;
+    clock_matches(expected, student_answer)
+}
+
+/// Validate clock-time addition with carrying: "9:45" + "1:35" = "11:20".
+#[wasm_bindgen]
+pub fn validate_time_addition(start: &str, duration: &str, student_answer: &str) -> bool {
+    match (parse_clock_time(start), parse_duration(duration)) {
+        (Some(s), Some(d)) => match s.minutes.checked_add(d) {
+            Some(end) => clock_matches(end, student_answer),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_forms() {
+        assert_eq!(parse_duration("1:35"), Some(95));
+        assert_eq!(parse_duration("1h 35m"), Some(95));
+        assert_eq!(parse_duration("1 hr 35 min"), Some(95));
+        assert_eq!(parse_duration("95"), Some(95));
+        assert_eq!(parse_duration("2 hours"), Some(120));
+        assert_eq!(parse_duration("1:75"), None);
+        assert_eq!(parse_duration("soon"), None);
+    }
+
+    #[test]
+    fn test_elapsed_time_wraps() {
+        assert!(validate_elapsed_time("9:45", "11:20", "1:35"));
+        assert!(validate_elapsed_time("11:30", "1:15", "1:45"));
+        assert!(validate_elapsed_time("11:30 pm", "1:15 am", "1h 45m"));
+        assert!(!validate_elapsed_time("9:45", "11:20", "2:35"));
+    }
+
+    #[test]
+    fn test_clock_reading() {
+        assert!(validate_clock_reading(18, 45, "3:45"));
+        assert!(validate_clock_reading(0, 0, "12:00"));
+        assert!(!validate_clock_reading(15, 45, "3:45")); // inconsistent hands
+    }
+
+    #[test]
+    fn test_time_addition_carries() {
+        assert!(validate_time_addition("9:45", "1:35", "11:20"));
+        assert!(validate_time_addition("11:50", "0:20", "12:10"));
+        assert!(validate_time_addition("12:50", "0:30", "1:20"));
+        assert!(!validate_time_addition("9:45", "1:35", "10:80"));
+    }
+}
@@ -0,0 +1,459 @@
+// ─── Built-in Validators ─────────────────────────────────────────────
+//
+// One `ProblemValidator` per built-in problem type. Each parses the
+// problem text for its type and delegates to the matching
+// `validate_*` export, so the WASM API and `check_answer` agree.
+
+use std::cmp::Ordering;
+
+use serde_json::Value;
+
+use crate::compare::{self, Relation};
+use crate::fill_blank;
+use crate::hints::{self, Hint};
+use crate::list::{self, ListProblem};
+use crate::rational::Rational;
+use crate::registry::{ProblemValidator, Verdict};
+use crate::temperature::split_temperature;
+use crate::{
+    validate_arithmetic, validate_comparison, validate_elapsed_time, validate_fraction,
+    validate_money_sum, validate_temperature_conversion, validate_time_addition,
+    validate_true_false, validate_unit_price,
+};
+
+fn invalid_format() -> Verdict {
+    Verdict::new(false, &hints::INVALID_FORMAT, &[])
+}
+
+fn verdict(correct: bool, hint: &Hint) -> Verdict {
+    Verdict::graded(correct, hint, &[])
+}
+
+/// The numbers written in a problem ("$3.07 + $1.95" → "$3.07", "$1.95"),
+/// for hints that point at operands. A '-' at the start, or after an
+/// operator or '(', is a sign and stays with its number ("-3 + 5" →
+/// "-3", "5").
+fn numbers(problem: &str) -> Vec<&str> {
+    let is_part = |c: char| c.is_ascii_digit() || c == '.' || c == '$';
+    let mut tokens = Vec::new();
+    let mut start = None;
+    // The last non-space character seen.
+    let mut previous: Option<char> = None;
+    for (i, c) in problem.char_indices() {
+        if !is_part(c) {
+            if let Some(s) = start.take() {
+                tokens.push(&problem[s..i]);
+            }
+            let sign = c == '-'
+                && previous.is_none_or(|p| "+-*/×÷^=<>(,;".contains(p))
+                && problem[i + 1..].starts_with(is_part);
+            if sign {
+                start = Some(i);
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+        if !c.is_whitespace() {
+            previous = Some(c);
+        }
+    }
+    tokens.extend(start.map(|s| &problem[s..]));
+    tokens.retain(|token| token.bytes().any(|b| b.is_ascii_digit()));
+    tokens
+}
+
+pub(crate) struct Arithmetic;
+
+impl ProblemValidator for Arithmetic {
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        let answer: f64 = student_answer.parse().unwrap_or(f64::NAN);
+        let correct = validate_arithmetic(problem, answer);
+        Verdict::graded(
+            correct,
+            &hints::ARITHMETIC_STEP_BY_STEP,
+            &[("problem", problem)],
+        )
+        .with_operands(&numbers(problem))
+    }
+
+    // Second path: the full expression parser instead of the
+    // two-operand splitter.
+    fn cross_check(&self, problem: &str, student_answer: &str) -> Option<bool> {
+        let expected = compare::float_value(problem)?;
+        let answer: f64 = student_answer.parse().unwrap_or(f64::NAN);
+        Some((expected - answer).abs() < 1e-9)
+    }
+}
+
+pub(crate) struct Fraction;
+
+impl ProblemValidator for Fraction {
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        let parts: Vec<&str> = student_answer.split('/').collect();
+        if parts.len() != 2 {
+            return Verdict::new(false, &hints::FRACTION_ENTER_AS_FRACTION, &[]);
+        }
+        let num = parts[0].trim().parse::<i64>().unwrap_or(0);
+        let den = parts[1].trim().parse::<i64>().unwrap_or(0);
+        let prob_parts: Vec<&str> = problem.split('/').collect();
+        if prob_parts.len() != 2 {
+            return invalid_format();
+        }
+        let exp_num = prob_parts[0].trim().parse::<i64>().unwrap_or(0);
+        let exp_den = prob_parts[1].trim().parse::<i64>().unwrap_or(0);
+        let correct = validate_fraction(exp_num, exp_den, num, den);
+        verdict(correct, &hints::FRACTION_SIMPLIFY).with_operands(&[problem])
+    }
+
+    // Second path: reduce both with `Rational` instead of cross-multiplying.
+    fn cross_check(&self, problem: &str, student_answer: &str) -> Option<bool> {
+        let fraction = |text: &str| {
+            let (num, den) = text.split_once('/')?;
+            Rational::new(num.trim().parse().ok()?, den.trim().parse().ok()?)
+        };
+        let expected = fraction(problem)?;
+        Some(fraction(student_answer) == Some(expected))
+    }
+}
+
+pub(crate) struct ElapsedTime;
+
+impl ProblemValidator for ElapsedTime {
+    // Problem: "9:45 to 11:20" (optionally prefixed with "from")
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        let trimmed = problem.trim();
+        let trimmed = trimmed.strip_prefix("from").unwrap_or(trimmed);
+        match trimmed.split_once(" to ") {
+            Some((start, end)) => verdict(
+                validate_elapsed_time(start, end, student_answer),
+                &hints::ELAPSED_TIME_COUNT_UP,
+            )
+            .with_operands(&[start, end]),
+            None => invalid_format(),
+        }
+    }
+}
+
+pub(crate) struct TimeAddition;
+
+impl ProblemValidator for TimeAddition {
+    // Problem: "9:45 + 1:35"
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        match problem.split_once('+') {
+            Some((start, duration)) => verdict(
+                validate_time_addition(start, duration, student_answer),
+                &hints::TIME_ADDITION_CARRY,
+            )
+            .with_operands(&[start, duration]),
+            None => invalid_format(),
+        }
+    }
+}
+
+pub(crate) struct Money;
+
+impl ProblemValidator for Money {
+    // Problem: "$3.07 + $1.95" or "$10.00 - $3.07"
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        verdict(
+            validate_money_sum(problem, student_answer),
+            &hints::MONEY_WORK_IN_CENTS,
+        )
+        .with_operands(&numbers(problem))
+    }
+}
+
+pub(crate) struct UnitPrice;
+
+impl ProblemValidator for UnitPrice {
+    // Problem: "$3.60 / 4"
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        let Some((total, quantity)) = problem.split_once('/') else {
+            return invalid_format();
+        };
+        match quantity.trim().parse::<u32>() {
+            Ok(quantity) => verdict(
+                validate_unit_price(total, quantity, student_answer),
+                &hints::UNIT_PRICE_DIVIDE,
+            )
+            .with_operands(&[total.trim(), &quantity.to_string()]),
+            Err(_) => invalid_format(),
+        }
+    }
+}
+
+pub(crate) struct Temperature;
+
+impl ProblemValidator for Temperature {
+    // Problem: "25 C to F" or "77°F to °C"
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        match problem.split_once(" to ") {
+            Some((from, to)) => {
+                let (value, from_unit) = split_temperature(from);
+                verdict(
+                    validate_temperature_conversion(value, from_unit, to, student_answer),
+                    &hints::TEMPERATURE_FORMULA,
+                )
+                .with_operands(&[from])
+            }
+            None => invalid_format(),
+        }
+    }
+}
+
+pub(crate) struct MultipleChoice;
+
+/// One option: a plain string, or `{"text": …, "misconception": …}`
+/// (`"answer"` is accepted for `text`, matching `generate_distractors`).
+fn option_parts(option: &Value) -> Option<(&str, Option<&str>)> {
+    match option {
+        Value::String(text) => Some((text, None)),
+        Value::Object(fields) => {
+            let text = fields
+                .get("text")
+                .or_else(|| fields.get("answer"))?
+                .as_str()?;
+            Some((text, fields.get("misconception").and_then(Value::as_str)))
+        }
+        _ => None,
+    }
+}
+
+/// Which option the student chose: a 0-based index ("1"), a letter
+/// ("B"), or the option's text.
+fn chosen_index(options: &[(&str, Option<&str>)], answer: &str) -> Option<usize> {
+    let answer = answer.trim();
+    if let Some(index) = answer.parse::<usize>().ok().filter(|&i| i < options.len()) {
+        return Some(index);
+    }
+    if let [letter] = answer.as_bytes() {
+        let index = letter.to_ascii_uppercase().wrapping_sub(b'A') as usize;
+        if letter.is_ascii_alphabetic() && index < options.len() {
+            return Some(index);
+        }
+    }
+    options.iter().position(|(text, _)| text.trim() == answer)
+}
+
+impl ProblemValidator for MultipleChoice {
+    // Problem: {"options":["72",{"text":"62","misconception":"forgotten_carry"}],"correct":0}
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        let Ok(payload) = serde_json::from_str::<Value>(problem) else {
+            return invalid_format();
+        };
+        let options = payload.get("options").and_then(Value::as_array);
+        let Some(options) = options
+            .and_then(|list| list.iter().map(option_parts).collect::<Option<Vec<_>>>())
+            .filter(|list| !list.is_empty())
+        else {
+            return invalid_format();
+        };
+        let correct = payload.get("correct").and_then(Value::as_u64);
+        let Some(correct) = correct.map(|c| c as usize).filter(|&c| c < options.len()) else {
+            return invalid_format();
+        };
+        let Some(chosen) = chosen_index(&options, student_answer) else {
+            return Verdict::new(false, &hints::MULTIPLE_CHOICE_CHOOSE_OPTION, &[]);
+        };
+        if chosen == correct {
+            return Verdict::new(true, &hints::CORRECT, &[]).at_step(chosen);
+        }
+
+        let misconception = options[chosen].1;
+        let hint = misconception
+            .and_then(hints::for_misconception)
+            .unwrap_or(hints::MULTIPLE_CHOICE_TRY_AGAIN);
+        Verdict {
+            misconception: misconception.map(str::to_string),
+            ..Verdict::new(false, &hint, &[])
+                .at_step(chosen)
+                .with_operands(&[options[chosen].0])
+        }
+    }
+}
+
+pub(crate) struct TrueFalse;
+
+impl ProblemValidator for TrueFalse {
+    // Problem: "3/4 > 2/3"; answer "true" or "false"
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        if compare::evaluate_statement(problem).is_none() {
+            return invalid_format();
+        }
+        if compare::parse_truth(student_answer).is_none() {
+            return Verdict::new(false, &hints::TRUE_FALSE_ANSWER, &[]);
+        }
+        let sides = compare::split_statement(problem)
+            .map(|(left, _, right)| vec![left, right])
+            .unwrap_or_default();
+        verdict(
+            validate_true_false(problem, student_answer),
+            &hints::TRUE_FALSE_EACH_SIDE,
+        )
+        .with_operands(&sides)
+    }
+
+    fn cross_check(&self, problem: &str, student_answer: &str) -> Option<bool> {
+        let truth = compare::evaluate_statement_f64(problem)?;
+        Some(compare::parse_truth(student_answer)? == truth)
+    }
+}
+
+pub(crate) struct Comparison;
+
+impl ProblemValidator for Comparison {
+    // Problem: "3/4 ? 2/3"; answer "<", ">" or "="
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        let Some((left, right)) = problem.split_once('?') else {
+            return invalid_format();
+        };
+        if compare::exact_value(left).is_none() || compare::exact_value(right).is_none() {
+            return invalid_format();
+        }
+        if Relation::parse(student_answer).is_none() {
+            return Verdict::new(false, &hints::COMPARISON_CHOOSE_SYMBOL, &[]);
+        }
+        verdict(
+            validate_comparison(left, right, student_answer),
+            &hints::COMPARISON_COMMON_DENOMINATOR,
+        )
+        .with_operands(&[left, right])
+    }
+
+    fn cross_check(&self, problem: &str, student_answer: &str) -> Option<bool> {
+        let (left, right) = problem.split_once('?')?;
+        let ordering =
+            compare::float_ordering(compare::float_value(left)?, compare::float_value(right)?);
+        Some(matches!(
+            (Relation::parse(student_answer)?, ordering),
+            (Relation::Less, Ordering::Less)
+                | (Relation::Equal, Ordering::Equal)
+                | (Relation::Greater, Ordering::Greater)
+        ))
+    }
+}
+
+pub(crate) struct ListAnswer;
+
+impl ProblemValidator for ListAnswer {
+    // Problem: {"items":["1","2","3","6"],"ordered":false}; answer "1, 2, 3, 6"
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        let Some(list) = ListProblem::from_json(problem) else {
+            return invalid_format();
+        };
+        let Some(given) = list::split_answer(student_answer) else {
+            return Verdict::new(false, &hints::LIST_SEPARATE_ITEMS, &[]);
+        };
+        list.grade(&given).verdict()
+    }
+}
+
+pub(crate) struct FillBlank;
+
+impl ProblemValidator for FillBlank {
+    // Problem: "7 × __ = 56" or "__ + __ = 10"; answer "8" or "3, 7"
+    fn check(&self, problem: &str, student_answer: &str) -> Verdict {
+        let blanks = fill_blank::template_parts(problem).len() - 1;
+        if blanks == 0 {
+            return invalid_format();
+        }
+        let values = fill_blank::blank_values(student_answer).filter(|v| v.len() == blanks);
+        let Some(values) = values else {
+            let count = blanks.to_string();
+            return Verdict::new(
+                false,
+                &hints::FILL_BLANK_ONE_PER_BLANK,
+                &[("count", &count)],
+            );
+        };
+        match fill_blank::fill_and_check(problem, &values) {
+            Some(correct) => {
+                verdict(correct, &hints::FILL_BLANK_CHECK).with_operands(&numbers(problem))
+            }
+            None => invalid_format(),
+        }
+    }
+
+    fn cross_check(&self, problem: &str, student_answer: &str) -> Option<bool> {
+        fill_blank::fill_and_check_f64(problem, student_answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROBLEM: &str =
+        r#"{"options":["72",{"text":"62","misconception":"forgotten_carry"},"-72"],"correct":0}"#;
+
+    #[test]
+    fn test_multiple_choice_reports_misconception() {
+        let verdict = MultipleChoice.check(PROBLEM, "1");
+        assert!(!verdict.correct);
+        assert_eq!(verdict.misconception.as_deref(), Some("forgotten_carry"));
+        assert_eq!(verdict.hint_id, "misconception.forgotten_carry");
+
+        assert!(MultipleChoice.check(PROBLEM, "A").correct);
+        assert!(MultipleChoice.check(PROBLEM, "72").correct);
+        let verdict = MultipleChoice.check(PROBLEM, "c");
+        assert_eq!(verdict.hint_id, "multiple_choice.try_again");
+        assert_eq!(verdict.misconception, None);
+    }
+
+    #[test]
+    fn test_multiple_choice_bad_input() {
+        assert_eq!(
+            MultipleChoice.check(PROBLEM, "7").hint_id,
+            "multiple_choice.choose_option"
+        );
+        let bad = r#"{"options":["1","2"],"correct":5}"#;
+        assert_eq!(MultipleChoice.check(bad, "0").hint_id, "invalid_format");
+    }
+
+    #[test]
+    fn test_true_false_and_comparison() {
+        assert!(TrueFalse.check("3/4 > 2/3", "true").correct);
+        assert_eq!(
+            TrueFalse.check("3/4 > 2/3", "false").hint_id,
+            "true_false.each_side"
+        );
+        assert_eq!(
+            TrueFalse.check("3/4 > 2/3", "?").hint_id,
+            "true_false.answer"
+        );
+        assert_eq!(TrueFalse.check("3/4", "true").hint_id, "invalid_format");
+
+        assert!(Comparison.check("3/4 ? 2/3", ">").correct);
+        let verdict = Comparison.check("3/4 ? 2/3", "<");
+        assert_eq!(verdict.hint_id, "comparison.common_denominator");
+        assert_eq!(
+            Comparison.check("3/4 ? 2/3", "bigger").hint_id,
+            "comparison.choose_symbol"
+        );
+    }
+
+    #[test]
+    fn test_numbers_keep_signs() {
+        assert_eq!(numbers("$3.07 + $1.95"), ["$3.07", "$1.95"]);
+        assert_eq!(numbers("-3 + 5"), ["-3", "5"]);
+        assert_eq!(numbers("5 - 3"), ["5", "3"]);
+        assert_eq!(numbers("5-3"), ["5", "3"]);
+        assert_eq!(numbers("5 - -3"), ["5", "-3"]);
+        assert_eq!(numbers("4 × (-2)"), ["4", "-2"]);
+        assert_eq!(numbers("-$2.50 + $1"), ["-$2.50", "$1"]);
+        assert_eq!(Arithmetic.check("-3 + 5", "8").operands, ["-3", "5"]);
+    }
+
+    #[test]
+    fn test_fill_blank_hints() {
+        assert!(FillBlank.check("7 × __ = 56", "8").correct);
+        assert_eq!(
+            FillBlank.check("7 × __ = 56", "7").hint_id,
+            "fill_blank.check"
+        );
+        let verdict = FillBlank.check("__ + __ = 10", "10");
+        assert_eq!(verdict.hint_id, "fill_blank.one_per_blank");
+        assert!(verdict.hint.contains("(2)"));
+        assert_eq!(FillBlank.check("7 × 8 = 56", "8").hint_id, "invalid_format");
+    }
+}
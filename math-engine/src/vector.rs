@@ -0,0 +1,226 @@
+// ─── Vector Arithmetic ───────────────────────────────────────────────
+//
+// Addition, scalar multiplication, magnitude, and dot product on 2-D
+// and 3-D vectors over exact rationals. Vectors arrive as JSON arrays
+// (entries are numbers or fraction strings, as for matrices).
+// Magnitudes are compared exactly by squaring, so radical answers such
+// as "√13", "2√3", or "sqrt(13)/2" are graded without rounding.
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::expr::{self, BinOp, Expr, Func};
+use crate::matrix::rational_from_json;
+use crate::rational::Rational;
+use crate::version;
+
+fn vector_from_json(value: &Value) -> Option<Vec<Rational>> {
+    let entries = value.as_array()?;
+    if !(2..=3).contains(&entries.len()) {
+        return None;
+    }
+    entries.iter().map(rational_from_json).collect()
+}
+
+fn add(a: &[Rational], b: &[Rational]) -> Option<Vec<Rational>> {
+    if a.len() != b.len() {
+        return None;
+    }
+    a.iter().zip(b).map(|(x, y)| x.checked_add(*y)).collect()
+}
+
+fn scale(k: Rational, v: &[Rational]) -> Option<Vec<Rational>> {
+    v.iter().map(|x| k.checked_mul(*x)).collect()
+}
+
+fn dot(a: &[Rational], b: &[Rational]) -> Option<Rational> {
+    if a.len() != b.len() {
+        return None;
+    }
+    a.iter().zip(b).try_fold(Rational::ZERO, |acc, (x, y)| {
+        acc.checked_add(x.checked_mul(*y)?)
+    })
+}
+
+/// Split a constant expression into `coefficient × √radicand`.
+/// Handles products and quotients of rationals and square roots.
+fn radical_parts(e: &Expr) -> Option<(Rational, Rational)> {
+    let constant = |_: &str| None;
+    match e {
+        Expr::Call(Func::Sqrt, arg) => {
+            let radicand = arg.eval_rational(&constant)?;
+            (radicand >= Rational::ZERO).then_some((Rational::ONE, radicand))
+        }
+        Expr::Neg(inner) => {
+            let (c, r) = radical_parts(inner)?;
+            Some((c.checked_neg()?, r))
+        }
+        Expr::Bin(BinOp::Mul, l, r) => {
+            let ((c1, r1), (c2, r2)) = (radical_parts(l)?, radical_parts(r)?);
+            Some((c1.checked_mul(c2)?, r1.checked_mul(r2)?))
+        }
+        Expr::Bin(BinOp::Div, l, r) => {
+            let ((c1, r1), (c2, r2)) = (radical_parts(l)?, radical_parts(r)?);
+            Some((c1.checked_div(c2)?, r1.checked_div(r2)?))
+        }
+        _ => Some((e.eval_rational(&constant)?, Rational::ONE)),
+    }
+}
+
+/// Does `answer` equal √`squared`? Exact forms are compared by
+/// squaring; irrational magnitudes also accept a decimal rounded to the
+/// precision the student wrote (at least two places).
+fn magnitude_matches(squared: Rational, answer: &str) -> bool {
+    let exact = expr::parse(answer).ok().and_then(|e| radical_parts(&e));
+    if let Some((coef, radicand)) = exact {
+        let value_squared = coef.checked_mul(coef).and_then(|c| c.checked_mul(radicand));
+        if coef >= Rational::ZERO && value_squared == Some(squared) {
+            return true;
+        }
+    }
+    if squared.checked_sqrt().is_some() {
+        return false;
+    }
+    let places = answer.trim().split_once('.').map_or(0, |(_, f)| f.len());
+    match Rational::parse(answer) {
+        Some(decimal) if places >= 2 => {
+            let tolerance = 0.5 * 10f64.powi(-(places as i32));
+            (decimal.to_f64() - squared.to_f64().sqrt()).abs() <= tolerance + 1e-12
+        }
+        _ => false,
+    }
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(n) => Some(n.to_string()),
+        Value::String(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn vector_error(error: &str) -> String {
+    version::result(json!({ "correct": false, "error": error }))
+}
+
+/// Validate a vector operation and report wrong components as JSON.
+///
+/// `operation`: "add" | "scale" | "magnitude" | "dot"
+/// `operands_json`: `[u, v]` for add/dot, `[k, v]` for scale, `[v]` for magnitude
+/// `student_json`: the student's vector, or a scalar (number or string
+/// such as `"2√3"`) for magnitude and dot
+///
+/// Returns `{"correct":bool,"wrong_components":[i,…],"expected_dimension":n}`
+/// for vector results, `{"correct":bool}` for scalars, or
+/// `{"correct":false,"error":"…"}`.
+#[wasm_bindgen]
+pub fn validate_vector_operation(
+    operation: &str,
+    operands_json: &str,
+    student_json: &str,
+) -> String {
+    let Ok(Value::Array(operands)) = serde_json::from_str::<Value>(operands_json) else {
+        return vector_error("invalid_operands");
+    };
+    let Ok(student) = serde_json::from_str::<Value>(student_json) else {
+        return vector_error("invalid_answer");
+    };
+
+    let expected = match (operation, operands.as_slice()) {
+        ("add", [u, v]) => match (vector_from_json(u), vector_from_json(v)) {
+            (Some(u), Some(v)) => add(&u, &v),
+            _ => return vector_error("invalid_operands"),
+        },
+        ("scale", [k, v]) => match (rational_from_json(k), vector_from_json(v)) {
+            (Some(k), Some(v)) => scale(k, &v),
+            _ => return vector_error("invalid_operands"),
+        },
+        ("magnitude", [v]) => {
+            let Some(v) = vector_from_json(v) else {
+                return vector_error("invalid_operands");
+            };
+            let Some(squared) = dot(&v, &v) else {
+                return vector_error("overflow");
+            };
+            return match scalar_text(&student) {
+                Some(answer) => {
+                    version::result(json!({ "correct": magnitude_matches(squared, &answer) }))
+                }
+                None => vector_error("invalid_answer"),
+            };
+        }
+        ("dot", [u, v]) => {
+            let (Some(u), Some(v)) = (vector_from_json(u), vector_from_json(v)) else {
+                return vector_error("invalid_operands");
+            };
+            let Some(expected) = dot(&u, &v) else {
+                return vector_error("dimension_mismatch");
+            };
+            return match rational_from_json(&student) {
+                Some(answer) => version::result(json!({ "correct": answer == expected })),
+                None => vector_error("invalid_answer"),
+            };
+        }
+        ("add" | "scale" | "magnitude" | "dot", _) => return vector_error("invalid_operands"),
+        _ => return vector_error("unknown_operation"),
+    };
+
+    let Some(expected) = expected else {
+        return vector_error("dimension_mismatch");
+    };
+    let Some(answer) = vector_from_json(&student) else {
+        return vector_error("invalid_answer");
+    };
+    if answer.len() != expected.len() {
+        return version::result(
+            json!({ "correct": false, "wrong_components": [], "expected_dimension": expected.len(), "error": "wrong_dimension" }),
+        );
+    }
+
+    let wrong: Vec<usize> = (0..expected.len())
+        .filter(|&i| answer[i] != expected[i])
+        .collect();
+    version::result(
+        json!({ "correct": wrong.is_empty(), "wrong_components": wrong, "expected_dimension": expected.len() }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_add_and_scale() {
+        let result = validate_vector_operation("add", "[[1,2,3],[4,5,6]]", "[5,7,10]");
+        assert!(result.contains("\"correct\":false"));
+        assert!(result.contains("\"wrong_components\":[2]"));
+        let result = validate_vector_operation("scale", r#"["1/2",[4,-3]]"#, r#"[2,"-3/2"]"#);
+        assert!(result.contains("\"correct\":true"));
+    }
+
+    #[test]
+    fn test_magnitude_accepts_exact_radicals() {
+        for answer in [r#""√13""#, r#""sqrt(13)""#, r#""3.61""#] {
+            let result = validate_vector_operation("magnitude", "[[2,3]]", answer);
+            assert!(result.contains("\"correct\":true"), "{}", answer);
+        }
+        let result = validate_vector_operation("magnitude", "[[2,2,2]]", r#""2√3""#);
+        assert!(result.contains("\"correct\":true"));
+        let result = validate_vector_operation("magnitude", "[[3,4]]", "5");
+        assert!(result.contains("\"correct\":true"));
+        let result = validate_vector_operation("magnitude", "[[3,4]]", r#""-5""#);
+        assert!(result.contains("\"correct\":false"));
+        let result = validate_vector_operation("magnitude", "[[2,3]]", r#""3.6""#);
+        assert!(result.contains("\"correct\":false"));
+    }
+
+    #[test]
+    fn test_dot_product_and_errors() {
+        let result = validate_vector_operation("dot", "[[1,2,3],[4,-5,6]]", "12");
+        assert!(result.contains("\"correct\":true"));
+        let result = validate_vector_operation("dot", "[[1,2],[1,2,3]]", "5");
+        assert!(result.contains("dimension_mismatch"));
+        let result = validate_vector_operation("cross", "[[1,2],[3,4]]", "[0,0]");
+        assert!(result.contains("unknown_operation"));
+    }
+}
@@ -0,0 +1,166 @@
+// ─── Versions ────────────────────────────────────────────────────────
+//
+// The Fresh frontend and this WASM module ship separately, and saved
+// progress outlives both. So that either side can update first:
+//
+//   - `engine_version()` names the build;
+//   - every result is a JSON object carrying `result_schema_version`,
+//     the shape it follows, at its top level (objects nested in a
+//     result never do);
+//   - a frontend written against an older shape asks for it with
+//     `set_result_schema_version`, as it would pick a locale.
+//
+// Schema history (add a line, and a branch where results are built,
+// whenever a result's shape changes):
+//
+//   1. Results as they were before versioning: no
+//      `result_schema_version`, and list results are bare JSON arrays.
+//   2. `result_schema_version` on every result. List results are
+//      wrapped in an object under one key (see `list`):
+//      `batch_validate` → `{"results":[…]}`, `supported_types` →
+//      `{"types":[…]}`, `generate_distractors` → `{"distractors":[…]}`,
+//      `Session.results` → `{"problems":[…]}`.
+//
+// Some outputs are not results and never carry the field:
+// `hint_catalog` (its keys are hint IDs), `Attempt.hintData` (a piece of
+// `check_answer`'s result), `QuizSession.toJson` (saved state, read
+// back by `fromJson`), and the bare arrays of `supported_locales` and
+// `supported_result_schema_versions` (read before a schema is chosen).
+// MessagePack results carry it like JSON.
+
+use std::cell::Cell;
+
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+/// The newest result schema, used unless the caller asks otherwise.
+pub(crate) const RESULT_SCHEMA_VERSION: u32 = 2;
+
+/// The oldest result schema still produced.
+const OLDEST_RESULT_SCHEMA_VERSION: u32 = 1;
+
+thread_local! {
+    static RESULT_SCHEMA: Cell<u32> = const { Cell::new(RESULT_SCHEMA_VERSION) };
+}
+
+/// The schema results are being produced in.
+pub(crate) fn result_schema() -> u32 {
+    RESULT_SCHEMA.with(Cell::get)
+}
+
+/// Whether `set_result_schema_version` would accept `version`.
+pub(crate) fn is_supported(version: u32) -> bool {
+    (OLDEST_RESULT_SCHEMA_VERSION..=RESULT_SCHEMA_VERSION).contains(&version)
+}
+
+/// `value` with `result_schema_version` added to it if it is an object
+/// and the schema in effect has the field.
+pub(crate) fn stamp(mut value: Value) -> Value {
+    let schema = result_schema();
+    if let (Value::Object(map), 2..) = (&mut value, schema) {
+        map.insert("result_schema_version".to_string(), schema.into());
+    }
+    value
+}
+
+/// A list result in the schema in effect: `items` itself in schema 1,
+/// `{key: items}` (stamped later, like any object) from schema 2.
+pub(crate) fn list(key: &str, items: Value) -> Value {
+    if result_schema() < 2 {
+        return items;
+    }
+    let mut map = serde_json::Map::new();
+    map.insert(key.to_string(), items);
+    Value::Object(map)
+}
+
+/// A result as JSON text, stamped.
+pub(crate) fn result(value: Value) -> String {
+    stamp(value).to_string()
+}
+
+/// `stamp` for a result object already serialized to JSON text: the
+/// field is spliced in without re-parsing.
+pub(crate) fn stamp_text(json: String) -> String {
+    let schema = result_schema();
+    match json.strip_prefix('{') {
+        Some(rest) if schema >= 2 => {
+            let separator = if rest == "}" { "" } else { "," };
+            format!(
+                r#"{{"result_schema_version":{}{}{}"#,
+                schema, separator, rest
+            )
+        }
+        _ => json,
+    }
+}
+
+/// This build's version, e.g. "0.1.0".
+#[wasm_bindgen]
+pub fn engine_version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+/// Produce later results in schema `version`. Returns `false` (and
+/// keeps the current schema) if this engine cannot produce it.
+#[wasm_bindgen]
+pub fn set_result_schema_version(version: u32) -> bool {
+    if !is_supported(version) {
+        return false;
+    }
+    RESULT_SCHEMA.with(|current| current.set(version));
+    true
+}
+
+/// The result schema currently in effect.
+#[wasm_bindgen]
+pub fn get_result_schema_version() -> u32 {
+    result_schema()
+}
+
+/// The result schemas this engine can produce, oldest first, as a JSON
+/// array, e.g. `[1,2]`.
+#[wasm_bindgen]
+pub fn supported_result_schema_versions() -> String {
+    let versions: Vec<u32> = (OLDEST_RESULT_SCHEMA_VERSION..=RESULT_SCHEMA_VERSION).collect();
+    Value::from(versions).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_stamp() {
+        assert_eq!(
+            stamp(json!({"ok": true})),
+            json!({"ok": true, "result_schema_version": 2})
+        );
+        assert_eq!(stamp(json!([{"a": 1}, 2])), json!([{"a": 1}, 2]));
+        assert_eq!(
+            stamp(list("items", json!([1, 2]))),
+            json!({"items": [1, 2], "result_schema_version": 2})
+        );
+        assert_eq!(stamp_text("{}".into()), r#"{"result_schema_version":2}"#);
+        assert_eq!(
+            stamp_text(r#"{"a":1}"#.into()),
+            r#"{"result_schema_version":2,"a":1}"#
+        );
+    }
+
+    #[test]
+    fn test_negotiation() {
+        assert_eq!(engine_version(), env!("CARGO_PKG_VERSION"));
+        assert_eq!(supported_result_schema_versions(), "[1,2]");
+        assert!(!set_result_schema_version(0));
+        assert!(!set_result_schema_version(RESULT_SCHEMA_VERSION + 1));
+        assert_eq!(get_result_schema_version(), RESULT_SCHEMA_VERSION);
+
+        assert!(set_result_schema_version(1));
+        assert_eq!(stamp(json!({"ok": true})), json!({"ok": true}));
+        assert_eq!(stamp_text(r#"{"a":1}"#.into()), r#"{"a":1}"#);
+        assert_eq!(stamp(list("items", json!([1, 2]))), json!([1, 2]));
+        assert!(set_result_schema_version(RESULT_SCHEMA_VERSION));
+    }
+}
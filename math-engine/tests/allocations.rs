@@ -0,0 +1,84 @@
+// ═══════════════════════════════════════════════════════════════════
+// Sovereign Academy — Allocation Counts
+// ═══════════════════════════════════════════════════════════════════
+//
+// A lesson validates on every keystroke, so the boolean validators
+// must not touch the heap: no token lists, no substituted copies of
+// the input, no AST for constant expressions. A counting allocator
+// wraps the system one and counts, on the test's own thread only,
+// every allocation made while a closure runs.
+//
+// Entry points that return JSON allocate their result text; they are
+// held to time budgets instead (tests/perf_budget.rs).
+// ═══════════════════════════════════════════════════════════════════
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use math_validator::*;
+
+struct Counting;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Heap allocations made while `f` runs.
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    ALLOCATIONS.with(|n| n.set(0));
+    COUNTING.with(|c| c.set(true));
+    let result = f();
+    COUNTING.with(|c| c.set(false));
+    drop(result);
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn arithmetic_and_equations_never_allocate() {
+    for (expression, answer) in [("12 * 34", 408.0), ("7", 7.0), ("1 / 0", 0.0), ("2 +", 2.0)] {
+        let count = allocations(|| validate_arithmetic(expression, answer));
+        assert_eq!(count, 0, "{}", expression);
+    }
+    for (equation, x) in [("x * 3 = 12", 4.0), ("x + 0.1 = 0.3", 0.2), ("x = 1 = 1", 1.0)] {
+        let count = allocations(|| validate_equation(equation, x));
+        assert_eq!(count, 0, "{}", equation);
+    }
+}
+
+#[test]
+fn comparisons_never_allocate() {
+    for (left, right, symbol) in [
+        ("3/4", "2/3", ">"),
+        ("2 1/2", "5/2", "="),
+        ("1/3 + 1/6", "sqrt(1/4)", "="),
+        ("2^10", "1000", "<"),
+        ("3 $", "1", ">"),
+    ] {
+        let count = allocations(|| validate_comparison(left, right, symbol));
+        assert_eq!(count, 0, "{} {} {}", left, symbol, right);
+    }
+}
@@ -0,0 +1,119 @@
+// ═══════════════════════════════════════════════════════════════════
+// Sovereign Academy — Performance Budget
+// ═══════════════════════════════════════════════════════════════════
+//
+// A lesson grades on every keystroke, so the hot path has a ceiling:
+// each entry point below must finish one validation within its budget
+// in microseconds. The budgets are release-build figures with roughly
+// 10× headroom over a laptop's timings; debug builds (plain
+// `cargo test`) get DEBUG_SLOWDOWN on top.
+//
+// Set PERF_BUDGET_SCALE to loosen (or tighten) every budget at once on
+// an unusually slow or fast machine, e.g. PERF_BUDGET_SCALE=2.
+// Throughput numbers come from `cargo bench` (benches/validation.rs).
+// ═══════════════════════════════════════════════════════════════════
+
+use std::time::Instant;
+
+use math_validator::*;
+
+/// How much slower an unoptimized build may be.
+const DEBUG_SLOWDOWN: f64 = 10.0;
+
+/// Samples per measurement; the median counts.
+const SAMPLES: usize = 15;
+
+/// Median time of one call to `f` over samples of `calls` calls, in
+/// microseconds.
+fn median_micros(calls: u32, mut f: impl FnMut()) -> f64 {
+    for _ in 0..calls {
+        f();
+    }
+    let mut samples: Vec<f64> = (0..SAMPLES)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..calls {
+                f();
+            }
+            start.elapsed().as_secs_f64() * 1e6 / f64::from(calls)
+        })
+        .collect();
+    samples.sort_by(f64::total_cmp);
+    samples[SAMPLES / 2]
+}
+
+/// The budget in effect for a release-build figure.
+fn budget(release_micros: f64) -> f64 {
+    let scale = std::env::var("PERF_BUDGET_SCALE")
+        .ok()
+        .and_then(|s| s.parse::<f64>().ok())
+        .filter(|s| *s > 0.0)
+        .unwrap_or(1.0);
+    let build = if cfg!(debug_assertions) {
+        DEBUG_SLOWDOWN
+    } else {
+        1.0
+    };
+    release_micros * build * scale
+}
+
+fn assert_within(name: &str, release_micros: f64, f: impl FnMut()) {
+    let (took, limit) = (median_micros(200, f), budget(release_micros));
+    assert!(
+        took <= limit,
+        "{} took {:.2}µs per call, over its {:.2}µs budget",
+        name,
+        took,
+        limit
+    );
+}
+
+// ─── Single Validation ───────────────────────────────────────────────
+
+#[test]
+fn single_validation_within_budget() {
+    assert_within("validate_arithmetic", 2.0, || {
+        validate_arithmetic("12 * 34", 408.0);
+    });
+    assert_within("validate_equation", 5.0, || {
+        validate_equation("x * 3 = 12", 4.0);
+    });
+    assert_within("check_answer arithmetic", 25.0, || {
+        check_answer("arithmetic", "12 * 34", "408");
+    });
+    assert_within("check_answer fraction", 25.0, || {
+        check_answer("fraction", "2/4", "1/2");
+    });
+    assert_within("check_answer LaTeX", 50.0, || {
+        check_answer("arithmetic", r"\frac{12}{4}", "3");
+    });
+}
+
+#[test]
+fn parsing_within_budget() {
+    assert_within("format_canonical", 30.0, || {
+        format_canonical("3x^2 + 2(x - 1)/4");
+    });
+}
+
+// ─── Batches ─────────────────────────────────────────────────────────
+
+#[test]
+fn batch_item_within_budget() {
+    let problems: Vec<String> = (0..1_000).map(|i| format!("{} + {}", i, i % 7)).collect();
+    let answers: Vec<String> = (0..1_000)
+        .map(|i| (i + i % 7 + usize::from(i % 10 == 0)).to_string())
+        .collect();
+    let (problems, answers) = (problems.join(";"), answers.join(";"));
+    // Per item: one call grades 1,000.
+    let took = median_micros(2, || {
+        batch_validate(&problems, &answers);
+    }) / 1_000.0;
+    let limit = budget(10.0);
+    assert!(
+        took <= limit,
+        "batch_validate took {:.2}µs per item, over its {:.2}µs budget",
+        took,
+        limit
+    );
+}
@@ -0,0 +1,211 @@
+// ═══════════════════════════════════════════════════════════════════
+// Sovereign Academy — Parser Property Tests
+// ═══════════════════════════════════════════════════════════════════
+//
+// The engine grades whatever a student types, so no input may abort
+// the WASM instance: a panic or a stack overflow takes every island on
+// the page down with it. These suites throw generated input at the
+// entry points and check that:
+//
+//   1. nothing panics — arbitrary text, near-miss expressions, and
+//      deeply nested input alike;
+//   2. every JSON-returning function returns valid JSON;
+//   3. printing is stable — `format_canonical` is idempotent and keeps
+//      the parse tree (its output renders identically);
+//   4. `batch_validate`'s vectorized grading agrees with grading each
+//      item alone through `check_answer`.
+//
+// Failing cases are saved next to this file in
+// property_test.proptest-regressions; commit that file.
+// The cargo-fuzz targets in ../fuzz cover the same ground unbounded.
+// ═══════════════════════════════════════════════════════════════════
+
+use math_validator::*;
+use proptest::prelude::*;
+use serde_json::Value;
+
+/// Built-in problem types, as `supported_types` lists them.
+fn builtin_types() -> Vec<String> {
+    let types: Value = serde_json::from_str(&supported_types()).unwrap();
+    types["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["type"].as_str().unwrap().to_string())
+        .collect()
+}
+
+/// Expressions in the engine's syntax, with irregular spacing, implicit
+/// products, Unicode operators, and an occasional stray character.
+fn expression() -> impl Strategy<Value = String> {
+    let leaf = prop_oneof![
+        (0u32..1000).prop_map(|n| n.to_string()),
+        (0u32..100, 0u32..100).prop_map(|(w, f)| format!("{}.{}", w, f)),
+        (1u32..20, 1u32..20).prop_map(|(n, d)| format!("{}/{}", n, d)),
+        prop::sample::select(vec!["x", "y", "pi", "speed"]).prop_map(String::from),
+    ];
+    let glue = prop::sample::select(vec![
+        " + ", "+", " - ", "-", " * ", "*", "×", " / ", "÷", "^", "", " ", "−",
+    ]);
+    leaf.prop_recursive(6, 64, 4, move |inner| {
+        prop_oneof![
+            (inner.clone(), glue.clone(), inner.clone())
+                .prop_map(|(l, op, r)| format!("{}{}{}", l, op, r)),
+            inner.clone().prop_map(|e| format!("({})", e)),
+            inner.clone().prop_map(|e| format!("-{}", e)),
+            inner.clone().prop_map(|e| format!("sqrt({})", e)),
+            inner.clone().prop_map(|e| format!("abs({})", e)),
+            inner.clone().prop_map(|e| format!("√{}", e)),
+            (inner, "[()=<>?_.,a-z0-9 ]").prop_map(|(e, s)| format!("{}{}", e, s)),
+        ]
+    })
+}
+
+/// Input a student might plausibly type: expressions, statements,
+/// LaTeX, JSON, and noise.
+fn answer() -> impl Strategy<Value = String> {
+    prop_oneof![
+        expression(),
+        (expression(), "(=|<|>|<=|>=|!=|≤|≠)", expression())
+            .prop_map(|(l, rel, r)| format!("{} {} {}", l, rel, r)),
+        expression().prop_map(|e| format!(r"\frac{{{}}}{{2}}", e)),
+        "\\PC{0,40}",
+        r#"\{"[a-z]{1,8}":\[[0-9, "]{0,12}\]\}"#,
+    ]
+}
+
+/// A batch item: a two-operand problem (sometimes LaTeX or garbled)
+/// and an answer that is right, nearly right, wrong, or not a number.
+fn batch_item() -> impl Strategy<Value = (String, String)> {
+    let operand = prop_oneof![
+        (-1000i32..1000).prop_map(|n| n.to_string()),
+        (0u32..100, 0u32..1000).prop_map(|(w, f)| format!("{}.{}", w, f)),
+        Just("0".to_string()),
+    ];
+    let op = prop::sample::select(vec!['+', '-', '*', '/']);
+    (operand.clone(), op, operand, 0u8..6, -2i32..3).prop_map(|(l, op, r, form, off)| {
+        let problem = match form {
+            0 => format!(r"\frac{{{}}}{{{}}}", l, r),
+            1 => format!("{}{} {}", l, op, "x"),
+            _ => format!("{} {} {}", l, op, r),
+        };
+        let (a, b) = (l.parse::<f64>().unwrap(), r.parse::<f64>().unwrap());
+        let value = match op {
+            '+' => a + b,
+            '-' => a - b,
+            '*' => a * b,
+            _ => a / b,
+        };
+        let answer = match off {
+            -2 => "seven".to_string(),
+            -1 => format!("${}$", value),
+            0 => value.to_string(),
+            _ => (value + f64::from(off) * 1e-10).to_string(),
+        };
+        (problem, answer)
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    // ─── No Panics ───────────────────────────────────────────────────
+
+    #[test]
+    fn validate_arithmetic_never_panics(expr in answer(), value in any::<f64>()) {
+        validate_arithmetic(&expr, value);
+    }
+
+    #[test]
+    fn validate_equation_never_panics(equation in answer(), value in any::<f64>()) {
+        validate_equation(&equation, value);
+    }
+
+    #[test]
+    fn check_answer_never_panics(
+        type_index in any::<prop::sample::Index>(),
+        problem in answer(),
+        student_answer in answer(),
+    ) {
+        let types = builtin_types();
+        let problem_type = type_index.get(&types);
+        let result = check_answer(problem_type, &problem, &student_answer);
+        prop_assert!(serde_json::from_str::<Value>(&result).is_ok(), "{}", result);
+    }
+
+    #[test]
+    fn expression_tools_never_panic(expr in answer()) {
+        for output in [
+            format_canonical(&expr),
+            render_expression(&expr, "latex"),
+            render_expression(&expr, "mathml"),
+            read_aloud(&expr, "en"),
+            to_nemeth(&expr),
+        ] {
+            prop_assert!(serde_json::from_str::<Value>(&output).is_ok(), "{}", output);
+        }
+    }
+
+    // ─── No Runaway Recursion ────────────────────────────────────────
+
+    #[test]
+    fn deep_nesting_is_rejected_not_overflowed(
+        depth in 0usize..20_000,
+        (open, close) in prop::sample::select(vec![
+            ("(", ")"),
+            ("-", ""),
+            ("sqrt(", ")"),
+            ("√", ""),
+            ("2^", ""),
+            ("x^{", "}"),
+            (r"\frac{1}{", "}"),
+        ]),
+    ) {
+        let expr = format!("{}1{}", open.repeat(depth), close.repeat(depth));
+        validate_arithmetic(&expr, 1.0);
+        validate_equation(&format!("x = {}", expr), 1.0);
+        check_answer("arithmetic", &expr, "1");
+        format_canonical(&expr);
+    }
+
+    // ─── Round-Trip Stability ────────────────────────────────────────
+
+    #[test]
+    fn canonical_form_is_a_fixed_point(expr in expression()) {
+        let first: Value = serde_json::from_str(&format_canonical(&expr)).unwrap();
+        if let Some(canonical) = first["canonical"].as_str() {
+            let second: Value = serde_json::from_str(&format_canonical(canonical)).unwrap();
+            prop_assert_eq!(second["canonical"].as_str(), Some(canonical));
+            // Same rendering, same parse tree (statements do not render).
+            let rendered = render_expression(&expr, "latex");
+            if !rendered.contains("parse_error") {
+                prop_assert_eq!(render_expression(canonical, "latex"), rendered);
+            }
+        }
+    }
+
+    // ─── Batch Grading ───────────────────────────────────────────────
+
+    #[test]
+    fn batch_grading_matches_single_grading(
+        items in prop::collection::vec(batch_item(), 0..300),
+    ) {
+        let problems: Vec<&str> = items.iter().map(|(p, _)| p.as_str()).collect();
+        let answers: Vec<&str> = items.iter().map(|(_, a)| a.as_str()).collect();
+        let batch: Value =
+            serde_json::from_str(&batch_validate(&problems.join(";"), &answers.join(";"))).unwrap();
+        for (result, (problem, answer)) in batch["results"].as_array().unwrap().iter().zip(&items) {
+            if result["error"] == "invalid_problem" {
+                continue;
+            }
+            let single: Value =
+                serde_json::from_str(&check_answer("arithmetic", problem, answer)).unwrap();
+            prop_assert_eq!(&result["hint_id"], &single["hint_id"], "{} ; {}", problem, answer);
+            prop_assert_eq!(&result["hint"], &single["hint"]);
+            prop_assert_eq!(
+                result["correct"].as_bool(),
+                single["correct"].as_bool().map(|c| c && result["error"].is_null())
+            );
+        }
+    }
+}
@@ -195,17 +195,21 @@ fn purity_check_answer_unknown_type() {
 
 #[test]
 fn purity_batch_validate_always_same() {
+    let first = batch_validate("2 + 3;4 * 5;10 / 2", "5;20;5");
+    assert_eq!(first.matches("\"correct\":true").count(), 3);
     for _ in 0..100 {
-        assert_eq!(batch_validate("2 + 3;4 * 5;10 / 2", "5;20;5"), 3);
-        assert_eq!(batch_validate("2 + 3;4 * 5", "5;21"), 1);
-        assert_eq!(batch_validate("1 + 1", "3"), 0);
+        assert_eq!(batch_validate("2 + 3;4 * 5;10 / 2", "5;20;5"), first);
+        assert_eq!(batch_validate("2 + 3;4 * 5", "5;21").matches("\"correct\":true").count(), 1);
+        assert!(batch_validate("1 + 1", "3").contains("\"correct\":false"));
     }
 }
 
 #[test]
 fn purity_batch_validate_mismatched_lengths() {
     for _ in 0..100 {
-        assert_eq!(batch_validate("1 + 1;2 + 2", "2"), 0);
+        let result = batch_validate("1 + 1;2 + 2", "2");
+        assert!(result.contains("\"error\":\"missing_answer\""));
+        assert_eq!(result.matches("\"correct\":true").count(), 1);
     }
 }
 
@@ -228,7 +232,7 @@ fn determinism_full_battery() {
     results.push(format!("{:?}", simplify_fraction(6, 9)));
     results.push(format!("{}", validate_equation("x + 1 = 2", 1.0)));
     results.push(check_answer("arithmetic", "2 + 3", "5"));
-    results.push(format!("{}", batch_validate("2 + 3;4 * 5", "5;20")));
+    results.push(batch_validate("2 + 3;4 * 5", "5;20"));
 
     // Run 99 more times and compare
     for iteration in 1..100 {
@@ -243,7 +247,7 @@ fn determinism_full_battery() {
         current.push(format!("{:?}", simplify_fraction(6, 9)));
         current.push(format!("{}", validate_equation("x + 1 = 2", 1.0)));
         current.push(check_answer("arithmetic", "2 + 3", "5"));
-        current.push(format!("{}", batch_validate("2 + 3;4 * 5", "5;20")));
+        current.push(batch_validate("2 + 3;4 * 5", "5;20"));
 
         assert_eq!(
             results, current,
@@ -252,3 +256,581 @@ fn determinism_full_battery() {
         );
     }
 }
+
+// ─── Time Arithmetic Purity ──────────────────────────────────────────
+
+#[test]
+fn purity_time_arithmetic_always_same() {
+    for _ in 0..100 {
+        assert!(validate_elapsed_time("9:45", "11:20", "1:35"));
+        assert!(validate_elapsed_time("9:45", "11:20", "95 min"));
+        assert!(validate_time_addition("9:45", "1:35", "11:20"));
+        assert!(validate_clock_reading(18, 45, "3:45"));
+        assert!(!validate_elapsed_time("9:45", "11:20", "1:25"));
+    }
+}
+
+#[test]
+fn purity_check_answer_time_types() {
+    for _ in 0..100 {
+        let result = check_answer("elapsed_time", "from 9:45 to 11:20", "1h 35m");
+        assert!(result.contains("\"correct\":true"));
+        let result = check_answer("time_addition", "9:45 + 1:35", "11:10");
+        assert!(result.contains("\"correct\":false"));
+    }
+}
+
+// ─── Money Purity ────────────────────────────────────────────────────
+
+#[test]
+fn purity_money_always_exact() {
+    for _ in 0..100 {
+        assert!(validate_money_sum("$0.10 + $0.20", "$0.30"));
+        assert!(validate_change("$10.00", "$3.07", "$6.93"));
+        assert!(validate_unit_price("$3.60", 4, "$0.90"));
+        assert!(!validate_money_sum("$0.10 + $0.20", "$0.31"));
+    }
+}
+
+#[test]
+fn purity_check_answer_money_types() {
+    for _ in 0..100 {
+        let result = check_answer("money", "$3.07 + $1.95", "$5.02");
+        assert!(result.contains("\"correct\":true"));
+        let result = check_answer("unit_price", "$3.60 / 4", "$0.95");
+        assert!(result.contains("\"correct\":false"));
+    }
+}
+
+// ─── Temperature Purity ──────────────────────────────────────────────
+
+#[test]
+fn purity_temperature_conversion_always_same() {
+    for _ in 0..100 {
+        assert!(validate_temperature_conversion("25", "C", "F", "77"));
+        assert!(validate_temperature_conversion("10", "F", "C", "-110/9"));
+        assert!(validate_temperature_conversion("0", "C", "K", "273.15"));
+        assert!(!validate_temperature_conversion("25", "C", "F", "78"));
+        let result = check_answer("temperature", "25°C to °F", "77°F");
+        assert!(result.contains("\"correct\":true"));
+    }
+}
+
+// ─── Matrix Purity ───────────────────────────────────────────────────
+
+#[test]
+fn purity_matrix_operations_always_same_json() {
+    let first = validate_matrix_operation("multiply", "[[[1,2],[3,4]],[[5,6],[7,8]]]", "[[19,22],[43,50]]");
+    assert_eq!(first, r#"{"correct":true,"expected_shape":[2,2],"result_schema_version":2,"wrong_entries":[]}"#);
+    for _ in 0..100 {
+        assert_eq!(
+            validate_matrix_operation("multiply", "[[[1,2],[3,4]],[[5,6],[7,8]]]", "[[19,22],[43,50]]"),
+            first
+        );
+        let det = validate_matrix_operation("determinant", "[[[1,2],[3,4]]]", "-2");
+        assert!(det.contains("\"correct\":true"));
+    }
+}
+
+// ─── Custom Type Purity ──────────────────────────────────────────────
+
+#[test]
+fn purity_custom_type_always_same() {
+    let spec = r#"{"name":"purity_circle_area","formula":"r^2 * 314/100","hint":"A = πr²"}"#;
+    let registered = register_custom_type(spec);
+    assert!(registered.contains("\"ok\":true"));
+    let first = check_answer("purity_circle_area", "r = 2", "12.56");
+    assert!(first.contains("\"correct\":true"));
+    for _ in 0..100 {
+        assert_eq!(register_custom_type(spec), registered);
+        assert_eq!(check_answer("purity_circle_area", "r = 2", "12.56"), first);
+        let wrong = check_answer("purity_circle_area", "r = 2", "12.5");
+        assert!(wrong.contains("\"correct\":false"));
+    }
+}
+
+// ─── Vector Purity ───────────────────────────────────────────────────
+
+#[test]
+fn purity_vector_operations_always_same_json() {
+    let first = validate_vector_operation("add", "[[1,2,3],[4,5,6]]", "[5,7,9]");
+    assert_eq!(first, r#"{"correct":true,"expected_dimension":3,"result_schema_version":2,"wrong_components":[]}"#);
+    for _ in 0..100 {
+        assert_eq!(validate_vector_operation("add", "[[1,2,3],[4,5,6]]", "[5,7,9]"), first);
+        let magnitude = validate_vector_operation("magnitude", "[[2,2,2]]", r#""2√3""#);
+        assert_eq!(magnitude, r#"{"correct":true,"result_schema_version":2}"#);
+    }
+}
+
+// ─── Format Feedback Purity ──────────────────────────────────────────
+
+#[test]
+fn purity_format_feedback_always_same_json() {
+    let spec = r#"{"simplified":true,"partial_credit":0.5}"#;
+    let first = check_answer_formatted("fraction", "1/2", "4/8", spec);
+    assert!(first.contains("\"score\":0.5"));
+    for _ in 0..100 {
+        assert_eq!(check_answer_formatted("fraction", "1/2", "4/8", spec), first);
+    }
+}
+
+// ─── Hint ID Purity ──────────────────────────────────────────────────
+
+#[test]
+fn purity_hint_ids_always_same() {
+    let catalog = hint_catalog();
+    for _ in 0..100 {
+        let result = check_answer("arithmetic", "2 + 3", "5");
+        assert!(result.contains("\"hint_id\":\"correct\""));
+        let result = check_answer("arithmetic", "2 + 3", "6");
+        assert!(result.contains("\"hint_id\":\"arithmetic.step_by_step\""));
+        let result = check_answer("fraction", "1/2", "1/3");
+        assert!(result.contains("\"hint_id\":\"fraction.simplify\""));
+        let result = check_answer("no_such_type", "x", "y");
+        assert!(result.contains("\"hint_id\":\"unknown_type\""));
+        assert_eq!(hint_catalog(), catalog);
+    }
+}
+
+// ─── Distractor Purity ───────────────────────────────────────────────
+
+#[test]
+fn purity_distractors_same_seed_same_list() {
+    let first = generate_distractors("27 + 45", "72", 2024);
+    assert!(first.contains("forgotten_carry"));
+    for _ in 0..100 {
+        assert_eq!(generate_distractors("27 + 45", "72", 2024), first);
+    }
+}
+
+// ─── Operation Budget Purity ─────────────────────────────────────────
+
+#[test]
+fn purity_budget_exceeded_is_deterministic() {
+    let spec = format!(r#"{{"name":"purity_deep","formula":"{}1"}}"#, "(".repeat(20_000));
+    let first = register_custom_type(&spec);
+    assert!(first.contains("operation budget exceeded"));
+    for _ in 0..100 {
+        assert_eq!(register_custom_type(&spec), first);
+    }
+}
+
+// ─── Multiple Choice Purity ──────────────────────────────────────────
+
+#[test]
+fn purity_multiple_choice_always_same_json() {
+    let problem = r#"{"options":["72",{"text":"62","misconception":"forgotten_carry"}],"correct":0}"#;
+    let first = check_answer("multiple_choice", problem, "1");
+    assert!(first.contains("\"misconception\":\"forgotten_carry\""));
+    assert!(first.contains("\"hint_id\":\"misconception.forgotten_carry\""));
+    for _ in 0..100 {
+        assert_eq!(check_answer("multiple_choice", problem, "1"), first);
+        assert!(check_answer("multiple_choice", problem, "0").contains("\"correct\":true"));
+    }
+}
+
+// ─── Engine State Purity ─────────────────────────────────────────────
+
+#[test]
+fn purity_export_state_is_stable() {
+    register_custom_type(r#"{"name":"purity_snapshot","formula":"3n"}"#);
+    let first = export_state();
+    for _ in 0..100 {
+        assert!(import_state(&first).contains("\"ok\":true"));
+        assert_eq!(export_state(), first);
+    }
+}
+
+// ─── Comparison Purity ───────────────────────────────────────────────
+
+#[test]
+fn purity_comparisons_are_exact_every_time() {
+    for _ in 0..100 {
+        assert!(validate_true_false("0.1 + 0.2 = 0.3", "true"));
+        assert!(validate_comparison("3/4", "2/3", ">"));
+        assert!(check_answer("comparison", "1/3 ? 2/6", "=").contains("\"correct\":true"));
+    }
+}
+
+// ─── List Answer Purity ──────────────────────────────────────────────
+
+#[test]
+fn purity_list_answer_always_same_json() {
+    let problem = r#"{"items":["1","2","3","6"]}"#;
+    let first = check_list_answer(problem, "6, 3, 2, 5");
+    assert!(first.contains("\"status\":\"wrong\""));
+    for _ in 0..100 {
+        assert_eq!(check_list_answer(problem, "6, 3, 2, 5"), first);
+        assert!(check_answer("list", problem, "1;2;3;6").contains("\"correct\":true"));
+    }
+}
+
+// ─── Fill-in-the-Blank Purity ────────────────────────────────────────
+
+#[test]
+fn purity_fill_blank_always_same() {
+    let first = check_answer("fill_blank", "__ × __ = 56", "7, 8");
+    assert!(first.contains("\"correct\":true"));
+    for _ in 0..100 {
+        assert_eq!(check_answer("fill_blank", "__ × __ = 56", "7, 8"), first);
+        assert!(!validate_fill_blank("7 × __ = 56", "9"));
+    }
+}
+
+// ─── Dual-Path Grading Purity ────────────────────────────────────────
+
+#[test]
+fn purity_dual_path_grading_always_same() {
+    let agree = check_answer_dual("true_false", "1/3 + 1/3 = 2/3", "true");
+    assert!(agree.contains("\"cross_check\":\"agree\""));
+    let disagree = check_answer_dual("arithmetic", "2 + 3 * 4", "14");
+    assert!(disagree.contains("\"cross_check\":\"disagree\""));
+    for _ in 0..100 {
+        assert_eq!(
+            check_answer_dual("true_false", "1/3 + 1/3 = 2/3", "true"),
+            agree
+        );
+        assert_eq!(check_answer_dual("arithmetic", "2 + 3 * 4", "14"), disagree);
+    }
+}
+
+// ─── Localized Hint Purity ───────────────────────────────────────────
+
+#[test]
+fn purity_localized_hints_always_same() {
+    let first = check_answer_localized("fraction", "1/2", "1/3", "th");
+    assert!(first.contains("\"hint_id\":\"fraction.simplify\""));
+    for _ in 0..100 {
+        assert_eq!(check_answer_localized("fraction", "1/2", "1/3", "th"), first);
+        // A per-call locale never leaks into later results.
+        assert_eq!(get_locale(), "en");
+        assert!(check_answer("fraction", "1/2", "1/3").contains("lowest terms"));
+    }
+}
+
+// ─── Structured Hint Purity ──────────────────────────────────────────
+
+#[test]
+fn purity_hint_data_always_same() {
+    let first = check_answer("money", "$3.07 + $1.95", "$4.92");
+    assert!(first.contains("\"referenced_operands\":[\"$3.07\",\"$1.95\"]"));
+    for _ in 0..100 {
+        assert_eq!(check_answer("money", "$3.07 + $1.95", "$4.92"), first);
+    }
+}
+
+// ─── Partial-Credit Scoring Purity ───────────────────────────────────
+
+#[test]
+fn purity_batch_score_always_same() {
+    let first = batch_score("27 + 45;8 - 3;6 * 7", "62;-5;42");
+    assert!(first.contains("\"score\":1.75"));
+    for _ in 0..100 {
+        assert_eq!(batch_score("27 + 45;8 - 3;6 * 7", "62;-5;42"), first);
+    }
+}
+
+// ─── Question-Bank Import Purity ─────────────────────────────────────
+
+#[test]
+fn purity_import_bank_always_same() {
+    let gift = "::carry:: What is 27 + 45? {=72 ~62 ~82}\n\n2/5 = 4/10 {T}\n";
+    let first = import_bank("gift", gift);
+    assert!(first.contains("\"imported\":2"));
+    for _ in 0..100 {
+        assert_eq!(import_bank("gift", gift), first);
+    }
+}
+
+// ─── Template Instantiation Purity ───────────────────────────────────
+
+#[test]
+fn purity_instantiate_always_same() {
+    let template = r#"{"type":"arithmetic","problem":"{a} * {b}","answer":"{a} * {b}","vars":{"a":{"min":2,"max":12},"b":{"min":2,"max":12}}}"#;
+    let first = instantiate(template, 2024);
+    assert!(first.contains("\"answer\":"));
+    for _ in 0..100 {
+        assert_eq!(instantiate(template, 2024), first);
+    }
+}
+
+// ─── Quiz Scoring Purity ─────────────────────────────────────────────
+
+#[test]
+fn purity_score_quiz_always_same() {
+    let items = r#"[{"topic":"fractions","weight":2,"difficulty":"hard","correct":true},{"topic":"time","credit":0.5}]"#;
+    let first = score_quiz(items);
+    assert!(first.contains("\"grade\":"));
+    for _ in 0..100 {
+        assert_eq!(score_quiz(items), first);
+    }
+}
+
+// ─── Gamification Purity ─────────────────────────────────────────────
+
+#[test]
+fn purity_compute_xp_always_same() {
+    let results = r#"[{"correct":true,"difficulty":"hard"},{"correct":true,"hints":1},{"correct":false}]"#;
+    let first = compute_xp(results, "");
+    assert!(first.contains("\"xp\":"));
+    for _ in 0..100 {
+        assert_eq!(compute_xp(results, ""), first);
+    }
+}
+
+#[test]
+fn purity_update_streak_always_same() {
+    let first = update_streak("[40, 41, 42, 30]", 43);
+    assert!(first.contains("\"current\":4"));
+    for _ in 0..100 {
+        assert_eq!(update_streak("[40, 41, 42, 30]", 43), first);
+    }
+}
+
+// ─── Spaced Repetition Purity ────────────────────────────────────────
+
+#[test]
+fn purity_schedule_review_always_same() {
+    let card = r#"{"repetitions":2,"interval":6,"ease":2.5}"#;
+    let first = schedule_review(card, 4, 7);
+    assert!(first.contains("\"interval\":"));
+    for _ in 0..100 {
+        assert_eq!(schedule_review(card, 4, 7), first);
+    }
+}
+
+// ─── Mastery Purity ──────────────────────────────────────────────────
+
+#[test]
+fn purity_update_mastery_always_same() {
+    let first = update_mastery(1137.5, 1220.0, true);
+    assert!(first > 1137.5);
+    for _ in 0..100 {
+        assert_eq!(update_mastery(1137.5, 1220.0, true), first);
+    }
+}
+
+#[test]
+fn purity_select_next_always_same() {
+    let mastery = r#"{"fractions":1100}"#;
+    let problems = r#"[{"id":"a","skill":"fractions","difficulty":900},{"id":"b","skill":"fractions","difficulty":1000},{"id":"c","skill":"time","difficulty":800}]"#;
+    let first = select_next(mastery, problems, 77);
+    assert!(first.contains("\"id\":"));
+    for _ in 0..100 {
+        assert_eq!(select_next(mastery, problems, 77), first);
+    }
+}
+
+// ─── Curriculum Purity ───────────────────────────────────────────────
+
+#[test]
+fn purity_curriculum_always_same() {
+    let graph = r#"{"skills":[{"id":"b","requires":["a"]},{"id":"a"},{"id":"c","requires":["a"]}]}"#;
+    let order = curriculum_order(graph);
+    let unlocked = unlocked_skills(graph, r#"["a"]"#);
+    assert!(order.contains("\"order\":"));
+    for _ in 0..100 {
+        assert_eq!(curriculum_order(graph), order);
+        assert_eq!(unlocked_skills(graph, r#"["a"]"#), unlocked);
+    }
+}
+
+// ─── Exercise Schema Purity ──────────────────────────────────────────
+
+#[test]
+fn purity_exercise_schema_always_same() {
+    let source = "{\"exercises\":[\n{\"id\":\"a\",\"type\":\"arithmetic\",\"problem\":\"2 + 2\",\"answer\":\"5\"}\n]}";
+    let report = validate_exercises(source);
+    assert!(report.contains("answer_key_rejected"));
+    for _ in 0..100 {
+        assert_eq!(validate_exercises(source), report);
+    }
+}
+
+// ─── Question Bank Purity ────────────────────────────────────────────
+
+#[test]
+fn purity_question_bank_always_same() {
+    let bundle = r#"{"questions":[
+        {"id":"a","topic":"time","grade":2,"difficulty":800,"tags":["clock"]},
+        {"id":"b","topic":"time","grade":3,"difficulty":950}]}"#;
+    load_question_bank(bundle);
+    let query = r#"{"topic":"time","max_difficulty":900}"#;
+    let result = query_questions(query);
+    assert!(result.contains("\"total\":1"));
+    for _ in 0..100 {
+        assert_eq!(query_questions(query), result);
+    }
+}
+
+// ─── Object API Purity ───────────────────────────────────────────────
+
+#[test]
+fn purity_problem_check_always_same() {
+    let problem = Problem::new("arithmetic", "6 * 7");
+    for _ in 0..100 {
+        let attempt = problem.check("42");
+        assert!(attempt.correct());
+        assert_eq!(attempt.hint(), problem.check("42").hint());
+    }
+}
+
+// ─── Rational Class Purity ───────────────────────────────────────────
+
+#[test]
+fn purity_rational_class_always_same() {
+    let half = JsRational::parse_text("1/2").unwrap();
+    let third = JsRational::from_parts(1, 3).unwrap();
+    for _ in 0..100 {
+        assert_eq!(half.add(&third).unwrap().to_fraction_string(), "5/6");
+        assert_eq!(third.to_exact_decimal().unwrap(), "0.(3)");
+    }
+}
+
+// ─── LaTeX Input Purity ──────────────────────────────────────────────
+
+#[test]
+fn purity_latex_input_always_same() {
+    let result = check_answer("fraction", r"\frac{6}{8}", r"\frac{3}{4}");
+    assert!(result.contains("\"correct\":true"));
+    for _ in 0..100 {
+        assert_eq!(check_answer("fraction", r"\frac{6}{8}", r"\frac{3}{4}"), result);
+    }
+}
+
+// ─── Expression Rendering Purity ─────────────────────────────────────
+
+#[test]
+fn purity_render_expression_always_same() {
+    let latex = render_expression("3/4 + 2x^2", "latex");
+    let mathml = render_expression("3/4 + 2x^2", "mathml");
+    assert!(latex.contains("\\\\frac{3}{4}"));
+    for _ in 0..100 {
+        assert_eq!(render_expression("3/4 + 2x^2", "latex"), latex);
+        assert_eq!(render_expression("3/4 + 2x^2", "mathml"), mathml);
+    }
+}
+
+// ─── Read Aloud Purity ───────────────────────────────────────────────
+
+#[test]
+fn purity_read_aloud_always_same() {
+    let english = read_aloud("2/3 + 1/2 = ?", "en");
+    let thai = read_aloud("2/3 + 1/2 = ?", "th");
+    assert!(english.contains("two thirds plus one half equals what"));
+    for _ in 0..100 {
+        assert_eq!(read_aloud("2/3 + 1/2 = ?", "en"), english);
+        assert_eq!(read_aloud("2/3 + 1/2 = ?", "th"), thai);
+    }
+}
+
+// ─── Nemeth Braille Purity ───────────────────────────────────────────
+
+#[test]
+fn purity_to_nemeth_always_same() {
+    let result = to_nemeth("2x^2 + 5 = 3/4");
+    assert!(result.contains("⠼⠆⠭⠘⠆⠐⠬⠢⠀⠨⠅⠀⠹⠒⠌⠲⠼"));
+    for _ in 0..100 {
+        assert_eq!(to_nemeth("2x^2 + 5 = 3/4"), result);
+    }
+}
+
+// ─── Canonical Form Purity ───────────────────────────────────────────
+
+#[test]
+fn purity_format_canonical_always_same() {
+    let result = format_canonical("2*x^2+ 3 ( x+1 ) = 1 /2");
+    assert!(result.contains("2x^2 + 3(x + 1) = 1/2"));
+    for _ in 0..100 {
+        assert_eq!(format_canonical("2*x^2+ 3 ( x+1 ) = 1 /2"), result);
+    }
+}
+
+// ─── MessagePack Purity ──────────────────────────────────────────────
+
+#[test]
+fn purity_msgpack_results_always_same() {
+    let validated = batch_validate_msgpack("2 + 3;4 * 5", "5;21");
+    let scored = batch_score_msgpack("2 + 3;8 - 3", "5;-5");
+    assert_eq!(validated[0], 0x82); // {"results","result_schema_version"}
+    for _ in 0..100 {
+        assert_eq!(batch_validate_msgpack("2 + 3;4 * 5", "5;21"), validated);
+        assert_eq!(batch_score_msgpack("2 + 3;8 - 3", "5;-5"), scored);
+    }
+}
+
+// ─── Result Schema Purity ────────────────────────────────────────────
+
+#[test]
+fn purity_result_schema_1_gives_the_old_shapes() {
+    let current = check_answer("fraction", "2/4", "1/2");
+    assert!(current.contains("\"result_schema_version\":2"));
+    let batch = batch_validate("1 + 1", "2");
+    assert!(set_result_schema_version(1));
+    let old_batch = batch_validate("1 + 1", "2");
+    assert!(old_batch.starts_with('['));
+    assert_eq!(
+        batch,
+        format!(r#"{{"result_schema_version":2,"results":{}}}"#, old_batch)
+    );
+    let old = check_answer("fraction", "2/4", "1/2");
+    assert_eq!(old, current.replacen("\"result_schema_version\":2,", "", 1));
+    assert_eq!(format_canonical("2 * x"), r#"{"canonical":"2x"}"#);
+    for _ in 0..100 {
+        assert_eq!(check_answer("fraction", "2/4", "1/2"), old);
+        assert_eq!(batch_validate("1 + 1", "2"), old_batch);
+        assert_eq!(get_result_schema_version(), 1);
+    }
+    assert!(set_result_schema_version(2));
+    assert_eq!(check_answer("fraction", "2/4", "1/2"), current);
+    assert_eq!(engine_version(), engine_version());
+}
+
+// ─── Quiz Session Purity ─────────────────────────────────────────────
+
+#[test]
+fn purity_quiz_session_transitions_always_same() {
+    let problems = r#"[{"problem_type":"arithmetic","problem":"6 * 7"},
+        {"problem_type":"fraction","problem":"3/6"}]"#;
+    let play = |quiz: &mut QuizSession| {
+        [quiz.start(), quiz.answer("48"), quiz.use_hint(), quiz.answer("42"), quiz.answer("1/2")]
+    };
+    let mut first = QuizSession::new(problems).unwrap();
+    let outputs = play(&mut first);
+    let saved = first.to_json();
+    let summary = first.finish();
+    assert!(summary.contains("\"score\":1.5"));
+    for _ in 0..100 {
+        let mut quiz = QuizSession::new(problems).unwrap();
+        assert_eq!(play(&mut quiz), outputs);
+        // A quiz saved and resumed mid-way ends exactly the same.
+        let mut resumed = QuizSession::from_json(&quiz.to_json()).unwrap();
+        assert_eq!(resumed.to_json(), saved);
+        assert_eq!(resumed.finish(), summary);
+    }
+}
+
+// ─── Timed Quiz Purity ───────────────────────────────────────────────
+
+#[test]
+fn purity_score_timed_quiz_always_same() {
+    let items = r#"[{"correct":true,"elapsed_ms":6000},{"credit":0.5,"elapsed_ms":2000},
+        {"correct":false,"elapsed_ms":40000}]"#;
+    let first = score_timed_quiz(items);
+    assert!(first.contains("\"bonus\":0.4"));
+    for _ in 0..100 {
+        assert_eq!(score_timed_quiz(items), first);
+    }
+}
+
+// ─── Mastery Decay Purity ────────────────────────────────────────────
+
+#[test]
+fn purity_decay_mastery_always_same() {
+    let first = decay_mastery(1350.0, 12.5, r#"{"half_life_days":21}"#);
+    assert!(first.contains("\"due\":true"));
+    for _ in 0..100 {
+        assert_eq!(decay_mastery(1350.0, 12.5, r#"{"half_life_days":21}"#), first);
+        assert_eq!(decay_mastery(1000.0, 400.0, ""), decay_mastery(1000.0, 400.0, ""));
+    }
+}
@@ -0,0 +1,241 @@
+// ═════════════════════════════════════════════════════════════════
+//  Keyboard Accelerators — app shortcuts that work inside the webview
+// ═════════════════════════════════════════════════════════════════
+//
+// Once the webview has focus it swallows every key, so the host never
+// sees F11 (fullscreen) or Ctrl+R (reload). The host registers such
+// combos per window with an id of its choosing; a press is taken from
+// the page and queued as
+//
+//   {"type":"accelerator","id":7}
+//
+// Combos are written like "Ctrl+Shift+R" or "F11" (case-insensitive).
+// They need Ctrl or Alt, unless the key types no character (F-keys,
+// Esc, arrows, ...): WebView2 only reports those as accelerators.
+// Modifiers must match exactly, so "Ctrl+R" ignores Ctrl+Shift+R, and
+// holding a combo down reports it once.
+//
+// Only Windows reports accelerators; elsewhere registering one returns
+// `STATUS_UNSUPPORTED`.
+
+/// A key with its modifiers, as a Windows virtual-key code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Accelerator {
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    key: u32,
+}
+
+impl Accelerator {
+    /// Parse a combo such as "Ctrl+R"; `None` if it is malformed or
+    /// could never be reported (a plain character key).
+    pub fn parse(combo: &str) -> Option<Accelerator> {
+        let mut accelerator = Accelerator {
+            ctrl: false,
+            alt: false,
+            shift: false,
+            key: 0,
+        };
+        let mut parts = combo
+            .split('+')
+            .map(|part| part.trim().to_ascii_lowercase());
+        let key = parts.next_back()?;
+        for modifier in parts {
+            let flag = match modifier.as_str() {
+                "ctrl" | "control" => &mut accelerator.ctrl,
+                "alt" => &mut accelerator.alt,
+                "shift" => &mut accelerator.shift,
+                _ => return None,
+            };
+            if *flag {
+                return None;
+            }
+            *flag = true;
+        }
+        let (key, types_character) = virtual_key(&key)?;
+        if types_character && !accelerator.ctrl && !accelerator.alt {
+            return None;
+        }
+        accelerator.key = key;
+        Some(accelerator)
+    }
+}
+
+/// The virtual-key code of a key name, and whether the key types a
+/// character.
+fn virtual_key(name: &str) -> Option<(u32, bool)> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_alphanumeric() {
+            return Some((c.to_ascii_uppercase() as u32, true));
+        }
+    }
+    if let Some(n) = name.strip_prefix('f').and_then(|n| n.parse::<u32>().ok()) {
+        return (1..=24).contains(&n).then_some((0x6F + n, false));
+    }
+    Some(match name {
+        "esc" | "escape" => (0x1B, false),
+        "enter" | "return" => (0x0D, true),
+        "tab" => (0x09, true),
+        "space" => (0x20, true),
+        "backspace" => (0x08, false),
+        "delete" | "del" => (0x2E, false),
+        "insert" => (0x2D, false),
+        "home" => (0x24, false),
+        "end" => (0x23, false),
+        "pageup" => (0x21, false),
+        "pagedown" => (0x22, false),
+        "left" => (0x25, false),
+        "up" => (0x26, false),
+        "right" => (0x27, false),
+        "down" => (0x28, false),
+        "plus" | "=" => (0xBB, true),
+        "minus" | "-" => (0xBD, true),
+        _ => return None,
+    })
+}
+
+/// A window's accelerators with their ids.
+pub type Registry = std::rc::Rc<std::cell::RefCell<Vec<(Accelerator, u32)>>>;
+
+/// Report presses of the registry's accelerators to `events`, taking
+/// them from the page.
+#[cfg(target_os = "windows")]
+pub fn install(
+    webview: &wry::WebView,
+    registry: Registry,
+    events: crate::events::EventQueue,
+) -> windows_core::Result<()> {
+    use webview2_com::AcceleratorKeyPressedEventHandler;
+    use webview2_com::Microsoft::Web::WebView2::Win32::*;
+    use wry::WebViewExtWindows;
+
+    // ─── Raw Win32 FFI declarations ──
+    const VK_SHIFT: i32 = 0x10;
+    const VK_CONTROL: i32 = 0x11;
+    const VK_MENU: i32 = 0x12;
+
+    extern "system" {
+        // user32.dll
+        fn GetKeyState(key: i32) -> i16;
+    }
+
+    let held = |key| unsafe { GetKeyState(key) } < 0;
+    let mut token = Default::default();
+    unsafe {
+        webview.controller().add_AcceleratorKeyPressed(
+            &AcceleratorKeyPressedEventHandler::create(Box::new(move |_, args| {
+                let Some(args) = args else {
+                    return Ok(());
+                };
+                let mut kind = COREWEBVIEW2_KEY_EVENT_KIND::default();
+                args.KeyEventKind(&mut kind)?;
+                if kind != COREWEBVIEW2_KEY_EVENT_KIND_KEY_DOWN
+                    && kind != COREWEBVIEW2_KEY_EVENT_KIND_SYSTEM_KEY_DOWN
+                {
+                    return Ok(());
+                }
+                let mut key = 0;
+                args.VirtualKey(&mut key)?;
+                let pressed = Accelerator {
+                    ctrl: held(VK_CONTROL),
+                    alt: held(VK_MENU),
+                    shift: held(VK_SHIFT),
+                    key,
+                };
+                let Some(&(_, id)) = registry
+                    .borrow()
+                    .iter()
+                    .find(|(accelerator, _)| *accelerator == pressed)
+                else {
+                    return Ok(());
+                };
+                args.SetHandled(true)?;
+                let mut status = COREWEBVIEW2_PHYSICAL_KEY_STATUS::default();
+                args.PhysicalKeyStatus(&mut status)?;
+                // Skip the auto-repeats of a held combo.
+                if status.WasKeyDown.0 == 0 {
+                    events.push(format!(r#"{{"type":"accelerator","id":{}}}"#, id));
+                }
+                Ok(())
+            })),
+            &mut token,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accelerator(ctrl: bool, alt: bool, shift: bool, key: u32) -> Option<Accelerator> {
+        Some(Accelerator {
+            ctrl,
+            alt,
+            shift,
+            key,
+        })
+    }
+
+    #[test]
+    fn test_parse_modifier_combinations() {
+        assert_eq!(
+            Accelerator::parse("F11"),
+            accelerator(false, false, false, 0x7A)
+        );
+        assert_eq!(
+            Accelerator::parse("Ctrl+R"),
+            accelerator(true, false, false, 0x52)
+        );
+        assert_eq!(
+            Accelerator::parse("Ctrl+Shift+R"),
+            accelerator(true, false, true, 0x52)
+        );
+        assert_eq!(
+            Accelerator::parse("shift + control + alt + delete"),
+            accelerator(true, true, true, 0x2E)
+        );
+        assert_eq!(
+            Accelerator::parse("Alt+F4"),
+            accelerator(false, true, false, 0x73)
+        );
+        assert_eq!(
+            Accelerator::parse("Shift+Esc"),
+            accelerator(false, false, true, 0x1B)
+        );
+        assert_eq!(
+            Accelerator::parse("Ctrl+Plus"),
+            accelerator(true, false, false, 0xBB)
+        );
+        assert_eq!(
+            Accelerator::parse("Ctrl+5"),
+            accelerator(true, false, false, 0x35)
+        );
+        assert_ne!(
+            Accelerator::parse("Ctrl+R"),
+            Accelerator::parse("Ctrl+Shift+R")
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_keys() {
+        for combo in [
+            "",
+            "R",
+            "Shift+R",
+            "Space",
+            "Ctrl+",
+            "+R",
+            "Ctrl+Ctrl+R",
+            "Win+R",
+            "Ctrl+RR",
+            "Ctrl+é",
+            "F0",
+            "F25",
+            "Ctrl+R+Shift",
+        ] {
+            assert_eq!(Accelerator::parse(combo), None, "{combo}");
+        }
+    }
+}
@@ -0,0 +1,221 @@
+// ═════════════════════════════════════════════════════════════════
+//  Window Appearance — shadow, Windows 11 corners, backdrop, title bar
+// ═════════════════════════════════════════════════════════════════
+//
+// A frameless window loses the styling Windows 11 gives its own
+// windows. The Desktop Window Manager still draws it when asked,
+// mostly through window attributes:
+//
+//   - shadow: the drop shadow around the window. With the "windows"
+//     feature it comes from extending the DWM frame (see `frameless`),
+//     which is on from the start; other Windows builds ask tao for it;
+//   - corners: the system default (round for most windows), square,
+//     round, or slightly round;
+//   - backdrop: none, Mica (the tinted desktop wallpaper) or Acrylic
+//     (blurred content behind the window);
+//   - title bar: dark or light, and its color. The web UI draws the
+//     title bar, but Windows still shows the frame for an instant
+//     while minimizing or restoring; in a dark app a light frame
+//     flashes unless it is made dark too.
+//
+// A backdrop shows through wherever the page's own background is
+// transparent, so choosing one also clears the webview's background;
+// `Backdrop::None` restores it to opaque white.
+//
+// Windows 10 only knows the shadow and dark mode (under an older
+// attribute id before build 19041), so the other setters return
+// `STATUS_UNSUPPORTED` there, as they do without the "windows" feature.
+// Only Windows has a shadow setting.
+
+use tao::window::Window;
+
+/// How the window's corners are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corners {
+    /// Whatever Windows picks for the window.
+    Default,
+    Square,
+    Round,
+    /// A smaller radius, as for menus.
+    RoundSmall,
+}
+
+impl Corners {
+    /// The preference for its FFI code (0 = default … 3 = small round),
+    /// which is also its `DWM_WINDOW_CORNER_PREFERENCE`.
+    pub fn from_code(code: i32) -> Option<Corners> {
+        Some(match code {
+            0 => Corners::Default,
+            1 => Corners::Square,
+            2 => Corners::Round,
+            3 => Corners::RoundSmall,
+            _ => return None,
+        })
+    }
+}
+
+/// The material drawn behind the window's transparent parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backdrop {
+    None,
+    Mica,
+    Acrylic,
+}
+
+impl Backdrop {
+    /// The backdrop for its FFI code (0 = none, 1 = Mica, 2 = Acrylic).
+    pub fn from_code(code: i32) -> Option<Backdrop> {
+        Some(match code {
+            0 => Backdrop::None,
+            1 => Backdrop::Mica,
+            2 => Backdrop::Acrylic,
+            _ => return None,
+        })
+    }
+}
+
+// ─── Raw Win32 FFI declarations ──
+#[cfg(all(target_os = "windows", feature = "windows"))]
+mod dwm {
+    pub const DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1: u32 = 19;
+    pub const DWMWA_USE_IMMERSIVE_DARK_MODE: u32 = 20;
+    pub const DWMWA_WINDOW_CORNER_PREFERENCE: u32 = 33;
+    pub const DWMWA_CAPTION_COLOR: u32 = 35;
+    pub const DWMWA_SYSTEMBACKDROP_TYPE: u32 = 38;
+    pub const DWMSBT_NONE: i32 = 1;
+    pub const DWMSBT_MAINWINDOW: i32 = 2;
+    pub const DWMSBT_TRANSIENTWINDOW: i32 = 3;
+
+    #[link(name = "dwmapi")]
+    extern "system" {
+        // dwmapi.dll
+        pub fn DwmSetWindowAttribute(
+            hwnd: isize,
+            attribute: u32,
+            value: *const i32,
+            size: u32,
+        ) -> i32;
+    }
+}
+
+/// Set a 32-bit DWM attribute of `window`. Returns a status code;
+/// `STATUS_UNSUPPORTED` if this Windows does not know the attribute.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+fn set_attribute(window: &Window, attribute: u32, value: i32) -> i32 {
+    use tao::platform::windows::WindowExtWindows;
+
+    let size = std::mem::size_of::<i32>() as u32;
+    let result = unsafe { dwm::DwmSetWindowAttribute(window.hwnd(), attribute, &value, size) };
+    if result >= 0 {
+        crate::STATUS_OK
+    } else {
+        crate::STATUS_UNSUPPORTED
+    }
+}
+
+/// Draw the window's corners as `corners`. Returns a status code.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub fn set_corners(window: &Window, corners: Corners) -> i32 {
+    let preference = match corners {
+        Corners::Default => 0,
+        Corners::Square => 1,
+        Corners::Round => 2,
+        Corners::RoundSmall => 3,
+    };
+    set_attribute(window, dwm::DWMWA_WINDOW_CORNER_PREFERENCE, preference)
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows")))]
+/// No DWM without the "windows" feature.
+pub fn set_corners(_window: &Window, _corners: Corners) -> i32 {
+    crate::STATUS_UNSUPPORTED
+}
+
+/// Draw `backdrop` behind the window and make the webview's background
+/// transparent for it (opaque for `Backdrop::None`). Returns a status
+/// code.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub fn set_backdrop(window: &Window, webview: &wry::WebView, backdrop: Backdrop) -> i32 {
+    let kind = match backdrop {
+        Backdrop::None => dwm::DWMSBT_NONE,
+        Backdrop::Mica => dwm::DWMSBT_MAINWINDOW,
+        Backdrop::Acrylic => dwm::DWMSBT_TRANSIENTWINDOW,
+    };
+    let status = set_attribute(window, dwm::DWMWA_SYSTEMBACKDROP_TYPE, kind);
+    if status != crate::STATUS_OK {
+        return status;
+    }
+    let background = match backdrop {
+        Backdrop::None => (255, 255, 255, 255),
+        Backdrop::Mica | Backdrop::Acrylic => (0, 0, 0, 0),
+    };
+    match webview.set_background_color(background) {
+        Ok(()) => crate::STATUS_OK,
+        Err(_) => crate::STATUS_WEBVIEW_FAILED,
+    }
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows")))]
+/// No DWM without the "windows" feature.
+pub fn set_backdrop(_window: &Window, _webview: &wry::WebView, _backdrop: Backdrop) -> i32 {
+    crate::STATUS_UNSUPPORTED
+}
+
+/// Make the window's frame dark or light, and paint its caption
+/// `color` (0xRRGGBB) or, with `None`, the system color. Returns a
+/// status code; `STATUS_UNSUPPORTED` if Windows lacks either setting,
+/// in which case the other one still applies.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub fn set_titlebar(window: &Window, dark: bool, color: Option<u32>) -> i32 {
+    const DWMWA_COLOR_DEFAULT: u32 = 0xFFFF_FFFF;
+
+    let dark = dark as i32;
+    let mut status = set_attribute(window, dwm::DWMWA_USE_IMMERSIVE_DARK_MODE, dark);
+    if status != crate::STATUS_OK {
+        status = set_attribute(window, dwm::DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1, dark);
+    }
+    // COLORREF is 0x00BBGGRR.
+    let colorref = color.map_or(DWMWA_COLOR_DEFAULT, |rgb| {
+        (rgb & 0xFF) << 16 | (rgb & 0xFF00) | (rgb >> 16 & 0xFF)
+    });
+    let caption = set_attribute(window, dwm::DWMWA_CAPTION_COLOR, colorref as i32);
+    if status == crate::STATUS_OK {
+        caption
+    } else {
+        status
+    }
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows")))]
+/// No DWM without the "windows" feature.
+pub fn set_titlebar(_window: &Window, _dark: bool, _color: Option<u32>) -> i32 {
+    crate::STATUS_UNSUPPORTED
+}
+
+/// Give the window the drop shadow or take it away. Returns a status
+/// code.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub fn set_shadow(window: &Window, enabled: bool) -> i32 {
+    use tao::platform::windows::WindowExtWindows;
+
+    if crate::frameless::set_shadow(window.hwnd(), enabled) {
+        crate::STATUS_OK
+    } else {
+        crate::STATUS_OS_ERROR
+    }
+}
+
+#[cfg(all(target_os = "windows", not(feature = "windows")))]
+/// Without the frameless subclass, tao draws the shadow itself.
+pub fn set_shadow(window: &Window, enabled: bool) -> i32 {
+    use tao::platform::windows::WindowExtWindows;
+
+    window.set_undecorated_shadow(enabled);
+    crate::STATUS_OK
+}
+
+#[cfg(not(target_os = "windows"))]
+/// No shadow setting outside Windows.
+pub fn set_shadow(_window: &Window, _enabled: bool) -> i32 {
+    crate::STATUS_UNSUPPORTED
+}
@@ -0,0 +1,72 @@
+// ═════════════════════════════════════════════════════════════════
+//  Webview Capture — screenshots for "report a problem"
+// ═════════════════════════════════════════════════════════════════
+//
+// The in-app problem report attaches what the student was looking at.
+// `capture_window` writes the webview's current contents, as rendered
+// and without the OS frame, to a PNG file.
+//
+// WebView2 captures asynchronously on the UI thread, so the caller
+// gets a channel instead of a result: the UI thread starts the capture
+// and moves on, and the completion handler sends the status once the
+// file is written. The caller may wait on it without blocking the UI.
+//
+// Only Windows can capture a webview; elsewhere `start` fails with
+// `STATUS_UNSUPPORTED`.
+
+use std::path::Path;
+use std::sync::mpsc;
+use wry::WebView;
+
+/// Start writing `webview`'s contents to the PNG file `path`, replacing
+/// any file there. The capture's status code is sent on `done`; `Err`
+/// with a status code if it could not start.
+#[cfg(target_os = "windows")]
+pub fn start(webview: &WebView, path: &Path, done: mpsc::Sender<i32>) -> Result<(), i32> {
+    use webview2_com::CapturePreviewCompletedHandler;
+    use webview2_com::Microsoft::Web::WebView2::Win32::*;
+    use windows::Win32::System::Com::{IStream, STGM_CREATE, STGM_SHARE_DENY_WRITE, STGM_WRITE};
+    use windows::Win32::UI::Shell::SHCreateStreamOnFileEx;
+    use windows_core::HSTRING;
+    use wry::WebViewExtWindows;
+
+    const FILE_ATTRIBUTE_NORMAL: u32 = 0x80;
+
+    let core =
+        unsafe { webview.controller().CoreWebView2() }.map_err(|_| crate::STATUS_WEBVIEW_FAILED)?;
+    let mode = STGM_CREATE | STGM_WRITE | STGM_SHARE_DENY_WRITE;
+    let stream = unsafe {
+        SHCreateStreamOnFileEx(
+            &HSTRING::from(path.as_os_str()),
+            mode.0,
+            FILE_ATTRIBUTE_NORMAL,
+            true,
+            None::<&IStream>,
+        )
+    }
+    .map_err(|_| crate::STATUS_OS_ERROR)?;
+    // The handler keeps the file open until the image is in it.
+    let file = stream.clone();
+    let handler = CapturePreviewCompletedHandler::create(Box::new(move |result| {
+        drop(file);
+        let _ = done.send(match result {
+            Ok(()) => crate::STATUS_OK,
+            Err(_) => crate::STATUS_WEBVIEW_FAILED,
+        });
+        Ok(())
+    }));
+    unsafe {
+        core.CapturePreview(
+            COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG,
+            &stream,
+            &handler,
+        )
+    }
+    .map_err(|_| crate::STATUS_WEBVIEW_FAILED)
+}
+
+#[cfg(not(target_os = "windows"))]
+/// No webview capture outside Windows.
+pub fn start(_webview: &WebView, _path: &Path, _done: mpsc::Sender<i32>) -> Result<(), i32> {
+    Err(crate::STATUS_UNSUPPORTED)
+}
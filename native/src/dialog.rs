@@ -0,0 +1,105 @@
+// ═════════════════════════════════════════════════════════════════
+//  Native Message Boxes
+// ═════════════════════════════════════════════════════════════════
+//
+// Critical prompts (an unsaved quiz, a corrupted profile) must reach
+// the student even when the webview is stuck, so they are drawn by the
+// OS instead of the page. The box runs on the calling thread, not the
+// UI thread, and has no owner window: both may be the thing that hangs.
+//
+// Only the "windows" feature has a dialog; elsewhere `show` returns
+// `None` and the host falls back to its own UI.
+
+/// What the box asks, which sets its icon and buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Information, with OK.
+    Info,
+    /// A warning, with OK.
+    Warning,
+    /// An error, with OK.
+    Error,
+    /// A confirmation, with OK and Cancel.
+    Confirm,
+    /// A yes/no question.
+    Question,
+}
+
+impl Kind {
+    /// The kind for its FFI code (0 = info … 4 = question).
+    pub fn from_code(code: i32) -> Option<Kind> {
+        Some(match code {
+            0 => Kind::Info,
+            1 => Kind::Warning,
+            2 => Kind::Error,
+            3 => Kind::Confirm,
+            4 => Kind::Question,
+            _ => return None,
+        })
+    }
+}
+
+/// The button the student pressed. Closing the box counts as Cancel
+/// (or No for a question).
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Button {
+    Ok = 1,
+    Cancel = 2,
+    Yes = 3,
+    No = 4,
+}
+
+/// Show a message box and wait for the answer; `None` where there is
+/// no native dialog.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub fn show(kind: Kind, title: &str, text: &str) -> Option<Button> {
+    // ─── Raw Win32 FFI declarations ──
+    const MB_OK: u32 = 0x0000_0000;
+    const MB_OKCANCEL: u32 = 0x0000_0001;
+    const MB_YESNO: u32 = 0x0000_0004;
+    const MB_ICONERROR: u32 = 0x0000_0010;
+    const MB_ICONQUESTION: u32 = 0x0000_0020;
+    const MB_ICONWARNING: u32 = 0x0000_0030;
+    const MB_ICONINFORMATION: u32 = 0x0000_0040;
+    const MB_TOPMOST: u32 = 0x0004_0000;
+    const MB_SETFOREGROUND: u32 = 0x0001_0000;
+    const IDOK: i32 = 1;
+    const IDYES: i32 = 6;
+
+    extern "system" {
+        // user32.dll
+        fn MessageBoxW(hwnd: isize, text: *const u16, caption: *const u16, flags: u32) -> i32;
+    }
+
+    let style = match kind {
+        Kind::Info => MB_OK | MB_ICONINFORMATION,
+        Kind::Warning => MB_OK | MB_ICONWARNING,
+        Kind::Error => MB_OK | MB_ICONERROR,
+        Kind::Confirm => MB_OKCANCEL | MB_ICONWARNING,
+        Kind::Question => MB_YESNO | MB_ICONQUESTION,
+    };
+    let wide = |text: &str| text.encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
+    let (text, caption) = (wide(text), wide(title));
+    let answer = unsafe {
+        MessageBoxW(
+            0,
+            text.as_ptr(),
+            caption.as_ptr(),
+            style | MB_TOPMOST | MB_SETFOREGROUND,
+        )
+    };
+    Some(match (kind, answer) {
+        (Kind::Question, IDYES) => Button::Yes,
+        (Kind::Question, _) => Button::No,
+        (Kind::Confirm, IDOK) => Button::Ok,
+        (Kind::Confirm, _) => Button::Cancel,
+        _ => Button::Ok,
+    })
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows")))]
+/// No dialog without the "windows" feature.
+pub fn show(_kind: Kind, _title: &str, _text: &str) -> Option<Button> {
+    None
+}
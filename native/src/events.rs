@@ -0,0 +1,144 @@
+// ═════════════════════════════════════════════════════════════════
+//  Window Events — a per-window queue Deno drains with `poll_event`
+// ═════════════════════════════════════════════════════════════════
+//
+// Deno cannot take callbacks from the UI thread without blocking it,
+// so the event loop encodes the window events the host cares about as
+// small JSON objects and queues them; the host polls on its own
+// schedule. Each object has a "type":
+//
+//   {"type":"resized","width":1280,"height":720}       inner size, px
+//   {"type":"moved","x":10,"y":20}                     outer position
+//   {"type":"focused","focused":true}
+//   {"type":"dpi-changed","scale_factor":1.5}
+//   {"type":"close-requested"}
+//   {"type":"new-window","url":"https://…","opened":true}
+//   {"type":"accelerator","id":7}
+//   {"type":"theme-changed","theme":"dark"}
+//
+// "close-requested" is the last event of a window the OS closed,
+// unless the host called `set_close_policy` to keep it open.
+// "new-window" is a link that asked for a popup; "opened" tells
+// whether it went to the system browser (see `links`). "accelerator"
+// is a shortcut the host registered (see `accelerators`).
+// "theme-changed" is the OS theme the window now follows (see `theme`).
+//
+// A queue holds at most `QUEUE_LIMIT` events; when the host stops
+// polling, the oldest are dropped so memory stays bounded. A
+// "close-requested" is never the one dropped: a window that keeps
+// itself open waits for the host to act on it.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use tao::event::WindowEvent;
+
+use crate::theme::Theme;
+
+/// Most events kept per window.
+pub const QUEUE_LIMIT: usize = 64;
+
+const CLOSE_REQUESTED: &str = r#"{"type":"close-requested"}"#;
+
+/// Pending events of one window, oldest first. Clones share the queue,
+/// so the webview's handlers can report events on the UI thread.
+#[derive(Debug, Default, Clone)]
+pub struct EventQueue {
+    events: Rc<RefCell<VecDeque<String>>>,
+}
+
+impl EventQueue {
+    /// Queue an event, dropping the oldest one other than
+    /// "close-requested" if the queue is full.
+    pub fn push(&self, event: String) {
+        let mut events = self.events.borrow_mut();
+        if events.len() == QUEUE_LIMIT {
+            // Only repeated "close-requested" left: drop the oldest copy.
+            let victim = events.iter().position(|e| e != CLOSE_REQUESTED);
+            events.remove(victim.unwrap_or(0));
+        }
+        events.push_back(event);
+    }
+
+    /// Remove the oldest event if it fits in `limit` bytes.
+    /// `Ok(None)` when the queue is empty, `Err(len)` when the event
+    /// is longer than `limit` (it stays queued).
+    pub fn pop(&self, limit: usize) -> Result<Option<String>, usize> {
+        let mut events = self.events.borrow_mut();
+        match events.front() {
+            Some(event) if event.len() > limit => Err(event.len()),
+            _ => Ok(events.pop_front()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.borrow().is_empty()
+    }
+}
+
+/// The JSON for a window event, or `None` for events not reported.
+pub fn encode(event: &WindowEvent) -> Option<String> {
+    Some(match event {
+        WindowEvent::Resized(size) => format!(
+            r#"{{"type":"resized","width":{},"height":{}}}"#,
+            size.width, size.height
+        ),
+        WindowEvent::Moved(position) => format!(
+            r#"{{"type":"moved","x":{},"y":{}}}"#,
+            position.x, position.y
+        ),
+        WindowEvent::Focused(focused) => {
+            format!(r#"{{"type":"focused","focused":{}}}"#, focused)
+        }
+        WindowEvent::ScaleFactorChanged { scale_factor, .. } => format!(
+            r#"{{"type":"dpi-changed","scale_factor":{}}}"#,
+            scale_factor
+        ),
+        WindowEvent::CloseRequested => CLOSE_REQUESTED.to_owned(),
+        _ => return None,
+    })
+}
+
+/// The JSON for a change of the window's theme.
+pub fn theme_changed(theme: Theme) -> String {
+    format!(r#"{{"type":"theme-changed","theme":"{}"}}"#, theme.name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_queue_drops_oldest() {
+        let queue = EventQueue::default();
+        for i in 0..=QUEUE_LIMIT {
+            queue.push(format!(r#"{{"type":"moved","x":{i},"y":0}}"#));
+        }
+        let first = queue.pop(usize::MAX).unwrap().unwrap();
+        assert_eq!(first, r#"{"type":"moved","x":1,"y":0}"#);
+    }
+
+    #[test]
+    fn test_long_event_reports_its_length() {
+        let queue = EventQueue::default();
+        queue.push(CLOSE_REQUESTED.to_owned());
+        let len = CLOSE_REQUESTED.len();
+        assert_eq!(queue.pop(len - 1), Err(len));
+        assert_eq!(queue.pop(len), Ok(Some(CLOSE_REQUESTED.to_owned())));
+        assert_eq!(queue.pop(len), Ok(None));
+    }
+
+    #[test]
+    fn test_full_queue_keeps_close_requested() {
+        let queue = EventQueue::default();
+        queue.push(CLOSE_REQUESTED.to_owned());
+        for i in 0..QUEUE_LIMIT {
+            queue.push(format!(r#"{{"type":"moved","x":{i},"y":0}}"#));
+        }
+        assert_eq!(queue.pop(usize::MAX), Ok(Some(CLOSE_REQUESTED.to_owned())));
+        assert_eq!(
+            queue.pop(usize::MAX),
+            Ok(Some(r#"{"type":"moved","x":1,"y":0}"#.to_owned()))
+        );
+    }
+}
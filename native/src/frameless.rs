@@ -0,0 +1,474 @@
+// ═════════════════════════════════════════════════════════════════
+//  Win32 Frameless Window Support (feature = "windows")
+// ═════════════════════════════════════════════════════════════════
+//
+// The "Discord technique", shared by this FFI library and the desktop
+// launcher so both run one audited implementation:
+//   1. Remove WS_CAPTION but keep WS_THICKFRAME for native resize
+//   2. DwmExtendFrameIntoClientArea(-1) for DWM shadow & composition
+//   3. Subclass the WndProc to handle:
+//      - WM_NCCALCSIZE: return 0 so entire window = client area
+//      - WM_NCHITTEST:  custom hit-testing for drag bar + resize edges
+//      - WM_DWMSENDICONIC*: private taskbar previews (see `preview`)
+//
+// Resizing from the webview goes through `start_native_resize`: the
+// page sends "resize-<edge>" over IPC on mousedown, and Windows takes
+// over the resize loop (zero lag).
+//
+// Caption buttons drawn by the web UI are declared with
+// `set_hit_regions`; hit-testing reports them as the real minimize /
+// maximize / close buttons, which gives native hover behavior, the
+// Windows 11 snap-layout flyout, and correct accessibility roles.
+//
+// `set_hit_target_scale` widens the drag bar and resize edges when the
+// webview is zoomed for a low-vision student (see `magnifier`).
+
+use std::sync::atomic::{AtomicI32, AtomicIsize, AtomicU32, Ordering};
+use std::sync::Mutex;
+
+// ─── Raw Win32 FFI declarations (avoids windows-sys version conflicts) ──
+#[allow(clippy::upper_case_acronyms)]
+mod win32 {
+    pub type HWND = isize;
+    pub type HBRUSH = isize;
+    pub type LRESULT = isize;
+    pub type WPARAM = usize;
+    pub type LPARAM = isize;
+
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct RECT {
+        pub left: i32,
+        pub top: i32,
+        pub right: i32,
+        pub bottom: i32,
+    }
+
+    #[repr(C)]
+    pub struct MARGINS {
+        pub cx_left_width: i32,
+        pub cx_right_width: i32,
+        pub cy_top_height: i32,
+        pub cy_bottom_height: i32,
+    }
+
+    // Window style constants
+    pub const WS_CAPTION: u32 = 0x00C00000;
+    pub const WS_THICKFRAME: u32 = 0x00040000;
+    pub const WS_CLIPCHILDREN: u32 = 0x02000000;
+
+    // GetWindowLongPtr / SetWindowLongPtr indices
+    pub const GWL_STYLE: i32 = -16;
+    pub const GWLP_WNDPROC: i32 = -4;
+
+    // SetClassLongPtr indices
+    pub const GCLP_HBRBACKGROUND: i32 = -10;
+
+    // SetWindowPos flags
+    pub const SWP_FRAMECHANGED: u32 = 0x0020;
+    pub const SWP_NOMOVE: u32 = 0x0002;
+    pub const SWP_NOSIZE: u32 = 0x0001;
+    pub const SWP_NOZORDER: u32 = 0x0004;
+
+    // Window messages
+    pub const WM_NCCALCSIZE: u32 = 0x0083;
+    pub const WM_NCHITTEST: u32 = 0x0084;
+    pub const WM_ERASEBKGND: u32 = 0x0014;
+    pub const WM_SIZE: u32 = 0x0005;
+    pub const WM_DWMSENDICONICTHUMBNAIL: u32 = 0x0323;
+    pub const WM_DWMSENDICONICLIVEPREVIEWBITMAP: u32 = 0x0326;
+
+    // WM_NCLBUTTONDOWN — used to initiate native resize from IPC
+    pub const WM_NCLBUTTONDOWN: u32 = 0x00A1;
+
+    extern "system" {
+        // user32.dll
+        pub fn GetWindowLongPtrW(hwnd: HWND, index: i32) -> isize;
+        pub fn SetWindowLongPtrW(hwnd: HWND, index: i32, new_long: isize) -> isize;
+        pub fn SetClassLongPtrW(hwnd: HWND, index: i32, new_long: isize) -> isize;
+        pub fn SetWindowPos(
+            hwnd: HWND,
+            hwnd_after: HWND,
+            x: i32,
+            y: i32,
+            cx: i32,
+            cy: i32,
+            flags: u32,
+        ) -> i32;
+        pub fn GetWindowRect(hwnd: HWND, rect: *mut RECT) -> i32;
+        pub fn DefWindowProcW(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT;
+        pub fn InvalidateRect(hwnd: HWND, rect: *const RECT, erase: i32) -> i32;
+        pub fn ReleaseCapture() -> i32;
+        pub fn SendMessageW(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT;
+    }
+
+    extern "system" {
+        // gdi32.dll
+        pub fn CreateSolidBrush(color: u32) -> HBRUSH;
+    }
+
+    #[link(name = "dwmapi")]
+    extern "system" {
+        // dwmapi.dll
+        pub fn DwmExtendFrameIntoClientArea(hwnd: HWND, margins: *const MARGINS) -> i32;
+    }
+}
+
+/// Frameless window settings. Passed by value over FFI, so the layout
+/// is fixed.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FramelessOptions {
+    /// Title bar height in physical pixels (matches the CSS drag bar).
+    pub titlebar_height: i32,
+    /// Resize border width in physical pixels.
+    pub resize_border: i32,
+    /// Background fill as a Win32 COLORREF (0x00BBGGRR).
+    pub background: u32,
+}
+
+impl Default for FramelessOptions {
+    fn default() -> Self {
+        FramelessOptions {
+            titlebar_height: 32,
+            // Matches Windows SM_CXFRAME + SM_CXPADDEDBORDER (~8px at 100% DPI).
+            resize_border: 8,
+            // #1e1f22
+            background: 0x0022_1F1E,
+        }
+    }
+}
+
+/// A window edge or corner to resize from. Values are the `HT*`
+/// hit-test codes Windows uses for the same edge.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeDirection {
+    Left = 10,
+    Right = 11,
+    Top = 12,
+    TopLeft = 13,
+    TopRight = 14,
+    Bottom = 15,
+    BottomLeft = 16,
+    BottomRight = 17,
+}
+
+impl ResizeDirection {
+    /// Parse the edge name sent by the webview's resize handles
+    /// ("top", "bottomright", …).
+    pub fn from_name(name: &str) -> Option<ResizeDirection> {
+        Some(match name {
+            "top" => ResizeDirection::Top,
+            "bottom" => ResizeDirection::Bottom,
+            "left" => ResizeDirection::Left,
+            "right" => ResizeDirection::Right,
+            "topleft" => ResizeDirection::TopLeft,
+            "topright" => ResizeDirection::TopRight,
+            "bottomleft" => ResizeDirection::BottomLeft,
+            "bottomright" => ResizeDirection::BottomRight,
+            _ => return None,
+        })
+    }
+
+    /// Convert an `HT*` code received over FFI.
+    pub fn from_code(code: i32) -> Option<ResizeDirection> {
+        Some(match code {
+            10 => ResizeDirection::Left,
+            11 => ResizeDirection::Right,
+            12 => ResizeDirection::Top,
+            13 => ResizeDirection::TopLeft,
+            14 => ResizeDirection::TopRight,
+            15 => ResizeDirection::Bottom,
+            16 => ResizeDirection::BottomLeft,
+            17 => ResizeDirection::BottomRight,
+            _ => return None,
+        })
+    }
+}
+
+/// A caption button drawn by the web UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptionButton {
+    Minimize,
+    Maximize,
+    Close,
+}
+
+impl CaptionButton {
+    /// Parse "minimize", "maximize", or "close".
+    pub fn from_name(name: &str) -> Option<CaptionButton> {
+        match name {
+            "minimize" => Some(CaptionButton::Minimize),
+            "maximize" => Some(CaptionButton::Maximize),
+            "close" => Some(CaptionButton::Close),
+            _ => None,
+        }
+    }
+
+    /// The `WM_NCHITTEST` result for this button.
+    fn hit_code(self) -> isize {
+        match self {
+            CaptionButton::Minimize => HTMINBUTTON,
+            CaptionButton::Maximize => HTMAXBUTTON,
+            CaptionButton::Close => HTCLOSE,
+        }
+    }
+}
+
+/// Where a caption button sits, in physical pixels relative to the
+/// window's top-left corner (the whole window is client area, so these
+/// are the page's CSS pixels × devicePixelRatio).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HitRegion {
+    pub button: CaptionButton,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl HitRegion {
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x
+            && y >= self.y
+            && x < self.x.saturating_add(self.width)
+            && y < self.y.saturating_add(self.height)
+    }
+}
+
+// WM_NCHITTEST return values
+const HTCLIENT: isize = 1;
+const HTCAPTION: isize = 2;
+const HTMINBUTTON: isize = 8;
+const HTMAXBUTTON: isize = 9;
+const HTCLOSE: isize = 20;
+
+/// Stores the original WndProc so our subclass can forward messages.
+/// Must be static because GWLP_USERDATA is used internally by tao.
+static ORIGINAL_WNDPROC: AtomicIsize = AtomicIsize::new(0);
+
+/// Hit-test geometry for `custom_wndproc`, set by `setup_frameless`.
+static TITLEBAR_HEIGHT: AtomicI32 = AtomicI32::new(32);
+static RESIZE_BORDER: AtomicI32 = AtomicI32::new(8);
+
+/// Percentage applied to the title bar height and resize border.
+static HIT_TARGET_SCALE: AtomicU32 = AtomicU32::new(100);
+
+/// Caption buttons declared by the web UI, checked after resize edges.
+static HIT_REGIONS: Mutex<Vec<HitRegion>> = Mutex::new(Vec::new());
+
+/// Replace the declared caption-button regions. The web UI re-declares
+/// them whenever its layout changes (resize, DPI change).
+pub fn set_hit_regions(regions: Vec<HitRegion>) {
+    if let Ok(mut current) = HIT_REGIONS.lock() {
+        *current = regions;
+    }
+}
+
+/// Scale the title bar height and resize border by `percent` (100 =
+/// as configured, clamped to 100–400), to match a zoomed webview.
+pub fn set_hit_target_scale(percent: u32) {
+    HIT_TARGET_SCALE.store(percent.clamp(100, 400), Ordering::SeqCst);
+}
+
+/// `value` scaled by the current hit-target scale.
+fn scaled(value: i32) -> i32 {
+    let percent = HIT_TARGET_SCALE.load(Ordering::SeqCst) as i32;
+    value.saturating_mul(percent) / 100
+}
+
+/// The caption button under window-relative point (`x`, `y`), if any.
+fn caption_button_at(x: i32, y: i32) -> Option<CaptionButton> {
+    let regions = HIT_REGIONS.lock().ok()?;
+    regions
+        .iter()
+        .find(|region| region.contains(x, y))
+        .map(|region| region.button)
+}
+
+/// Make `hwnd` frameless: DWM shadow, no caption, custom hit-testing
+/// for the drag bar and resize edges.
+///
+/// Call once per process, on the thread that owns the window — the
+/// WndProc subclass keeps its state in statics.
+pub fn setup_frameless(hwnd: isize, options: FramelessOptions) {
+    use win32::*;
+
+    TITLEBAR_HEIGHT.store(options.titlebar_height, Ordering::SeqCst);
+    RESIZE_BORDER.store(options.resize_border, Ordering::SeqCst);
+
+    unsafe {
+        // Remove caption but keep thick frame + clip children (reduces flicker)
+        let style = GetWindowLongPtrW(hwnd, GWL_STYLE);
+        let new_style = (style & !(WS_CAPTION as isize))
+            | (WS_THICKFRAME as isize)
+            | (WS_CLIPCHILDREN as isize);
+        SetWindowLongPtrW(hwnd, GWL_STYLE, new_style);
+
+        // Extend DWM frame into entire client area → enables shadow + composition
+        let margins = MARGINS {
+            cx_left_width: -1,
+            cx_right_width: -1,
+            cy_top_height: -1,
+            cy_bottom_height: -1,
+        };
+        DwmExtendFrameIntoClientArea(hwnd, &margins);
+
+        // Paint background (COLORREF is BGR)
+        let brush = CreateSolidBrush(options.background);
+        SetClassLongPtrW(hwnd, GCLP_HBRBACKGROUND, brush);
+
+        // Save original WndProc in a static (NOT GWLP_USERDATA — tao uses that)
+        let original_proc = GetWindowLongPtrW(hwnd, GWLP_WNDPROC);
+        ORIGINAL_WNDPROC.store(original_proc, Ordering::SeqCst);
+        let subclass: unsafe extern "system" fn(isize, u32, usize, isize) -> isize = custom_wndproc;
+        SetWindowLongPtrW(hwnd, GWLP_WNDPROC, subclass as usize as isize);
+
+        // Force Windows to recalculate the frame
+        SetWindowPos(
+            hwnd,
+            0,
+            0,
+            0,
+            0,
+            0,
+            SWP_FRAMECHANGED | SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER,
+        );
+
+        InvalidateRect(hwnd, std::ptr::null(), 1);
+    }
+}
+
+/// Extend the DWM frame over the whole window (which draws the drop
+/// shadow), or take it back to remove the shadow. `setup_frameless`
+/// turns it on. Returns whether DWM accepted it.
+pub fn set_shadow(hwnd: isize, enabled: bool) -> bool {
+    let extent = if enabled { -1 } else { 0 };
+    let margins = win32::MARGINS {
+        cx_left_width: extent,
+        cx_right_width: extent,
+        cy_top_height: extent,
+        cy_bottom_height: extent,
+    };
+    unsafe { win32::DwmExtendFrameIntoClientArea(hwnd, &margins) >= 0 }
+}
+
+/// Start a native resize drag from `direction`. Windows takes over the
+/// resize loop, so this is instant with zero IPC lag. Call while the
+/// mouse button is down.
+pub fn start_native_resize(hwnd: isize, direction: ResizeDirection) {
+    unsafe {
+        win32::ReleaseCapture();
+        win32::SendMessageW(hwnd, win32::WM_NCLBUTTONDOWN, direction as usize, 0);
+    }
+}
+
+/// Forward a message to the WndProc we replaced.
+unsafe fn call_original(hwnd: isize, msg: u32, wparam: usize, lparam: isize) -> isize {
+    let original_proc = ORIGINAL_WNDPROC.load(Ordering::SeqCst);
+    if original_proc != 0 {
+        let proc_fn: unsafe extern "system" fn(isize, u32, usize, isize) -> isize =
+            std::mem::transmute(original_proc);
+        return proc_fn(hwnd, msg, wparam, lparam);
+    }
+    win32::DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+/// Which part of the window a point is over, as a `WM_NCHITTEST` result.
+/// Distances are from the cursor to each window edge.
+fn hit_test(left: i32, right: i32, top: i32, bottom: i32) -> isize {
+    let border = scaled(RESIZE_BORDER.load(Ordering::SeqCst));
+    let (near_left, near_right) = (left <= border, right <= border);
+    let (near_top, near_bottom) = (top <= border, bottom <= border);
+
+    // Corners first (they overlap edges)
+    let direction = match (near_top, near_bottom, near_left, near_right) {
+        (true, _, true, _) => Some(ResizeDirection::TopLeft),
+        (true, _, _, true) => Some(ResizeDirection::TopRight),
+        (_, true, true, _) => Some(ResizeDirection::BottomLeft),
+        (_, true, _, true) => Some(ResizeDirection::BottomRight),
+        (true, ..) => Some(ResizeDirection::Top),
+        (_, true, ..) => Some(ResizeDirection::Bottom),
+        (.., true, _) => Some(ResizeDirection::Left),
+        (.., true) => Some(ResizeDirection::Right),
+        _ => None,
+    };
+    if let Some(direction) = direction {
+        return direction as isize;
+    }
+    // Declared caption buttons, then the title bar drag region, then
+    // client area (WebView content)
+    if let Some(button) = caption_button_at(left, top) {
+        return button.hit_code();
+    }
+    if top <= scaled(TITLEBAR_HEIGHT.load(Ordering::SeqCst)) {
+        HTCAPTION
+    } else {
+        HTCLIENT
+    }
+}
+
+/// Custom WndProc for frameless hit-testing.
+///
+/// Handles:
+/// - `WM_NCCALCSIZE` → returns 0 so entire window is client area
+/// - `WM_NCHITTEST`  → returns HTCAPTION for drag bar, HT*LEFT/RIGHT/etc.
+///   for resize edges, HTMINBUTTON/HTMAXBUTTON/HTCLOSE for declared
+///   caption buttons, HTCLIENT for everything else
+unsafe extern "system" fn custom_wndproc(
+    hwnd: isize,
+    msg: u32,
+    wparam: usize,
+    lparam: isize,
+) -> isize {
+    use win32::*;
+
+    match msg {
+        // ── WM_NCCALCSIZE: make entire window = client area ──
+        // Returning 0 allows client area = window rect. We rely on
+        // DwmExtendFrameIntoClientArea(-1) + WS_CLIPCHILDREN rather than
+        // WVR_VALIDRECTS, and avoid DefWindowProc, which adds the
+        // borders/caption back.
+        WM_NCCALCSIZE if wparam != 0 => 0,
+
+        // ── WM_ERASEBKGND: dark fill immediately (no white flash) ──
+        // The class brush paints the background; returning 1 tells
+        // Windows "I handled it".
+        WM_ERASEBKGND => 1,
+
+        // ── WM_SIZE: forward so tao/wry resize the WebView ──
+        WM_SIZE => call_original(hwnd, msg, wparam, lparam),
+
+        // ── WM_NCHITTEST: custom drag bar + resize edges ──
+        WM_NCHITTEST => {
+            // Cursor position in screen coords (packed in lparam)
+            let cursor_x = (lparam & 0xFFFF) as i16 as i32;
+            let cursor_y = ((lparam >> 16) & 0xFFFF) as i16 as i32;
+
+            let mut rect = RECT {
+                left: 0,
+                top: 0,
+                right: 0,
+                bottom: 0,
+            };
+            GetWindowRect(hwnd, &mut rect);
+
+            hit_test(
+                cursor_x - rect.left,
+                rect.right - cursor_x,
+                cursor_y - rect.top,
+                rect.bottom - cursor_y,
+            )
+        }
+
+        // ── DWM preview requests: answered by `preview` when enabled ──
+        WM_DWMSENDICONICTHUMBNAIL | WM_DWMSENDICONICLIVEPREVIEWBITMAP => {
+            crate::preview::handle_message(hwnd, msg, lparam)
+                .unwrap_or_else(|| call_original(hwnd, msg, wparam, lparam))
+        }
+
+        // All other messages → forward to original WndProc
+        _ => call_original(hwnd, msg, wparam, lparam),
+    }
+}
@@ -0,0 +1,410 @@
+// ═════════════════════════════════════════════════════════════════
+//  Jump List — app shortcuts on the taskbar button's right-click menu
+// ═════════════════════════════════════════════════════════════════
+//
+// Right-clicking the taskbar button (or the Start menu entry) offers a
+// "Tasks" section such as "Resume lesson" or "Parent dashboard". Each
+// task starts the app's executable again with its own command line,
+// which the host reads from its arguments. The host sets the tasks as
+// a JSON array:
+//
+//   [{"title":"Resume lesson","arguments":"--resume"},
+//    {"title":"Parent dashboard","arguments":"--parent",
+//     "description":"Progress reports and settings"}]
+//
+// An empty array removes the tasks. Windows keeps the list between
+// runs, so the tasks stay after the app exits until they are replaced.
+// The list needs no window: it is written from the calling thread.
+//
+// Only the "windows" feature has a jump list; elsewhere `set` returns
+// `STATUS_UNSUPPORTED`.
+
+/// One entry of the jump list's "Tasks" section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Task {
+    title: String,
+    arguments: String,
+    /// Hover text.
+    description: Option<String>,
+}
+
+impl Task {
+    /// Parse a JSON array of tasks. `None` for anything else, including
+    /// a task without a title.
+    pub fn parse_list(json: &str) -> Option<Vec<Task>> {
+        let value: serde_json::Value = serde_json::from_str(json).ok()?;
+        value
+            .as_array()?
+            .iter()
+            .map(|entry| {
+                let text = |key| match entry.get(key) {
+                    None => Some(None),
+                    // Windows takes NUL-terminated strings.
+                    Some(value) => value
+                        .as_str()
+                        .filter(|text| !text.contains('\0'))
+                        .map(|text| Some(text.to_owned())),
+                };
+                let title = text("title")?.filter(|title| !title.trim().is_empty())?;
+                Some(Task {
+                    title,
+                    arguments: text("arguments")?.unwrap_or_default(),
+                    description: text("description")?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Replace the jump list's tasks with `tasks`, each starting this
+/// executable. Returns a status code.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub fn set(tasks: &[Task]) -> i32 {
+    use std::ffi::{c_void, OsStr};
+    use std::os::windows::ffi::OsStrExt;
+
+    // ─── Raw Win32 FFI declarations ──
+    #[repr(C)]
+    struct Guid {
+        data1: u32,
+        data2: u16,
+        data3: u16,
+        data4: [u8; 8],
+    }
+
+    // {77F10CF0-3DB5-4966-B520-B7C54FD35ED6}
+    const CLSID_DESTINATION_LIST: Guid = Guid {
+        data1: 0x77F1_0CF0,
+        data2: 0x3DB5,
+        data3: 0x4966,
+        data4: [0xB5, 0x20, 0xB7, 0xC5, 0x4F, 0xD3, 0x5E, 0xD6],
+    };
+    // {6332DEBF-87B5-4670-90C0-5E57B408A49E}
+    const IID_ICUSTOM_DESTINATION_LIST: Guid = Guid {
+        data1: 0x6332_DEBF,
+        data2: 0x87B5,
+        data3: 0x4670,
+        data4: [0x90, 0xC0, 0x5E, 0x57, 0xB4, 0x08, 0xA4, 0x9E],
+    };
+    // {2D3468C1-36A7-43B6-AC24-D3F02FD9607A}
+    const CLSID_ENUMERABLE_OBJECT_COLLECTION: Guid = Guid {
+        data1: 0x2D34_68C1,
+        data2: 0x36A7,
+        data3: 0x43B6,
+        data4: [0xAC, 0x24, 0xD3, 0xF0, 0x2F, 0xD9, 0x60, 0x7A],
+    };
+    // {5632B1A4-E38A-400A-928A-D4CD63230295}
+    const IID_IOBJECT_COLLECTION: Guid = Guid {
+        data1: 0x5632_B1A4,
+        data2: 0xE38A,
+        data3: 0x400A,
+        data4: [0x92, 0x8A, 0xD4, 0xCD, 0x63, 0x23, 0x02, 0x95],
+    };
+    // {92CA9DCD-5622-4BBA-A805-5E9F541BD8C9}
+    const IID_IOBJECT_ARRAY: Guid = Guid {
+        data1: 0x92CA_9DCD,
+        data2: 0x5622,
+        data3: 0x4BBA,
+        data4: [0xA8, 0x05, 0x5E, 0x9F, 0x54, 0x1B, 0xD8, 0xC9],
+    };
+    // {00021401-0000-0000-C000-000000000046}
+    const CLSID_SHELL_LINK: Guid = Guid {
+        data1: 0x0002_1401,
+        data2: 0x0000,
+        data3: 0x0000,
+        data4: [0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+    };
+    // {000214F9-0000-0000-C000-000000000046}
+    const IID_ISHELL_LINKW: Guid = Guid {
+        data1: 0x0002_14F9,
+        data2: 0x0000,
+        data3: 0x0000,
+        data4: [0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+    };
+    // {886D8EEB-8CF2-4446-8D02-CDBA1DBDCF99}
+    const IID_IPROPERTY_STORE: Guid = Guid {
+        data1: 0x886D_8EEB,
+        data2: 0x8CF2,
+        data3: 0x4446,
+        data4: [0x8D, 0x02, 0xCD, 0xBA, 0x1D, 0xBD, 0xCF, 0x99],
+    };
+
+    #[repr(C)]
+    struct PropertyKey {
+        fmtid: Guid,
+        pid: u32,
+    }
+
+    // {F29F85E0-4FF9-1068-AB91-08002B27B3D9}, 2
+    const PKEY_TITLE: PropertyKey = PropertyKey {
+        fmtid: Guid {
+            data1: 0xF29F_85E0,
+            data2: 0x4FF9,
+            data3: 0x1068,
+            data4: [0xAB, 0x91, 0x08, 0x00, 0x2B, 0x27, 0xB3, 0xD9],
+        },
+        pid: 2,
+    };
+    const VT_LPWSTR: u16 = 31;
+    const COINIT_APARTMENTTHREADED: u32 = 0x2;
+    const CLSCTX_INPROC_SERVER: u32 = 0x1;
+
+    /// A `PROPVARIANT` holding a string.
+    #[repr(C)]
+    struct PropVariant {
+        vt: u16,
+        reserved: [u16; 3],
+        value: *const u16,
+        padding: usize,
+    }
+
+    /// `IUnknown`, which every vtable below starts with.
+    #[repr(C)]
+    struct UnknownVtbl {
+        query_interface:
+            unsafe extern "system" fn(*mut c_void, *const Guid, *mut *mut c_void) -> i32,
+        add_ref: usize,
+        release: unsafe extern "system" fn(*mut c_void) -> u32,
+    }
+
+    /// `ICustomDestinationList`, up to the methods used here.
+    #[repr(C)]
+    struct DestinationListVtbl {
+        unknown: UnknownVtbl,
+        set_app_id: usize,
+        begin_list:
+            unsafe extern "system" fn(*mut c_void, *mut u32, *const Guid, *mut *mut c_void) -> i32,
+        append_category: usize,
+        append_known_category: usize,
+        add_user_tasks: unsafe extern "system" fn(*mut c_void, *mut c_void) -> i32,
+        commit_list: unsafe extern "system" fn(*mut c_void) -> i32,
+        get_removed_destinations: usize,
+        delete_list: usize,
+        abort_list: unsafe extern "system" fn(*mut c_void) -> i32,
+    }
+
+    /// `IObjectCollection`, up to the methods used here.
+    #[repr(C)]
+    struct ObjectCollectionVtbl {
+        unknown: UnknownVtbl,
+        get_count: usize,
+        get_at: usize,
+        add_object: unsafe extern "system" fn(*mut c_void, *mut c_void) -> i32,
+    }
+
+    /// `IShellLinkW`, up to the methods used here.
+    #[repr(C)]
+    struct ShellLinkVtbl {
+        unknown: UnknownVtbl,
+        get_path: usize,
+        get_id_list: usize,
+        set_id_list: usize,
+        get_description: usize,
+        set_description: unsafe extern "system" fn(*mut c_void, *const u16) -> i32,
+        get_working_directory: usize,
+        set_working_directory: usize,
+        get_arguments: usize,
+        set_arguments: unsafe extern "system" fn(*mut c_void, *const u16) -> i32,
+        get_hotkey: usize,
+        set_hotkey: usize,
+        get_show_cmd: usize,
+        set_show_cmd: usize,
+        get_icon_location: usize,
+        set_icon_location: unsafe extern "system" fn(*mut c_void, *const u16, i32) -> i32,
+        set_relative_path: usize,
+        resolve: usize,
+        set_path: unsafe extern "system" fn(*mut c_void, *const u16) -> i32,
+    }
+
+    /// `IPropertyStore`, up to the methods used here.
+    #[repr(C)]
+    struct PropertyStoreVtbl {
+        unknown: UnknownVtbl,
+        get_count: usize,
+        get_at: usize,
+        get_value: usize,
+        set_value:
+            unsafe extern "system" fn(*mut c_void, *const PropertyKey, *const PropVariant) -> i32,
+        commit: unsafe extern "system" fn(*mut c_void) -> i32,
+    }
+
+    #[link(name = "ole32")]
+    extern "system" {
+        // ole32.dll
+        fn CoInitializeEx(reserved: *const c_void, flags: u32) -> i32;
+        fn CoCreateInstance(
+            clsid: *const Guid,
+            outer: *mut c_void,
+            context: u32,
+            iid: *const Guid,
+            object: *mut *mut c_void,
+        ) -> i32;
+    }
+
+    /// A COM object released when dropped.
+    struct Object(*mut c_void);
+
+    impl Object {
+        /// Its vtable, viewed as `V`.
+        unsafe fn vtbl<V>(&self) -> &V {
+            &**(self.0 as *const *const V)
+        }
+
+        unsafe fn create(clsid: &Guid, iid: &Guid) -> Option<Object> {
+            let mut object = std::ptr::null_mut();
+            let created = CoCreateInstance(
+                clsid,
+                std::ptr::null_mut(),
+                CLSCTX_INPROC_SERVER,
+                iid,
+                &mut object,
+            );
+            (created >= 0 && !object.is_null()).then_some(Object(object))
+        }
+
+        unsafe fn cast(&self, iid: &Guid) -> Option<Object> {
+            let mut object = std::ptr::null_mut();
+            let found = (self.vtbl::<UnknownVtbl>().query_interface)(self.0, iid, &mut object);
+            (found >= 0 && !object.is_null()).then_some(Object(object))
+        }
+    }
+
+    impl Drop for Object {
+        fn drop(&mut self) {
+            if !self.0.is_null() {
+                unsafe { (self.vtbl::<UnknownVtbl>().release)(self.0) };
+            }
+        }
+    }
+
+    fn wide(text: &OsStr) -> Vec<u16> {
+        text.encode_wide().chain(std::iter::once(0)).collect()
+    }
+
+    /// A shell link that starts `exe` with the task's arguments.
+    unsafe fn link(exe: &[u16], task: &Task) -> Option<Object> {
+        let link = Object::create(&CLSID_SHELL_LINK, &IID_ISHELL_LINKW)?;
+        let vtbl = link.vtbl::<ShellLinkVtbl>();
+        let arguments = wide(OsStr::new(&task.arguments));
+        let description = task
+            .description
+            .as_deref()
+            .map(|text| wide(OsStr::new(text)));
+        let title = wide(OsStr::new(&task.title));
+        let ok = (vtbl.set_path)(link.0, exe.as_ptr()) >= 0
+            && (vtbl.set_arguments)(link.0, arguments.as_ptr()) >= 0
+            && (vtbl.set_icon_location)(link.0, exe.as_ptr(), 0) >= 0
+            && description
+                .as_ref()
+                .is_none_or(|text| (vtbl.set_description)(link.0, text.as_ptr()) >= 0);
+        if !ok {
+            return None;
+        }
+        // The menu shows the link's title property, not a file name.
+        let store = link.cast(&IID_IPROPERTY_STORE)?;
+        let store_vtbl = store.vtbl::<PropertyStoreVtbl>();
+        let value = PropVariant {
+            vt: VT_LPWSTR,
+            reserved: [0; 3],
+            value: title.as_ptr(),
+            padding: 0,
+        };
+        let ok = (store_vtbl.set_value)(store.0, &PKEY_TITLE, &value) >= 0
+            && (store_vtbl.commit)(store.0) >= 0;
+        ok.then_some(link)
+    }
+
+    let Ok(exe) = std::env::current_exe() else {
+        return crate::STATUS_OS_ERROR;
+    };
+    let exe = wide(exe.as_os_str());
+    unsafe {
+        // Any apartment will do; a thread already in one keeps it.
+        CoInitializeEx(std::ptr::null(), COINIT_APARTMENTTHREADED);
+        let Some(list) = Object::create(&CLSID_DESTINATION_LIST, &IID_ICUSTOM_DESTINATION_LIST)
+        else {
+            return crate::STATUS_OS_ERROR;
+        };
+        let vtbl = list.vtbl::<DestinationListVtbl>();
+        let mut min_slots = 0;
+        let mut removed = std::ptr::null_mut();
+        if (vtbl.begin_list)(list.0, &mut min_slots, &IID_IOBJECT_ARRAY, &mut removed) < 0 {
+            return crate::STATUS_OS_ERROR;
+        }
+        drop(Object(removed));
+        let added = tasks.is_empty()
+            || Object::create(&CLSID_ENUMERABLE_OBJECT_COLLECTION, &IID_IOBJECT_COLLECTION)
+                .is_some_and(|collection| {
+                    let add = collection.vtbl::<ObjectCollectionVtbl>().add_object;
+                    tasks.iter().all(|task| {
+                        link(&exe, task).is_some_and(|link| add(collection.0, link.0) >= 0)
+                    }) && (vtbl.add_user_tasks)(list.0, collection.0) >= 0
+                });
+        if added && (vtbl.commit_list)(list.0) >= 0 {
+            crate::STATUS_OK
+        } else {
+            (vtbl.abort_list)(list.0);
+            crate::STATUS_OS_ERROR
+        }
+    }
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows")))]
+/// No jump list without the "windows" feature.
+pub fn set(_tasks: &[Task]) -> i32 {
+    crate::STATUS_UNSUPPORTED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(title: &str, arguments: &str, description: Option<&str>) -> Task {
+        Task {
+            title: title.to_owned(),
+            arguments: arguments.to_owned(),
+            description: description.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let json = r#"[{"title":"Resume lesson","arguments":"--resume"},
+            {"title":"Parent dashboard","arguments":"--parent",
+             "description":"Progress reports and settings"},
+            {"title":"Open"}]"#;
+        assert_eq!(
+            Task::parse_list(json),
+            Some(vec![
+                task("Resume lesson", "--resume", None),
+                task(
+                    "Parent dashboard",
+                    "--parent",
+                    Some("Progress reports and settings")
+                ),
+                task("Open", "", None),
+            ])
+        );
+        assert_eq!(Task::parse_list("[]"), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_parse_list_rejects_malformed_tasks() {
+        for json in [
+            "",
+            "[",
+            r#"{"title":"Open"}"#,
+            r#"["Open"]"#,
+            r#"[{"arguments":"--resume"}]"#,
+            r#"[{"title":"  "}]"#,
+            r#"[{"title":null}]"#,
+            r#"[{"title":7}]"#,
+            r#"[{"title":"Open","arguments":["--resume"]}]"#,
+            r#"[{"title":"Open","description":false}]"#,
+            r#"[{"title":"Open\u0000"}]"#,
+            r#"[{"title":"Open"},{"title":""}]"#,
+        ] {
+            assert_eq!(Task::parse_list(json), None, "{json}");
+        }
+    }
+}
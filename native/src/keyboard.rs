@@ -0,0 +1,228 @@
+// ═════════════════════════════════════════════════════════════════
+//  Win32 Restricted Keyboard Input (feature = "windows")
+// ═════════════════════════════════════════════════════════════════
+//
+// Numpad-only mode for young students during drills: while the window
+// is in the foreground, a low-level keyboard hook lets through only
+// digits, the fraction slash, minus, Enter, Backspace, and Num Lock,
+// and swallows everything else — Alt+Tab, the Windows key, browser
+// shortcuts, F5, arrow-key navigation — before it reaches the webview.
+//
+// A low-level hook is needed because WebView2's input window belongs
+// to the browser process, so the host's own message loop never sees
+// those keys. The hook runs on the thread that installed it, which
+// must pump messages (the tao event loop does).
+//
+// With Num Lock off the numpad sends navigation keys (Home, ↑, PgUp…);
+// in numpad mode they are re-sent as the digits printed on the keys.
+
+use std::sync::atomic::{AtomicIsize, Ordering};
+
+// ─── Raw Win32 FFI declarations (avoids windows-sys version conflicts) ──
+#[allow(clippy::upper_case_acronyms)]
+mod win32 {
+    pub type HWND = isize;
+    pub type HHOOK = isize;
+    pub type LRESULT = isize;
+    pub type WPARAM = usize;
+    pub type LPARAM = isize;
+    pub type HOOKPROC = unsafe extern "system" fn(i32, WPARAM, LPARAM) -> LRESULT;
+
+    #[repr(C)]
+    pub struct KBDLLHOOKSTRUCT {
+        pub vk_code: u32,
+        pub scan_code: u32,
+        pub flags: u32,
+        pub time: u32,
+        pub extra_info: usize,
+    }
+
+    #[repr(C)]
+    pub struct KEYBDINPUT {
+        pub vk: u16,
+        pub scan: u16,
+        pub flags: u32,
+        pub time: u32,
+        pub extra_info: usize,
+    }
+
+    /// `INPUT` with the keyboard arm of its union; the padding covers
+    /// the larger `MOUSEINPUT` arm so `size_of` matches the C struct.
+    #[repr(C)]
+    pub struct INPUT {
+        pub kind: u32,
+        pub ki: KEYBDINPUT,
+        pub _pad: [u8; 8],
+    }
+
+    pub const WH_KEYBOARD_LL: i32 = 13;
+    pub const HC_ACTION: i32 = 0;
+    pub const LLKHF_EXTENDED: u32 = 0x01;
+    pub const WM_KEYUP: usize = 0x0101;
+    pub const WM_SYSKEYUP: usize = 0x0105;
+    pub const INPUT_KEYBOARD: u32 = 1;
+    pub const KEYEVENTF_KEYUP: u32 = 0x0002;
+
+    extern "system" {
+        // user32.dll
+        pub fn SetWindowsHookExW(id: i32, proc_: HOOKPROC, module: isize, thread_id: u32) -> HHOOK;
+        pub fn UnhookWindowsHookEx(hook: HHOOK) -> i32;
+        pub fn CallNextHookEx(hook: HHOOK, code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT;
+        pub fn GetForegroundWindow() -> HWND;
+        pub fn SendInput(count: u32, inputs: *const INPUT, size: i32) -> u32;
+    }
+
+    extern "system" {
+        // kernel32.dll
+        pub fn GetModuleHandleW(name: *const u16) -> isize;
+    }
+}
+
+/// Which keys reach the webview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    /// Every key, as normal.
+    Full,
+    /// Digits, '/', '-', Enter, Backspace, and Num Lock only.
+    Numpad,
+}
+
+impl InputMode {
+    /// Parse "full" or "numpad".
+    pub fn from_name(name: &str) -> Option<InputMode> {
+        match name {
+            "full" => Some(InputMode::Full),
+            "numpad" => Some(InputMode::Numpad),
+            _ => None,
+        }
+    }
+}
+
+// Virtual-key codes
+const VK_BACK: u32 = 0x08;
+const VK_RETURN: u32 = 0x0D;
+const VK_NUMLOCK: u32 = 0x90;
+const VK_NUMPAD0: u32 = 0x60;
+const VK_SUBTRACT: u32 = 0x6D;
+const VK_DIVIDE: u32 = 0x6F;
+const VK_OEM_MINUS: u32 = 0xBD;
+const VK_OEM_2: u32 = 0xBF; // '/' on US layouts
+
+/// Numpad keys with Num Lock off, in digit order: Insert, End, ↓,
+/// PgDn, ←, Clear, →, Home, ↑, PgUp.
+const NUMPAD_NAVIGATION: [u32; 10] = [0x2D, 0x23, 0x28, 0x22, 0x25, 0x0C, 0x27, 0x24, 0x26, 0x21];
+
+/// Marks keys this module injects, so the hook lets them through.
+const INJECTED_MARKER: usize = 0x5341_4B42;
+
+/// Installed hook (0 = none) and the window it guards.
+static HOOK: AtomicIsize = AtomicIsize::new(0);
+static TARGET: AtomicIsize = AtomicIsize::new(0);
+
+fn is_allowed(vk: u32) -> bool {
+    matches!(
+        vk,
+        0x30..=0x39 // top-row digits
+            | VK_NUMPAD0..=0x69
+            | VK_DIVIDE
+            | VK_SUBTRACT
+            | VK_OEM_MINUS
+            | VK_OEM_2
+            | VK_RETURN
+            | VK_BACK
+            | VK_NUMLOCK
+    )
+}
+
+/// The digit a numpad key stands for when Num Lock is off. Navigation
+/// keys from the numpad are the non-extended ones.
+fn numpad_digit(info: &win32::KBDLLHOOKSTRUCT) -> Option<u32> {
+    if info.flags & win32::LLKHF_EXTENDED != 0 {
+        return None;
+    }
+    NUMPAD_NAVIGATION
+        .iter()
+        .position(|&vk| vk == info.vk_code)
+        .map(|digit| digit as u32)
+}
+
+/// Inject a marked key press or release.
+fn send_key(vk: u32, key_up: bool) {
+    let input = win32::INPUT {
+        kind: win32::INPUT_KEYBOARD,
+        ki: win32::KEYBDINPUT {
+            vk: vk as u16,
+            scan: 0,
+            flags: if key_up { win32::KEYEVENTF_KEYUP } else { 0 },
+            time: 0,
+            extra_info: INJECTED_MARKER,
+        },
+        _pad: [0; 8],
+    };
+    unsafe {
+        win32::SendInput(1, &input, std::mem::size_of::<win32::INPUT>() as i32);
+    }
+}
+
+/// Low-level keyboard hook: filters keys while the guarded window is
+/// in the foreground. Returning 1 swallows the key.
+unsafe extern "system" fn keyboard_proc(
+    code: i32,
+    wparam: win32::WPARAM,
+    lparam: win32::LPARAM,
+) -> win32::LRESULT {
+    if code == win32::HC_ACTION {
+        let info = &*(lparam as *const win32::KBDLLHOOKSTRUCT);
+        let target = TARGET.load(Ordering::SeqCst);
+        if target != 0
+            && info.extra_info != INJECTED_MARKER
+            && win32::GetForegroundWindow() == target
+        {
+            if let Some(digit) = numpad_digit(info) {
+                let key_up = wparam == win32::WM_KEYUP || wparam == win32::WM_SYSKEYUP;
+                send_key(VK_NUMPAD0 + digit, key_up);
+                return 1;
+            }
+            if !is_allowed(info.vk_code) {
+                return 1;
+            }
+        }
+    }
+    win32::CallNextHookEx(0, code, wparam, lparam)
+}
+
+/// Switch the input mode for `hwnd`. Call from the thread that runs
+/// the window's message loop. Returns `false` if the hook could not be
+/// installed.
+pub fn set_input_mode(hwnd: isize, mode: InputMode) -> bool {
+    match mode {
+        InputMode::Full => {
+            TARGET.store(0, Ordering::SeqCst);
+            let hook = HOOK.swap(0, Ordering::SeqCst);
+            if hook != 0 {
+                unsafe { win32::UnhookWindowsHookEx(hook) };
+            }
+            true
+        }
+        InputMode::Numpad => {
+            TARGET.store(hwnd, Ordering::SeqCst);
+            if HOOK.load(Ordering::SeqCst) != 0 {
+                return true;
+            }
+            let hook = unsafe {
+                win32::SetWindowsHookExW(
+                    win32::WH_KEYBOARD_LL,
+                    keyboard_proc,
+                    win32::GetModuleHandleW(std::ptr::null()),
+                    0,
+                )
+            };
+            if hook == 0 {
+                TARGET.store(0, Ordering::SeqCst);
+                return false;
+            }
+            HOOK.store(hook, Ordering::SeqCst);
+            true
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// ═════════════════════════════════════════════════════════════════
+//  Kiosk Mode — a locked-down shell for timed assessments
+// ═════════════════════════════════════════════════════════════════
+//
+// During a timed assessment the window must not be left behind other
+// apps or turned into a browser. Kiosk mode makes the window
+// borderless fullscreen and always on top, hides the webview's context
+// menu (no "Back", "Reload" or "Inspect"), and locks navigation to the
+// origin of the page shown when it starts: links and scripts can still
+// move between the assessment's own pages, but not to any other site,
+// even one the navigation policy allows. Popups never open anyway
+// (see `links`). The one exception is the page the watchdog shows
+// while the host is stuck (see `watchdog`).
+//
+// Leaving kiosk mode restores what it changed: the window returns to
+// the fullscreen and always-on-top state it had before, and the
+// context menu comes back.
+//
+// Only Windows can hide the context menu, and a lock without it is not
+// one, so elsewhere entering and leaving change nothing and report
+// `STATUS_UNSUPPORTED`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use tao::window::{Fullscreen, Window};
+use wry::WebView;
+
+/// A window's kiosk state, shared with its navigation handler; `None`
+/// outside kiosk mode.
+pub type Lock = Rc<RefCell<Option<Locked>>>;
+
+/// What kiosk mode locked and what it will restore.
+#[derive(Debug)]
+pub struct Locked {
+    /// `scheme://host:port` pages must stay on.
+    origin: String,
+    was_fullscreen: bool,
+    was_always_on_top: bool,
+}
+
+/// `scheme://authority` of a hierarchical URL, lowercased.
+fn origin(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    (!authority.is_empty()).then(|| format!("{scheme}://{authority}").to_ascii_lowercase())
+}
+
+/// Whether the window's kiosk lock lets it navigate to `url`.
+pub fn allows(lock: &Lock, url: &str) -> bool {
+    match &*lock.borrow() {
+        Some(locked) => origin(url).is_some_and(|origin| origin == locked.origin),
+        None => true,
+    }
+}
+
+/// Lock the window down (see the header); entering again only
+/// re-applies the window state. Returns a status code.
+pub fn enter(window: &Window, webview: &WebView, lock: &Lock) -> i32 {
+    if !cfg!(target_os = "windows") {
+        return crate::STATUS_UNSUPPORTED;
+    }
+    if lock.borrow().is_none() {
+        let Some(origin) = webview.url().ok().as_deref().and_then(origin) else {
+            // Nothing loaded yet, so nothing to lock navigation to.
+            return crate::STATUS_INVALID_ARGUMENT;
+        };
+        *lock.borrow_mut() = Some(Locked {
+            origin,
+            was_fullscreen: window.fullscreen().is_some(),
+            was_always_on_top: window.is_always_on_top(),
+        });
+    }
+    window.set_fullscreen(Some(Fullscreen::Borderless(None)));
+    window.set_always_on_top(true);
+    window.set_focus();
+    set_context_menus(webview, false)
+}
+
+/// Undo `enter`. Returns a status code; `STATUS_OK` outside kiosk mode.
+pub fn exit(window: &Window, webview: &WebView, lock: &Lock) -> i32 {
+    if !cfg!(target_os = "windows") {
+        return crate::STATUS_UNSUPPORTED;
+    }
+    let Some(locked) = lock.borrow_mut().take() else {
+        return crate::STATUS_OK;
+    };
+    if !locked.was_fullscreen {
+        window.set_fullscreen(None);
+    }
+    window.set_always_on_top(locked.was_always_on_top);
+    set_context_menus(webview, true)
+}
+
+/// Show or hide the webview's default context menu. Returns a status
+/// code.
+#[cfg(target_os = "windows")]
+fn set_context_menus(webview: &WebView, enabled: bool) -> i32 {
+    use wry::WebViewExtWindows;
+
+    let result = unsafe {
+        webview
+            .controller()
+            .CoreWebView2()
+            .and_then(|core| core.Settings())
+            .and_then(|settings| settings.SetAreDefaultContextMenusEnabled(enabled))
+    };
+    match result {
+        Ok(()) => crate::STATUS_OK,
+        Err(_) => crate::STATUS_WEBVIEW_FAILED,
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+/// The context menu cannot be hidden outside Windows.
+fn set_context_menus(_webview: &WebView, _enabled: bool) -> i32 {
+    crate::STATUS_UNSUPPORTED
+}
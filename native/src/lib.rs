@@ -1,145 +1,1888 @@
 // Sovereign Academy - Native Frameless Window with WebView (Deno FFI)
 //
 // This library creates a frameless (borderless) native window with
-// an embedded webview that loads the Fresh server (localhost:8000).
+// an embedded webview that loads the Fresh server (localhost:8000 by
+// default; see `create_frameless_window_with_url`).
 // The custom title bar is rendered by Preact inside the webview.
+//
+// Threading: tao windows only paint, resize and answer input while
+// their event loop is pumped, and Deno's main thread is busy running
+// JavaScript. The first `create_window` therefore spawns a dedicated
+// UI thread that owns the event loop and every window; the FFI
+// functions post jobs to it and block until the job has run, so the
+// calls stay synchronous for Deno.
+//
+// Ownership: windows live in a handle table on the UI thread.
+// Deno only ever holds an opaque `u32` handle; every function looks it
+// up first, so a stale or made-up handle is rejected instead of being
+// dereferenced. Handles are never reused.
+//
+// Status codes: every window function returns an `i32`; negative
+// values are the `STATUS_*` errors below, so Deno can tell a closed
+// window (`STATUS_INVALID_HANDLE`) from a bad argument, a dead UI
+// thread or a webview or OS error. Queries such as `is_maximized`
+// return their answer (>= 0).
+//
+// Events: resizes, moves, focus changes and close requests are queued
+// per window for the host to drain with `poll_event` (see `events`).
+//
+// Hangs: the host calls `heartbeat` on an interval once it has set a
+// `set_watchdog_timeout`; if the beats stop, the windows show a page
+// offering to reload or quit (see `watchdog`).
+
+pub mod accelerators;
+pub mod appearance;
+pub mod capture;
+pub mod dialog;
+pub mod events;
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub mod frameless;
+pub mod jumplist;
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub mod keyboard;
+pub mod kiosk;
+pub mod links;
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub mod magnifier;
+pub mod policy;
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub mod preview;
+pub mod scheme;
+pub mod scripts;
+pub mod sleep;
+pub mod taskbar;
+pub mod theme;
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub mod tray;
+pub mod watchdog;
 
+use std::cell::Cell;
+use std::collections::BTreeMap;
 use std::ffi::c_void;
-use std::sync::Mutex;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::thread;
 use tao::{
-    dpi::PhysicalSize,
-    event_loop::EventLoop,
-    window::{Window, WindowBuilder},
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::{Event, WindowEvent},
+    event_loop::{ControlFlow, EventLoopBuilder, EventLoopProxy, EventLoopWindowTarget},
+    window::{Icon, ResizeDirection, UserAttentionType, Window, WindowBuilder, WindowId},
 };
-use wry::WebViewBuilder;
+use wry::{ProxyConfig, ProxyEndpoint, WebContext, WebView, WebViewBuilder};
 
-// Wrapper to mark the raw pointer as thread-safe for global storage.
-// Safety: All access to the window is protected by the Mutex.
-struct SendPtr(*mut Window);
-unsafe impl Send for SendPtr {}
+use events::EventQueue;
 
-// Global window handle for FFI access
-static WINDOW: Mutex<Option<SendPtr>> = Mutex::new(None);
+/// Page the webview loads unless the host picks another.
+pub const DEFAULT_URL: &str = "http://localhost:8000";
 
-/// Create a frameless window with embedded webview.
-/// The webview loads http://localhost:8000 (Fresh server).
-/// Returns a raw pointer to the window handle.
+/// Opaque window handle given to Deno. 0 is never a valid handle.
+pub type WindowHandle = u32;
+
+/// The call succeeded.
+pub const STATUS_OK: i32 = 0;
+/// The handle was never issued, or its window has been closed.
+pub const STATUS_INVALID_HANDLE: i32 = -1;
+/// An argument was rejected, e.g. a title that is not UTF-8.
+pub const STATUS_INVALID_ARGUMENT: i32 = -2;
+/// The UI thread is not running (its event loop failed to start).
+pub const STATUS_UI_UNAVAILABLE: i32 = -3;
+/// The webview refused the request, e.g. a page load it could not start.
+pub const STATUS_WEBVIEW_FAILED: i32 = -4;
+/// The caller's buffer is too small for the next event.
+pub const STATUS_BUFFER_TOO_SMALL: i32 = -5;
+/// The OS refused the request, e.g. a drag with no mouse button down.
+pub const STATUS_OS_ERROR: i32 = -6;
+/// This build or platform has no such feature, e.g. native dialogs
+/// without the "windows" feature.
+pub const STATUS_UNSUPPORTED: i32 = -7;
+
+/// Collapse a window call's outcome into its status code.
+fn status(result: Result<(), i32>) -> i32 {
+    match result {
+        Ok(()) => STATUS_OK,
+        Err(code) => code,
+    }
+}
+
+/// Borrow a UTF-8 string passed from Deno; `None` for a null pointer
+/// or invalid UTF-8.
 ///
 /// # Safety
-/// Called from Deno FFI. The pointer must be managed by the caller.
-#[no_mangle]
-pub extern "C" fn create_frameless_window(width: i32, height: i32) -> *mut c_void {
-    let event_loop = EventLoop::new();
+/// `ptr` must be null or point to `len` readable bytes that outlive `'a`.
+unsafe fn read_str<'a>(ptr: *const u8, len: u32) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    std::str::from_utf8(std::slice::from_raw_parts(ptr, len as usize)).ok()
+}
+
+/// A window and the webview inside it, dropped together on close.
+struct NativeWindow {
+    window: Window,
+    webview: WebView,
+    events: EventQueue,
+    /// Leave closing to the host after "close-requested".
+    intercept_close: bool,
+    /// Shortcuts taken from the page, see `register_accelerator`.
+    #[cfg(target_os = "windows")]
+    accelerators: accelerators::Registry,
+    /// Where links asking for a new window go, see `links`.
+    external_links: Rc<Cell<links::ExternalLinks>>,
+    /// The webview was built with the inspector attached.
+    devtools: bool,
+    /// Kept alive for the webview, which is dropped first.
+    _context: WebContext,
+    /// Forgets the webview's unanswered scheme requests when dropped.
+    requests: scheme::Requests,
+    /// Scripts run at the start of each page, see `set_init_script`.
+    scripts: scripts::InitScripts,
+    /// What the webview may load, see `set_navigation_policy`.
+    policy: policy::SharedPolicy,
+    /// Navigation lock and saved state of kiosk mode.
+    kiosk: kiosk::Lock,
+    /// The page the watchdog covered while the host is stuck.
+    stalled: Option<String>,
+    /// The theme last reported in a "theme-changed" event.
+    theme: theme::Theme,
+    /// Subresource requests are checked against `policy`.
+    #[cfg(target_os = "windows")]
+    filtering: bool,
+    /// Key presses are checked against `accelerators`.
+    #[cfg(target_os = "windows")]
+    listening: bool,
+}
+
+struct WindowTable {
+    next: WindowHandle,
+    windows: BTreeMap<WindowHandle, NativeWindow>,
+    /// Undrained events of windows the OS closed, so the host still
+    /// sees "close-requested"; the handle dies once they are polled.
+    closed: BTreeMap<WindowHandle, EventQueue>,
+    /// Tray icons, which share the handle space with windows.
+    #[cfg(all(target_os = "windows", feature = "windows"))]
+    trays: BTreeMap<WindowHandle, tray::Tray>,
+}
+
+impl WindowTable {
+    fn get(&self, handle: WindowHandle) -> Option<&Window> {
+        self.windows.get(&handle).map(|native| &native.window)
+    }
+
+    /// A fresh handle; 0 once the handle space is exhausted.
+    fn next_handle(&mut self) -> WindowHandle {
+        let handle = self.next;
+        if handle != 0 {
+            self.next = handle.wrapping_add(1);
+        }
+        handle
+    }
+
+    fn insert(&mut self, native: NativeWindow) -> WindowHandle {
+        let handle = self.next_handle();
+        if handle != 0 {
+            self.windows.insert(handle, native);
+        }
+        handle
+    }
+
+    /// Queue a window event for the host and act on it.
+    fn window_event(&mut self, id: WindowId, event: &WindowEvent) {
+        let Some((&handle, native)) = self
+            .windows
+            .iter_mut()
+            .find(|(_, native)| native.window.id() == id)
+        else {
+            return;
+        };
+        if let Some(json) = events::encode(event) {
+            native.events.push(json);
+        }
+        if matches!(
+            event,
+            WindowEvent::ThemeChanged(_) | WindowEvent::Focused(true)
+        ) {
+            // High contrast has no event of its own, see `theme`.
+            let theme = theme::Theme::of(&native.window);
+            if theme != native.theme {
+                native.theme = theme;
+                native.events.push(events::theme_changed(theme));
+            }
+        }
+        if matches!(event, WindowEvent::CloseRequested) && !native.intercept_close {
+            // The OS asked to close (Alt+F4, taskbar menu): drop the
+            // window but keep its events until the host drains them.
+            // An intercepting host decides for itself.
+            if let Some(native) = self.windows.remove(&handle) {
+                self.closed.insert(handle, native.events);
+            }
+        }
+    }
+
+    /// Take the next event of `handle` that fits in `limit` bytes; see
+    /// `EventQueue::pop`. `None` for an unknown handle.
+    fn pop_event(
+        &mut self,
+        handle: WindowHandle,
+        limit: usize,
+    ) -> Option<Result<Option<String>, usize>> {
+        if let Some(native) = self.windows.get_mut(&handle) {
+            return Some(native.events.pop(limit));
+        }
+        #[cfg(all(target_os = "windows", feature = "windows"))]
+        if let Some(tray) = self.trays.get(&handle) {
+            return Some(tray.pop_event(limit));
+        }
+        let queue = self.closed.get_mut(&handle)?;
+        let event = queue.pop(limit);
+        if queue.is_empty() {
+            self.closed.remove(&handle);
+        }
+        Some(event)
+    }
+}
+
+/// Work for the UI thread, run with its window table and event loop.
+struct Job(Box<JobFn>);
+
+type JobFn = dyn FnOnce(&mut WindowTable, &UiTarget) + Send;
+
+type UiTarget = EventLoopWindowTarget<Job>;
+
+/// The UI thread's mailbox, or `None` if its event loop could not start.
+static UI_THREAD: OnceLock<Option<Mutex<EventLoopProxy<Job>>>> = OnceLock::new();
+
+/// Spawn the UI thread on first use and return its mailbox.
+fn ui_thread() -> Option<&'static Mutex<EventLoopProxy<Job>>> {
+    UI_THREAD
+        .get_or_init(|| {
+            let (ready, proxy) = mpsc::channel();
+            thread::Builder::new()
+                .name("native-ui".into())
+                .spawn(move || run_event_loop(ready))
+                .ok()?;
+            // A panic while creating the loop drops `ready` and ends up here.
+            proxy.recv().ok().map(Mutex::new)
+        })
+        .as_ref()
+}
+
+fn run_event_loop(ready: mpsc::Sender<EventLoopProxy<Job>>) -> ! {
+    let mut builder = EventLoopBuilder::<Job>::with_user_event();
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::EventLoopBuilderExtWindows;
+        builder.with_any_thread(true);
+    }
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    {
+        use tao::platform::unix::EventLoopBuilderExtUnix;
+        builder.with_any_thread(true);
+    }
+    let event_loop = builder.build();
+    let _ = ready.send(event_loop.create_proxy());
+
+    let mut table = WindowTable {
+        next: 1,
+        windows: BTreeMap::new(),
+        closed: BTreeMap::new(),
+        #[cfg(all(target_os = "windows", feature = "windows"))]
+        trays: BTreeMap::new(),
+    };
+    event_loop.run(move |event, target, control_flow| {
+        *control_flow = ControlFlow::Wait;
+        match event {
+            Event::UserEvent(Job(job)) => job(&mut table, target),
+            Event::WindowEvent {
+                window_id, event, ..
+            } => table.window_event(window_id, &event),
+            _ => {}
+        }
+    })
+}
 
+/// Post `job` to the UI thread without waiting for it.
+fn post(job: impl FnOnce(&mut WindowTable, &UiTarget) + Send + 'static) -> bool {
+    let Some(proxy) = ui_thread() else {
+        return false;
+    };
+    let Ok(proxy) = proxy.lock() else {
+        return false;
+    };
+    proxy.send_event(Job(Box::new(job))).is_ok()
+}
+
+/// Run `job` on the UI thread and wait for its result; `None` if the
+/// UI thread is not running.
+fn call<R: Send + 'static>(
+    job: impl FnOnce(&mut WindowTable, &UiTarget) -> R + Send + 'static,
+) -> Option<R> {
+    let (reply, result) = mpsc::channel();
+    if !post(move |table, target| {
+        let _ = reply.send(job(table, target));
+    }) {
+        return None;
+    }
+    // A job that panics drops `reply`, so this never waits forever.
+    result.recv().ok()
+}
+
+/// Run `f` on the UI thread's window table, where it returns `None`
+/// if the handle it looks up is not there.
+fn with_table<R: Send + 'static>(
+    f: impl FnOnce(&mut WindowTable) -> Option<R> + Send + 'static,
+) -> Result<R, i32> {
+    // No window exists before the UI thread does; don't spawn it here.
+    if UI_THREAD.get().is_none() {
+        return Err(STATUS_INVALID_HANDLE);
+    }
+    call(move |table, _| f(table))
+        .ok_or(STATUS_UI_UNAVAILABLE)?
+        .ok_or(STATUS_INVALID_HANDLE)
+}
+
+/// Run `f` on the window behind `handle`, or fail with the status code
+/// saying why it could not run.
+fn with_window<R: Send + 'static>(
+    handle: WindowHandle,
+    f: impl FnOnce(&Window) -> R + Send + 'static,
+) -> Result<R, i32> {
+    with_table(move |table| table.get(handle).map(f))
+}
+
+/// Run `f` on the webview behind `handle`, mapping a webview error to
+/// `STATUS_WEBVIEW_FAILED`.
+fn with_webview(
+    handle: WindowHandle,
+    f: impl FnOnce(&WebView) -> wry::Result<()> + Send + 'static,
+) -> i32 {
+    let result =
+        with_table(move |table| table.windows.get(&handle).map(|native| f(&native.webview)));
+    match result {
+        Ok(Ok(())) => STATUS_OK,
+        Ok(Err(_)) => STATUS_WEBVIEW_FAILED,
+        Err(code) => code,
+    }
+}
+
+/// What a new window looks like.
+struct WindowOptions {
+    /// `None` leaves the webview blank until `webview_navigate`.
+    url: Option<String>,
+    width: i32,
+    height: i32,
+    /// Hidden windows wait for `show_window`.
+    visible: bool,
+    /// Browsing data folder (cookies, localStorage, cache); `None`
+    /// shares the default one.
+    data_directory: Option<PathBuf>,
+}
+
+/// A window size from Deno; `None` unless both sides are positive.
+fn window_size(width: i32, height: i32) -> Option<PhysicalSize<u32>> {
+    (width > 0 && height > 0).then(|| PhysicalSize::new(width as u32, height as u32))
+}
+
+fn build_window(target: &UiTarget, options: &WindowOptions) -> Option<NativeWindow> {
     let window = WindowBuilder::new()
         .with_title("Sovereign Academy")
-        .with_inner_size(PhysicalSize::new(width as u32, height as u32))
+        .with_inner_size(window_size(options.width, options.height)?)
         .with_decorations(false) // FRAMELESS - no system title bar
         .with_resizable(true)
-        .build(&event_loop)
-        .expect("Failed to create window");
+        .with_visible(options.visible)
+        .build(target)
+        .ok()?;
+
+    #[cfg(all(target_os = "windows", feature = "windows"))]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        frameless::setup_frameless(window.hwnd(), frameless::FramelessOptions::default());
+    }
 
     // Create webview inside the window
-    let _webview = WebViewBuilder::new(&window)
-        .unwrap()
-        .with_url("http://localhost:8000")
-        .unwrap()
-        .build()
-        .expect("Failed to create webview");
+    let devtools = DEVTOOLS_ENABLED.load(Ordering::Relaxed);
+    let mut context = WebContext::new(options.data_directory.clone());
+    let policy = policy::SharedPolicy::default();
+    let navigation = policy.clone();
+    let kiosk = kiosk::Lock::default();
+    let kiosk_navigation = kiosk.clone();
+    let events = EventQueue::default();
+    let external_links = Rc::new(Cell::new(links::ExternalLinks::Browser));
+    let (popup_events, popup_links) = (events.clone(), external_links.clone());
+    let window_id = window.id();
+    let mut builder = WebViewBuilder::with_web_context(&mut context)
+        .with_navigation_handler(move |url| {
+            policy::allows(&navigation, &url)
+                && (kiosk::allows(&kiosk_navigation, &url) || watchdog::is_showing())
+        })
+        .with_new_window_req_handler(move |url| {
+            links::new_window(&url, popup_links.get(), &popup_events)
+        })
+        .with_ipc_handler(move |request| watchdog::message(window_id, request.body()))
+        .with_devtools(devtools);
+    if let Some(url) = &options.url {
+        builder = builder.with_url(url);
+    }
+    if let Some(user_agent) = USER_AGENT.lock().ok().and_then(|ua| ua.clone()) {
+        builder = builder.with_user_agent(user_agent);
+    }
+    if let Some(proxy) = PROXY.lock().ok().and_then(|proxy| proxy.clone()) {
+        builder = builder.with_proxy_config(proxy);
+    }
+    let (builder, requests) = scheme::attach(builder);
+    let webview = builder.build(&window).ok()?;
+    let theme = theme::Theme::of(&window);
+
+    Some(NativeWindow {
+        window,
+        webview,
+        events,
+        intercept_close: false,
+        #[cfg(target_os = "windows")]
+        accelerators: accelerators::Registry::default(),
+        external_links,
+        devtools,
+        _context: context,
+        requests,
+        scripts: scripts::InitScripts::default(),
+        policy,
+        kiosk,
+        stalled: None,
+        theme,
+        #[cfg(target_os = "windows")]
+        filtering: false,
+        #[cfg(target_os = "windows")]
+        listening: false,
+    })
+}
+
+/// Create a frameless window with embedded webview.
+/// The webview loads `DEFAULT_URL` (the Fresh server).
+/// Returns the window's handle, or 0 for a non-positive size or if
+/// creation failed.
+#[no_mangle]
+pub extern "C" fn create_window(width: i32, height: i32) -> WindowHandle {
+    open_window(WindowOptions {
+        url: Some(DEFAULT_URL.to_owned()),
+        width,
+        height,
+        visible: true,
+        data_directory: None,
+    })
+}
+
+/// Create a frameless window whose webview loads the given UTF-8 URL,
+/// so the host can pick the port, host and query (e.g. `?desktop=1`).
+/// Only `http://` and `https://` URLs and registered schemes (see
+/// `register_scheme_handler`) are accepted; an empty URL leaves the
+/// webview blank, so `add_init_script` can run before the first page.
+/// Returns the window's handle, or 0 for an invalid URL, a non-positive
+/// size, or if creation failed.
+///
+/// # Safety
+/// `url_ptr` must point to `url_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn create_frameless_window_with_url(
+    url_ptr: *const u8,
+    url_len: u32,
+    width: i32,
+    height: i32,
+) -> WindowHandle {
+    create_window_with_options(url_ptr, url_len, std::ptr::null(), 0, width, height, 1)
+}
 
-    // Note: WebView must be kept alive - in production, store it alongside window
-    // For now, we leak it intentionally to keep it alive
-    std::mem::forget(_webview);
+/// `create_frameless_window_with_url` with the window's options.
+/// `data_dir` is the UTF-8 path of the folder holding the webview's
+/// browsing data, so each student profile on a shared machine gets its
+/// own cookies and localStorage; an empty path uses the default folder.
+/// With `visible` = 0 the window starts hidden, so the page can finish
+/// loading before `show_window` reveals it without a blank first paint.
+/// Returns the window's handle, or 0 for an invalid URL or directory, a
+/// non-positive size, or if creation failed.
+///
+/// # Safety
+/// `url_ptr` must point to `url_len` readable bytes and `dir_ptr` to
+/// `dir_len` readable bytes (`dir_ptr` may be null when `dir_len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn create_window_with_options(
+    url_ptr: *const u8,
+    url_len: u32,
+    dir_ptr: *const u8,
+    dir_len: u32,
+    width: i32,
+    height: i32,
+    visible: i32,
+) -> WindowHandle {
+    // Deno may pass a null pointer for an empty buffer.
+    let data_directory = match dir_len {
+        0 => None,
+        _ => match read_str(dir_ptr, dir_len) {
+            Some(dir) => Some(PathBuf::from(dir)),
+            None => return 0,
+        },
+    };
+    let url = match url_len {
+        0 => None,
+        _ => match read_str(url_ptr, url_len) {
+            Some(url) if is_web_url(url) => Some(url.to_owned()),
+            _ => return 0,
+        },
+    };
+    open_window(WindowOptions {
+        url,
+        width,
+        height,
+        visible: visible != 0,
+        data_directory,
+    })
+}
+
+/// Whether `url` is one the webview may load: http(s) with a host, or
+/// a scheme registered with `register_scheme_handler`.
+fn is_web_url(url: &str) -> bool {
+    is_http_url(url) || scheme::is_registered(url)
+}
 
-    let window_ptr = Box::into_raw(Box::new(window));
+/// Whether `url` is http(s) with a host.
+fn is_http_url(url: &str) -> bool {
+    let rest = url
+        .strip_prefix("http://")
+        .or_else(|| url.strip_prefix("https://"));
+    matches!(rest, Some(rest) if !rest.is_empty() && !rest.starts_with('/'))
+}
 
-    // Store globally for minimize/maximize/close access
-    if let Ok(mut guard) = WINDOW.lock() {
-        *guard = Some(SendPtr(window_ptr));
+fn open_window(options: WindowOptions) -> WindowHandle {
+    if window_size(options.width, options.height).is_none() {
+        return 0;
     }
+    call(move |table, target| match build_window(target, &options) {
+        Some(native) => table.insert(native),
+        None => 0,
+    })
+    .unwrap_or(0)
+}
 
-    window_ptr as *mut c_void
+/// Deprecated: use `create_window`.
+///
+/// Returns the new window's handle (see `create_window`) widened to a
+/// pointer, for callers written against the old pointer API. It is an
+/// opaque token — never dereference or free it.
+#[no_mangle]
+pub extern "C" fn create_frameless_window(width: i32, height: i32) -> *mut c_void {
+    create_window(width, height) as usize as *mut c_void
 }
 
-/// Minimize the window to taskbar.
+/// Minimize the window to taskbar. Returns a status code.
 #[no_mangle]
-pub extern "C" fn minimize_window() {
-    if let Ok(guard) = WINDOW.lock() {
-        if let Some(ref sp) = *guard {
-            unsafe {
-                let window = &*sp.0;
-                window.set_minimized(true);
-            }
+pub extern "C" fn minimize_window(handle: WindowHandle) -> i32 {
+    status(with_window(handle, |window| window.set_minimized(true)))
+}
+
+/// Toggle maximize/restore for the window. Returns a status code.
+#[no_mangle]
+pub extern "C" fn maximize_window(handle: WindowHandle) -> i32 {
+    status(with_window(handle, |window| {
+        window.set_maximized(!window.is_maximized())
+    }))
+}
+
+/// Close and destroy the window; the handle becomes invalid.
+/// Returns a status code.
+#[no_mangle]
+pub extern "C" fn close_window(handle: WindowHandle) -> i32 {
+    // Dropped on the UI thread, which owns the window and webview.
+    status(with_table(move |table| {
+        table.closed.remove(&handle);
+        table.windows.remove(&handle).map(drop)
+    }))
+}
+
+/// Set the window title from a UTF-8 buffer. Returns a status code;
+/// `STATUS_INVALID_ARGUMENT` for a null pointer or invalid UTF-8.
+///
+/// # Safety
+/// `title_ptr` must point to `title_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn set_window_title(
+    handle: WindowHandle,
+    title_ptr: *const u8,
+    title_len: u32,
+) -> i32 {
+    let Some(title) = read_str(title_ptr, title_len) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    let title = title.to_owned();
+    status(with_window(handle, move |window| window.set_title(&title)))
+}
+
+/// Show a window created hidden (or hidden with `hide_window`) and
+/// give it focus. Returns a status code.
+#[no_mangle]
+pub extern "C" fn show_window(handle: WindowHandle) -> i32 {
+    status(with_window(handle, |window| {
+        window.set_visible(true);
+        window.set_focus();
+    }))
+}
+
+/// Bring the window to the front and give it keyboard focus.
+/// Returns a status code.
+#[no_mangle]
+pub extern "C" fn focus_window(handle: WindowHandle) -> i32 {
+    status(with_window(handle, |window| window.set_focus()))
+}
+
+/// Flash the window's taskbar entry, e.g. when a lesson reminder fires
+/// while the app is in the background: `level` 1 flashes until the
+/// window is focused, 2 flashes more urgently (platform dependent), 0
+/// stops. Returns a status code.
+#[no_mangle]
+pub extern "C" fn request_user_attention(handle: WindowHandle, level: i32) -> i32 {
+    let attention = match level {
+        0 => None,
+        1 => Some(UserAttentionType::Informational),
+        2 => Some(UserAttentionType::Critical),
+        _ => return STATUS_INVALID_ARGUMENT,
+    };
+    status(with_window(handle, move |window| {
+        window.request_user_attention(attention)
+    }))
+}
+
+/// Hide the window without closing it. Returns a status code.
+#[no_mangle]
+pub extern "C" fn hide_window(handle: WindowHandle) -> i32 {
+    status(with_window(handle, |window| window.set_visible(false)))
+}
+
+/// Center the window on the monitor under the mouse cursor (falling
+/// back to the window's own monitor, then the primary one), e.g. on
+/// first launch. A window larger than the monitor is aligned to its
+/// top-left corner. Returns a status code.
+#[no_mangle]
+pub extern "C" fn center_window(handle: WindowHandle) -> i32 {
+    status(with_window(handle, |window| {
+        let monitor = window
+            .cursor_position()
+            .ok()
+            .and_then(|cursor| window.monitor_from_point(cursor.x, cursor.y))
+            .or_else(|| window.current_monitor())
+            .or_else(|| window.primary_monitor());
+        let Some(monitor) = monitor else {
+            return; // Headless: nothing to center on
+        };
+        let (area, origin, size) = (monitor.size(), monitor.position(), window.outer_size());
+        let offset = |area: u32, size: u32| (area.saturating_sub(size) / 2) as i32;
+        window.set_outer_position(PhysicalPosition::new(
+            origin.x + offset(area.width, size.width),
+            origin.y + offset(area.height, size.height),
+        ));
+    }))
+}
+
+/// Set the taskbar and title bar icon from `width` × `height` RGBA
+/// pixels (`len` = width × height × 4 bytes), e.g. a profile's avatar.
+/// Returns a status code; `STATUS_INVALID_ARGUMENT` for a null pointer
+/// or a length that does not match the size.
+///
+/// # Safety
+/// `rgba_ptr` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn set_window_icon(
+    handle: WindowHandle,
+    rgba_ptr: *const u8,
+    len: u32,
+    width: u32,
+    height: u32,
+) -> i32 {
+    let expected = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(4));
+    if rgba_ptr.is_null() || width == 0 || expected != Some(len) {
+        return STATUS_INVALID_ARGUMENT;
+    }
+    let rgba = std::slice::from_raw_parts(rgba_ptr, len as usize).to_vec();
+    // Built on the UI thread: platform icons are not `Send`.
+    let result = with_window(handle, move |window| {
+        Icon::from_rgba(rgba, width, height).map(|icon| window.set_window_icon(Some(icon)))
+    });
+    match result {
+        Ok(Ok(())) => STATUS_OK,
+        Ok(Err(_)) => STATUS_INVALID_ARGUMENT,
+        Err(code) => code,
+    }
+}
+
+/// Resize the window. Returns a status code;
+/// `STATUS_INVALID_ARGUMENT` for a non-positive size.
+#[no_mangle]
+pub extern "C" fn set_window_size(handle: WindowHandle, width: i32, height: i32) -> i32 {
+    let Some(size) = window_size(width, height) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    status(with_window(handle, move |window| {
+        window.set_inner_size(size)
+    }))
+}
+
+/// A size limit from Deno: `Ok(None)` for 0 × 0 (no limit),
+/// `Err(STATUS_INVALID_ARGUMENT)` for a negative or half-zero size.
+fn size_limit(width: i32, height: i32) -> Result<Option<PhysicalSize<u32>>, i32> {
+    match (width, height) {
+        (0, 0) => Ok(None),
+        (1.., 1..) => Ok(Some(PhysicalSize::new(width as u32, height as u32))),
+        _ => Err(STATUS_INVALID_ARGUMENT),
+    }
+}
+
+/// Keep the window's inner size at least `width` × `height`, e.g. so
+/// the lesson layout cannot be squeezed until it breaks. 0 × 0 removes
+/// the limit. Returns a status code.
+#[no_mangle]
+pub extern "C" fn set_min_size(handle: WindowHandle, width: i32, height: i32) -> i32 {
+    status(
+        size_limit(width, height)
+            .and_then(|size| with_window(handle, move |window| window.set_min_inner_size(size))),
+    )
+}
+
+/// Keep the window's inner size at most `width` × `height`. 0 × 0
+/// removes the limit. Returns a status code.
+#[no_mangle]
+pub extern "C" fn set_max_size(handle: WindowHandle, width: i32, height: i32) -> i32 {
+    status(
+        size_limit(width, height)
+            .and_then(|size| with_window(handle, move |window| window.set_max_inner_size(size))),
+    )
+}
+
+/// Check if window is currently maximized.
+/// Returns 1 if maximized, 0 if not, or a negative status code.
+#[no_mangle]
+pub extern "C" fn is_maximized(handle: WindowHandle) -> i32 {
+    with_window(handle, |window| window.is_maximized() as i32).unwrap_or_else(|code| code)
+}
+
+/// Check if window is currently minimized.
+/// Returns 1 if minimized, 0 if not, or a negative status code.
+#[no_mangle]
+pub extern "C" fn is_minimized(handle: WindowHandle) -> i32 {
+    with_window(handle, |window| window.is_minimized() as i32).unwrap_or_else(|code| code)
+}
+
+/// Write `[width, height]` in physical pixels to `size_ptr`.
+///
+/// # Safety
+/// `size_ptr` must be null or point to two writable `u32`s.
+unsafe fn write_size(
+    handle: WindowHandle,
+    size_ptr: *mut u32,
+    size: fn(&Window) -> PhysicalSize<u32>,
+) -> i32 {
+    if size_ptr.is_null() {
+        return STATUS_INVALID_ARGUMENT;
+    }
+    match with_window(handle, size) {
+        Ok(PhysicalSize { width, height }) => {
+            size_ptr.write_unaligned(width);
+            size_ptr.add(1).write_unaligned(height);
+            STATUS_OK
         }
+        Err(code) => code,
     }
 }
 
-/// Toggle maximize/restore for the window.
+/// Get the window's inner (client area) size, as set by
+/// `set_window_size`. Returns a status code.
+///
+/// # Safety
+/// `size_ptr` must point to two writable `u32`s (width, height).
 #[no_mangle]
-pub extern "C" fn maximize_window() {
-    if let Ok(guard) = WINDOW.lock() {
-        if let Some(ref sp) = *guard {
-            unsafe {
-                let window = &*sp.0;
-                let is_maximized = window.is_maximized();
-                window.set_maximized(!is_maximized);
-            }
+pub unsafe extern "C" fn get_window_size(handle: WindowHandle, size_ptr: *mut u32) -> i32 {
+    write_size(handle, size_ptr, Window::inner_size)
+}
+
+/// Get the window's outer size, including any border and shadow.
+/// Returns a status code.
+///
+/// # Safety
+/// `size_ptr` must point to two writable `u32`s (width, height).
+#[no_mangle]
+pub unsafe extern "C" fn get_outer_window_size(handle: WindowHandle, size_ptr: *mut u32) -> i32 {
+    write_size(handle, size_ptr, Window::outer_size)
+}
+
+/// Start moving the window with the mouse, e.g. on mousedown over the
+/// Preact title bar inside the webview. Call it while the button is
+/// held; the OS drags the window until it is released.
+/// Returns a status code; `STATUS_OS_ERROR` if the drag could not start.
+#[no_mangle]
+pub extern "C" fn start_drag_window(handle: WindowHandle) -> i32 {
+    match with_window(handle, |window| window.drag_window()) {
+        Ok(Ok(())) => STATUS_OK,
+        Ok(Err(_)) => STATUS_OS_ERROR,
+        Err(code) => code,
+    }
+}
+
+/// The resize edge for a Win32 `HT*` hit-test code, the numbering the
+/// desktop launcher's resize handles already use.
+fn resize_direction(code: i32) -> Option<ResizeDirection> {
+    Some(match code {
+        10 => ResizeDirection::West,
+        11 => ResizeDirection::East,
+        12 => ResizeDirection::North,
+        13 => ResizeDirection::NorthWest,
+        14 => ResizeDirection::NorthEast,
+        15 => ResizeDirection::South,
+        16 => ResizeDirection::SouthWest,
+        17 => ResizeDirection::SouthEast,
+        _ => return None,
+    })
+}
+
+/// Start a native resize drag from an edge or corner, e.g. on mousedown
+/// over a resize handle drawn in the webview. `direction` is a Win32
+/// `HT*` hit-test code (10 = left … 17 = bottom-right) on every
+/// platform. On Windows this posts `WM_NCLBUTTONDOWN`, like the desktop
+/// launcher; the OS then resizes until the mouse is released.
+/// Returns a status code; `STATUS_INVALID_ARGUMENT` for any other
+/// direction, `STATUS_OS_ERROR` if the drag could not start.
+#[no_mangle]
+pub extern "C" fn start_resize(handle: WindowHandle, direction: i32) -> i32 {
+    let Some(direction) = resize_direction(direction) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    match with_window(handle, move |window| window.drag_resize_window(direction)) {
+        Ok(Ok(())) => STATUS_OK,
+        Ok(Err(_)) => STATUS_OS_ERROR,
+        Err(code) => code,
+    }
+}
+
+/// Deprecated: use `start_resize`, which needs no "windows" feature.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+#[no_mangle]
+pub extern "C" fn start_window_resize(handle: WindowHandle, direction: i32) -> i32 {
+    start_resize(handle, direction)
+}
+
+/// Load `url` in the window's webview, e.g. to jump straight to a
+/// lesson route. Only `http://` and `https://` URLs and registered
+/// schemes (see `register_scheme_handler`) are accepted.
+/// Returns a status code.
+///
+/// # Safety
+/// `url_ptr` must point to `url_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn webview_navigate(
+    handle: WindowHandle,
+    url_ptr: *const u8,
+    url_len: u32,
+) -> i32 {
+    let Some(url) = read_str(url_ptr, url_len).filter(|url| is_web_url(url)) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    let url = url.to_owned();
+    with_webview(handle, move |webview| {
+        webview.load_url(&scheme::webview_url(&url))
+    })
+}
+
+/// Reload the webview's current page. Returns a status code.
+#[no_mangle]
+pub extern "C" fn webview_reload(handle: WindowHandle) -> i32 {
+    with_webview(handle, |webview| {
+        webview.evaluate_script("location.reload()")
+    })
+}
+
+/// Go back one page in the webview's history. Returns a status code.
+#[no_mangle]
+pub extern "C" fn webview_go_back(handle: WindowHandle) -> i32 {
+    with_webview(handle, |webview| webview.evaluate_script("history.back()"))
+}
+
+/// Go forward one page in the webview's history. Returns a status code.
+#[no_mangle]
+pub extern "C" fn webview_go_forward(handle: WindowHandle) -> i32 {
+    with_webview(handle, |webview| {
+        webview.evaluate_script("history.forward()")
+    })
+}
+
+/// Run a UTF-8 script in the webview's page, e.g. to push a theme
+/// change or session token. The script runs asynchronously and its
+/// result is discarded. Returns a status code.
+///
+/// # Safety
+/// `js_ptr` must point to `js_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn webview_eval(handle: WindowHandle, js_ptr: *const u8, js_len: u32) -> i32 {
+    let Some(js) = read_str(js_ptr, js_len) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    let js = js.to_owned();
+    with_webview(handle, move |webview| webview.evaluate_script(&js))
+}
+
+/// Whether windows created from now on get the web inspector. Off by
+/// default; the launcher turns it on for content authors, so release
+/// builds can be inspected without recompiling.
+static DEVTOOLS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Allow (`enabled` = 1) or forbid (0) the web inspector in windows
+/// created after this call; existing windows keep their setting.
+/// Returns a status code.
+#[no_mangle]
+pub extern "C" fn set_devtools_enabled(enabled: i32) -> i32 {
+    let enabled = match enabled {
+        0 => false,
+        1 => true,
+        _ => return STATUS_INVALID_ARGUMENT,
+    };
+    DEVTOOLS_ENABLED.store(enabled, Ordering::Relaxed);
+    STATUS_OK
+}
+
+/// Run `f` on the webview of a window built with devtools;
+/// `STATUS_UNSUPPORTED` for one created while they were disabled.
+fn with_devtools(handle: WindowHandle, f: impl FnOnce(&WebView) + Send + 'static) -> i32 {
+    match with_table(move |table| {
+        let native = table.windows.get(&handle)?;
+        Some(if native.devtools {
+            f(&native.webview);
+            STATUS_OK
+        } else {
+            STATUS_UNSUPPORTED
+        })
+    }) {
+        Ok(code) | Err(code) => code,
+    }
+}
+
+/// Open the web inspector of a window created after
+/// `set_devtools_enabled(1)`. Returns a status code.
+#[no_mangle]
+pub extern "C" fn open_devtools(handle: WindowHandle) -> i32 {
+    with_devtools(handle, |webview| webview.open_devtools())
+}
+
+/// Close the web inspector of a window created after
+/// `set_devtools_enabled(1)`. A no-op on Windows, where the inspector
+/// is closed from its own window. Returns a status code.
+#[no_mangle]
+pub extern "C" fn close_devtools(handle: WindowHandle) -> i32 {
+    with_devtools(handle, |webview| webview.close_devtools())
+}
+
+/// User-agent string for webviews created from now on; `None` keeps
+/// the engine's own.
+static USER_AGENT: Mutex<Option<String>> = Mutex::new(None);
+
+/// Proxy for webviews created from now on; `None` connects directly.
+static PROXY: Mutex<Option<ProxyConfig>> = Mutex::new(None);
+
+/// Set the user-agent string of windows created after this call, e.g.
+/// for school filters that allow-list browsers by it. An empty string
+/// restores the default. Existing windows keep theirs. Returns a
+/// status code.
+///
+/// # Safety
+/// `ua_ptr` must point to `ua_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn set_user_agent(ua_ptr: *const u8, ua_len: u32) -> i32 {
+    let Some(user_agent) = read_str(ua_ptr, ua_len) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    let user_agent = (!user_agent.is_empty()).then(|| user_agent.to_owned());
+    if let Ok(mut current) = USER_AGENT.lock() {
+        *current = user_agent;
+    }
+    STATUS_OK
+}
+
+/// Route windows created after this call through a proxy, for school
+/// networks that require one. `url` is `http://host:port` (HTTP
+/// CONNECT) or `socks5://host:port`; an empty string connects directly
+/// again. Existing windows keep theirs. Returns a status code.
+///
+/// # Safety
+/// `url_ptr` must point to `url_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn set_proxy(url_ptr: *const u8, url_len: u32) -> i32 {
+    let Some(url) = read_str(url_ptr, url_len) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    let proxy = if url.is_empty() {
+        None
+    } else {
+        match parse_proxy(url) {
+            Some(proxy) => Some(proxy),
+            None => return STATUS_INVALID_ARGUMENT,
         }
+    };
+    if let Ok(mut current) = PROXY.lock() {
+        *current = proxy;
+    }
+    STATUS_OK
+}
+
+/// The proxy named by an `http://host:port` or `socks5://host:port`
+/// URL; `None` for any other scheme or a missing host or port.
+fn parse_proxy(url: &str) -> Option<ProxyConfig> {
+    let (scheme, rest) = url.split_once("://")?;
+    let (host, port) = rest.strip_suffix('/').unwrap_or(rest).rsplit_once(':')?;
+    if host.is_empty() || host.contains('/') || !matches!(port.parse::<u16>(), Ok(1..)) {
+        return None;
+    }
+    let endpoint = ProxyEndpoint {
+        host: host.to_owned(),
+        port: port.to_owned(),
+    };
+    match scheme {
+        "http" => Some(ProxyConfig::Http(endpoint)),
+        "socks5" => Some(ProxyConfig::Socks5(endpoint)),
+        _ => None,
+    }
+}
+
+/// `clear_browsing_data` kind: cached lesson assets (the HTTP cache and
+/// Cache Storage).
+pub const BROWSING_DATA_CACHE: u32 = 1;
+/// `clear_browsing_data` kind: site storage (localStorage, IndexedDB and
+/// the other DOM stores).
+pub const BROWSING_DATA_STORAGE: u32 = 2;
+/// `clear_browsing_data` kind: cookies.
+pub const BROWSING_DATA_COOKIES: u32 = 4;
+/// Every `clear_browsing_data` kind.
+pub const BROWSING_DATA_ALL: u32 =
+    BROWSING_DATA_CACHE | BROWSING_DATA_STORAGE | BROWSING_DATA_COOKIES;
+
+/// Wipe the browsing data of a window's profile (its data directory),
+/// e.g. when a parent resets the student's profile. `kinds` is a
+/// non-empty mask of `BROWSING_DATA_*`. Clearing finishes in the
+/// background. Outside Windows only `BROWSING_DATA_ALL` is supported.
+/// Returns a status code.
+#[no_mangle]
+pub extern "C" fn clear_browsing_data(handle: WindowHandle, kinds: u32) -> i32 {
+    if kinds == 0 || kinds & !BROWSING_DATA_ALL != 0 {
+        return STATUS_INVALID_ARGUMENT;
+    }
+    match with_table(move |table| {
+        let native = table.windows.get(&handle)?;
+        Some(clear_webview_data(&native.webview, kinds))
+    }) {
+        Ok(code) | Err(code) => code,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn clear_webview_data(webview: &WebView, kinds: u32) -> i32 {
+    use webview2_com::ClearBrowsingDataCompletedHandler;
+    use webview2_com::Microsoft::Web::WebView2::Win32::*;
+    use windows_core::Interface;
+    use wry::WebViewExtWindows;
+
+    let mut mask = COREWEBVIEW2_BROWSING_DATA_KINDS(0);
+    if kinds & BROWSING_DATA_CACHE != 0 {
+        mask |= COREWEBVIEW2_BROWSING_DATA_KINDS_DISK_CACHE
+            | COREWEBVIEW2_BROWSING_DATA_KINDS_CACHE_STORAGE;
+    }
+    if kinds & BROWSING_DATA_STORAGE != 0 {
+        mask |= COREWEBVIEW2_BROWSING_DATA_KINDS_ALL_DOM_STORAGE;
+    }
+    if kinds & BROWSING_DATA_COOKIES != 0 {
+        mask |= COREWEBVIEW2_BROWSING_DATA_KINDS_COOKIES;
+    }
+    // Profiles need WebView2 runtime 1.0.1245 or newer.
+    let cleared = unsafe {
+        webview
+            .controller()
+            .CoreWebView2()
+            .and_then(|core| core.cast::<ICoreWebView2_13>())
+            .and_then(|core| core.Profile())
+            .and_then(|profile| profile.cast::<ICoreWebView2Profile2>())
+            .and_then(|profile| {
+                profile.ClearBrowsingData(
+                    mask,
+                    &ClearBrowsingDataCompletedHandler::create(Box::new(|_| Ok(()))),
+                )
+            })
+    };
+    match cleared {
+        Ok(()) => STATUS_OK,
+        Err(_) => STATUS_WEBVIEW_FAILED,
     }
 }
 
-/// Close and destroy the window.
+#[cfg(not(target_os = "windows"))]
+fn clear_webview_data(webview: &WebView, kinds: u32) -> i32 {
+    if kinds != BROWSING_DATA_ALL {
+        return STATUS_UNSUPPORTED;
+    }
+    match webview.clear_all_browsing_data() {
+        Ok(()) => STATUS_OK,
+        Err(_) => STATUS_WEBVIEW_FAILED,
+    }
+}
+
+/// Serve `scheme://` URLs (e.g. `app://localhost/lesson.html`) from the
+/// host instead of an HTTP server: webviews created after this call
+/// pass each such request to `callback`, on a dispatch thread, as a
+/// request id and its URL; the host answers with
+/// `respond_scheme_request` within 30 seconds, after which the request
+/// gets a 504. Registering a scheme again replaces its callback. The
+/// scheme must be lowercase and not a built-in one (http, file, data,
+/// ...). Returns a status code.
+///
+/// # Safety
+/// `scheme_ptr` must point to `scheme_len` readable bytes, and
+/// `callback` must stay callable from any thread for as long as
+/// webviews created afterwards are open.
 #[no_mangle]
-pub extern "C" fn close_window() {
-    if let Ok(mut guard) = WINDOW.lock() {
-        if let Some(sp) = guard.take() {
-            unsafe {
-                let _ = Box::from_raw(sp.0);
+pub unsafe extern "C" fn register_scheme_handler(
+    scheme_ptr: *const u8,
+    scheme_len: u32,
+    callback: Option<scheme::SchemeCallback>,
+) -> i32 {
+    match (read_str(scheme_ptr, scheme_len), callback) {
+        (Some(name), Some(callback)) if scheme::register(name, callback) => STATUS_OK,
+        _ => STATUS_INVALID_ARGUMENT,
+    }
+}
+
+/// Answer a `register_scheme_handler` request with an HTTP status, a
+/// MIME type (e.g. "text/html") and the body bytes. Returns a status
+/// code; `STATUS_INVALID_HANDLE` when the request is not pending (already
+/// answered, timed out, or its window closed).
+///
+/// # Safety
+/// `mime_ptr` must point to `mime_len` readable bytes and `body_ptr`
+/// to `body_len` readable bytes (`body_ptr` may be null when `body_len`
+/// is 0).
+#[no_mangle]
+pub unsafe extern "C" fn respond_scheme_request(
+    request: u32,
+    status: u32,
+    mime_ptr: *const u8,
+    mime_len: u32,
+    body_ptr: *const u8,
+    body_len: u32,
+) -> i32 {
+    let Some(mime) = read_str(mime_ptr, mime_len) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    let body = match body_len {
+        0 => Vec::new(),
+        _ if body_ptr.is_null() => return STATUS_INVALID_ARGUMENT,
+        _ => std::slice::from_raw_parts(body_ptr, body_len as usize).to_vec(),
+    };
+    let Ok(status) = u16::try_from(status) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    let response = wry::http::Response::builder()
+        .status(status)
+        .header(wry::http::header::CONTENT_TYPE, mime)
+        .body(body);
+    let Ok(response) = response else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    if scheme::respond(request, response) {
+        STATUS_OK
+    } else {
+        STATUS_INVALID_HANDLE
+    }
+}
+
+/// Restrict what a window's webview may load to the hosts of a JSON
+/// allowlist such as `["localhost"]` (see `policy`); an empty string
+/// lifts the restriction. Navigations are checked on every platform,
+/// subresource loads only on Windows.
+/// Returns a status code.
+///
+/// # Safety
+/// `json_ptr` must point to `json_len` readable bytes (`json_ptr` may be
+/// null when `json_len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn set_navigation_policy(
+    handle: WindowHandle,
+    json_ptr: *const u8,
+    json_len: u32,
+) -> i32 {
+    let allowlist = match json_len {
+        0 => None,
+        _ => match read_str(json_ptr, json_len).and_then(policy::Allowlist::parse) {
+            Some(allowlist) => Some(allowlist),
+            None => return STATUS_INVALID_ARGUMENT,
+        },
+    };
+    match with_table(move |table| {
+        let native = table.windows.get_mut(&handle)?;
+        #[cfg(target_os = "windows")]
+        let filter = allowlist.is_some() && !native.filtering;
+        if let Ok(mut policy) = native.policy.lock() {
+            *policy = allowlist.map(|allowlist| allowlist.with_schemes(native.requests.schemes()));
+        }
+        #[cfg(target_os = "windows")]
+        if filter {
+            // Installed on first use: it sees every request.
+            if policy::filter_requests(&native.webview, native.policy.clone()).is_err() {
+                return Some(STATUS_WEBVIEW_FAILED);
             }
+            native.filtering = true;
         }
+        Some(STATUS_OK)
+    }) {
+        Ok(code) | Err(code) => code,
     }
 }
 
-/// Set the window title from a UTF-8 C string.
+/// Run a UTF-8 script at the start of every document the webview loads
+/// from now on, before the page's own scripts, e.g. the `__DESKTOP__`
+/// bridge. It does not run in the current page: to cover the first one,
+/// create the window with an empty URL, add the scripts, then
+/// `webview_navigate`. The script cannot be removed; see
+/// `set_init_script` for named ones. Windows only
+/// (`STATUS_UNSUPPORTED` elsewhere). Returns a status code.
+///
+/// # Safety
+/// `js_ptr` must point to `js_len` readable bytes.
 #[no_mangle]
-pub extern "C" fn set_window_title(title_ptr: *const u8, title_len: u32) {
-    if let Ok(guard) = WINDOW.lock() {
-        if let Some(ref sp) = *guard {
-            unsafe {
-                let slice = std::slice::from_raw_parts(title_ptr, title_len as usize);
-                if let Ok(title) = std::str::from_utf8(slice) {
-                    let window = &*sp.0;
-                    window.set_title(title);
-                }
-            }
+pub unsafe extern "C" fn add_init_script(
+    handle: WindowHandle,
+    js_ptr: *const u8,
+    js_len: u32,
+) -> i32 {
+    let Some(js) = read_str(js_ptr, js_len) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    let js = js.to_owned();
+    with_scripts(handle, move |scripts| scripts.add(js))
+}
+
+/// Add or replace the init script `name` (see `scripts`): scripts run
+/// by ascending `order`, so the IPC bridge, resize handles and exam
+/// lockdown can be managed independently. Like `add_init_script`,
+/// changes apply from the next page. Windows only
+/// (`STATUS_UNSUPPORTED` elsewhere). Returns a status code.
+///
+/// # Safety
+/// `name_ptr` must point to `name_len` readable bytes and `js_ptr` to
+/// `js_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn set_init_script(
+    handle: WindowHandle,
+    name_ptr: *const u8,
+    name_len: u32,
+    order: i32,
+    js_ptr: *const u8,
+    js_len: u32,
+) -> i32 {
+    let (Some(name), Some(js)) = (read_str(name_ptr, name_len), read_str(js_ptr, js_len)) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    if name.is_empty() {
+        return STATUS_INVALID_ARGUMENT;
+    }
+    let (name, js) = (name.to_owned(), js.to_owned());
+    with_scripts(handle, move |scripts| scripts.set(&name, order, js))
+}
+
+/// Remove the init script `name` from the next page on; removing a
+/// script that is not set does nothing. Returns a status code.
+///
+/// # Safety
+/// `name_ptr` must point to `name_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn remove_init_script(
+    handle: WindowHandle,
+    name_ptr: *const u8,
+    name_len: u32,
+) -> i32 {
+    let Some(name) = read_str(name_ptr, name_len) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    let name = name.to_owned();
+    with_scripts(handle, move |scripts| {
+        scripts.remove(&name);
+    })
+}
+
+/// Change a window's init scripts with `f` and hand them to its
+/// webview. Returns a status code.
+fn with_scripts(
+    handle: WindowHandle,
+    f: impl FnOnce(&mut scripts::InitScripts) + Send + 'static,
+) -> i32 {
+    match with_table(move |table| {
+        let native = table.windows.get_mut(&handle)?;
+        f(&mut native.scripts);
+        Some(native.scripts.apply(&native.webview))
+    }) {
+        Ok(code) | Err(code) => code,
+    }
+}
+
+/// Take a key combo such as "Ctrl+R" or "F11" from the page and queue
+/// an "accelerator" event with `id` when it is pressed (see
+/// `accelerators`), so app shortcuts work while the webview has focus.
+/// Registering a combo again replaces its id. Windows only
+/// (`STATUS_UNSUPPORTED` elsewhere). Returns a status code.
+///
+/// # Safety
+/// `combo_ptr` must point to `combo_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn register_accelerator(
+    handle: WindowHandle,
+    combo_ptr: *const u8,
+    combo_len: u32,
+    id: u32,
+) -> i32 {
+    let Some(accelerator) =
+        read_str(combo_ptr, combo_len).and_then(accelerators::Accelerator::parse)
+    else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    match with_table(move |table| {
+        let native = table.windows.get_mut(&handle)?;
+        Some(add_accelerator(native, accelerator, id))
+    }) {
+        Ok(code) | Err(code) => code,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn add_accelerator(
+    native: &mut NativeWindow,
+    accelerator: accelerators::Accelerator,
+    id: u32,
+) -> i32 {
+    if !native.listening {
+        // Installed on first use: it sees every accelerator key.
+        let installed = accelerators::install(
+            &native.webview,
+            native.accelerators.clone(),
+            native.events.clone(),
+        );
+        if installed.is_err() {
+            return STATUS_WEBVIEW_FAILED;
         }
+        native.listening = true;
     }
+    let mut registry = native.accelerators.borrow_mut();
+    registry.retain(|(registered, _)| *registered != accelerator);
+    registry.push((accelerator, id));
+    STATUS_OK
+}
+
+#[cfg(not(target_os = "windows"))]
+fn add_accelerator(
+    _native: &mut NativeWindow,
+    _accelerator: accelerators::Accelerator,
+    _id: u32,
+) -> i32 {
+    STATUS_UNSUPPORTED
 }
 
-/// Resize the window.
+/// Show progress on the window's taskbar button, e.g. how far the
+/// current lesson is (see `taskbar`). `state` is 0 = none,
+/// 1 = indeterminate, 2 = normal, 3 = error or 4 = paused; `percent`
+/// (0-100) fills the bar in the last three. Windows with the "windows"
+/// feature only (`STATUS_UNSUPPORTED` elsewhere). Returns a status code.
 #[no_mangle]
-pub extern "C" fn set_window_size(width: i32, height: i32) {
-    if let Ok(guard) = WINDOW.lock() {
-        if let Some(ref sp) = *guard {
-            unsafe {
-                let window = &*sp.0;
-                let _ = window.set_inner_size(PhysicalSize::new(width as u32, height as u32));
-            }
+pub extern "C" fn set_taskbar_progress(handle: WindowHandle, state: i32, percent: u32) -> i32 {
+    let Some(state) = taskbar::Progress::from_code(state) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    if percent > 100 {
+        return STATUS_INVALID_ARGUMENT;
+    }
+    match with_window(handle, move |window| {
+        taskbar::set_progress(window, state, percent)
+    }) {
+        Ok(code) | Err(code) => code,
+    }
+}
+
+/// Show a small badge over the window's taskbar button, e.g. the
+/// number of reviews due, from `width` × `height` RGBA pixels (`len` =
+/// width × height × 4 bytes); `len` 0 removes it. Windows draws it at
+/// 16 × 16. Windows with the "windows" feature only
+/// (`STATUS_UNSUPPORTED` elsewhere). Returns a status code;
+/// `STATUS_INVALID_ARGUMENT` for a null pointer or a length that does
+/// not match the size.
+///
+/// # Safety
+/// `rgba_ptr` must point to `len` readable bytes unless `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn set_overlay_badge(
+    handle: WindowHandle,
+    rgba_ptr: *const u8,
+    len: u32,
+    width: u32,
+    height: u32,
+) -> i32 {
+    let rgba = if len == 0 {
+        None
+    } else {
+        let expected = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(4));
+        if rgba_ptr.is_null() || width == 0 || expected != Some(len) {
+            return STATUS_INVALID_ARGUMENT;
         }
+        Some(std::slice::from_raw_parts(rgba_ptr, len as usize).to_vec())
+    };
+    match with_window(handle, move |window| {
+        taskbar::set_badge(window, rgba.as_deref().map(|rgba| (rgba, width, height)))
+    }) {
+        Ok(code) | Err(code) => code,
     }
 }
 
-/// Check if window is currently maximized.
-/// Returns 1 if maximized, 0 if not.
-#[no_mangle]
-pub extern "C" fn is_maximized() -> i32 {
-    if let Ok(guard) = WINDOW.lock() {
-        if let Some(ref sp) = *guard {
-            unsafe {
-                let window = &*sp.0;
-                return if window.is_maximized() { 1 } else { 0 };
+/// Turn the window's drop shadow on (`enabled` = 1) or off. With the
+/// "windows" feature the shadow is on from the start; other Windows
+/// builds create the window without it. Windows only
+/// (`STATUS_UNSUPPORTED` elsewhere). Returns a status code.
+#[no_mangle]
+pub extern "C" fn set_window_shadow(handle: WindowHandle, enabled: i32) -> i32 {
+    match with_window(handle, move |window| {
+        appearance::set_shadow(window, enabled != 0)
+    }) {
+        Ok(code) | Err(code) => code,
+    }
+}
+
+/// Choose how the window's corners are drawn on Windows 11:
+/// 0 = system default, 1 = square, 2 = round, 3 = small round. Windows
+/// 11 with the "windows" feature only (`STATUS_UNSUPPORTED` elsewhere).
+/// Returns a status code.
+#[no_mangle]
+pub extern "C" fn set_corner_preference(handle: WindowHandle, preference: i32) -> i32 {
+    let Some(corners) = appearance::Corners::from_code(preference) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    match with_window(handle, move |window| {
+        appearance::set_corners(window, corners)
+    }) {
+        Ok(code) | Err(code) => code,
+    }
+}
+
+/// Draw a Windows 11 backdrop behind the window: 0 = none, 1 = Mica,
+/// 2 = Acrylic. It shows where the page's background is transparent
+/// (see `appearance`). Windows 11 with the "windows" feature only
+/// (`STATUS_UNSUPPORTED` elsewhere). Returns a status code.
+#[no_mangle]
+pub extern "C" fn set_backdrop(handle: WindowHandle, kind: i32) -> i32 {
+    let Some(backdrop) = appearance::Backdrop::from_code(kind) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    match with_table(move |table| {
+        let native = table.windows.get(&handle)?;
+        Some(appearance::set_backdrop(
+            &native.window,
+            &native.webview,
+            backdrop,
+        ))
+    }) {
+        Ok(code) | Err(code) => code,
+    }
+}
+
+/// Make the window's frame match the app's theme: `dark` = 1 for a dark
+/// frame, and `color` as 0xRRGGBB for the caption (0xFFFFFFFF keeps
+/// the system color). The web UI draws the title bar, but the frame
+/// shows during minimize and restore animations. Windows with the
+/// "windows" feature only (`STATUS_UNSUPPORTED` elsewhere, and for the
+/// caption color before Windows 11). Returns a status code.
+#[no_mangle]
+pub extern "C" fn set_titlebar_theme(handle: WindowHandle, dark: i32, color: u32) -> i32 {
+    let color = (color != u32::MAX).then_some(color);
+    if color.is_some_and(|rgb| rgb > 0xFF_FFFF) {
+        return STATUS_INVALID_ARGUMENT;
+    }
+    match with_window(handle, move |window| {
+        appearance::set_titlebar(window, dark != 0, color)
+    }) {
+        Ok(code) | Err(code) => code,
+    }
+}
+
+/// Lock the window down for a timed assessment: borderless fullscreen,
+/// always on top, no context menu, and navigation kept to the origin
+/// of the current page (see `kiosk`). Returns a status code;
+/// `STATUS_INVALID_ARGUMENT` if no page is loaded, and
+/// `STATUS_UNSUPPORTED` outside Windows, where the context menu cannot
+/// be hidden and so nothing is changed.
+#[no_mangle]
+pub extern "C" fn enter_kiosk_mode(handle: WindowHandle) -> i32 {
+    match with_table(move |table| {
+        let native = table.windows.get(&handle)?;
+        Some(kiosk::enter(&native.window, &native.webview, &native.kiosk))
+    }) {
+        Ok(code) | Err(code) => code,
+    }
+}
+
+/// Leave kiosk mode, restoring the window as it was. Returns a status
+/// code (`STATUS_OK` if the window was not in kiosk mode,
+/// `STATUS_UNSUPPORTED` outside Windows).
+#[no_mangle]
+pub extern "C" fn exit_kiosk_mode(handle: WindowHandle) -> i32 {
+    match with_table(move |table| {
+        let native = table.windows.get(&handle)?;
+        Some(kiosk::exit(&native.window, &native.webview, &native.kiosk))
+    }) {
+        Ok(code) | Err(code) => code,
+    }
+}
+
+/// Save the webview's current contents as a PNG file at the UTF-8
+/// `path`, replacing any file there, e.g. for a problem report. Blocks
+/// the calling thread until the file is written; the window keeps
+/// running meanwhile. Windows only (`STATUS_UNSUPPORTED` elsewhere).
+/// Returns a status code; `STATUS_OS_ERROR` if the file cannot be
+/// created.
+///
+/// # Safety
+/// `path_ptr` must point to `path_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn capture_window(
+    handle: WindowHandle,
+    path_ptr: *const u8,
+    path_len: u32,
+) -> i32 {
+    let Some(path) = read_str(path_ptr, path_len)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+    else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    let (done, result) = mpsc::channel();
+    let started = with_table(move |table| {
+        let native = table.windows.get(&handle)?;
+        Some(capture::start(&native.webview, &path, done))
+    });
+    match started {
+        // A webview closed mid-capture drops `done`.
+        Ok(Ok(())) => result.recv().unwrap_or(STATUS_WEBVIEW_FAILED),
+        Ok(Err(code)) | Err(code) => code,
+    }
+}
+
+/// Choose where links that ask for a new window (`target="_blank"`,
+/// `window.open`) go: 0 opens http(s) links in the system browser (the
+/// default), 1 blocks them, e.g. in exam mode. Either way the window
+/// never opens a popup and queues a "new-window" event. Returns a
+/// status code.
+#[no_mangle]
+pub extern "C" fn set_external_link_policy(handle: WindowHandle, policy: i32) -> i32 {
+    let Some(links) = links::ExternalLinks::from_code(policy) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    status(with_table(move |table| {
+        table.windows.get(&handle)?.external_links.set(links);
+        Some(())
+    }))
+}
+
+/// Choose what happens when the student closes the window from the OS
+/// (Alt+F4, taskbar menu). By default (`intercept` = 0) the window
+/// closes at once. With `intercept` = 1 it stays open and only queues
+/// "close-requested", so the host can ask "quiz in progress — really
+/// quit?" and call `close_window` itself. Returns a status code.
+#[no_mangle]
+pub extern "C" fn set_close_policy(handle: WindowHandle, intercept: i32) -> i32 {
+    let intercept = match intercept {
+        0 => false,
+        1 => true,
+        _ => return STATUS_INVALID_ARGUMENT,
+    };
+    status(with_table(move |table| {
+        let native = table.windows.get_mut(&handle)?;
+        native.intercept_close = intercept;
+        Some(())
+    }))
+}
+
+/// Take the oldest pending event of a window or tray (see `events`,
+/// `tray`) and write its
+/// UTF-8 JSON to `buf`. Returns the event's length in bytes, 0 when no
+/// event is pending, or a negative status code;
+/// `STATUS_BUFFER_TOO_SMALL` leaves the event queued and writes its
+/// length to `needed_ptr`, so the caller can retry with a larger `buf`
+/// (events have no length limit: a "new-window" URL or a tray item id
+/// can be any size).
+///
+/// After the OS closes a window, its remaining events (ending with
+/// "close-requested") can still be polled; then the handle is invalid.
+///
+/// # Safety
+/// `buf` must point to `buf_len` writable bytes, and `needed_ptr` must
+/// be null or point to a writable `u32`.
+#[no_mangle]
+pub unsafe extern "C" fn poll_event(
+    handle: WindowHandle,
+    buf: *mut u8,
+    buf_len: u32,
+    needed_ptr: *mut u32,
+) -> i32 {
+    if buf.is_null() {
+        return STATUS_INVALID_ARGUMENT;
+    }
+    let limit = buf_len as usize;
+    match with_table(move |table| table.pop_event(handle, limit)) {
+        Ok(Ok(Some(json))) => {
+            std::ptr::copy_nonoverlapping(json.as_ptr(), buf, json.len());
+            json.len() as i32
+        }
+        Ok(Ok(None)) => 0,
+        Ok(Err(needed)) => {
+            if !needed_ptr.is_null() {
+                needed_ptr.write_unaligned(needed as u32);
             }
+            STATUS_BUFFER_TOO_SMALL
+        }
+        Err(code) => code,
+    }
+}
+
+/// Show an icon of `width` × `height` RGBA pixels in the notification
+/// area, with a right-click menu described by UTF-8 JSON (see `tray`).
+/// Clicks are polled with `poll_event` on the returned handle.
+/// Returns the tray's handle, or 0 for invalid pixels or menu, or if
+/// Windows refused the icon.
+///
+/// # Safety
+/// `rgba_ptr` must point to `rgba_len` readable bytes and `menu_ptr` to
+/// `menu_len`.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+#[no_mangle]
+pub unsafe extern "C" fn create_tray(
+    rgba_ptr: *const u8,
+    rgba_len: u32,
+    width: u32,
+    height: u32,
+    menu_ptr: *const u8,
+    menu_len: u32,
+) -> WindowHandle {
+    let expected = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(4));
+    if rgba_ptr.is_null() || width == 0 || expected != Some(rgba_len) {
+        return 0;
+    }
+    let Some(menu) = read_str(menu_ptr, menu_len).and_then(tray::parse_menu) else {
+        return 0;
+    };
+    let rgba = std::slice::from_raw_parts(rgba_ptr, rgba_len as usize).to_vec();
+    call(move |table, _| {
+        let Some(tray) = tray::Tray::new(&rgba, width, height, &menu) else {
+            return 0;
+        };
+        let handle = table.next_handle();
+        if handle != 0 {
+            table.trays.insert(handle, tray);
+        }
+        handle
+    })
+    .unwrap_or(0)
+}
+
+/// Remove a tray icon; the handle becomes invalid.
+/// Returns a status code.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+#[no_mangle]
+pub extern "C" fn remove_tray(handle: WindowHandle) -> i32 {
+    status(with_table(move |table| {
+        table.trays.remove(&handle).map(drop)
+    }))
+}
+
+/// Set the "Tasks" of the taskbar button's jump list from a UTF-8 JSON
+/// array of `{"title", "arguments", "description"?}` objects, each
+/// starting this executable with `arguments` (see `jumplist`); an empty
+/// array removes them. Needs no window. Windows with the "windows"
+/// feature only (`STATUS_UNSUPPORTED` elsewhere). Returns a status
+/// code; `STATUS_INVALID_ARGUMENT` for malformed JSON.
+///
+/// # Safety
+/// `json_ptr` must point to `json_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn set_jump_list(json_ptr: *const u8, json_len: u32) -> i32 {
+    let Some(tasks) = read_str(json_ptr, json_len).and_then(jumplist::Task::parse_list) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    jumplist::set(&tasks)
+}
+
+/// The OS theme: 0 = light, 1 = dark, 2 = high contrast. Windows
+/// with the "windows" feature reads it from the system; elsewhere it is
+/// the theme of an open window, and `STATUS_UNSUPPORTED` without one.
+/// Windows report changes as "theme-changed" events.
+#[no_mangle]
+pub extern "C" fn get_system_theme() -> i32 {
+    if let Some(theme) = theme::system() {
+        return theme as i32;
+    }
+    match with_table(|table| {
+        table
+            .windows
+            .values()
+            .next()
+            .map(|native| theme::Theme::of(&native.window))
+    }) {
+        Ok(theme) => theme as i32,
+        Err(_) => STATUS_UNSUPPORTED,
+    }
+}
+
+/// Show a native message box and wait for the student's answer, even
+/// while the webview is unresponsive. `kind`: 0 = info, 1 = warning,
+/// 2 = error (each with OK), 3 = confirm (OK / Cancel), 4 = question
+/// (Yes / No). Title and text are UTF-8.
+/// Returns the button pressed (1 = OK, 2 = Cancel, 3 = Yes, 4 = No) or
+/// a negative status code; `STATUS_UNSUPPORTED` without a native
+/// dialog. Needs no window: it blocks the calling thread only.
+///
+/// # Safety
+/// `title_ptr` must point to `title_len` readable bytes and `text_ptr`
+/// to `text_len`.
+#[no_mangle]
+pub unsafe extern "C" fn show_message_box(
+    kind: i32,
+    title_ptr: *const u8,
+    title_len: u32,
+    text_ptr: *const u8,
+    text_len: u32,
+) -> i32 {
+    let (Some(kind), Some(title), Some(text)) = (
+        dialog::Kind::from_code(kind),
+        read_str(title_ptr, title_len),
+        read_str(text_ptr, text_len),
+    ) else {
+        return STATUS_INVALID_ARGUMENT;
+    };
+    match dialog::show(kind, title, text) {
+        Some(button) => button as i32,
+        None => STATUS_UNSUPPORTED,
+    }
+}
+
+/// Keep the system and display awake, e.g. during a timed exam, until
+/// `allow_sleep`; `reason` (UTF-8) is shown where the OS lists who
+/// keeps it awake (see `sleep`). Needs no window. Returns a status
+/// code; `STATUS_UNSUPPORTED` where sleep cannot be inhibited.
+///
+/// # Safety
+/// `reason_ptr` must point to `reason_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn inhibit_sleep(reason_ptr: *const u8, reason_len: u32) -> i32 {
+    let reason = if reason_len == 0 {
+        ""
+    } else {
+        match read_str(reason_ptr, reason_len) {
+            Some(reason) => reason,
+            None => return STATUS_INVALID_ARGUMENT,
+        }
+    };
+    sleep::inhibit(reason)
+}
+
+/// Let the system sleep again after `inhibit_sleep`. Returns a status
+/// code (`STATUS_OK` if sleep was not inhibited).
+#[no_mangle]
+pub extern "C" fn allow_sleep() -> i32 {
+    sleep::allow()
+}
+
+/// Arm the hang watchdog: if `heartbeat` is not called for
+/// `timeout_ms`, the windows offer the student to reload or quit.
+/// 0 disarms it. Returns `STATUS_OK`.
+#[no_mangle]
+pub extern "C" fn set_watchdog_timeout(timeout_ms: u32) -> i32 {
+    watchdog::arm(timeout_ms);
+    STATUS_OK
+}
+
+/// Tell the watchdog the host's event loop is alive.
+/// Returns 1 while the watchdog is armed, 0 otherwise.
+#[no_mangle]
+pub extern "C" fn heartbeat() -> i32 {
+    watchdog::beat();
+    watchdog::is_armed() as i32
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The scheme, host and port `parse_proxy` found.
+    fn proxy(url: &str) -> Option<(&'static str, String, String)> {
+        Some(match parse_proxy(url)? {
+            ProxyConfig::Http(ProxyEndpoint { host, port }) => ("http", host, port),
+            ProxyConfig::Socks5(ProxyEndpoint { host, port }) => ("socks5", host, port),
+        })
+    }
+
+    #[test]
+    fn test_window_size_rejects_non_positive() {
+        assert_eq!(window_size(1280, 720), Some(PhysicalSize::new(1280, 720)));
+        assert_eq!(window_size(0, 720), None);
+        assert_eq!(window_size(1280, 0), None);
+        assert_eq!(window_size(-1, 720), None);
+        assert_eq!(window_size(1280, i32::MIN), None);
+    }
+
+    #[test]
+    fn test_parse_proxy_schemes() {
+        let expected = |scheme, host: &str, port: &str| Some((scheme, host.into(), port.into()));
+        assert_eq!(
+            proxy("http://127.0.0.1:8080"),
+            expected("http", "127.0.0.1", "8080")
+        );
+        assert_eq!(
+            proxy("http://proxy.school.local:3128/"),
+            expected("http", "proxy.school.local", "3128")
+        );
+        assert_eq!(
+            proxy("socks5://localhost:1080"),
+            expected("socks5", "localhost", "1080")
+        );
+        assert_eq!(
+            proxy("http://[::1]:8080"),
+            expected("http", "[::1]", "8080")
+        );
+    }
+
+    #[test]
+    fn test_parse_proxy_rejects() {
+        for url in [
+            "",
+            "127.0.0.1:8080",
+            "https://127.0.0.1:8080",
+            "socks4://127.0.0.1:1080",
+            "HTTP://127.0.0.1:8080",
+            "http://127.0.0.1",
+            "http://:8080",
+            "http://127.0.0.1:",
+            "http://127.0.0.1:0",
+            "http://127.0.0.1:65536",
+            "http://127.0.0.1:port",
+            "http://127.0.0.1:8080/path",
+        ] {
+            assert!(parse_proxy(url).is_none(), "{url}");
         }
     }
-    0
 }
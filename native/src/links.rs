@@ -0,0 +1,118 @@
+// ═════════════════════════════════════════════════════════════════
+//  External Links — popups go to the system browser, or nowhere
+// ═════════════════════════════════════════════════════════════════
+//
+// A `target="_blank"` link or `window.open` would otherwise open a
+// bare popup webview: no title bar, no navigation policy, no way back
+// for a young student. Instead such requests never open a webview;
+// an http(s) link goes to the system browser, or is dropped while the
+// host has blocked external links (exam mode). Either way the window
+// queues a "new-window" event so the host knows (see `events`). The
+// event holds the whole URL, however long; `poll_event` reports the
+// length of one that does not fit the host's buffer.
+
+use crate::events::EventQueue;
+
+/// What a link that asks for a new window does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalLinks {
+    /// Open http(s) links in the system browser.
+    Browser,
+    /// Drop them, e.g. during an exam.
+    Block,
+}
+
+impl ExternalLinks {
+    /// The policy for its FFI code (0 = browser, 1 = block).
+    pub fn from_code(code: i32) -> Option<ExternalLinks> {
+        match code {
+            0 => Some(ExternalLinks::Browser),
+            1 => Some(ExternalLinks::Block),
+            _ => None,
+        }
+    }
+}
+
+/// Handle a webview's request to open `url` in a new window. Always
+/// `false`: the webview itself never opens one.
+pub fn new_window(url: &str, links: ExternalLinks, events: &EventQueue) -> bool {
+    let opened = links == ExternalLinks::Browser && crate::is_http_url(url) && open_in_browser(url);
+    events
+        .push(serde_json::json!({"type": "new-window", "url": url, "opened": opened}).to_string());
+    false
+}
+
+/// Open `url` in the student's default browser; `false` if it could
+/// not be started.
+#[cfg(target_os = "windows")]
+fn open_in_browser(url: &str) -> bool {
+    // ─── Raw Win32 FFI declarations ──
+    const SW_SHOWNORMAL: i32 = 1;
+
+    #[link(name = "shell32")]
+    extern "system" {
+        // shell32.dll
+        fn ShellExecuteW(
+            hwnd: isize,
+            operation: *const u16,
+            file: *const u16,
+            parameters: *const u16,
+            directory: *const u16,
+            show: i32,
+        ) -> isize;
+    }
+
+    let wide = |text: &str| text.encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
+    let (operation, file) = (wide("open"), wide(url));
+    let result = unsafe {
+        ShellExecuteW(
+            0,
+            operation.as_ptr(),
+            file.as_ptr(),
+            std::ptr::null(),
+            std::ptr::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+    // Values above 32 mean success.
+    result > 32
+}
+
+#[cfg(not(target_os = "windows"))]
+/// Open `url` with the desktop's opener.
+fn open_in_browser(url: &str) -> bool {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    match std::process::Command::new(opener).arg(url).spawn() {
+        Ok(mut child) => {
+            // Reap the opener without blocking the UI thread.
+            std::thread::spawn(move || child.wait());
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_url_is_queued_whole() {
+        let events = EventQueue::default();
+        let url = format!("https://example.org/?q={}", "a".repeat(4096));
+        assert!(!new_window(&url, ExternalLinks::Block, &events));
+        let len = match events.pop(256) {
+            Err(len) => len,
+            other => panic!("fit in 256 bytes: {other:?}"),
+        };
+        let event = events.pop(len).unwrap().unwrap();
+        assert_eq!(event.len(), len);
+        let event: serde_json::Value = serde_json::from_str(&event).unwrap();
+        assert_eq!(event["url"], url);
+        assert_eq!(event["opened"], false);
+    }
+}
@@ -0,0 +1,120 @@
+// ═════════════════════════════════════════════════════════════════
+//  Win32 Magnifier & Text-Size Detection (feature = "windows")
+// ═════════════════════════════════════════════════════════════════
+//
+// Low-vision students often run Windows Magnifier or raise the system
+// text size. Neither reaches a webview: Magnifier only enlarges pixels
+// (the 46×32 caption buttons stay tiny targets under a magnified
+// cursor), and WebView2 ignores the text-size slider.
+//
+// `detect` reports both so the launcher can zoom the webview and widen
+// the native hit targets to match (`frameless::set_hit_target_scale`).
+// Magnifier sends no notification to other windows, so callers poll.
+
+// ─── Raw Win32 FFI declarations (avoids windows-sys version conflicts) ──
+#[allow(clippy::upper_case_acronyms)]
+mod win32 {
+    pub type HWND = isize;
+    pub type HKEY = isize;
+
+    pub const HKEY_CURRENT_USER: HKEY = 0x8000_0001_u32 as i32 as isize;
+    pub const RRF_RT_REG_DWORD: u32 = 0x0000_0010;
+
+    extern "system" {
+        // user32.dll
+        pub fn FindWindowW(class_name: *const u16, window_name: *const u16) -> HWND;
+    }
+
+    #[link(name = "advapi32")]
+    extern "system" {
+        // advapi32.dll
+        pub fn RegGetValueW(
+            key: HKEY,
+            sub_key: *const u16,
+            value: *const u16,
+            flags: u32,
+            kind: *mut u32,
+            data: *mut u32,
+            size: *mut u32,
+        ) -> i32;
+    }
+}
+
+/// Window class of the Magnifier toolbar, present while it runs.
+const MAGNIFIER_CLASS: &str = "MagUIClass";
+
+/// Accessibility → Text size, as a percentage (100–225).
+const TEXT_SCALE_KEY: &str = r"Software\Microsoft\Accessibility";
+const TEXT_SCALE_VALUE: &str = "TextScaleFactor";
+
+/// Magnification the system applies for the student.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoomState {
+    /// Windows Magnifier is running.
+    pub magnifier: bool,
+    /// System text size in percent (100 = default).
+    pub text_scale: u32,
+}
+
+impl Default for ZoomState {
+    fn default() -> Self {
+        ZoomState {
+            magnifier: false,
+            text_scale: 100,
+        }
+    }
+}
+
+impl ZoomState {
+    /// Webview zoom in percent: the text size, raised to
+    /// `magnifier_zoom` while Magnifier runs.
+    pub fn zoom_percent(&self, magnifier_zoom: u32) -> u32 {
+        if self.magnifier {
+            self.text_scale.max(magnifier_zoom)
+        } else {
+            self.text_scale
+        }
+    }
+}
+
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Whether Windows Magnifier is running (docked, lens, or full screen).
+pub fn magnifier_active() -> bool {
+    let class = wide(MAGNIFIER_CLASS);
+    unsafe { win32::FindWindowW(class.as_ptr(), std::ptr::null()) != 0 }
+}
+
+/// The system text size in percent; 100 when it was never changed.
+pub fn text_scale() -> u32 {
+    let (key, value) = (wide(TEXT_SCALE_KEY), wide(TEXT_SCALE_VALUE));
+    let mut data: u32 = 0;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let status = unsafe {
+        win32::RegGetValueW(
+            win32::HKEY_CURRENT_USER,
+            key.as_ptr(),
+            value.as_ptr(),
+            win32::RRF_RT_REG_DWORD,
+            std::ptr::null_mut(),
+            &mut data,
+            &mut size,
+        )
+    };
+    if status == 0 {
+        data.clamp(100, 225)
+    } else {
+        100
+    }
+}
+
+/// Current Magnifier and text-size state. Cheap enough to poll about
+/// once a second from any thread.
+pub fn detect() -> ZoomState {
+    ZoomState {
+        magnifier: magnifier_active(),
+        text_scale: text_scale(),
+    }
+}
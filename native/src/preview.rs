@@ -0,0 +1,352 @@
+// ═════════════════════════════════════════════════════════════════
+//  Win32 Private Taskbar Preview (feature = "windows")
+// ═════════════════════════════════════════════════════════════════
+//
+// By default DWM builds the taskbar thumbnail and the Aero Peek
+// preview from the window's live contents — so hovering the taskbar
+// button during a test shows the question and the answer being typed.
+//
+// With `enable_private_preview` the window asks DWM for "iconic"
+// representations instead, and draws both itself: the app icon, the
+// app name, and the current streak on the title-bar colour. DWM
+// requests the bitmaps with WM_DWMSENDICONICTHUMBNAIL and
+// WM_DWMSENDICONICLIVEPREVIEWBITMAP, which the frameless WndProc
+// subclass forwards to `handle_message` — so the window must also go
+// through `frameless::setup_frameless`.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+// ─── Raw Win32 FFI declarations (avoids windows-sys version conflicts) ──
+#[allow(clippy::upper_case_acronyms)]
+mod win32 {
+    pub type HWND = isize;
+    pub type HDC = isize;
+    pub type HBITMAP = isize;
+    pub type HGDIOBJ = isize;
+    pub type HICON = isize;
+    pub type LPARAM = isize;
+
+    #[repr(C)]
+    pub struct RECT {
+        pub left: i32,
+        pub top: i32,
+        pub right: i32,
+        pub bottom: i32,
+    }
+
+    #[repr(C)]
+    pub struct BITMAPINFOHEADER {
+        pub size: u32,
+        pub width: i32,
+        pub height: i32,
+        pub planes: u16,
+        pub bit_count: u16,
+        pub compression: u32,
+        pub size_image: u32,
+        pub x_pels_per_meter: i32,
+        pub y_pels_per_meter: i32,
+        pub clr_used: u32,
+        pub clr_important: u32,
+    }
+
+    /// `BITMAPINFO` for 32-bit pixels, which need no colour table.
+    #[repr(C)]
+    pub struct BITMAPINFO {
+        pub header: BITMAPINFOHEADER,
+        pub colors: [u32; 1],
+    }
+
+    // DWM window attributes
+    pub const DWMWA_FORCE_ICONIC_REPRESENTATION: u32 = 7;
+    pub const DWMWA_HAS_ICONIC_BITMAP: u32 = 10;
+
+    // Window messages
+    pub const WM_GETICON: u32 = 0x007F;
+    pub const WM_DWMSENDICONICTHUMBNAIL: u32 = 0x0323;
+    pub const WM_DWMSENDICONICLIVEPREVIEWBITMAP: u32 = 0x0326;
+
+    pub const ICON_BIG: usize = 1;
+    pub const GCLP_HICON: i32 = -14;
+    pub const DI_NORMAL: u32 = 0x0003;
+    pub const BI_RGB: u32 = 0;
+    pub const DIB_RGB_COLORS: u32 = 0;
+    pub const TRANSPARENT: i32 = 1;
+    pub const FW_SEMIBOLD: i32 = 600;
+    pub const DT_CENTER: u32 = 0x0001;
+    pub const DT_SINGLELINE: u32 = 0x0020;
+    pub const DT_NOPREFIX: u32 = 0x0800;
+    pub const DT_END_ELLIPSIS: u32 = 0x8000;
+
+    extern "system" {
+        // user32.dll
+        pub fn GetClientRect(hwnd: HWND, rect: *mut RECT) -> i32;
+        pub fn SendMessageW(hwnd: HWND, msg: u32, wparam: usize, lparam: LPARAM) -> isize;
+        pub fn GetClassLongPtrW(hwnd: HWND, index: i32) -> usize;
+        pub fn DrawIconEx(
+            dc: HDC,
+            x: i32,
+            y: i32,
+            icon: HICON,
+            width: i32,
+            height: i32,
+            step: u32,
+            brush: isize,
+            flags: u32,
+        ) -> i32;
+        pub fn DrawTextW(dc: HDC, text: *const u16, len: i32, rect: *mut RECT, format: u32) -> i32;
+    }
+
+    extern "system" {
+        // gdi32.dll
+        pub fn CreateCompatibleDC(dc: HDC) -> HDC;
+        pub fn DeleteDC(dc: HDC) -> i32;
+        pub fn CreateDIBSection(
+            dc: HDC,
+            info: *const BITMAPINFO,
+            usage: u32,
+            bits: *mut *mut u32,
+            section: isize,
+            offset: u32,
+        ) -> HBITMAP;
+        pub fn SelectObject(dc: HDC, object: HGDIOBJ) -> HGDIOBJ;
+        pub fn DeleteObject(object: HGDIOBJ) -> i32;
+        pub fn GdiFlush() -> i32;
+        pub fn SetBkMode(dc: HDC, mode: i32) -> i32;
+        pub fn SetTextColor(dc: HDC, color: u32) -> u32;
+        pub fn CreateFontW(
+            height: i32,
+            width: i32,
+            escapement: i32,
+            orientation: i32,
+            weight: i32,
+            italic: u32,
+            underline: u32,
+            strike_out: u32,
+            charset: u32,
+            out_precision: u32,
+            clip_precision: u32,
+            quality: u32,
+            pitch_and_family: u32,
+            face: *const u16,
+        ) -> HGDIOBJ;
+    }
+
+    #[link(name = "dwmapi")]
+    extern "system" {
+        // dwmapi.dll
+        pub fn DwmSetWindowAttribute(hwnd: HWND, attribute: u32, value: *const i32, size: u32) -> i32;
+        pub fn DwmSetIconicThumbnail(hwnd: HWND, bitmap: HBITMAP, flags: u32) -> i32;
+        pub fn DwmSetIconicLivePreviewBitmap(
+            hwnd: HWND,
+            bitmap: HBITMAP,
+            client_offset: *const i32,
+            flags: u32,
+        ) -> i32;
+        pub fn DwmInvalidateIconicBitmaps(hwnd: HWND) -> i32;
+    }
+}
+
+/// Preview background, as opaque 0xAARRGGBB (#1e1f22, the title bar).
+const BACKGROUND: u32 = 0xFF1E_1F22;
+/// Text colours as Win32 COLORREF (0x00BBGGRR).
+const TITLE_COLOR: u32 = 0x00F2_F3F5;
+const STREAK_COLOR: u32 = 0x0000_A5FF; // #ffa500
+
+/// Whether DWM has been asked for iconic representations.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Consecutive correct answers shown in the preview.
+static STREAK: AtomicU32 = AtomicU32::new(0);
+
+/// Replace the live taskbar thumbnail and Aero Peek preview of `hwnd`
+/// with the app's own. Returns `false` if DWM refused.
+pub fn enable_private_preview(hwnd: isize) -> bool {
+    use win32::*;
+
+    let on: i32 = 1;
+    let size = std::mem::size_of::<i32>() as u32;
+    let ok = unsafe {
+        DwmSetWindowAttribute(hwnd, DWMWA_FORCE_ICONIC_REPRESENTATION, &on, size) >= 0
+            && DwmSetWindowAttribute(hwnd, DWMWA_HAS_ICONIC_BITMAP, &on, size) >= 0
+    };
+    ENABLED.store(ok, Ordering::SeqCst);
+    ok
+}
+
+/// Update the streak shown in the preview. DWM asks for fresh bitmaps
+/// the next time a preview is shown.
+pub fn set_streak(hwnd: isize, streak: u32) {
+    if STREAK.swap(streak, Ordering::SeqCst) != streak && ENABLED.load(Ordering::SeqCst) {
+        unsafe { win32::DwmInvalidateIconicBitmaps(hwnd) };
+    }
+}
+
+/// Answer a DWM request for a preview bitmap. `None` for any other
+/// message, or while the private preview is off.
+pub(crate) fn handle_message(hwnd: isize, msg: u32, lparam: isize) -> Option<isize> {
+    use win32::*;
+
+    if !ENABLED.load(Ordering::SeqCst) {
+        return None;
+    }
+    match msg {
+        WM_DWMSENDICONICTHUMBNAIL => {
+            // Maximum thumbnail size: width in the high word, height in the low
+            let max_width = ((lparam >> 16) & 0xFFFF) as i32;
+            let max_height = (lparam & 0xFFFF) as i32;
+            let bitmap = render(hwnd, max_width, max_height)?;
+            unsafe {
+                DwmSetIconicThumbnail(hwnd, bitmap, 0);
+                DeleteObject(bitmap);
+            }
+            Some(0)
+        }
+        WM_DWMSENDICONICLIVEPREVIEWBITMAP => {
+            let mut rect = RECT {
+                left: 0,
+                top: 0,
+                right: 0,
+                bottom: 0,
+            };
+            unsafe { GetClientRect(hwnd, &mut rect) };
+            let bitmap = render(hwnd, rect.right - rect.left, rect.bottom - rect.top)?;
+            unsafe {
+                DwmSetIconicLivePreviewBitmap(hwnd, bitmap, std::ptr::null(), 0);
+                DeleteObject(bitmap);
+            }
+            Some(0)
+        }
+        _ => None,
+    }
+}
+
+/// The window's large icon, if it has one.
+fn app_icon(hwnd: isize) -> isize {
+    use win32::*;
+
+    let icon = unsafe { SendMessageW(hwnd, WM_GETICON, ICON_BIG, 0) };
+    if icon != 0 {
+        return icon;
+    }
+    unsafe { GetClassLongPtrW(hwnd, GCLP_HICON) as isize }
+}
+
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Draw one centred line of text with its top at `top`.
+unsafe fn draw_line(dc: isize, text: &str, height: i32, color: u32, width: i32, top: i32) {
+    use win32::*;
+
+    let face = wide("Segoe UI");
+    let font = CreateFontW(
+        -height,
+        0,
+        0,
+        0,
+        FW_SEMIBOLD,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        5, // CLEARTYPE_QUALITY
+        0,
+        face.as_ptr(),
+    );
+    let previous = SelectObject(dc, font);
+    SetTextColor(dc, color);
+    let text = wide(text);
+    let mut rect = RECT {
+        left: 0,
+        top,
+        right: width,
+        bottom: top + height * 2,
+    };
+    DrawTextW(
+        dc,
+        text.as_ptr(),
+        -1,
+        &mut rect,
+        DT_CENTER | DT_SINGLELINE | DT_NOPREFIX | DT_END_ELLIPSIS,
+    );
+    SelectObject(dc, previous);
+    DeleteObject(font);
+}
+
+/// Render the preview at `width` × `height`: icon, app name, and
+/// streak, centred. The caller owns the returned bitmap.
+fn render(hwnd: isize, width: i32, height: i32) -> Option<isize> {
+    use win32::*;
+
+    if width <= 0 || height <= 0 {
+        return None;
+    }
+    let info = BITMAPINFO {
+        header: BITMAPINFOHEADER {
+            size: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            width,
+            height: -height, // top-down rows
+            planes: 1,
+            bit_count: 32,
+            compression: BI_RGB,
+            size_image: 0,
+            x_pels_per_meter: 0,
+            y_pels_per_meter: 0,
+            clr_used: 0,
+            clr_important: 0,
+        },
+        colors: [0],
+    };
+
+    unsafe {
+        let dc = CreateCompatibleDC(0);
+        if dc == 0 {
+            return None;
+        }
+        let mut bits: *mut u32 = std::ptr::null_mut();
+        let bitmap = CreateDIBSection(dc, &info, DIB_RGB_COLORS, &mut bits, 0, 0);
+        if bitmap == 0 || bits.is_null() {
+            DeleteDC(dc);
+            return None;
+        }
+        let len = (width * height) as usize;
+        std::slice::from_raw_parts_mut(bits, len).fill(BACKGROUND);
+
+        let previous = SelectObject(dc, bitmap);
+        SetBkMode(dc, TRANSPARENT);
+
+        // Scale everything from the shorter side; a thumbnail is ~200px.
+        let unit = width.min(height);
+        let icon_size = unit * 3 / 8;
+        let text_height = (unit / 10).max(10);
+        let block = icon_size + text_height * 3;
+        let mut top = (height - block) / 2;
+
+        let icon = app_icon(hwnd);
+        if icon != 0 {
+            DrawIconEx(dc, (width - icon_size) / 2, top, icon, icon_size, icon_size, 0, 0, DI_NORMAL);
+        }
+        top += icon_size + text_height / 2;
+        draw_line(dc, "Sovereign Academy", text_height, TITLE_COLOR, width, top);
+
+        let streak = STREAK.load(Ordering::SeqCst);
+        if streak > 0 {
+            top += text_height * 3 / 2;
+            let label = format!("{streak} in a row");
+            draw_line(dc, &label, text_height, STREAK_COLOR, width, top);
+        }
+
+        SelectObject(dc, previous);
+        DeleteDC(dc);
+
+        // GDI text leaves alpha at 0; DWM needs the bitmap opaque.
+        GdiFlush();
+        for pixel in std::slice::from_raw_parts_mut(bits, len) {
+            *pixel |= 0xFF00_0000;
+        }
+        Some(bitmap)
+    }
+}
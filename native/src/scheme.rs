@@ -0,0 +1,252 @@
+// ═════════════════════════════════════════════════════════════════
+//  Custom URL Schemes — serve `app://` straight from Deno
+// ═════════════════════════════════════════════════════════════════
+//
+// Lesson assets can come from the Deno host itself instead of an HTTP
+// server on localhost: the host registers a scheme with a callback,
+// and every webview created afterwards routes `scheme://…` requests
+// to it.
+//
+// The webview asks on the UI thread, but the UI thread must never wait
+// for Deno: Deno's main thread may itself be blocked in an FFI call
+// that waits for the UI thread. So a request only parks its responder
+// under a fresh id, and a dispatch thread hands the id and URL to the
+// callback. The host answers whenever it is ready with `respond`, from
+// any thread. A request it has not answered within `REQUEST_TIMEOUT`
+// gets a 504 instead, and the requests of a closed window are
+// forgotten, so no responder outlives its use.
+//
+// WebView2 only loads standard schemes, so on Windows wry serves
+// `app://localhost/x` as `http://app.localhost/x`; `webview_url` makes
+// the same rewrite for URLs the host navigates to.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use wry::http::Response;
+use wry::{RequestAsyncResponder, WebViewBuilder};
+
+/// Told about a request: its id and its UTF-8 URL, which is only valid
+/// during the call.
+pub type SchemeCallback = extern "C" fn(request: u32, url_ptr: *const u8, url_len: u32);
+
+/// Schemes that keep their usual meaning.
+const RESERVED: [&str; 9] = [
+    "about",
+    "blob",
+    "data",
+    "file",
+    "http",
+    "https",
+    "javascript",
+    "ws",
+    "wss",
+];
+
+/// How long the host has to answer a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the dispatch thread looks for requests past the timeout.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+static HANDLERS: Mutex<Vec<(String, SchemeCallback)>> = Mutex::new(Vec::new());
+
+/// A request waiting for the host.
+struct Pending {
+    /// The webview that asked, see `Requests`.
+    webview: u32,
+    issued: Instant,
+    responder: RequestAsyncResponder,
+}
+
+/// Requests waiting for the host, by request id.
+static PENDING: Mutex<BTreeMap<u32, Pending>> = Mutex::new(BTreeMap::new());
+
+static NEXT_REQUEST: AtomicU32 = AtomicU32::new(1);
+
+static NEXT_WEBVIEW: AtomicU32 = AtomicU32::new(1);
+
+/// The scheme requests of one webview. Dropping it with the webview
+/// forgets those still pending: nobody is left to read the answer.
+#[derive(Debug)]
+pub struct Requests {
+    webview: u32,
+    /// The schemes attached to the webview when it was built.
+    schemes: Vec<String>,
+}
+
+impl Requests {
+    /// The schemes this webview serves; one registered after it was
+    /// built is not among them.
+    pub fn schemes(&self) -> &[String] {
+        &self.schemes
+    }
+}
+
+impl Drop for Requests {
+    fn drop(&mut self) {
+        if let Ok(mut pending) = PENDING.lock() {
+            pending.retain(|_, request| request.webview != self.webview);
+        }
+    }
+}
+
+/// A request on its way to the host's callback.
+struct Request {
+    callback: SchemeCallback,
+    id: u32,
+    url: String,
+}
+
+/// The dispatch thread's queue, started with the first request. The
+/// thread also times out requests the host leaves unanswered.
+fn dispatcher() -> Option<&'static mpsc::Sender<Request>> {
+    static DISPATCH: OnceLock<Option<mpsc::Sender<Request>>> = OnceLock::new();
+    DISPATCH
+        .get_or_init(|| {
+            let (tx, rx) = mpsc::channel::<Request>();
+            thread::Builder::new()
+                .name("native-scheme".into())
+                .spawn(move || loop {
+                    match rx.recv_timeout(SWEEP_INTERVAL) {
+                        Ok(request) => {
+                            let url = request.url.as_bytes();
+                            (request.callback)(request.id, url.as_ptr(), url.len() as u32);
+                        }
+                        Err(mpsc::RecvTimeoutError::Timeout) => {}
+                        Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                    }
+                    expire(Instant::now());
+                })
+                .ok()
+                .map(|_| tx)
+        })
+        .as_ref()
+}
+
+/// Answer the requests issued before `now - REQUEST_TIMEOUT` with a 504.
+fn expire(now: Instant) {
+    let expired: Vec<Pending> = match PENDING.lock() {
+        Ok(mut pending) => {
+            let ids: Vec<u32> = pending
+                .iter()
+                .filter(|(_, request)| now.duration_since(request.issued) >= REQUEST_TIMEOUT)
+                .map(|(&id, _)| id)
+                .collect();
+            ids.iter().filter_map(|id| pending.remove(id)).collect()
+        }
+        Err(_) => return,
+    };
+    // Answered outside the lock, which `respond` also takes.
+    for request in expired {
+        if let Ok(response) = Response::builder().status(504).body(Vec::new()) {
+            request.responder.respond(response);
+        }
+    }
+}
+
+/// Whether `scheme` can be registered: a lowercase URL scheme that is
+/// not one the webview already handles.
+fn is_scheme_name(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    matches!(chars.next(), Some('a'..='z'))
+        && chars.all(|c| matches!(c, 'a'..='z' | '0'..='9' | '+' | '-' | '.'))
+        && !RESERVED.contains(&scheme)
+}
+
+/// Route `scheme://` requests of webviews created from now on to
+/// `callback`, replacing any earlier callback for it. `false` for a
+/// scheme that cannot be registered.
+pub fn register(scheme: &str, callback: SchemeCallback) -> bool {
+    if !is_scheme_name(scheme) {
+        return false;
+    }
+    let Ok(mut handlers) = HANDLERS.lock() else {
+        return false;
+    };
+    match handlers.iter_mut().find(|(name, _)| name == scheme) {
+        Some(handler) => handler.1 = callback,
+        None => handlers.push((scheme.to_owned(), callback)),
+    }
+    true
+}
+
+/// The registered scheme `url` uses, if it names something after it.
+fn registered_scheme(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let handlers = HANDLERS.lock().ok()?;
+    (!rest.is_empty() && handlers.iter().any(|(name, _)| name == scheme)).then(|| scheme.to_owned())
+}
+
+/// Whether `url` uses a registered scheme.
+pub fn is_registered(url: &str) -> bool {
+    registered_scheme(url).is_some()
+}
+
+/// `url` as the webview must load it (see the header).
+pub fn webview_url(url: &str) -> String {
+    match registered_scheme(url) {
+        Some(scheme) if cfg!(target_os = "windows") => {
+            format!("http://{scheme}.{}", &url[scheme.len() + 3..])
+        }
+        _ => url.to_owned(),
+    }
+}
+
+/// Add every registered scheme to a webview being built. The webview
+/// must keep the returned `Requests` for as long as it lives.
+pub fn attach(mut builder: WebViewBuilder<'_>) -> (WebViewBuilder<'_>, Requests) {
+    let webview = NEXT_WEBVIEW.fetch_add(1, Ordering::Relaxed);
+    let handlers = match HANDLERS.lock() {
+        Ok(handlers) => handlers.clone(),
+        Err(_) => {
+            let schemes = Vec::new();
+            return (builder, Requests { webview, schemes });
+        }
+    };
+    let schemes = handlers.iter().map(|(scheme, _)| scheme.clone()).collect();
+    for (scheme, callback) in handlers {
+        builder =
+            builder.with_asynchronous_custom_protocol(scheme, move |_, request, responder| {
+                let id = NEXT_REQUEST.fetch_add(1, Ordering::Relaxed);
+                let url = request.uri().to_string();
+                if let Ok(mut pending) = PENDING.lock() {
+                    pending.insert(
+                        id,
+                        Pending {
+                            webview,
+                            issued: Instant::now(),
+                            responder,
+                        },
+                    );
+                }
+                let sent =
+                    dispatcher().is_some_and(|tx| tx.send(Request { callback, id, url }).is_ok());
+                if !sent {
+                    let unavailable = Response::builder().status(503).body(Vec::new());
+                    if let Ok(response) = unavailable {
+                        respond(id, response);
+                    }
+                }
+            });
+    }
+    (builder, Requests { webview, schemes })
+}
+
+/// Answer a pending request; `false` if `request` is not pending (never
+/// issued, already answered, timed out or its window closed).
+pub fn respond(request: u32, response: Response<Vec<u8>>) -> bool {
+    let pending = PENDING
+        .lock()
+        .ok()
+        .and_then(|mut pending| pending.remove(&request));
+    match pending {
+        Some(pending) => {
+            pending.responder.respond(response);
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,150 @@
+// ═════════════════════════════════════════════════════════════════
+//  Init Scripts — ordered scripts injected into every page
+// ═════════════════════════════════════════════════════════════════
+//
+// The host keeps several independent scripts in each page: the IPC
+// bridge, the resize handles, the exam-mode lockdown. Each is
+// registered under a name with an order; lower orders run first, and
+// equal orders run in the order they were first registered. Scripts
+// added with `add_init_script` have no name and cannot be removed.
+//
+// A change takes effect from the next document the webview loads: the
+// current page keeps what it ran. On Windows every change unregisters
+// the window's scripts from WebView2 and registers them again in
+// order, each on its own so a failing script does not stop the next.
+// WebView2 reports the id of a registration asynchronously; an id that
+// arrives after a newer change is unregistered on arrival.
+//
+// Only Windows can inject scripts after creation; elsewhere changes
+// are kept but report `STATUS_UNSUPPORTED`.
+
+use wry::WebView;
+
+/// One script of a window.
+#[derive(Debug)]
+struct Script {
+    /// `None` for `add_init_script` scripts.
+    name: Option<String>,
+    order: i32,
+    /// When it was first registered, to order equal `order`s.
+    seq: u64,
+    js: String,
+}
+
+/// A window's init scripts and their WebView2 registrations.
+#[derive(Debug, Default)]
+pub struct InitScripts {
+    scripts: Vec<Script>,
+    next_seq: u64,
+    #[cfg(target_os = "windows")]
+    registered: std::rc::Rc<std::cell::RefCell<Registered>>,
+}
+
+/// WebView2 ids of the current registrations.
+#[cfg(target_os = "windows")]
+#[derive(Debug, Default)]
+struct Registered {
+    /// Bumped on every change, so late ids of older ones are dropped.
+    generation: u64,
+    ids: Vec<String>,
+}
+
+impl InitScripts {
+    /// Add a script that cannot be removed, after those with order 0.
+    pub fn add(&mut self, js: String) {
+        self.insert(None, 0, js);
+    }
+
+    /// Add the script `name`, or replace its order and source.
+    pub fn set(&mut self, name: &str, order: i32, js: String) {
+        match self
+            .scripts
+            .iter_mut()
+            .find(|script| script.name.as_deref() == Some(name))
+        {
+            Some(script) => {
+                script.order = order;
+                script.js = js;
+            }
+            None => self.insert(Some(name.to_owned()), order, js),
+        }
+    }
+
+    /// Remove the script `name`; `false` if there is none.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.scripts.len();
+        self.scripts
+            .retain(|script| script.name.as_deref() != Some(name));
+        self.scripts.len() != before
+    }
+
+    fn insert(&mut self, name: Option<String>, order: i32, js: String) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.scripts.push(Script {
+            name,
+            order,
+            seq,
+            js,
+        });
+    }
+
+    /// Sources in the order they run.
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    fn ordered(&self) -> Vec<&str> {
+        let mut scripts: Vec<&Script> = self.scripts.iter().collect();
+        scripts.sort_by_key(|script| (script.order, script.seq));
+        scripts.iter().map(|script| script.js.as_str()).collect()
+    }
+
+    /// Make the webview run the current scripts from its next document
+    /// on. Returns a status code.
+    #[cfg(target_os = "windows")]
+    pub fn apply(&self, webview: &WebView) -> i32 {
+        use webview2_com::AddScriptToExecuteOnDocumentCreatedCompletedHandler;
+        use windows_core::HSTRING;
+        use wry::WebViewExtWindows;
+
+        let Ok(core) = (unsafe { webview.controller().CoreWebView2() }) else {
+            return crate::STATUS_WEBVIEW_FAILED;
+        };
+        let generation = {
+            let mut registered = self.registered.borrow_mut();
+            for id in registered.ids.drain(..) {
+                let _ = unsafe { core.RemoveScriptToExecuteOnDocumentCreated(&HSTRING::from(id)) };
+            }
+            registered.generation += 1;
+            registered.generation
+        };
+        for js in self.ordered() {
+            let registered = self.registered.clone();
+            let owner = core.clone();
+            let handler = AddScriptToExecuteOnDocumentCreatedCompletedHandler::create(Box::new(
+                move |result, id| {
+                    result?;
+                    let mut registered = registered.borrow_mut();
+                    if registered.generation == generation {
+                        registered.ids.push(id);
+                    } else {
+                        unsafe {
+                            owner.RemoveScriptToExecuteOnDocumentCreated(&HSTRING::from(id))?
+                        };
+                    }
+                    Ok(())
+                },
+            ));
+            if unsafe { core.AddScriptToExecuteOnDocumentCreated(&HSTRING::from(js), &handler) }
+                .is_err()
+            {
+                return crate::STATUS_WEBVIEW_FAILED;
+            }
+        }
+        crate::STATUS_OK
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    /// No script injection after creation outside Windows.
+    pub fn apply(&self, _webview: &WebView) -> i32 {
+        crate::STATUS_UNSUPPORTED
+    }
+}
@@ -0,0 +1,164 @@
+// ═════════════════════════════════════════════════════════════════
+//  Sleep Inhibition — keep the display awake during timed exams
+// ═════════════════════════════════════════════════════════════════
+//
+// A timed exam can run for many minutes without input while the
+// student works on paper; the display must not dim or the computer
+// sleep in the middle of it. `inhibit` holds the system and display
+// awake until `allow`, for the whole process (not per window).
+//
+// Each platform holds the inhibition with something that lives exactly
+// as long as it:
+//
+//   - Windows: a "native-awake" thread that sets
+//     `SetThreadExecutionState(ES_CONTINUOUS | …)`, which only lasts
+//     while the thread that set it runs;
+//   - Linux: `systemd-inhibit`, which shows `reason` in
+//     `systemd-inhibit --list`, around a command that ends with us;
+//   - macOS: `caffeinate`, which ends with us.
+//
+// The child processes exit with the host even if it crashes, so an
+// inhibition never outlives the app. Calling `inhibit` again replaces
+// the held one (with the new reason).
+
+use std::sync::Mutex;
+
+/// Who asks, as shown next to the reason.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const WHO: &str = "Sovereign Academy";
+
+/// The current inhibition, released when dropped.
+static HELD: Mutex<Option<Inhibition>> = Mutex::new(None);
+
+/// Keep the system and display awake until `allow`. Returns a status
+/// code; `STATUS_UNSUPPORTED` where there is no way to.
+pub fn inhibit(reason: &str) -> i32 {
+    let Ok(mut held) = HELD.lock() else {
+        return crate::STATUS_OS_ERROR;
+    };
+    // Release first: one inhibition at a time.
+    *held = None;
+    match Inhibition::start(reason) {
+        Ok(inhibition) => {
+            *held = Some(inhibition);
+            crate::STATUS_OK
+        }
+        Err(code) => code,
+    }
+}
+
+/// Let the system sleep again. Returns `STATUS_OK`, also when nothing
+/// was inhibited.
+pub fn allow() -> i32 {
+    match HELD.lock() {
+        Ok(mut held) => {
+            *held = None;
+            crate::STATUS_OK
+        }
+        Err(_) => crate::STATUS_OS_ERROR,
+    }
+}
+
+/// An inhibition held by a thread that keeps the execution state until
+/// its channel closes.
+#[cfg(target_os = "windows")]
+struct Inhibition {
+    _release: std::sync::mpsc::Sender<()>,
+}
+
+#[cfg(target_os = "windows")]
+impl Inhibition {
+    fn start(_reason: &str) -> Result<Inhibition, i32> {
+        use std::sync::mpsc;
+
+        // ─── Raw Win32 FFI declarations ──
+        const ES_SYSTEM_REQUIRED: u32 = 0x0000_0001;
+        const ES_DISPLAY_REQUIRED: u32 = 0x0000_0002;
+        const ES_CONTINUOUS: u32 = 0x8000_0000;
+
+        extern "system" {
+            // kernel32.dll
+            fn SetThreadExecutionState(flags: u32) -> u32;
+        }
+
+        let (release, released) = mpsc::channel::<()>();
+        let (started, result) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("native-awake".into())
+            .spawn(move || {
+                let flags = ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED;
+                let held = unsafe { SetThreadExecutionState(flags) } != 0;
+                let _ = started.send(held);
+                if held {
+                    // Hold until the sender is dropped.
+                    let _ = released.recv();
+                    unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+                }
+            })
+            .map_err(|_| crate::STATUS_OS_ERROR)?;
+        match result.recv() {
+            Ok(true) => Ok(Inhibition { _release: release }),
+            _ => Err(crate::STATUS_OS_ERROR),
+        }
+    }
+}
+
+/// An inhibition held by a child process, killed when dropped.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+struct Inhibition {
+    child: std::process::Child,
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl Inhibition {
+    fn start(reason: &str) -> Result<Inhibition, i32> {
+        use std::process::{Command, Stdio};
+
+        let pid = std::process::id().to_string();
+        let mut command = if cfg!(target_os = "macos") {
+            // -d: display, -i: idle sleep, -w: until our process exits.
+            let mut command = Command::new("caffeinate");
+            command.args(["-d", "-i", "-w", &pid]);
+            command
+        } else {
+            let mut command = Command::new("systemd-inhibit");
+            command
+                .args(["--what=idle:sleep", "--mode=block"])
+                .arg(format!("--who={WHO}"))
+                .arg(format!("--why={reason}"))
+                .args(["tail", "-f", "/dev/null", "--pid", &pid]);
+            command
+        };
+        match command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => Ok(Inhibition { child }),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                Err(crate::STATUS_UNSUPPORTED)
+            }
+            Err(_) => Err(crate::STATUS_OS_ERROR),
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl Drop for Inhibition {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// No way to inhibit sleep on other platforms.
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+struct Inhibition;
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+impl Inhibition {
+    fn start(_reason: &str) -> Result<Inhibition, i32> {
+        Err(crate::STATUS_UNSUPPORTED)
+    }
+}
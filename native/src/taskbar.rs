@@ -0,0 +1,247 @@
+// ═════════════════════════════════════════════════════════════════
+//  Taskbar Progress — lesson progress on the taskbar button
+// ═════════════════════════════════════════════════════════════════
+//
+// While a lesson or a download runs in the background, the student can
+// still see how far it got: the window's taskbar button fills like a
+// progress bar (green, or red on error, yellow when paused), driven by
+// the host through `set_taskbar_progress`.
+//
+// The button can also carry a small overlay badge in its corner, such
+// as the number of reviews due today, set from RGBA pixels through
+// `set_overlay_badge`. Windows draws it at 16 × 16 pixels (scaled with
+// the DPI), so larger images are shrunk.
+//
+// Windows exposes both through the `ITaskbarList3` COM object. It is
+// created once per UI thread and kept for the life of the process;
+// until the taskbar button exists the calls are silently ignored.
+//
+// Only the "windows" feature has a taskbar; elsewhere `set_progress`
+// and `set_badge` return `STATUS_UNSUPPORTED`.
+
+use tao::window::Window;
+
+/// How the taskbar button shows progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// No progress bar.
+    None,
+    /// A pulsing bar, for work of unknown length.
+    Indeterminate,
+    /// A green bar.
+    Normal,
+    /// A red bar.
+    Error,
+    /// A yellow bar.
+    Paused,
+}
+
+impl Progress {
+    /// The state for its FFI code (0 = none … 4 = paused).
+    pub fn from_code(code: i32) -> Option<Progress> {
+        Some(match code {
+            0 => Progress::None,
+            1 => Progress::Indeterminate,
+            2 => Progress::Normal,
+            3 => Progress::Error,
+            4 => Progress::Paused,
+            _ => return None,
+        })
+    }
+}
+
+// ─── Raw Win32 FFI declarations ──
+#[cfg(all(target_os = "windows", feature = "windows"))]
+mod com {
+    use std::cell::Cell;
+    use std::ffi::c_void;
+
+    #[repr(C)]
+    struct Guid {
+        data1: u32,
+        data2: u16,
+        data3: u16,
+        data4: [u8; 8],
+    }
+
+    // {56FDF344-FD6D-11D0-958A-006097C9A090}
+    const CLSID_TASKBAR_LIST: Guid = Guid {
+        data1: 0x56FD_F344,
+        data2: 0xFD6D,
+        data3: 0x11D0,
+        data4: [0x95, 0x8A, 0x00, 0x60, 0x97, 0xC9, 0xA0, 0x90],
+    };
+    // {EA1AFB91-9E28-4B86-90E9-9E9F8A5EEFAF}
+    const IID_ITASKBAR_LIST3: Guid = Guid {
+        data1: 0xEA1A_FB91,
+        data2: 0x9E28,
+        data3: 0x4B86,
+        data4: [0x90, 0xE9, 0x9E, 0x9F, 0x8A, 0x5E, 0xEF, 0xAF],
+    };
+    const COINIT_APARTMENTTHREADED: u32 = 0x2;
+    const CLSCTX_INPROC_SERVER: u32 = 0x1;
+    pub const TBPF_NOPROGRESS: u32 = 0x0;
+    pub const TBPF_INDETERMINATE: u32 = 0x1;
+    pub const TBPF_NORMAL: u32 = 0x2;
+    pub const TBPF_ERROR: u32 = 0x4;
+    pub const TBPF_PAUSED: u32 = 0x8;
+
+    /// `ITaskbarList3`, up to the methods used here.
+    #[repr(C)]
+    pub struct TaskbarListVtbl {
+        query_interface: usize,
+        add_ref: usize,
+        release: usize,
+        hr_init: unsafe extern "system" fn(*mut TaskbarList) -> i32,
+        add_tab: usize,
+        delete_tab: usize,
+        activate_tab: usize,
+        set_active_alt: usize,
+        mark_fullscreen_window: usize,
+        pub set_progress_value: unsafe extern "system" fn(*mut TaskbarList, isize, u64, u64) -> i32,
+        pub set_progress_state: unsafe extern "system" fn(*mut TaskbarList, isize, u32) -> i32,
+        register_tab: usize,
+        unregister_tab: usize,
+        set_tab_order: usize,
+        set_tab_active: usize,
+        thumb_bar_add_buttons: usize,
+        thumb_bar_update_buttons: usize,
+        thumb_bar_set_image_list: usize,
+        pub set_overlay_icon:
+            unsafe extern "system" fn(*mut TaskbarList, isize, isize, *const u16) -> i32,
+    }
+
+    #[repr(C)]
+    pub struct TaskbarList {
+        pub vtbl: *const TaskbarListVtbl,
+    }
+
+    #[link(name = "ole32")]
+    extern "system" {
+        // ole32.dll
+        fn CoInitializeEx(reserved: *const c_void, flags: u32) -> i32;
+        fn CoCreateInstance(
+            clsid: *const Guid,
+            outer: *mut c_void,
+            context: u32,
+            iid: *const Guid,
+            object: *mut *mut c_void,
+        ) -> i32;
+    }
+
+    extern "system" {
+        // user32.dll
+        pub fn DestroyIcon(icon: isize) -> i32;
+    }
+
+    thread_local! {
+        static TASKBAR: Cell<*mut TaskbarList> = const { Cell::new(std::ptr::null_mut()) };
+    }
+
+    /// This thread's `ITaskbarList3`, created on first use; `None` if
+    /// it cannot be created.
+    pub fn taskbar_list() -> Option<*mut TaskbarList> {
+        TASKBAR.with(|taskbar| {
+            if taskbar.get().is_null() {
+                let mut object = std::ptr::null_mut();
+                unsafe {
+                    // Already initialized by the event loop in most cases;
+                    // either way COM is usable afterwards.
+                    CoInitializeEx(std::ptr::null(), COINIT_APARTMENTTHREADED);
+                    let created = CoCreateInstance(
+                        &CLSID_TASKBAR_LIST,
+                        std::ptr::null_mut(),
+                        CLSCTX_INPROC_SERVER,
+                        &IID_ITASKBAR_LIST3,
+                        &mut object,
+                    );
+                    let list = object as *mut TaskbarList;
+                    if created >= 0 && !list.is_null() && ((*(*list).vtbl).hr_init)(list) >= 0 {
+                        taskbar.set(list);
+                    }
+                }
+            }
+            let list = taskbar.get();
+            (!list.is_null()).then_some(list)
+        })
+    }
+}
+
+/// Show `state` at `percent` (0-100) on the window's taskbar button.
+/// Returns a status code.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub fn set_progress(window: &Window, state: Progress, percent: u32) -> i32 {
+    use com::*;
+    use tao::platform::windows::WindowExtWindows;
+
+    let Some(taskbar) = taskbar_list() else {
+        return crate::STATUS_OS_ERROR;
+    };
+    let flags = match state {
+        Progress::None => TBPF_NOPROGRESS,
+        Progress::Indeterminate => TBPF_INDETERMINATE,
+        Progress::Normal => TBPF_NORMAL,
+        Progress::Error => TBPF_ERROR,
+        Progress::Paused => TBPF_PAUSED,
+    };
+    let hwnd = window.hwnd();
+    let result = unsafe {
+        let vtbl = &*(*taskbar).vtbl;
+        let result = (vtbl.set_progress_state)(taskbar, hwnd, flags);
+        if result >= 0 && matches!(state, Progress::Normal | Progress::Error | Progress::Paused) {
+            (vtbl.set_progress_value)(taskbar, hwnd, u64::from(percent), 100)
+        } else {
+            result
+        }
+    };
+    if result >= 0 {
+        crate::STATUS_OK
+    } else {
+        crate::STATUS_OS_ERROR
+    }
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows")))]
+/// No taskbar without the "windows" feature.
+pub fn set_progress(_window: &Window, _state: Progress, _percent: u32) -> i32 {
+    crate::STATUS_UNSUPPORTED
+}
+
+/// Show a `width` × `height` RGBA badge over the window's taskbar
+/// button, or remove it when `rgba` is `None`. Returns a status code.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub fn set_badge(window: &Window, rgba: Option<(&[u8], u32, u32)>) -> i32 {
+    use com::*;
+    use tao::platform::windows::WindowExtWindows;
+
+    let Some(taskbar) = taskbar_list() else {
+        return crate::STATUS_OS_ERROR;
+    };
+    let icon = match rgba {
+        Some((rgba, width, height)) => match crate::tray::create_icon(rgba, width, height) {
+            Some(icon) => icon,
+            None => return crate::STATUS_OS_ERROR,
+        },
+        None => 0,
+    };
+    let result = unsafe {
+        let result =
+            ((*(*taskbar).vtbl).set_overlay_icon)(taskbar, window.hwnd(), icon, std::ptr::null());
+        // The taskbar keeps its own copy.
+        if icon != 0 {
+            DestroyIcon(icon);
+        }
+        result
+    };
+    if result >= 0 {
+        crate::STATUS_OK
+    } else {
+        crate::STATUS_OS_ERROR
+    }
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows")))]
+/// No taskbar without the "windows" feature.
+pub fn set_badge(_window: &Window, _rgba: Option<(&[u8], u32, u32)>) -> i32 {
+    crate::STATUS_UNSUPPORTED
+}
@@ -0,0 +1,144 @@
+// ═════════════════════════════════════════════════════════════════
+//  System Theme — light, dark or high contrast, as the OS sets it
+// ═════════════════════════════════════════════════════════════════
+//
+// The UI follows the student's OS theme. `prefers-color-scheme` is not
+// enough: WebView2 does not always fire its `matchMedia` listeners, and
+// it has no notion of high contrast at all. So the host asks with
+// `get_system_theme` and each window queues
+//
+//   {"type":"theme-changed","theme":"dark"}
+//
+// when the theme differs from the one it last reported ("light",
+// "dark" or "high-contrast"). Windows reports a switch between light
+// and dark right away; turning high contrast on or off only changes
+// the colors the app would pick, so it is noticed when the window is
+// next focused (the student is in the Settings app while switching).
+//
+// High contrast is only detected with the "windows" feature; elsewhere
+// the theme is the one the windowing toolkit reports.
+
+use tao::window::Window;
+
+/// The theme the UI should use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    Light = 0,
+    Dark = 1,
+    HighContrast = 2,
+}
+
+impl Theme {
+    /// The theme `window` follows.
+    pub fn of(window: &Window) -> Theme {
+        if high_contrast() {
+            return Theme::HighContrast;
+        }
+        match window.theme() {
+            tao::window::Theme::Dark => Theme::Dark,
+            _ => Theme::Light,
+        }
+    }
+
+    /// Its name in events.
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::HighContrast => "high-contrast",
+        }
+    }
+}
+
+/// The OS theme without asking a window; `None` where only windows
+/// know it.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+pub fn system() -> Option<Theme> {
+    // ─── Raw Win32 FFI declarations ──
+    const HKEY_CURRENT_USER: isize = 0x8000_0001_u32 as i32 as isize;
+    const RRF_RT_REG_DWORD: u32 = 0x0000_0010;
+
+    #[link(name = "advapi32")]
+    extern "system" {
+        // advapi32.dll
+        fn RegGetValueW(
+            key: isize,
+            sub_key: *const u16,
+            value: *const u16,
+            flags: u32,
+            kind: *mut u32,
+            data: *mut u32,
+            size: *mut u32,
+        ) -> i32;
+    }
+
+    if high_contrast() {
+        return Some(Theme::HighContrast);
+    }
+    let wide = |text: &str| -> Vec<u16> { text.encode_utf16().chain(Some(0)).collect() };
+    let subkey = wide(r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize");
+    let value = wide("AppsUseLightTheme");
+    let mut light = 1u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let found = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            subkey.as_ptr(),
+            value.as_ptr(),
+            RRF_RT_REG_DWORD,
+            std::ptr::null_mut(),
+            &mut light,
+            &mut size,
+        )
+    };
+    // Without the value Windows uses the light theme.
+    Some(if found == 0 && light == 0 {
+        Theme::Dark
+    } else {
+        Theme::Light
+    })
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows")))]
+/// Only windows know the theme without the "windows" feature.
+pub fn system() -> Option<Theme> {
+    None
+}
+
+/// Whether Windows' high contrast mode is on.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+fn high_contrast() -> bool {
+    // ─── Raw Win32 FFI declarations ──
+    const SPI_GETHIGHCONTRAST: u32 = 0x0042;
+    const HCF_HIGHCONTRASTON: u32 = 0x0000_0001;
+
+    #[repr(C)]
+    struct HighContrast {
+        size: u32,
+        flags: u32,
+        default_scheme: *mut u16,
+    }
+
+    extern "system" {
+        // user32.dll
+        fn SystemParametersInfoW(
+            action: u32,
+            param: u32,
+            value: *mut HighContrast,
+            ini: u32,
+        ) -> i32;
+    }
+
+    let mut info = HighContrast {
+        size: std::mem::size_of::<HighContrast>() as u32,
+        flags: 0,
+        default_scheme: std::ptr::null_mut(),
+    };
+    let ok = unsafe { SystemParametersInfoW(SPI_GETHIGHCONTRAST, info.size, &mut info, 0) };
+    ok != 0 && info.flags & HCF_HIGHCONTRASTON != 0
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows")))]
+fn high_contrast() -> bool {
+    false
+}
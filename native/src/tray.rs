@@ -0,0 +1,475 @@
+// ═════════════════════════════════════════════════════════════════
+//  Win32 System Tray Icon (feature = "windows")
+// ═════════════════════════════════════════════════════════════════
+//
+// A notification-area icon with a right-click menu, so the host can
+// offer show / hide / quit without a window of its own. Each tray owns
+// a hidden message-only window that receives the icon's mouse
+// messages; it lives on the UI thread, whose event loop already pumps
+// every message of the thread.
+//
+// The menu is described as JSON, top to bottom:
+//
+//   [{"id":"show","label":"Show"},
+//    {"separator":true},
+//    {"id":"quit","label":"Quit","enabled":true}]
+//
+// Clicks are queued like window events (see `events`) and polled with
+// the tray's handle:
+//
+//   {"type":"tray-click"}                  left click on the icon
+//   {"type":"menu-click","id":"show"}      menu item chosen
+//
+// Tray state lives in a thread-local on the UI thread, because the
+// WndProc has no other way to reach it. No borrow of it is held while
+// the menu is open: the menu runs a modal loop that keeps serving UI
+// thread jobs, which may poll the same tray.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::Once;
+
+use crate::events::EventQueue;
+
+// ─── Raw Win32 FFI declarations (avoids windows-sys version conflicts) ──
+#[allow(clippy::upper_case_acronyms)]
+mod win32 {
+    pub type HWND = isize;
+    pub type HICON = isize;
+    pub type HMENU = isize;
+    pub type HBITMAP = isize;
+    pub type LRESULT = isize;
+    pub type WPARAM = usize;
+    pub type LPARAM = isize;
+
+    #[repr(C)]
+    pub struct POINT {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    #[repr(C)]
+    pub struct WNDCLASSW {
+        pub style: u32,
+        pub wnd_proc: unsafe extern "system" fn(HWND, u32, WPARAM, LPARAM) -> LRESULT,
+        pub cls_extra: i32,
+        pub wnd_extra: i32,
+        pub instance: isize,
+        pub icon: HICON,
+        pub cursor: isize,
+        pub background: isize,
+        pub menu_name: *const u16,
+        pub class_name: *const u16,
+    }
+
+    #[repr(C)]
+    pub struct GUID {
+        pub data1: u32,
+        pub data2: u16,
+        pub data3: u16,
+        pub data4: [u8; 8],
+    }
+
+    #[repr(C)]
+    pub struct NOTIFYICONDATAW {
+        pub size: u32,
+        pub hwnd: HWND,
+        pub id: u32,
+        pub flags: u32,
+        pub callback_message: u32,
+        pub icon: HICON,
+        pub tip: [u16; 128],
+        pub state: u32,
+        pub state_mask: u32,
+        pub info: [u16; 256],
+        pub version: u32,
+        pub info_title: [u16; 64],
+        pub info_flags: u32,
+        pub guid: GUID,
+        pub balloon_icon: HICON,
+    }
+
+    #[repr(C)]
+    pub struct ICONINFO {
+        pub is_icon: i32,
+        pub x_hotspot: u32,
+        pub y_hotspot: u32,
+        pub mask: HBITMAP,
+        pub color: HBITMAP,
+    }
+
+    // Shell_NotifyIconW
+    pub const NIM_ADD: u32 = 0x0000;
+    pub const NIM_DELETE: u32 = 0x0002;
+    pub const NIF_MESSAGE: u32 = 0x0001;
+    pub const NIF_ICON: u32 = 0x0002;
+    pub const NIF_TIP: u32 = 0x0004;
+
+    // Window messages
+    pub const WM_NULL: u32 = 0x0000;
+    pub const WM_APP: u32 = 0x8000;
+    pub const WM_LBUTTONUP: u32 = 0x0202;
+    pub const WM_RBUTTONUP: u32 = 0x0205;
+    pub const WM_CONTEXTMENU: u32 = 0x007B;
+
+    pub const HWND_MESSAGE: HWND = -3;
+
+    // Menus
+    pub const MF_STRING: u32 = 0x0000;
+    pub const MF_GRAYED: u32 = 0x0001;
+    pub const MF_SEPARATOR: u32 = 0x0800;
+    pub const TPM_RIGHTBUTTON: u32 = 0x0002;
+    pub const TPM_NONOTIFY: u32 = 0x0080;
+    pub const TPM_RETURNCMD: u32 = 0x0100;
+
+    extern "system" {
+        // user32.dll
+        pub fn RegisterClassW(class: *const WNDCLASSW) -> u16;
+        pub fn CreateWindowExW(
+            ex_style: u32,
+            class_name: *const u16,
+            window_name: *const u16,
+            style: u32,
+            x: i32,
+            y: i32,
+            width: i32,
+            height: i32,
+            parent: HWND,
+            menu: HMENU,
+            instance: isize,
+            param: *const core::ffi::c_void,
+        ) -> HWND;
+        pub fn DestroyWindow(hwnd: HWND) -> i32;
+        pub fn DefWindowProcW(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT;
+        pub fn PostMessageW(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> i32;
+        pub fn SetForegroundWindow(hwnd: HWND) -> i32;
+        pub fn GetCursorPos(point: *mut POINT) -> i32;
+        pub fn CreatePopupMenu() -> HMENU;
+        pub fn AppendMenuW(menu: HMENU, flags: u32, id: usize, text: *const u16) -> i32;
+        pub fn DestroyMenu(menu: HMENU) -> i32;
+        pub fn TrackPopupMenu(
+            menu: HMENU,
+            flags: u32,
+            x: i32,
+            y: i32,
+            reserved: i32,
+            hwnd: HWND,
+            rect: *const core::ffi::c_void,
+        ) -> i32;
+        pub fn CreateIconIndirect(info: *const ICONINFO) -> HICON;
+        pub fn DestroyIcon(icon: HICON) -> i32;
+    }
+
+    extern "system" {
+        // gdi32.dll
+        pub fn CreateBitmap(
+            width: i32,
+            height: i32,
+            planes: u32,
+            bits_per_pixel: u32,
+            bits: *const core::ffi::c_void,
+        ) -> HBITMAP;
+        pub fn DeleteObject(object: isize) -> i32;
+    }
+
+    extern "system" {
+        // kernel32.dll
+        pub fn GetModuleHandleW(name: *const u16) -> isize;
+    }
+
+    #[link(name = "shell32")]
+    extern "system" {
+        // shell32.dll
+        pub fn Shell_NotifyIconW(message: u32, data: *mut NOTIFYICONDATAW) -> i32;
+    }
+}
+
+/// Callback message the icon sends to its tray's window.
+const WM_TRAY: u32 = win32::WM_APP + 1;
+
+/// Hover text of the icon.
+const TOOLTIP: &str = "Sovereign Academy";
+
+/// Most items a menu may have.
+pub const MAX_MENU_ITEMS: usize = 32;
+
+/// One line of the tray menu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuItem {
+    Item {
+        id: String,
+        label: String,
+        enabled: bool,
+    },
+    Separator,
+}
+
+/// Parse a menu description (see the module header); `None` if it is
+/// not an array of well-formed items.
+pub fn parse_menu(json: &str) -> Option<Vec<MenuItem>> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    let items = value.as_array()?;
+    if items.len() > MAX_MENU_ITEMS {
+        return None;
+    }
+    items
+        .iter()
+        .map(|item| {
+            if item.get("separator").and_then(|s| s.as_bool()) == Some(true) {
+                return Some(MenuItem::Separator);
+            }
+            let id = item.get("id")?.as_str().filter(|id| !id.is_empty())?;
+            Some(MenuItem::Item {
+                id: id.to_owned(),
+                label: item.get("label")?.as_str()?.to_owned(),
+                enabled: match item.get("enabled") {
+                    None => true,
+                    Some(enabled) => enabled.as_bool()?,
+                },
+            })
+        })
+        .collect()
+}
+
+/// What the WndProc needs to know about a tray.
+struct TrayState {
+    menu: win32::HMENU,
+    /// Item ids by menu command; command `n` is `ids[n - 1]`.
+    ids: Vec<String>,
+    events: EventQueue,
+}
+
+thread_local! {
+    /// Trays of the UI thread, by their message window.
+    static TRAYS: RefCell<BTreeMap<win32::HWND, TrayState>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// A tray icon; removed from the notification area when dropped.
+/// Create and drop it on the UI thread only.
+pub struct Tray {
+    hwnd: win32::HWND,
+    icon: win32::HICON,
+}
+
+impl Tray {
+    /// Show `width` × `height` RGBA pixels in the notification area
+    /// with `menu` on right click. `None` if Windows refuses.
+    pub fn new(rgba: &[u8], width: u32, height: u32, menu: &[MenuItem]) -> Option<Tray> {
+        let hwnd = message_window()?;
+        let Some(icon) = create_icon(rgba, width, height) else {
+            unsafe { win32::DestroyWindow(hwnd) };
+            return None;
+        };
+        let tray = Tray { hwnd, icon };
+        let (menu, ids) = create_menu(menu)?;
+        TRAYS.with(|trays| {
+            trays.borrow_mut().insert(
+                hwnd,
+                TrayState {
+                    menu,
+                    ids,
+                    events: EventQueue::default(),
+                },
+            )
+        });
+
+        let mut data = tray.notify_data();
+        data.flags = win32::NIF_MESSAGE | win32::NIF_ICON | win32::NIF_TIP;
+        data.callback_message = WM_TRAY;
+        data.icon = icon;
+        for (slot, unit) in data.tip.iter_mut().zip(TOOLTIP.encode_utf16()) {
+            *slot = unit;
+        }
+        if unsafe { win32::Shell_NotifyIconW(win32::NIM_ADD, &mut data) } == 0 {
+            return None; // Dropping `tray` cleans up
+        }
+        Some(tray)
+    }
+
+    /// Take the tray's oldest event if it fits in `limit` bytes; see
+    /// `EventQueue::pop`.
+    pub fn pop_event(&self, limit: usize) -> Result<Option<String>, usize> {
+        TRAYS.with(|trays| match trays.borrow_mut().get_mut(&self.hwnd) {
+            Some(state) => state.events.pop(limit),
+            None => Ok(None),
+        })
+    }
+
+    fn notify_data(&self) -> win32::NOTIFYICONDATAW {
+        // Safety: all-zero is a valid NOTIFYICONDATAW (no flags set).
+        let mut data: win32::NOTIFYICONDATAW = unsafe { std::mem::zeroed() };
+        data.size = std::mem::size_of::<win32::NOTIFYICONDATAW>() as u32;
+        data.hwnd = self.hwnd;
+        data.id = 1;
+        data
+    }
+}
+
+impl Drop for Tray {
+    fn drop(&mut self) {
+        let mut data = self.notify_data();
+        let state = TRAYS.with(|trays| trays.borrow_mut().remove(&self.hwnd));
+        unsafe {
+            win32::Shell_NotifyIconW(win32::NIM_DELETE, &mut data);
+            if let Some(state) = state {
+                win32::DestroyMenu(state.menu);
+            }
+            win32::DestroyIcon(self.icon);
+            win32::DestroyWindow(self.hwnd);
+        }
+    }
+}
+
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// A hidden message-only window with `tray_proc` as its WndProc.
+fn message_window() -> Option<win32::HWND> {
+    static REGISTER: Once = Once::new();
+    let class_name = wide("SovereignAcademyTray");
+    let instance = unsafe { win32::GetModuleHandleW(std::ptr::null()) };
+    REGISTER.call_once(|| {
+        let class = win32::WNDCLASSW {
+            style: 0,
+            wnd_proc: tray_proc,
+            cls_extra: 0,
+            wnd_extra: 0,
+            instance,
+            icon: 0,
+            cursor: 0,
+            background: 0,
+            menu_name: std::ptr::null(),
+            class_name: class_name.as_ptr(),
+        };
+        unsafe { win32::RegisterClassW(&class) };
+    });
+    let hwnd = unsafe {
+        win32::CreateWindowExW(
+            0,
+            class_name.as_ptr(),
+            class_name.as_ptr(),
+            0,
+            0,
+            0,
+            0,
+            0,
+            win32::HWND_MESSAGE,
+            0,
+            instance,
+            std::ptr::null(),
+        )
+    };
+    (hwnd != 0).then_some(hwnd)
+}
+
+/// An icon from RGBA pixels; its alpha channel is kept.
+pub(crate) fn create_icon(rgba: &[u8], width: u32, height: u32) -> Option<win32::HICON> {
+    // Windows wants BGRA.
+    let bgra: Vec<u8> = rgba
+        .chunks_exact(4)
+        .flat_map(|p| [p[2], p[1], p[0], p[3]])
+        .collect();
+    // The AND mask is ignored for 32-bit icons but must exist: one bit
+    // per pixel, rows padded to 16 bits.
+    let mask = vec![0u8; width.div_ceil(16) as usize * 2 * height as usize];
+    unsafe {
+        let (w, h) = (width as i32, height as i32);
+        let color = win32::CreateBitmap(w, h, 1, 32, bgra.as_ptr().cast());
+        let mask = win32::CreateBitmap(w, h, 1, 1, mask.as_ptr().cast());
+        let info = win32::ICONINFO {
+            is_icon: 1,
+            x_hotspot: 0,
+            y_hotspot: 0,
+            mask,
+            color,
+        };
+        let icon = if color != 0 && mask != 0 {
+            win32::CreateIconIndirect(&info)
+        } else {
+            0
+        };
+        win32::DeleteObject(color);
+        win32::DeleteObject(mask);
+        (icon != 0).then_some(icon)
+    }
+}
+
+/// The popup menu and the item id behind each command.
+fn create_menu(items: &[MenuItem]) -> Option<(win32::HMENU, Vec<String>)> {
+    let menu = unsafe { win32::CreatePopupMenu() };
+    if menu == 0 {
+        return None;
+    }
+    let mut ids = Vec::new();
+    for item in items {
+        match item {
+            MenuItem::Separator => unsafe {
+                win32::AppendMenuW(menu, win32::MF_SEPARATOR, 0, std::ptr::null());
+            },
+            MenuItem::Item { id, label, enabled } => {
+                ids.push(id.clone());
+                let flags = if *enabled {
+                    win32::MF_STRING
+                } else {
+                    win32::MF_STRING | win32::MF_GRAYED
+                };
+                let label = wide(label);
+                unsafe { win32::AppendMenuW(menu, flags, ids.len(), label.as_ptr()) };
+            }
+        }
+    }
+    Some((menu, ids))
+}
+
+/// Queue an event for the tray behind `hwnd`.
+fn push_event(hwnd: win32::HWND, event: String) {
+    TRAYS.with(|trays| {
+        if let Some(state) = trays.borrow_mut().get_mut(&hwnd) {
+            state.events.push(event);
+        }
+    });
+}
+
+/// Show the menu at the cursor and queue the chosen item, if any.
+unsafe fn show_menu(hwnd: win32::HWND) {
+    let Some(menu) = TRAYS.with(|trays| trays.borrow().get(&hwnd).map(|state| state.menu)) else {
+        return;
+    };
+    let mut cursor = win32::POINT { x: 0, y: 0 };
+    win32::GetCursorPos(&mut cursor);
+    // Without this the menu does not close when clicking elsewhere.
+    win32::SetForegroundWindow(hwnd);
+    let flags = win32::TPM_RIGHTBUTTON | win32::TPM_RETURNCMD | win32::TPM_NONOTIFY;
+    let command = win32::TrackPopupMenu(menu, flags, cursor.x, cursor.y, 0, hwnd, std::ptr::null());
+    win32::PostMessageW(hwnd, win32::WM_NULL, 0, 0);
+
+    let id = TRAYS.with(|trays| {
+        let trays = trays.borrow();
+        let ids = &trays.get(&hwnd)?.ids;
+        ids.get((command as usize).checked_sub(1)?).cloned()
+    });
+    if let Some(id) = id {
+        push_event(
+            hwnd,
+            serde_json::json!({"type": "menu-click", "id": id}).to_string(),
+        );
+    }
+}
+
+unsafe extern "system" fn tray_proc(
+    hwnd: win32::HWND,
+    msg: u32,
+    wparam: win32::WPARAM,
+    lparam: win32::LPARAM,
+) -> win32::LRESULT {
+    if msg == WM_TRAY {
+        match (lparam & 0xFFFF) as u32 {
+            win32::WM_LBUTTONUP => push_event(hwnd, r#"{"type":"tray-click"}"#.to_owned()),
+            win32::WM_RBUTTONUP | win32::WM_CONTEXTMENU => show_menu(hwnd),
+            _ => {}
+        }
+        return 0;
+    }
+    win32::DefWindowProcW(hwnd, msg, wparam, lparam)
+}
@@ -0,0 +1,205 @@
+// ═════════════════════════════════════════════════════════════════
+//  Host Watchdog — heartbeat between Deno and the native window
+// ═════════════════════════════════════════════════════════════════
+//
+// The window and the Fresh server both live in the Deno process. If
+// Deno's event loop hangs, the page stops answering and the frameless
+// window has no working controls — not even a close button.
+//
+// Once armed with `arm`, the Deno host calls `beat` on an interval. A
+// watchdog thread, which keeps running while Deno is stuck, notices
+// when beats stop for longer than the timeout and has the UI thread
+// (which never waits for Deno) cover every window's page with a
+// built-in one: the app stopped responding, Reload or Quit. Reload
+// goes back to the page each window showed; Quit exits. The buttons
+// talk over the webview's IPC channel (`window.ipc.postMessage`),
+// which every platform has, and are only obeyed while the page is up.
+// If the beats come back first, the host recovered and the pages are
+// put back as if Reload had been pressed.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tao::window::WindowId;
+
+use crate::WindowTable;
+
+/// Longest pause between watchdog checks.
+const MAX_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Timeout in milliseconds; 0 = disarmed.
+static TIMEOUT_MS: AtomicU32 = AtomicU32::new(0);
+
+/// When the last beat (or `arm`) happened.
+static LAST_BEAT: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Whether the watchdog thread has been started.
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the windows show `PAGE` (or are about to).
+static SHOWING: AtomicBool = AtomicBool::new(false);
+
+/// IPC message of `PAGE`'s Reload button.
+const RELOAD: &str = "watchdog:reload";
+
+/// IPC message of `PAGE`'s Quit button.
+const QUIT: &str = "watchdog:quit";
+
+/// Shown in place of the pages while the host is stuck.
+const PAGE: &str = r#"<!doctype html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Sovereign Academy</title>
+<style>
+  body { margin: 0; height: 100vh; display: flex; align-items: center;
+         justify-content: center; font: 16px system-ui, sans-serif;
+         background: #1e1e2e; color: #e6e6f0; }
+  main { max-width: 28rem; padding: 2rem; text-align: center; }
+  button { margin: 0 .5rem; padding: .6rem 1.4rem; font: inherit;
+           border: 0; border-radius: .4rem; cursor: pointer; }
+  #reload { background: #7aa2f7; color: #1e1e2e; }
+</style>
+</head>
+<body>
+<main>
+  <h1>Sovereign Academy stopped responding</h1>
+  <p>Reload tries the lesson again. Quit closes the app.</p>
+  <button id="reload" onclick="window.ipc.postMessage('watchdog:reload')">Reload</button>
+  <button id="quit" onclick="window.ipc.postMessage('watchdog:quit')">Quit</button>
+</main>
+</body>
+</html>
+"#;
+
+/// What the student chose once the host stopped responding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Choice {
+    Reload,
+    Quit,
+}
+
+/// Record a heartbeat from the host.
+pub fn beat() {
+    if let Ok(mut last) = LAST_BEAT.lock() {
+        *last = Some(Instant::now());
+    }
+}
+
+/// Arm the watchdog with `timeout_ms` between beats (0 disarms it).
+/// The first call starts the watchdog thread.
+pub fn arm(timeout_ms: u32) {
+    beat();
+    TIMEOUT_MS.store(timeout_ms, Ordering::SeqCst);
+    if timeout_ms != 0 && !STARTED.swap(true, Ordering::SeqCst) {
+        thread::spawn(watch);
+    }
+}
+
+/// Whether the watchdog is armed.
+pub fn is_armed() -> bool {
+    TIMEOUT_MS.load(Ordering::SeqCst) != 0
+}
+
+/// Whether the watchdog's page is up (or about to be), which the kiosk
+/// lock lets through.
+pub(crate) fn is_showing() -> bool {
+    SHOWING.load(Ordering::SeqCst)
+}
+
+fn watch() {
+    loop {
+        let timeout = Duration::from_millis(u64::from(TIMEOUT_MS.load(Ordering::SeqCst)));
+        thread::sleep((timeout / 4).clamp(Duration::from_millis(10), MAX_CHECK_INTERVAL));
+        let stalled = !timeout.is_zero()
+            && match LAST_BEAT.lock() {
+                Ok(last) => last.is_some_and(|at| at.elapsed() > timeout),
+                Err(_) => false,
+            };
+
+        if stalled && !SHOWING.swap(true, Ordering::SeqCst) {
+            if !crate::post(|table, _| show(table)) {
+                SHOWING.store(false, Ordering::SeqCst);
+                eprintln!("[Native] Watchdog: the app stopped responding");
+                beat(); // Nowhere to show it; don't report the same stall again at once.
+            }
+        } else if !stalled && SHOWING.swap(false, Ordering::SeqCst) {
+            crate::post(|table, _| restore(table)); // The host recovered.
+        }
+    }
+}
+
+/// Cover every window's page with `PAGE`, remembering what it showed.
+fn show(table: &mut WindowTable) {
+    for native in table.windows.values_mut() {
+        native.stalled.get_or_insert_with(|| {
+            native
+                .webview
+                .url()
+                .unwrap_or_else(|_| crate::DEFAULT_URL.to_owned())
+        });
+        let _ = native.webview.load_html(PAGE);
+    }
+}
+
+/// Put back the pages `show` covered.
+fn restore(table: &mut WindowTable) {
+    for native in table.windows.values_mut() {
+        if let Some(url) = native.stalled.take() {
+            let _ = native.webview.load_url(&url);
+        }
+    }
+}
+
+/// The button behind an IPC message, if it is one of `PAGE`'s.
+fn choice(message: &str) -> Option<Choice> {
+    match message {
+        RELOAD => Some(Choice::Reload),
+        QUIT => Some(Choice::Quit),
+        _ => None,
+    }
+}
+
+/// Act on an IPC message from the page in window `id`. Only `PAGE`'s
+/// buttons are answered, and only while that window shows it.
+pub(crate) fn message(id: WindowId, message: &str) {
+    let Some(choice) = choice(message) else {
+        return;
+    };
+    // The IPC handler runs inside the event loop, away from the table.
+    crate::post(move |table, _| {
+        let covered = table
+            .windows
+            .values()
+            .any(|native| native.window.id() == id && native.stalled.is_some());
+        if !covered {
+            return;
+        }
+        match choice {
+            Choice::Reload => {
+                // Beat first, so the watchdog waits a whole timeout
+                // before covering the reloaded pages again.
+                beat();
+                SHOWING.store(false, Ordering::SeqCst);
+                restore(table);
+            }
+            Choice::Quit => std::process::exit(1),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_buttons_post_known_messages() {
+        for message in [RELOAD, QUIT] {
+            assert!(PAGE.contains(&format!("window.ipc.postMessage('{message}')")));
+        }
+        assert_eq!(choice(RELOAD), Some(Choice::Reload));
+        assert_eq!(choice(QUIT), Some(Choice::Quit));
+        assert_eq!(choice("resize-top"), None);
+    }
+}
@@ -243,14 +243,14 @@ const TEST_CASES: TestCase[] = [
     category: "check_answer",
     function: "check_answer",
     input: "arithmetic, 2 + 3, answer=5",
-    expectedOutput: '{"correct":true,"hint":"Correct!","problem":"2 + 3","answer":"5"}',
+    expectedOutput: '{"result_schema_version":2,"correct":true,"hint":"Correct!","hint_id":"correct","hint_data":{"kind":"correct","params":{},"referenced_operands":["2","3"],"step_index":null,"template_id":"correct"},"problem":"2 + 3","answer":"5"}',
   },
   {
     category: "check_answer",
     function: "check_answer",
     input: "arithmetic, 2 + 3, answer=6",
     expectedOutput:
-      '{"correct":false,"hint":"Try evaluating 2 + 3 step by step.","problem":"2 + 3","answer":"6"}',
+      '{"result_schema_version":2,"correct":false,"hint":"Try evaluating 2 + 3 step by step.","hint_id":"arithmetic.step_by_step","hint_data":{"kind":"strategy","params":{"problem":"2 + 3"},"referenced_operands":["2","3"],"step_index":null,"template_id":"arithmetic.step_by_step"},"problem":"2 + 3","answer":"6"}',
   },
 
   // batch_validate
@@ -258,19 +258,22 @@ const TEST_CASES: TestCase[] = [
     category: "batch",
     function: "batch_validate",
     input: "2 + 3;4 * 5;10 / 2 | 5;20;5",
-    expectedOutput: "3",
+    expectedOutput:
+      '{"result_schema_version":2,"results":[{"answer":"5","correct":true,"error":null,"hint":"Correct!","hint_id":"correct","problem":"2 + 3"},{"answer":"20","correct":true,"error":null,"hint":"Correct!","hint_id":"correct","problem":"4 * 5"},{"answer":"5","correct":true,"error":null,"hint":"Correct!","hint_id":"correct","problem":"10 / 2"}]}',
   },
   {
     category: "batch",
     function: "batch_validate",
     input: "2 + 3;4 * 5 | 5;21",
-    expectedOutput: "1",
+    expectedOutput:
+      '{"result_schema_version":2,"results":[{"answer":"5","correct":true,"error":null,"hint":"Correct!","hint_id":"correct","problem":"2 + 3"},{"answer":"21","correct":false,"error":null,"hint":"Try evaluating 4 * 5 step by step.","hint_id":"arithmetic.step_by_step","problem":"4 * 5"}]}',
   },
   {
     category: "batch",
     function: "batch_validate",
     input: "1 + 1 | 3",
-    expectedOutput: "0",
+    expectedOutput:
+      '{"result_schema_version":2,"results":[{"answer":"3","correct":false,"error":null,"hint":"Try evaluating 1 + 1 step by step.","hint_id":"arithmetic.step_by_step","problem":"1 + 1"}]}',
   },
 
   // Edge cases
@@ -285,7 +288,7 @@ const TEST_CASES: TestCase[] = [
     function: "check_answer",
     input: "unknown_type, x, answer=y",
     expectedOutput:
-      '{"correct":false,"hint":"Unknown problem type: unknown_type","problem":"x","answer":"y"}',
+      '{"result_schema_version":2,"correct":false,"hint":"Unknown problem type: unknown_type","hint_id":"unknown_type","hint_data":{"kind":"error","params":{"type":"unknown_type"},"referenced_operands":[],"step_index":null,"template_id":"unknown_type"},"problem":"x","answer":"y","supported_types":[{"builtin":true,"type":"arithmetic"},{"builtin":true,"type":"comparison"},{"builtin":true,"type":"elapsed_time"},{"builtin":true,"type":"fill_blank"},{"builtin":true,"type":"fraction"},{"builtin":true,"type":"list"},{"builtin":true,"type":"money"},{"builtin":true,"type":"multiple_choice"},{"builtin":true,"type":"temperature"},{"builtin":true,"type":"time_addition"},{"builtin":true,"type":"true_false"},{"builtin":true,"type":"unit_price"}]}',
   },
   {
     category: "edge",
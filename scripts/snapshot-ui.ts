@@ -53,6 +53,7 @@ const COMPONENTS: ComponentSnapshot[] = [
       "IPC: minimize → window.ipc.postMessage('minimize')",
       "IPC: maximize → window.ipc.postMessage('maximize')",
       "IPC: close → window.ipc.postMessage('close')",
+      "IPC: caption button rects → __ipc.declareHitRegions() on mount + resize",
       "Close button hover: red background (#f23f43)",
     ],
   },